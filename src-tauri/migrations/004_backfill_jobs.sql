@@ -0,0 +1,17 @@
+-- Multi-day historical backfill jobs, persisted so they can resume after restart
+CREATE TABLE IF NOT EXISTS backfill_jobs (
+    id TEXT PRIMARY KEY,
+    start_date TEXT NOT NULL,           -- 'YYYY-MM-DD', inclusive
+    end_date TEXT NOT NULL,             -- 'YYYY-MM-DD', inclusive
+    next_date TEXT,                     -- Next day to process, NULL once finished
+    timezone_offset INTEGER NOT NULL,   -- Minutes west of UTC (JS getTimezoneOffset convention)
+    sources TEXT NOT NULL,              -- JSON array: 'slack', 'jira', 'confluence'
+    status TEXT NOT NULL,               -- 'pending', 'running', 'completed', 'failed'
+    days_completed INTEGER NOT NULL DEFAULT 0,
+    items_synced INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_backfill_jobs_status ON backfill_jobs(status);
@@ -0,0 +1,21 @@
+-- Queued sync jobs, persisted so pending work and retry counts survive restarts
+CREATE TABLE IF NOT EXISTS sync_jobs (
+    id TEXT PRIMARY KEY,
+    kind TEXT NOT NULL,                 -- Payload kind, e.g. 'historical_day'
+    dedupe_key TEXT NOT NULL,           -- At most one pending/running job per key
+    payload TEXT NOT NULL,              -- JSON-encoded SyncJobPayload
+    status TEXT NOT NULL,               -- 'pending', 'running', 'completed', 'failed', 'cancelled'
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL,
+    next_run_at INTEGER NOT NULL,       -- Epoch ms; retries are pushed into the future
+    last_error TEXT,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_sync_jobs_status ON sync_jobs(status, next_run_at);
+CREATE INDEX IF NOT EXISTS idx_sync_jobs_dedupe ON sync_jobs(dedupe_key);
+
+-- Enforces the dedupe rule even when two enqueues race
+CREATE UNIQUE INDEX IF NOT EXISTS idx_sync_jobs_active_dedupe ON sync_jobs(dedupe_key)
+    WHERE status IN ('pending', 'running');
@@ -0,0 +1,10 @@
+-- Per-source and per-scope sync frequency. A row with an empty resource_type and
+-- resource_id is the source default; other rows override one channel, project or space.
+-- Due times are measured from sync_state.last_sync_at of the matching resource.
+CREATE TABLE IF NOT EXISTS sync_intervals (
+    source TEXT NOT NULL,
+    resource_type TEXT NOT NULL DEFAULT '',
+    resource_id TEXT NOT NULL DEFAULT '',
+    interval_minutes INTEGER NOT NULL,
+    PRIMARY KEY (source, resource_type, resource_id)
+);
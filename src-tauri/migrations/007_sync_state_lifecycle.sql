@@ -0,0 +1,6 @@
+-- Per-resource sync lifecycle. status moves pending -> syncing -> complete or error.
+-- started_at is when the latest attempt began and updated_at when status last changed.
+-- error_message and error_at hold the most recent failure until the resource completes again.
+ALTER TABLE sync_state ADD COLUMN started_at INTEGER;
+ALTER TABLE sync_state ADD COLUMN updated_at INTEGER;
+ALTER TABLE sync_state ADD COLUMN error_at INTEGER;
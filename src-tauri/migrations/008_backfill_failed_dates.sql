@@ -0,0 +1,3 @@
+-- Days of a backfill job whose sync or AI pass failed, as a JSON array of
+-- YYYY-MM-DD dates. They are retried before the job finishes and listed if it fails.
+ALTER TABLE backfill_jobs ADD COLUMN failed_dates TEXT NOT NULL DEFAULT '[]';
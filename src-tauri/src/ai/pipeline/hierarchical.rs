@@ -3,6 +3,7 @@ use super::types::{
 };
 use crate::ai::gemini::GeminiClient;
 use crate::ai::prompts::{self, ChannelSummary, GroupedAnalysisResult};
+use crate::pipeline::{CancellationToken, ProgressReporter};
 use std::collections::HashMap;
 
 /// Process messages using hierarchical summarization.
@@ -15,6 +16,9 @@ use std::collections::HashMap;
 /// * `date_str` - The date string in YYYY-MM-DD format
 /// * `messages_by_channel` - Messages grouped by channel name
 /// * `user_guidance` - Optional user-provided guidance for summarization preferences
+/// * `meetings_json` - The day's calendar events, given to the cross-channel pass
+/// * `cancel` - Checked before each AI request; cancelling stops with an error
+/// * `progress` - Receives a message and fraction before each AI request
 ///
 /// # Returns
 /// A GroupedAnalysisResult containing topic groups and ungrouped items
@@ -23,7 +27,27 @@ pub async fn process_hierarchical(
     date_str: &str,
     messages_by_channel: HashMap<String, Vec<MessageForPrompt>>,
     user_guidance: Option<&str>,
+    meetings_json: Option<&str>,
+    cancel: &CancellationToken,
+    progress: &ProgressReporter,
 ) -> Result<GroupedAnalysisResult, String> {
+    let check_cancelled = || {
+        if cancel.is_cancelled() {
+            Err(super::CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
+    };
+
+    // One request per high-volume channel chunk, plus the cross-channel pass
+    let total_requests = messages_by_channel
+        .values()
+        .filter(|messages| messages.len() >= HIERARCHICAL_CHANNEL_THRESHOLD)
+        .map(|messages| messages.len().div_ceil(HIERARCHICAL_CHANNEL_CHUNK_SIZE))
+        .sum::<usize>()
+        + 1;
+    let mut requests_done = 0;
+
     let mut channel_summaries: Vec<ChannelSummary> = Vec::new();
     let mut small_channel_messages: Vec<MessageForPrompt> = Vec::new();
 
@@ -36,16 +60,32 @@ pub async fn process_hierarchical(
             );
 
             if messages.len() > HIERARCHICAL_CHANNEL_CHUNK_SIZE {
+                let chunk_count = messages.len().div_ceil(HIERARCHICAL_CHANNEL_CHUNK_SIZE);
                 for (chunk_index, chunk) in messages.chunks(HIERARCHICAL_CHANNEL_CHUNK_SIZE).enumerate()
                 {
                     tracing::debug!(
                         "Channel {} chunk {}/{} ({} messages)",
                         channel,
                         chunk_index + 1,
-                        messages.len().div_ceil(HIERARCHICAL_CHANNEL_CHUNK_SIZE),
+                        chunk_count,
                         chunk.len()
                     );
 
+                    check_cancelled()?;
+                    progress
+                        .report(
+                            requests_done as f32 / total_requests as f32,
+                            format!(
+                                "Summarizing {} (chunk {}/{}) · {}/{} AI requests",
+                                channel,
+                                chunk_index + 1,
+                                chunk_count,
+                                requests_done,
+                                total_requests
+                            ),
+                        )
+                        .await;
+                    requests_done += 1;
                     let messages_json =
                         serde_json::to_string_pretty(chunk).map_err(|e| e.to_string())?;
                     let prompt = prompts::channel_summary_prompt(
@@ -72,6 +112,17 @@ pub async fn process_hierarchical(
                     }
                 }
             } else {
+                check_cancelled()?;
+                progress
+                    .report(
+                        requests_done as f32 / total_requests as f32,
+                        format!(
+                            "Summarizing {} · {}/{} AI requests",
+                            channel, requests_done, total_requests
+                        ),
+                    )
+                    .await;
+                requests_done += 1;
                 let messages_json =
                     serde_json::to_string_pretty(&messages).map_err(|e| e.to_string())?;
                 let prompt =
@@ -100,6 +151,17 @@ pub async fn process_hierarchical(
         small_channel_messages.len()
     );
 
+    check_cancelled()?;
+    progress
+        .report(
+            requests_done as f32 / total_requests as f32,
+            format!(
+                "Grouping topics across channels · {}/{} AI requests",
+                requests_done, total_requests
+            ),
+        )
+        .await;
+
     // Build the cross-channel grouping prompt
     let channel_summaries_json =
         serde_json::to_string_pretty(&channel_summaries).map_err(|e| e.to_string())?;
@@ -115,6 +177,7 @@ pub async fn process_hierarchical(
         &channel_summaries_json,
         ungrouped_json.as_deref(),
         user_guidance,
+        meetings_json,
     );
 
     let result: GroupedAnalysisResult = gemini
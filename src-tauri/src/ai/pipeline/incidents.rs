@@ -0,0 +1,113 @@
+//! Incident prioritization
+//!
+//! Active and high-severity incidents get a minimum importance and the
+//! engineering category after AI analysis, so on-call work isn't ranked below
+//! routine discussion when the model underrates a terse incident timeline.
+
+use super::super::prompts::GroupedAnalysisResult;
+use std::collections::HashMap;
+
+/// Incident state copied into the metadata of every incident content item
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IncidentFlags {
+    pub active: bool,
+    pub high_severity: bool,
+}
+
+impl IncidentFlags {
+    pub fn from_metadata(metadata: Option<&str>) -> Self {
+        let metadata: serde_json::Value = metadata
+            .and_then(|m| serde_json::from_str(m).ok())
+            .unwrap_or_default();
+        Self {
+            active: metadata["active"].as_bool().unwrap_or(false),
+            high_severity: metadata["high_severity"].as_bool().unwrap_or(false),
+        }
+    }
+
+    /// Minimum importance; resolved low-severity incidents keep the AI's score
+    fn importance_floor(&self) -> Option<f64> {
+        match (self.active, self.high_severity) {
+            (true, true) => Some(0.95),
+            (false, true) => Some(0.8),
+            (true, false) => Some(0.7),
+            (false, false) => None,
+        }
+    }
+}
+
+/// Raise groups and ungrouped items containing active or high-severity
+/// incidents. `incidents` maps content item ids to their incident state.
+pub fn prioritize_incidents(
+    result: &mut GroupedAnalysisResult,
+    incidents: &HashMap<String, IncidentFlags>,
+) {
+    if incidents.is_empty() {
+        return;
+    }
+    let floor_for = |ids: &mut dyn Iterator<Item = &String>| {
+        ids.filter_map(|id| incidents.get(id)?.importance_floor())
+            .reduce(f64::max)
+    };
+
+    for group in &mut result.groups {
+        if let Some(floor) = floor_for(&mut group.message_ids.iter()) {
+            group.importance_score = group.importance_score.max(floor);
+            group.category = "engineering".to_string();
+        }
+    }
+    for item in &mut result.ungrouped {
+        if let Some(floor) = floor_for(&mut std::iter::once(&item.message_id)) {
+            item.importance_score = item.importance_score.max(floor);
+            item.category = "engineering".to_string();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prioritize_incidents() {
+        let mut result: GroupedAnalysisResult = serde_json::from_value(serde_json::json!({
+            "groups": [
+                {"topic": "Checkout outage", "channels": ["Checkout"], "summary": "s",
+                 "highlights": [], "category": "other", "importance_score": 0.4,
+                 "message_ids": ["m1", "inc-1"], "people": []},
+                {"topic": "Lunch", "channels": ["#random"], "summary": "s",
+                 "highlights": [], "category": "other", "importance_score": 0.1,
+                 "message_ids": ["m2"], "people": []}
+            ],
+            "ungrouped": [
+                {"message_id": "inc-2", "summary": "s", "category": "other", "importance_score": 0.9}
+            ],
+            "daily_summary": "", "key_themes": [], "action_items": []
+        }))
+        .unwrap();
+        let incidents = HashMap::from([
+            (
+                "inc-1".to_string(),
+                IncidentFlags::from_metadata(Some(r#"{"active": true, "high_severity": true}"#)),
+            ),
+            (
+                "inc-2".to_string(),
+                IncidentFlags {
+                    active: false,
+                    high_severity: true,
+                },
+            ),
+        ]);
+
+        prioritize_incidents(&mut result, &incidents);
+
+        assert_eq!(result.groups[0].importance_score, 0.95);
+        assert_eq!(result.groups[0].category, "engineering");
+        assert_eq!(result.groups[1].importance_score, 0.1);
+        assert_eq!(result.groups[1].category, "other");
+        // An existing higher score is kept
+        assert_eq!(result.ungrouped[0].importance_score, 0.9);
+        assert_eq!(result.ungrouped[0].category, "engineering");
+        assert_eq!(IncidentFlags::from_metadata(None), IncidentFlags::default());
+    }
+}
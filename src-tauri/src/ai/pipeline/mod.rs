@@ -1,11 +1,13 @@
 mod hierarchical;
+mod incidents;
 mod storage;
 mod topics;
 mod types;
 
+use incidents::{prioritize_incidents, IncidentFlags};
 pub use topics::{convert_existing_topics, generate_topic_id, merge_message_ids};
 pub use types::{
-    ContentItemRow, ExistingTopicRow, MessageForPrompt, SlackUserRow, HISTORICAL_AI_CHUNK_SIZE,
+    ContentItemRow, ExistingTopicRow, MeetingForPrompt, MessageForPrompt, SlackUserRow, HISTORICAL_AI_CHUNK_SIZE,
     HIERARCHICAL_CHANNEL_CHUNK_SIZE, HIERARCHICAL_CHANNEL_THRESHOLD, HIERARCHICAL_TOTAL_THRESHOLD,
 };
 
@@ -13,15 +15,21 @@ use super::gemini::{GeminiClient, ServiceAccountCredentials};
 use super::prompts::{self, ExistingTopic, GroupedAnalysisResult};
 use crate::crypto::CryptoService;
 use crate::db::Database;
+use crate::pipeline::{CancellationToken, ProgressReporter};
 use chrono::Utc;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+/// Error returned when processing stops because its task was cancelled.
+pub const CANCELLED: &str = "Cancelled";
+
 /// Main AI processing pipeline for content analysis.
 pub struct ProcessingPipeline {
     gemini: GeminiClient,
     db: Arc<Database>,
     crypto: Arc<CryptoService>,
+    cancel: CancellationToken,
+    progress: ProgressReporter,
 }
 
 impl ProcessingPipeline {
@@ -48,7 +56,36 @@ impl ProcessingPipeline {
             GeminiClient::new(api_key_or_credentials)
         };
 
-        Self { gemini, db, crypto }
+        Self {
+            gemini,
+            db,
+            crypto,
+            cancel: CancellationToken::new(),
+            progress: ProgressReporter::default(),
+        }
+    }
+
+    /// Stop before the next AI request once `cancel` is cancelled.
+    ///
+    /// Results are only stored per completed batch or chunk, so cancelled work
+    /// leaves its items unprocessed for the next run.
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Report which channel, chunk or pass is being sent to Gemini.
+    pub fn with_progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    fn check_cancelled(&self) -> Result<(), String> {
+        if self.cancel.is_cancelled() {
+            Err(CANCELLED.to_string())
+        } else {
+            Ok(())
+        }
     }
 
     /// Load user guidance from preferences.
@@ -69,6 +106,54 @@ impl ProcessingPipeline {
         })
     }
 
+    /// Load calendar events starting within `[start_ts, end_ts)` as prompt JSON.
+    ///
+    /// Returns `None` when there are no meetings so prompts omit the section.
+    async fn load_meetings(&self, start_ts: i64, end_ts: i64) -> Option<String> {
+        let rows: Vec<(Option<String>, i64, Option<String>)> = sqlx::query_as(
+            "SELECT title, created_at, metadata FROM content_items
+             WHERE source = 'calendar' AND created_at >= ? AND created_at < ?
+             ORDER BY created_at ASC",
+        )
+        .bind(start_ts)
+        .bind(end_ts)
+        .fetch_all(self.db.pool())
+        .await
+        .ok()?;
+
+        let meetings: Vec<MeetingForPrompt> = rows
+            .into_iter()
+            .map(|(title, start, metadata)| MeetingForPrompt::from_row(title, start, metadata))
+            .collect();
+
+        if meetings.is_empty() {
+            return None;
+        }
+        serde_json::to_string_pretty(&meetings).ok()
+    }
+
+    /// Load the incident state of the incident items among `items`, keyed by item id.
+    async fn load_incident_flags(
+        &self,
+        items: &[ContentItemRow],
+    ) -> HashMap<String, IncidentFlags> {
+        let mut flags = HashMap::new();
+        for item in items.iter().filter(|item| item.source == "incident") {
+            let metadata: Option<(Option<String>,)> =
+                sqlx::query_as("SELECT metadata FROM content_items WHERE id = ?")
+                    .bind(&item.id)
+                    .fetch_optional(self.db.pool())
+                    .await
+                    .ok()
+                    .flatten();
+            flags.insert(
+                item.id.clone(),
+                IncidentFlags::from_metadata(metadata.and_then(|m| m.0).as_deref()),
+            );
+        }
+        flags
+    }
+
     /// Load Slack user ID to display name mapping.
     async fn load_user_map(&self) -> Result<HashMap<String, String>, String> {
         let users: Vec<SlackUserRow> =
@@ -125,7 +210,8 @@ impl ProcessingPipeline {
                     ci.author_id, ci.channel_or_project, ci.source_url, ci.parent_id, ci.created_at
              FROM content_items ci
              LEFT JOIN ai_summaries s ON ci.id = s.content_item_id
-             WHERE s.id IS NULL AND ci.created_at >= ? AND ci.created_at < ?
+             WHERE s.id IS NULL AND ci.source != 'calendar'
+               AND ci.created_at >= ? AND ci.created_at < ?
              ORDER BY ci.created_at ASC",
         )
         .bind(start_ts)
@@ -175,8 +261,11 @@ impl ProcessingPipeline {
             return Ok(0);
         }
 
+        self.check_cancelled()?;
+
         // Decide processing strategy
-        let result = if messages_for_prompt.len() >= HIERARCHICAL_TOTAL_THRESHOLD {
+        let meetings = self.load_meetings(start_ts, end_ts).await;
+        let mut result = if messages_for_prompt.len() >= HIERARCHICAL_TOTAL_THRESHOLD {
             tracing::info!(
                 "Using hierarchical summarization for {} messages",
                 messages_for_prompt.len()
@@ -195,18 +284,28 @@ impl ProcessingPipeline {
                 &date_str,
                 messages_by_channel,
                 user_guidance.as_deref(),
+                meetings.as_deref(),
+                &self.cancel,
+                &self.progress,
             )
             .await?
         } else {
+            self.progress
+                .message(format!("Analyzing {} messages", messages_for_prompt.len()))
+                .await;
             self.process_batch_direct(
                 &date_str,
                 messages_for_prompt,
                 &existing_topics,
                 user_guidance.as_deref(),
+                meetings.as_deref(),
             )
             .await?
         };
 
+        let incidents = self.load_incident_flags(&items).await;
+        prioritize_incidents(&mut result, &incidents);
+
         // Store results
         let stored_count = storage::store_results(
             self.db.pool(),
@@ -288,6 +387,7 @@ impl ProcessingPipeline {
         messages_for_prompt: Vec<MessageForPrompt>,
         existing_topics: &[ExistingTopic],
         user_guidance: Option<&str>,
+        meetings_json: Option<&str>,
     ) -> Result<GroupedAnalysisResult, String> {
         let messages_json =
             serde_json::to_string_pretty(&messages_for_prompt).map_err(|e| e.to_string())?;
@@ -298,6 +398,7 @@ impl ProcessingPipeline {
                 &messages_json,
                 None,
                 user_guidance,
+                meetings_json,
             )
         } else {
             let existing_topics_json =
@@ -307,6 +408,7 @@ impl ProcessingPipeline {
                 &messages_json,
                 Some(&existing_topics_json),
                 user_guidance,
+                meetings_json,
             )
         };
 
@@ -347,11 +449,13 @@ impl ProcessingPipeline {
 
         let user_map = self.load_user_map().await.unwrap_or_default();
         let user_guidance = self.load_user_guidance().await;
+        let meetings = self.load_meetings(start_ts, end_ts).await;
         let mut total_stored = 0;
         let mut chunk_index = 0;
         let mut cursor: Option<(i64, String)> = None;
 
         loop {
+            self.check_cancelled()?;
             let items: Vec<ContentItemRow> = if let Some((cursor_created_at, ref cursor_id)) = cursor
             {
                 sqlx::query_as(
@@ -359,7 +463,7 @@ impl ProcessingPipeline {
                             ci.author_id, ci.channel_or_project, ci.source_url, ci.parent_id, ci.created_at
                      FROM content_items ci
                      LEFT JOIN ai_summaries s ON ci.id = s.content_item_id
-                     WHERE s.id IS NULL
+                     WHERE s.id IS NULL AND ci.source != 'calendar'
                        AND ci.created_at >= ? AND ci.created_at < ?
                        AND (ci.created_at > ? OR (ci.created_at = ? AND ci.id > ?))
                      ORDER BY ci.created_at ASC, ci.id ASC
@@ -380,7 +484,8 @@ impl ProcessingPipeline {
                             ci.author_id, ci.channel_or_project, ci.source_url, ci.parent_id, ci.created_at
                      FROM content_items ci
                      LEFT JOIN ai_summaries s ON ci.id = s.content_item_id
-                     WHERE s.id IS NULL AND ci.created_at >= ? AND ci.created_at < ?
+                     WHERE s.id IS NULL AND ci.source != 'calendar'
+                       AND ci.created_at >= ? AND ci.created_at < ?
                      ORDER BY ci.created_at ASC, ci.id ASC
                      LIMIT ?",
                 )
@@ -435,7 +540,7 @@ impl ProcessingPipeline {
                 continue;
             }
 
-            let result = if messages_for_prompt.len() >= HIERARCHICAL_TOTAL_THRESHOLD {
+            let mut result = if messages_for_prompt.len() >= HIERARCHICAL_TOTAL_THRESHOLD {
                 tracing::info!(
                     "Using hierarchical summarization for {} messages",
                     messages_for_prompt.len()
@@ -454,18 +559,33 @@ impl ProcessingPipeline {
                     date_str,
                     messages_by_channel,
                     user_guidance.as_deref(),
+                    meetings.as_deref(),
+                    &self.cancel,
+                    &self.progress,
                 )
                 .await?
             } else {
+                self.progress
+                    .message(format!(
+                        "Analyzing chunk {} for {} ({} messages)",
+                        chunk_index,
+                        date_str,
+                        messages_for_prompt.len()
+                    ))
+                    .await;
                 self.process_batch_direct(
                     date_str,
                     messages_for_prompt,
                     &existing_topics,
                     user_guidance.as_deref(),
+                    meetings.as_deref(),
                 )
                 .await?
             };
 
+            let incidents = self.load_incident_flags(&items).await;
+            prioritize_incidents(&mut result, &incidents);
+
             let stored_count = storage::store_results(
                 self.db.pool(),
                 &result,
@@ -488,6 +608,102 @@ impl ProcessingPipeline {
         Ok(total_stored)
     }
 
+    /// Summarize an imported meeting transcript.
+    ///
+    /// The meeting's segments form a single channel that always goes through
+    /// hierarchical summarization, so long meetings are condensed chunk by
+    /// chunk before topics and action items are extracted. Results merge into
+    /// the topics already stored for the meeting's local day.
+    pub async fn process_meeting(
+        &self,
+        meeting_id: &str,
+        timezone_offset_minutes: i32,
+    ) -> Result<i32, String> {
+        let items: Vec<ContentItemRow> = sqlx::query_as(
+            "SELECT ci.id, ci.source, ci.content_type, ci.title, ci.body, 
+                    ci.author_id, ci.channel_or_project, ci.source_url, ci.parent_id, ci.created_at
+             FROM content_items ci
+             LEFT JOIN ai_summaries s ON ci.id = s.content_item_id
+             WHERE s.id IS NULL AND ci.source = 'transcript'
+               AND (ci.source_id = ? OR ci.parent_id = ?)
+             ORDER BY ci.created_at ASC, ci.source_id ASC",
+        )
+        .bind(meeting_id)
+        .bind(meeting_id)
+        .fetch_all(self.db.pool())
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let Some(first) = items.first() else {
+            tracing::info!("No unprocessed segments for meeting {}", meeting_id);
+            return Ok(0);
+        };
+        let offset = chrono::FixedOffset::west_opt(timezone_offset_minutes * 60)
+            .unwrap_or(chrono::FixedOffset::east_opt(0).unwrap());
+        let day = chrono::DateTime::from_timestamp_millis(first.created_at)
+            .ok_or("Invalid meeting time")?
+            .with_timezone(&offset)
+            .date_naive();
+        let date_str = day.format("%Y-%m-%d").to_string();
+        let start_ts = day
+            .and_hms_opt(0, 0, 0)
+            .ok_or("Invalid date")?
+            .and_local_timezone(offset)
+            .single()
+            .ok_or("Ambiguous or invalid local time")?
+            .with_timezone(&Utc)
+            .timestamp_millis();
+        let end_ts = start_ts + 86400 * 1000;
+
+        let user_map = self.load_user_map().await.unwrap_or_default();
+        let user_guidance = self.load_user_guidance().await;
+
+        let existing_topic_rows: Vec<ExistingTopicRow> = sqlx::query_as(
+            "SELECT id, summary, category, importance_score, entities
+             FROM ai_summaries
+             WHERE summary_type = 'group' AND generated_at >= ? AND generated_at < ?
+             ORDER BY importance_score DESC",
+        )
+        .bind(start_ts)
+        .bind(end_ts)
+        .fetch_all(self.db.pool())
+        .await
+        .map_err(|e| e.to_string())?;
+        let (mut existing_message_ids_map, _) = convert_existing_topics(&existing_topic_rows);
+
+        let (messages_for_prompt, _item_ids) =
+            self.build_messages_for_prompt(&items, &user_map).await;
+        if messages_for_prompt.is_empty() {
+            return Ok(0);
+        }
+
+        tracing::info!(
+            "Summarizing meeting {} ({} segments)",
+            meeting_id,
+            messages_for_prompt.len()
+        );
+        let channel = messages_for_prompt[0].channel.clone();
+        let messages_by_channel = HashMap::from([(channel, messages_for_prompt)]);
+        let result = hierarchical::process_hierarchical(
+            &self.gemini,
+            &date_str,
+            messages_by_channel,
+            user_guidance.as_deref(),
+            None,
+            &self.cancel,
+            &self.progress,
+        )
+        .await?;
+
+        storage::store_results(
+            self.db.pool(),
+            &result,
+            &date_str,
+            &mut existing_message_ids_map,
+        )
+        .await
+    }
+
     /// Generate daily digest for a specific date.
     pub async fn generate_daily_digest(&self, date: &str) -> Result<String, String> {
         let parsed_date =
@@ -518,8 +734,14 @@ impl ProcessingPipeline {
         }
 
         let user_guidance = self.load_user_guidance().await;
+        let meetings = self.load_meetings(start_ts, end_ts).await;
         let items_json = serde_json::to_string_pretty(&items).unwrap();
-        let prompt = prompts::daily_digest_prompt(date, &items_json, user_guidance.as_deref());
+        let prompt = prompts::daily_digest_prompt(
+            date,
+            &items_json,
+            user_guidance.as_deref(),
+            meetings.as_deref(),
+        );
 
         let digest: prompts::DigestSummary = self
             .gemini
@@ -42,6 +42,54 @@ pub struct MessageForPrompt {
     pub thread_id: Option<String>,
 }
 
+/// Calendar event formatted for AI prompts
+#[derive(Clone, Serialize)]
+pub struct MeetingForPrompt {
+    pub title: String,
+    pub start: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attendees: Vec<String>,
+}
+
+impl MeetingForPrompt {
+    /// Build from a calendar `content_items` row; times use the same `HH:MM`
+    /// UTC format as message timestamps
+    pub fn from_row(title: Option<String>, start_ms: i64, metadata: Option<String>) -> Self {
+        let metadata: serde_json::Value = metadata
+            .and_then(|m| serde_json::from_str(&m).ok())
+            .unwrap_or_default();
+        let all_day = metadata["all_day"].as_bool().unwrap_or(false);
+        let time = |ms: i64| {
+            chrono::DateTime::from_timestamp_millis(ms)
+                .map(|dt| dt.format("%H:%M").to_string())
+                .unwrap_or_default()
+        };
+
+        Self {
+            title: title.unwrap_or_else(|| "(untitled)".to_string()),
+            start: if all_day {
+                "all day".to_string()
+            } else {
+                time(start_ms)
+            },
+            end: metadata["end"].as_i64().filter(|_| !all_day).map(time),
+            location: metadata["location"].as_str().map(String::from),
+            attendees: metadata["attendees"]
+                .as_array()
+                .map(|a| {
+                    a.iter()
+                        .filter_map(|v| v.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
 /// Database row for existing topic summaries
 #[derive(sqlx::FromRow)]
 pub struct ExistingTopicRow {
@@ -72,6 +120,28 @@ mod tests {
         assert_eq!(HIERARCHICAL_CHANNEL_CHUNK_SIZE, 80);
     }
 
+    #[test]
+    fn test_meeting_for_prompt_from_row() {
+        let metadata = r#"{"end": 1705334400000, "all_day": false, "location": "Room 4", "attendees": ["Alice", "Bob"]}"#;
+        let meeting = MeetingForPrompt::from_row(
+            Some("Architecture review".to_string()),
+            1705330800000,
+            Some(metadata.to_string()),
+        );
+        assert_eq!(meeting.start, "15:00");
+        assert_eq!(meeting.end.as_deref(), Some("16:00"));
+        assert_eq!(meeting.location.as_deref(), Some("Room 4"));
+        assert_eq!(meeting.attendees, vec!["Alice", "Bob"]);
+
+        let all_day = MeetingForPrompt::from_row(
+            None,
+            1705276800000,
+            Some(r#"{"all_day": true}"#.to_string()),
+        );
+        assert_eq!(all_day.start, "all day");
+        assert_eq!(all_day.end, None);
+    }
+
     #[test]
     fn test_message_for_prompt_clone() {
         let original = MessageForPrompt {
@@ -18,6 +18,23 @@ Consider this guidance when scoring importance and selecting key information.
     }
 }
 
+fn format_meetings(meetings_json: Option<&str>, date: &str) -> String {
+    match meetings_json {
+        Some(meetings) if !meetings.trim().is_empty() && meetings.trim() != "[]" => format!(
+            r##"
+MEETINGS ON {date}:
+These calendar events took place that day (times in UTC, matching message timestamps):
+
+{meetings}
+
+Use them as context. When messages relate to a meeting - preparation before it, discussion during it, or follow-up after it - name the meeting in the summary (e.g., "the debate after the 14:00 architecture review"). Do not create topics or items for meetings on their own.
+
+"##
+        ),
+        _ => String::new(),
+    }
+}
+
 /// Generate a prompt for analyzing Slack messages.
 pub fn slack_message_prompt(channel: &str, messages: &str) -> String {
     format!(
@@ -105,13 +122,21 @@ Return JSON with this structure:
 }
 
 /// Generate a prompt for creating a daily digest.
-pub fn daily_digest_prompt(date: &str, items_json: &str, user_guidance: Option<&str>) -> String {
+///
+/// `meetings_json` lists the day's calendar events so the digest can tie items to meetings.
+pub fn daily_digest_prompt(
+    date: &str,
+    items_json: &str,
+    user_guidance: Option<&str>,
+    meetings_json: Option<&str>,
+) -> String {
     let guidance_section = format_user_guidance(user_guidance);
+    let meetings_section = format_meetings(meetings_json, date);
     format!(
         r#"Create a daily digest summary for {date} from these items:
 
 {items_json}
-{guidance_section}
+{meetings_section}{guidance_section}
 Return JSON with this structure:
 {{
   "summary": "3-4 sentence executive summary of the day's key activities",
@@ -189,20 +214,23 @@ Guidelines:
 ///
 /// This is a convenience wrapper around `batch_analysis_prompt_with_existing` with no existing topics.
 pub fn batch_analysis_prompt(date: &str, messages_json: &str) -> String {
-    batch_analysis_prompt_with_existing(date, messages_json, None, None)
+    batch_analysis_prompt_with_existing(date, messages_json, None, None, None)
 }
 
 /// Generate a prompt for batch analysis with optional existing topics.
 ///
 /// When existing topics are provided, the AI is instructed to merge new messages
-/// into existing topics where appropriate.
+/// into existing topics where appropriate. `meetings_json` lists the day's calendar
+/// events so discussions can be linked to the meetings they relate to.
 pub fn batch_analysis_prompt_with_existing(
     date: &str,
     messages_json: &str,
     existing_topics: Option<&str>,
     user_guidance: Option<&str>,
+    meetings_json: Option<&str>,
 ) -> String {
     let guidance_section = format_user_guidance(user_guidance);
+    let meetings_section = format_meetings(meetings_json, date);
     let existing_context = if let Some(topics_json) = existing_topics {
         format!(
             r##"
@@ -233,7 +261,7 @@ IMPORTANT MERGING RULES:
 
     format!(
         r##"You are analyzing all messages from {date} across multiple Slack channels and direct messages.
-{existing_context}{guidance_section}Your task is to:
+{existing_context}{meetings_section}{guidance_section}Your task is to:
 1. Identify related discussions that span multiple channels (e.g., a product launch discussed in #product, #marketing, and #sales)
 2. Group related messages together by topic/theme
 3. Summarize each group
@@ -279,6 +307,7 @@ Guidelines:
 - A single message can only belong to ONE group (use message_ids to track)
 - Low-content messages (just emojis, "ok", "thanks") should go in ungrouped with low importance
 - importance_score: 0.9-1.0 for critical business decisions, 0.6-0.8 for important updates, 0.3-0.5 for routine, 0.0-0.2 for noise
+- Incident items (status changes and notes from the incident tool, e.g. "Incident #1234 acknowledged") belong in engineering; active (triggered or acknowledged) and high-urgency or P1/P2 incidents score 0.8-1.0, and resolved low-urgency ones are routine
 - Identify action items that emerge from discussions
 - The daily_summary should give an executive the key takeaways in 30 seconds
 - topic_id: When updating an existing topic, copy the exact topic_id string from the existing topics list. For new topics, set topic_id to null
@@ -326,12 +355,14 @@ Guidelines:
 /// Generate a prompt for cross-channel grouping.
 ///
 /// Used as the second pass in hierarchical summarization to combine
-/// channel summaries into topic groups.
+/// channel summaries into topic groups. `meetings_json` lists the day's
+/// calendar events, as for the direct batch prompt.
 pub fn cross_channel_grouping_prompt(
     date: &str,
     channel_summaries_json: &str,
     ungrouped_messages_json: Option<&str>,
     user_guidance: Option<&str>,
+    meetings_json: Option<&str>,
 ) -> String {
     let ungrouped_section = ungrouped_messages_json
         .map(|json| {
@@ -344,13 +375,14 @@ MESSAGES FROM LOW-VOLUME CHANNELS (process directly):
         })
         .unwrap_or_default();
     let guidance_section = format_user_guidance(user_guidance);
+    let meetings_section = format_meetings(meetings_json, date);
 
     format!(
         r##"You are creating a daily digest for {date} by combining summaries from multiple Slack channels.
 
 CHANNEL SUMMARIES:
 {channel_summaries_json}
-{ungrouped_section}{guidance_section}Your task is to:
+{ungrouped_section}{meetings_section}{guidance_section}Your task is to:
 1. Identify topics that span multiple channels (cross-channel themes)
 2. Group related channel discussions together
 3. Create an executive summary of the entire day
@@ -439,7 +471,7 @@ mod tests {
 
     #[test]
     fn test_daily_digest_prompt_contains_date() {
-        let prompt = daily_digest_prompt("2024-01-15", r#"[{"summary": "test"}]"#, None);
+        let prompt = daily_digest_prompt("2024-01-15", r#"[{"summary": "test"}]"#, None, None);
         assert!(prompt.contains("2024-01-15"));
         assert!(prompt.contains("daily digest"));
     }
@@ -472,6 +504,7 @@ mod tests {
             messages,
             Some(existing_topics),
             None,
+            None,
         );
 
         assert!(prompt.contains("EXISTING TOPICS FROM EARLIER TODAY"));
@@ -483,7 +516,7 @@ mod tests {
     fn test_batch_analysis_prompt_without_existing_topics() {
         let messages = r##"[{"id": "1", "channel": "#general", "text": "Hello"}]"##;
 
-        let prompt = batch_analysis_prompt_with_existing("2024-01-15", messages, None, None);
+        let prompt = batch_analysis_prompt_with_existing("2024-01-15", messages, None, None, None);
 
         assert!(!prompt.contains("EXISTING TOPICS FROM EARLIER TODAY"));
         assert!(!prompt.contains("MERGING RULES"));
@@ -494,7 +527,7 @@ mod tests {
         let messages = r##"[{"id": "1", "channel": "#test", "text": "Test"}]"##;
 
         let prompt1 = batch_analysis_prompt("2024-01-15", messages);
-        let prompt2 = batch_analysis_prompt_with_existing("2024-01-15", messages, None, None);
+        let prompt2 = batch_analysis_prompt_with_existing("2024-01-15", messages, None, None, None);
 
         assert!(!prompt1.contains("EXISTING TOPICS"));
         assert!(!prompt2.contains("EXISTING TOPICS"));
@@ -505,12 +538,12 @@ mod tests {
         let messages = r##"[{"id": "1", "channel": "#test", "text": "Test"}]"##;
 
         let prompt_without =
-            batch_analysis_prompt_with_existing("2024-01-15", messages, None, None);
+            batch_analysis_prompt_with_existing("2024-01-15", messages, None, None, None);
         assert!(prompt_without.contains(r#""topic_id": null"#));
 
         let existing = r##"[{"topic_id": "t1", "topic": "Test"}]"##;
         let prompt_with =
-            batch_analysis_prompt_with_existing("2024-01-15", messages, Some(existing), None);
+            batch_analysis_prompt_with_existing("2024-01-15", messages, Some(existing), None, None);
         assert!(prompt_with.contains(r#""topic_id": "topic_abc123""#));
     }
 
@@ -538,7 +571,7 @@ mod tests {
 
     #[test]
     fn test_cross_channel_grouping_prompt_contains_date() {
-        let prompt = cross_channel_grouping_prompt("2024-01-20", "[]", None, None);
+        let prompt = cross_channel_grouping_prompt("2024-01-20", "[]", None, None, None);
         assert!(prompt.contains("2024-01-20"));
         assert!(prompt.contains("CHANNEL SUMMARIES"));
     }
@@ -546,14 +579,14 @@ mod tests {
     #[test]
     fn test_cross_channel_grouping_prompt_with_ungrouped() {
         let prompt =
-            cross_channel_grouping_prompt("2024-01-20", "[]", Some("[{\"id\":\"1\"}]"), None);
+            cross_channel_grouping_prompt("2024-01-20", "[]", Some("[{\"id\":\"1\"}]"), None, None);
         assert!(prompt.contains("MESSAGES FROM LOW-VOLUME CHANNELS"));
         assert!(prompt.contains("[{\"id\":\"1\"}]"));
     }
 
     #[test]
     fn test_cross_channel_grouping_prompt_without_ungrouped() {
-        let prompt = cross_channel_grouping_prompt("2024-01-20", "[]", None, None);
+        let prompt = cross_channel_grouping_prompt("2024-01-20", "[]", None, None, None);
         assert!(!prompt.contains("MESSAGES FROM LOW-VOLUME CHANNELS"));
     }
 
@@ -564,6 +597,7 @@ mod tests {
             "[]",
             None,
             Some("Focus on production issues"),
+            None,
         );
         assert!(prompt.contains("USER PREFERENCES"));
         assert!(prompt.contains("Focus on production issues"));
@@ -571,23 +605,59 @@ mod tests {
 
     #[test]
     fn test_format_user_guidance_empty() {
-        let prompt = batch_analysis_prompt_with_existing("2024-01-15", "[]", None, Some(""));
+        let prompt = batch_analysis_prompt_with_existing("2024-01-15", "[]", None, Some(""), None);
         assert!(!prompt.contains("USER PREFERENCES"));
     }
 
     #[test]
     fn test_format_user_guidance_none() {
-        let prompt = batch_analysis_prompt_with_existing("2024-01-15", "[]", None, None);
+        let prompt = batch_analysis_prompt_with_existing("2024-01-15", "[]", None, None, None);
         assert!(!prompt.contains("USER PREFERENCES"));
     }
 
     #[test]
     fn test_daily_digest_with_guidance() {
-        let prompt = daily_digest_prompt("2024-01-15", "[]", Some("Highlight customer feedback"));
+        let prompt = daily_digest_prompt(
+            "2024-01-15",
+            "[]",
+            Some("Highlight customer feedback"),
+            None,
+        );
         assert!(prompt.contains("USER PREFERENCES"));
         assert!(prompt.contains("Highlight customer feedback"));
     }
 
+    #[test]
+    fn test_daily_digest_with_meetings() {
+        let meetings = r#"[{"title": "Architecture review", "start": "14:00", "end": "15:00"}]"#;
+        let prompt = daily_digest_prompt("2024-01-15", "[]", None, Some(meetings));
+        assert!(prompt.contains("MEETINGS ON 2024-01-15"));
+        assert!(prompt.contains("Architecture review"));
+
+        let prompt = daily_digest_prompt("2024-01-15", "[]", None, Some("[]"));
+        assert!(!prompt.contains("MEETINGS ON"));
+    }
+
+    #[test]
+    fn test_batch_analysis_with_meetings() {
+        let meetings = r#"[{"title": "Sprint planning", "start": "09:30", "end": "10:30"}]"#;
+        let prompt =
+            batch_analysis_prompt_with_existing("2024-01-15", "[]", None, None, Some(meetings));
+        assert!(prompt.contains("MEETINGS ON 2024-01-15"));
+        assert!(prompt.contains("Sprint planning"));
+
+        let prompt = batch_analysis_prompt("2024-01-15", "[]");
+        assert!(!prompt.contains("MEETINGS ON"));
+    }
+
+    #[test]
+    fn test_cross_channel_grouping_with_meetings() {
+        let meetings = r#"[{"title": "Incident review", "start": "16:00", "end": "16:30"}]"#;
+        let prompt = cross_channel_grouping_prompt("2024-01-15", "[]", None, None, Some(meetings));
+        assert!(prompt.contains("MEETINGS ON 2024-01-15"));
+        assert!(prompt.contains("Incident review"));
+    }
+
     #[test]
     fn test_weekly_digest_with_guidance() {
         let prompt = weekly_digest_prompt("2024-01-08", "[]", Some("Focus on engineering updates"));
@@ -604,8 +674,13 @@ mod tests {
 
     #[test]
     fn test_cross_channel_with_guidance() {
-        let prompt =
-            cross_channel_grouping_prompt("2024-01-20", "[]", None, Some("Focus on sales topics"));
+        let prompt = cross_channel_grouping_prompt(
+            "2024-01-20",
+            "[]",
+            None,
+            Some("Focus on sales topics"),
+            None,
+        );
         assert!(prompt.contains("USER PREFERENCES"));
         assert!(prompt.contains("Focus on sales topics"));
     }
@@ -1,5 +1,6 @@
 use crate::ai::{GeminiClient, ServiceAccountCredentials};
-use crate::sync::{AtlassianClient, AtlassianTokens, CloudResource};
+use crate::sync::atlassian::connect_atlassian_oauth;
+use crate::sync::{AtlassianTokens, CloudResource, ConnectorContext};
 use crate::AppState;
 use std::sync::Arc;
 use tauri::State;
@@ -266,36 +267,14 @@ pub async fn connect_atlassian(
     client_id: String,
     client_secret: String,
 ) -> Result<(AtlassianTokens, Vec<CloudResource>), String> {
-    let client = AtlassianClient::new(client_id, client_secret);
-    let (tokens, resources) = client.start_oauth_flow().await.map_err(|e| e.to_string())?;
-
-    // Store tokens
-    let state = state.lock().await;
-    let encrypted = state
-        .crypto
-        .encrypt_string(&serde_json::to_string(&tokens).unwrap())
-        .map_err(|e| e.to_string())?;
-
-    let now = chrono::Utc::now().timestamp();
-    sqlx::query(
-        "INSERT INTO credentials (id, service, encrypted_data, created_at, updated_at)
-         VALUES ('atlassian', 'atlassian', ?, ?, ?)
-         ON CONFLICT(id) DO UPDATE SET encrypted_data = ?, updated_at = ?",
-    )
-    .bind(&encrypted)
-    .bind(now)
-    .bind(now)
-    .bind(&encrypted)
-    .bind(now)
-    .execute(state.db.pool())
-    .await
-    .map_err(|e| e.to_string())?;
+    let ctx = {
+        let state = state.lock().await;
+        ConnectorContext::new(state.db.clone(), Arc::new(state.crypto.clone()))
+    };
 
-    tracing::info!(
-        "Atlassian connected with {} cloud resources",
-        resources.len()
-    );
-    Ok((tokens, resources))
+    connect_atlassian_oauth(&ctx, client_id, client_secret)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// Select an Atlassian cloud resource
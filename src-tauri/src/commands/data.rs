@@ -15,6 +15,17 @@ pub async fn get_pipeline_status(
     Ok(pipeline.get_state().await)
 }
 
+/// Ask a running pipeline task to stop. Returns false if it already finished.
+#[tauri::command]
+pub async fn cancel_task(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    task_id: String,
+) -> Result<bool, String> {
+    let state = state.lock().await;
+    let pipeline = state.pipeline.lock().await;
+    Ok(pipeline.cancel_task(&task_id).await)
+}
+
 /// Get database statistics
 #[tauri::command]
 pub async fn get_data_stats(state: State<'_, Arc<Mutex<AppState>>>) -> Result<DataStats, String> {
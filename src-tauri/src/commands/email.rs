@@ -0,0 +1,99 @@
+//! Email-specific commands
+
+use crate::pipeline::PipelineTaskType;
+use crate::sync::email::{
+    load_sender_filter, save_sender_filter, EmailArchiveImporter, EmailImportResult, SenderFilter,
+};
+use crate::AppState;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Get the sender allow/deny list applied to synced email
+#[tauri::command]
+pub async fn get_email_sender_filter(
+    state: State<'_, Arc<Mutex<AppState>>>,
+) -> Result<SenderFilter, String> {
+    let db = state.lock().await.db.clone();
+    load_sender_filter(&db).await.map_err(|e| e.to_string())
+}
+
+/// Replace the sender allow/deny list. Already synced messages are kept.
+#[tauri::command]
+pub async fn save_email_sender_filter(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    filter: SenderFilter,
+) -> Result<(), String> {
+    let db = state.lock().await.db.clone();
+
+    let filter = SenderFilter {
+        allow: filter
+            .allow
+            .iter()
+            .flat_map(|e| SenderFilter::parse_list(e))
+            .collect(),
+        deny: filter
+            .deny
+            .iter()
+            .flat_map(|e| SenderFilter::parse_list(e))
+            .collect(),
+    };
+    save_sender_filter(&db, &filter)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    tracing::info!(
+        "Saved email sender filter: {} allowed, {} denied",
+        filter.allow.len(),
+        filter.deny.len()
+    );
+    Ok(())
+}
+
+/// Import an mbox file, an `.eml` file or a folder of them as threaded email
+#[tauri::command]
+pub async fn import_email_archive(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    path: String,
+) -> Result<EmailImportResult, String> {
+    let (db, crypto, pipeline) = {
+        let state = state.lock().await;
+        (
+            state.db.clone(),
+            Arc::new(state.crypto.clone()),
+            state.pipeline.clone(),
+        )
+    };
+
+    let task_id = {
+        let pipeline = pipeline.lock().await;
+        pipeline
+            .start_task(
+                PipelineTaskType::SyncEmail,
+                "Importing email archive...".to_string(),
+            )
+            .await
+    };
+
+    let importer = EmailArchiveImporter::new(db, crypto);
+    match importer.import_path(&path).await {
+        Ok(result) => {
+            let pipeline = pipeline.lock().await;
+            pipeline
+                .complete_task(
+                    &task_id,
+                    Some(format!(
+                        "Imported {} emails in {} threads",
+                        result.messages_imported, result.threads_imported
+                    )),
+                )
+                .await;
+            Ok(result)
+        }
+        Err(e) => {
+            let pipeline = pipeline.lock().await;
+            pipeline.fail_task(&task_id, e.to_string()).await;
+            Err(e.to_string())
+        }
+    }
+}
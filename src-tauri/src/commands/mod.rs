@@ -9,14 +9,20 @@
 //! - `analytics` - Event tracking and analytics
 //! - `data` - Data management and factory reset
 //! - `slack` - Slack-specific commands
+//! - `sources` - Generic source connector commands
+//! - `email` - Email-specific commands
+//! - `transcript` - Meeting transcript import
 
 mod analytics;
 mod credentials;
 mod data;
 mod digest;
+mod email;
 mod preferences;
 mod slack;
+mod sources;
 mod sync;
+mod transcript;
 mod types;
 
 // Re-export all commands using wildcard to include Tauri's internal __cmd__ symbols
@@ -24,9 +30,12 @@ pub use analytics::*;
 pub use credentials::*;
 pub use data::*;
 pub use digest::*;
+pub use email::*;
 pub use preferences::*;
 pub use slack::*;
+pub use sources::*;
 pub use sync::*;
+pub use transcript::*;
 
 // Re-export types for use by other modules
 pub use types::{
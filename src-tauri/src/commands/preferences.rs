@@ -1,4 +1,5 @@
 use super::types::Preferences;
+use crate::sync::SyncTimetable;
 use crate::AppState;
 use std::sync::Arc;
 use tauri::State;
@@ -18,7 +19,7 @@ pub async fn get_preferences(
             .map_err(|e| e.to_string())?;
 
     match result {
-        Some((json,)) => serde_json::from_str(&json).map_err(|e| e.to_string()),
+        Some((json,)) => Preferences::from_json(&json),
         None => {
             // Return defaults if no preferences saved yet
             Ok(Preferences::default())
@@ -32,6 +33,8 @@ pub async fn save_preferences(
     state: State<'_, Arc<Mutex<AppState>>>,
     preferences: Preferences,
 ) -> Result<(), String> {
+    let timetable = SyncTimetable::compile(&preferences.sync_schedule)?;
+
     let (db, background_sync) = {
         let state = state.lock().await;
         (state.db.clone(), state.background_sync.clone())
@@ -46,9 +49,7 @@ pub async fn save_preferences(
         .map_err(|e| e.to_string())?;
 
     if let Some(bg_sync) = background_sync {
-        bg_sync
-            .set_interval(preferences.sync_interval_minutes as u64)
-            .await;
+        bg_sync.set_schedule(timetable).await;
     }
 
     Ok(())
@@ -62,7 +63,7 @@ mod tests {
     fn test_preferences_default_values() {
         let prefs = Preferences::default();
 
-        assert_eq!(prefs.sync_interval_minutes, 15);
+        assert!(SyncTimetable::compile(&prefs.sync_schedule).is_ok());
         assert!(prefs.notifications_enabled);
         assert!(prefs.enabled_sources.is_empty());
         assert!(!prefs.enabled_categories.is_empty());
@@ -71,7 +72,7 @@ mod tests {
     #[test]
     fn test_preferences_serialization_roundtrip() {
         let prefs = Preferences {
-            sync_interval_minutes: 30,
+            sync_schedule: crate::sync::SyncScheduleConfig::from_interval_minutes(30),
             enabled_sources: vec!["slack".to_string()],
             enabled_categories: vec!["engineering".to_string(), "product".to_string()],
             notifications_enabled: false,
@@ -81,7 +82,7 @@ mod tests {
         let json = serde_json::to_string(&prefs).unwrap();
         let restored: Preferences = serde_json::from_str(&json).unwrap();
 
-        assert_eq!(restored.sync_interval_minutes, 30);
+        assert_eq!(restored.sync_schedule, prefs.sync_schedule);
         assert!(!restored.notifications_enabled);
         assert_eq!(restored.enabled_sources, vec!["slack"]);
         assert_eq!(
@@ -112,13 +113,10 @@ mod tests {
     }
 
     #[test]
-    fn test_sync_interval_conversion() {
-        let prefs = Preferences {
-            sync_interval_minutes: 60,
-            ..Preferences::default()
-        };
+    fn test_invalid_schedule_rejected() {
+        let mut prefs = Preferences::default();
+        prefs.sync_schedule.entries = vec!["every 10 minutes".to_string()];
 
-        let interval_u64 = prefs.sync_interval_minutes as u64;
-        assert_eq!(interval_u64, 60);
+        assert!(SyncTimetable::compile(&prefs.sync_schedule).is_err());
     }
 }
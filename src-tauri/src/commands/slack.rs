@@ -1,7 +1,12 @@
 //! Slack integration commands
 
+use crate::ai::ProcessingPipeline;
+use crate::pipeline::PipelineTaskType;
+use crate::sync::connector::clear_deselected_scopes;
+use crate::sync::slack::SlackConnector;
 use crate::sync::{
-    SlackChannel, SlackChannelSelection, SlackClient, SlackConnectionStatus, SlackTokens, SlackUser,
+    ConnectorContext, SlackChannel, SlackChannelSelection, SlackClient, SlackConnectionStatus,
+    SlackExportImporter, SlackImportResult, SlackTokens, SlackUser, SourceConnector,
 };
 use crate::AppState;
 use std::sync::Arc;
@@ -25,84 +30,15 @@ pub async fn connect_slack(
     state: State<'_, Arc<Mutex<AppState>>>,
     token: String,
 ) -> Result<SlackTokens, String> {
-    let client = SlackClient::new(String::new(), String::new()).with_token(token.clone());
-
-    let auth_info = client.test_auth().await.map_err(|e| e.to_string())?;
-
-    let mut detected_scopes = Vec::new();
-
-    let scope_tests = [
-        ("public_channel", "channels:read"),
-        ("private_channel", "groups:read"),
-        ("im", "im:read"),
-        ("mpim", "mpim:read"),
-    ];
-
-    for (channel_type, scope_name) in scope_tests {
-        let resp = client
-            .http_client()
-            .get("https://slack.com/api/conversations.list")
-            .bearer_auth(&token)
-            .query(&[("types", channel_type), ("limit", "1")])
-            .send()
-            .await;
-        if let Ok(resp) = resp {
-            if let Ok(json) = resp.json::<serde_json::Value>().await {
-                if json["ok"].as_bool().unwrap_or(false) {
-                    detected_scopes.push(scope_name);
-                }
-            }
-        }
-    }
-
-    let resp = client
-        .http_client()
-        .get("https://slack.com/api/users.list")
-        .bearer_auth(&token)
-        .query(&[("limit", "1")])
-        .send()
-        .await;
-    if let Ok(resp) = resp {
-        if let Ok(json) = resp.json::<serde_json::Value>().await {
-            if json["ok"].as_bool().unwrap_or(false) {
-                detected_scopes.push("users:read");
-            }
-        }
-    }
-
-    let tokens = SlackTokens {
-        access_token: token,
-        token_type: "bearer".to_string(),
-        scope: detected_scopes.join(","),
-        team_id: auth_info.team_id,
-        team_name: auth_info.team_name,
-        team_domain: auth_info.team_domain,
-        user_id: auth_info.user_id,
+    let ctx = {
+        let state = state.lock().await;
+        ConnectorContext::new(state.db.clone(), Arc::new(state.crypto.clone()))
     };
 
-    let state = state.lock().await;
-    let encrypted = state
-        .crypto
-        .encrypt_string(&serde_json::to_string(&tokens).unwrap())
-        .map_err(|e| e.to_string())?;
-
-    let now = chrono::Utc::now().timestamp();
-    sqlx::query(
-        "INSERT INTO credentials (id, service, encrypted_data, created_at, updated_at)
-         VALUES ('slack', 'slack', ?, ?, ?)
-         ON CONFLICT(id) DO UPDATE SET encrypted_data = ?, updated_at = ?",
-    )
-    .bind(&encrypted)
-    .bind(now)
-    .bind(now)
-    .bind(&encrypted)
-    .bind(now)
-    .execute(state.db.pool())
-    .await
-    .map_err(|e| e.to_string())?;
-
-    tracing::info!("Slack connected for team: {}", tokens.team_name);
-    Ok(tokens)
+    SlackConnector
+        .connect_token(&ctx, token)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -196,6 +132,15 @@ pub async fn save_slack_channels(
         .map_err(|e| e.to_string())?;
     }
 
+    let enabled: Vec<String> = channels
+        .iter()
+        .filter(|c| c.enabled)
+        .map(|c| c.channel_id.clone())
+        .collect();
+    clear_deselected_scopes(&state.db, "slack", "channel", &enabled)
+        .await
+        .map_err(|e| e.to_string())?;
+
     tracing::info!("Saved {} Slack channels for syncing", channels.len());
     Ok(())
 }
@@ -245,6 +190,16 @@ pub async fn remove_slack_channel(
         .await
         .map_err(|e| e.to_string())?;
 
+    let enabled: Vec<(String,)> =
+        sqlx::query_as("SELECT channel_id FROM slack_selected_channels WHERE enabled = 1")
+            .fetch_all(state.db.pool())
+            .await
+            .map_err(|e| e.to_string())?;
+    let enabled: Vec<String> = enabled.into_iter().map(|r| r.0).collect();
+    clear_deselected_scopes(&state.db, "slack", "channel", &enabled)
+        .await
+        .map_err(|e| e.to_string())?;
+
     tracing::info!("Removed Slack channel from sync: {}", channel_id);
     Ok(())
 }
@@ -296,22 +251,154 @@ pub async fn get_slack_connection_status(
 
 #[tauri::command]
 pub async fn disconnect_slack(state: State<'_, Arc<Mutex<AppState>>>) -> Result<(), String> {
-    let state = state.lock().await;
+    let ctx = {
+        let state = state.lock().await;
+        ConnectorContext::new(state.db.clone(), Arc::new(state.crypto.clone()))
+    };
 
-    sqlx::query("DELETE FROM credentials WHERE id = 'slack'")
-        .execute(state.db.pool())
+    SlackConnector
+        .disconnect(&ctx)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string())
+}
 
-    sqlx::query("DELETE FROM slack_selected_channels")
-        .execute(state.db.pool())
-        .await
-        .map_err(|e| e.to_string())?;
+/// Import a Slack workspace export ZIP from disk. Messages are stored exactly as the
+/// live sync stores them; with `process_ai` each imported day is summarized afterwards.
+#[tauri::command]
+pub async fn import_slack_export(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    path: String,
+    timezone_offset: i32,
+    process_ai: bool,
+    channels: Option<Vec<String>>,
+) -> Result<SlackImportResult, String> {
+    let (db, crypto, pipeline) = {
+        let state = state.lock().await;
+        (
+            state.db.clone(),
+            Arc::new(state.crypto.clone()),
+            state.pipeline.clone(),
+        )
+    };
 
-    sqlx::query("DELETE FROM sync_state WHERE source = 'slack'")
-        .execute(state.db.pool())
+    // Reuse the connected workspace domain so imported permalinks match synced ones
+    let encrypted: Option<(String,)> =
+        sqlx::query_as("SELECT encrypted_data FROM credentials WHERE id = 'slack'")
+            .fetch_optional(db.pool())
+            .await
+            .map_err(|e| e.to_string())?;
+    let team_domain = encrypted
+        .and_then(|(data,)| crypto.decrypt_string(&data).ok())
+        .and_then(|json| serde_json::from_str::<SlackTokens>(&json).ok())
+        .and_then(|tokens| tokens.team_domain);
+
+    let task_id = {
+        let pipeline = pipeline.lock().await;
+        pipeline
+            .start_task(
+                PipelineTaskType::SyncSlack,
+                "Importing Slack export...".to_string(),
+            )
+            .await
+    };
+
+    let importer =
+        SlackExportImporter::new(db.clone(), crypto.clone()).with_team_domain(team_domain);
+    let result = match importer.import_file(&path, channels.as_deref()).await {
+        Ok(result) => result,
+        Err(e) => {
+            pipeline
+                .lock()
+                .await
+                .fail_task(&task_id, e.to_string())
+                .await;
+            return Err(e.to_string());
+        }
+    };
+
+    tracing::info!(
+        "Slack export imported: {} messages across {} channels ({} errors)",
+        result.messages_imported,
+        result.channels_imported,
+        result.errors.len()
+    );
+    pipeline
+        .lock()
         .await
-        .map_err(|e| e.to_string())?;
+        .complete_task(
+            &task_id,
+            Some(format!(
+                "Imported {} messages from {} channels",
+                result.messages_imported, result.channels_imported
+            )),
+        )
+        .await;
 
-    Ok(())
+    if process_ai && !result.days.is_empty() {
+        let days = result.days.clone();
+        tokio::spawn(async move {
+            let Some(api_key_or_client) =
+                crate::sync::background::get_gemini_client(db.clone(), crypto.clone()).await
+            else {
+                tracing::warn!("Skipping AI processing of Slack export: no Gemini credentials");
+                return;
+            };
+
+            let (ai_task_id, cancel) = {
+                let pipeline = pipeline.lock().await;
+                let task_id = pipeline
+                    .start_task(
+                        PipelineTaskType::AiSummarize,
+                        format!("Analyzing {} imported days...", days.len()),
+                    )
+                    .await;
+                let cancel = pipeline.cancellation_token(&task_id).await;
+                (task_id, cancel)
+            };
+
+            let ai_pipeline = ProcessingPipeline::new(api_key_or_client, db, crypto)
+                .with_cancellation(cancel.clone());
+            let mut processed_total = 0;
+            for (i, date) in days.iter().enumerate() {
+                match ai_pipeline
+                    .process_batch_for_date(date, timezone_offset)
+                    .await
+                {
+                    Ok(processed) => processed_total += processed,
+                    // Days already analyzed keep their results; the rest wait for the next AI pass
+                    Err(e) if cancel.is_cancelled() => {
+                        pipeline.lock().await.fail_task(&ai_task_id, e).await;
+                        return;
+                    }
+                    Err(e) => {
+                        tracing::error!("Slack export AI processing error for {}: {}", date, e)
+                    }
+                }
+
+                pipeline
+                    .lock()
+                    .await
+                    .update_progress(
+                        &ai_task_id,
+                        (i + 1) as f32 / days.len() as f32,
+                        Some(format!("Analyzed {}", date)),
+                    )
+                    .await;
+            }
+
+            pipeline
+                .lock()
+                .await
+                .complete_task(
+                    &ai_task_id,
+                    Some(format!(
+                        "Processed {} groups/items from imported history",
+                        processed_total
+                    )),
+                )
+                .await;
+        });
+    }
+
+    Ok(result)
 }
@@ -0,0 +1,175 @@
+//! Generic data source commands backed by the source registry
+
+use crate::sync::{intervals, status};
+use crate::sync::{
+    ConnectorContext, ResourceHealth, SourceHealth, SourceInfo, SourceRegistry, SourceScope,
+    SyncInterval,
+};
+use crate::AppState;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+async fn connector_context(
+    state: &State<'_, Arc<Mutex<AppState>>>,
+) -> (ConnectorContext, Arc<SourceRegistry>) {
+    let state = state.lock().await;
+    (
+        ConnectorContext::new(state.db.clone(), Arc::new(state.crypto.clone())),
+        state.sources.clone(),
+    )
+}
+
+/// List every registered source and whether it is connected
+#[tauri::command]
+pub async fn list_sources(
+    state: State<'_, Arc<Mutex<AppState>>>,
+) -> Result<Vec<SourceInfo>, String> {
+    let (ctx, registry) = connector_context(&state).await;
+
+    let mut sources = Vec::new();
+    for connector in registry.all() {
+        sources.push(SourceInfo {
+            id: connector.id().to_string(),
+            display_name: connector.display_name().to_string(),
+            description: connector.description().to_string(),
+            connected: connector.is_connected(&ctx).await,
+            scope_selection: connector.supports_scope_selection(),
+            scope_intervals: connector.supports_scope_intervals(),
+        });
+    }
+
+    Ok(sources)
+}
+
+/// Connect a source with its source-specific configuration
+#[tauri::command]
+pub async fn connect_source(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    source: String,
+    config: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    let (ctx, registry) = connector_context(&state).await;
+    let connector = registry.require(&source).map_err(|e| e.to_string())?;
+
+    connector
+        .connect(&ctx, config)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// List the channels, projects or spaces a source can sync
+#[tauri::command]
+pub async fn list_source_scopes(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    source: String,
+) -> Result<Vec<SourceScope>, String> {
+    let (ctx, registry) = connector_context(&state).await;
+    let connector = registry.require(&source).map_err(|e| e.to_string())?;
+
+    connector.list_scopes(&ctx).await.map_err(|e| e.to_string())
+}
+
+/// Choose which channels, projects or repositories a source syncs
+#[tauri::command]
+pub async fn select_source_scopes(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    source: String,
+    scope_ids: Vec<String>,
+) -> Result<(), String> {
+    let (ctx, registry) = connector_context(&state).await;
+    let connector = registry.require(&source).map_err(|e| e.to_string())?;
+
+    connector
+        .select_scopes(&ctx, scope_ids)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// List per-source and per-scope sync intervals
+#[tauri::command]
+pub async fn list_sync_intervals(
+    state: State<'_, Arc<Mutex<AppState>>>,
+) -> Result<Vec<SyncInterval>, String> {
+    let db = state.lock().await.db.clone();
+    intervals::list_intervals(&db)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Set how often a source, or one of its channels, projects or spaces, syncs in
+/// the background. `None` falls back to the source interval or the global schedule.
+#[tauri::command]
+pub async fn set_sync_interval(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    source: String,
+    resource_type: Option<String>,
+    resource_id: Option<String>,
+    interval_minutes: Option<i64>,
+) -> Result<(), String> {
+    let (db, registry) = {
+        let state = state.lock().await;
+        (state.db.clone(), state.sources.clone())
+    };
+    registry.require(&source).map_err(|e| e.to_string())?;
+
+    let scope = match (resource_type.as_deref(), resource_id.as_deref()) {
+        (Some(resource_type), Some(resource_id)) => Some((resource_type, resource_id)),
+        (None, None) => None,
+        _ => return Err("A scope needs both a resource type and id".to_string()),
+    };
+
+    intervals::set_interval(&db, &source, scope, interval_minutes)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Check connection health of every registered source
+#[tauri::command]
+pub async fn get_source_health(
+    state: State<'_, Arc<Mutex<AppState>>>,
+) -> Result<Vec<SourceHealth>, String> {
+    let (ctx, registry) = connector_context(&state).await;
+
+    let mut health = Vec::new();
+    for connector in registry.all() {
+        health.push(connector.health(&ctx).await);
+    }
+
+    Ok(health)
+}
+
+/// Sync status of every channel, project, space, folder or feed, optionally of
+/// one source, with the last error of resources that are failing
+#[tauri::command]
+pub async fn get_resource_health(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    source: Option<String>,
+) -> Result<Vec<ResourceHealth>, String> {
+    let db = {
+        let state = state.lock().await;
+        state.db.clone()
+    };
+
+    status::list_resource_health(&db, source.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Disconnect a source, removing its credentials and sync state
+#[tauri::command]
+pub async fn disconnect_source(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    source: String,
+) -> Result<(), String> {
+    let (ctx, registry) = connector_context(&state).await;
+    let connector = registry.require(&source).map_err(|e| e.to_string())?;
+
+    connector
+        .disconnect(&ctx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    tracing::info!("Disconnected source: {}", source);
+    Ok(())
+}
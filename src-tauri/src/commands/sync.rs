@@ -1,33 +1,24 @@
 use super::credentials::get_gemini_client;
-use super::types::{SyncResult, SyncStatus};
+use super::types::{SourceStatus, SyncResult, SyncStatus};
+use crate::ai::pipeline::CANCELLED;
 use crate::ai::ProcessingPipeline;
-use crate::pipeline::PipelineTaskType;
-use crate::sync::{sync_slack_historical_day, sync_slack_now};
+use crate::pipeline::{PipelineTaskType, ProgressReporter};
+use crate::sync::backfill::insert_backfill_job;
+use crate::sync::background;
+use crate::sync::circuit::AI_CIRCUIT;
+use crate::sync::connector::{sync_connected_sources, SourcesSynced};
+use crate::sync::estimate;
+use crate::sync::{
+    AtlassianExportImporter, AtlassianExportKind, AtlassianImportResult, BackfillJob, CircuitState,
+    ConnectorContext, DryRun, SyncClaim, SyncEstimate, SyncJob, SyncJobPayload, SyncPriority,
+    SyncRange, SyncSchedule,
+};
 use crate::AppState;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::State;
 use tokio::sync::Mutex;
 
-/// Guard that sets is_syncing to false when dropped.
-/// Ensures the flag is reset even if the function returns early or panics.
-struct SyncingGuard {
-    is_syncing: Arc<AtomicBool>,
-}
-
-impl SyncingGuard {
-    fn new(is_syncing: Arc<AtomicBool>) -> Self {
-        is_syncing.store(true, Ordering::SeqCst);
-        Self { is_syncing }
-    }
-}
-
-impl Drop for SyncingGuard {
-    fn drop(&mut self) {
-        self.is_syncing.store(false, Ordering::SeqCst);
-    }
-}
-
 /// Trigger a sync operation for the specified sources
 #[tauri::command]
 pub async fn start_sync(
@@ -43,87 +34,38 @@ pub async fn start_sync(
 
     let app_state = state.inner().clone();
 
-    let (db, crypto, pipeline, sync_lock, is_syncing) = {
+    let (db, crypto, registry, pipeline, scheduler, circuits) = {
         let state = app_state.lock().await;
         (
             state.db.clone(),
             std::sync::Arc::new(state.crypto.clone()),
+            state.sources.clone(),
             state.pipeline.clone(),
-            state.sync_lock.clone(),
-            state.is_syncing.clone(),
+            state.scheduler.clone(),
+            state.circuits.clone(),
         )
     };
 
-    let _sync_guard = match sync_lock.try_lock() {
-        Ok(guard) => guard,
-        Err(_) => {
-            tracing::warn!("Sync already in progress, skipping duplicate request");
-            return Ok(SyncResult {
-                items_synced: 0,
-                channels_processed: 0,
-                errors: vec!["Sync already in progress".to_string()],
-                queued: false,
-            });
-        }
-    };
-
-    let _syncing_guard = SyncingGuard::new(is_syncing);
-
-    let mut total_items = 0;
-    let mut channels_processed = 0;
-    let mut errors: Vec<String> = Vec::new();
-
-    // Determine which sources to sync
-    let sync_slack = sources
-        .as_ref()
-        .is_none_or(|s| s.contains(&"slack".to_string()));
-
-    // Sync Slack
-    if sync_slack {
-        tracing::debug!("Starting Slack sync...");
-
-        // Start pipeline task
-        let task_id = {
-            let pipeline = pipeline.lock().await;
-            pipeline
-                .start_task(
-                    PipelineTaskType::SyncSlack,
-                    "Syncing Slack messages...".to_string(),
-                )
-                .await
-        };
-
-        match sync_slack_now(db.clone(), crypto.clone()).await {
-            Ok(items) => {
-                tracing::info!("Slack sync completed: {} items", items);
-                total_items += items;
-                channels_processed += 1;
-
-                // Complete pipeline task
-                let pipeline = pipeline.lock().await;
-                let message = if items > 0 {
-                    format!("Synced {} messages from Slack", items)
-                } else {
-                    "Slack sync complete (no new messages)".to_string()
-                };
-                pipeline.complete_task(&task_id, Some(message)).await;
-            }
-            Err(e) => {
-                tracing::error!("Slack sync error: {}", e);
-
-                // Fail pipeline task
-                let pipeline = pipeline.lock().await;
-                if e.contains("not connected") {
-                    pipeline
-                        .complete_task(&task_id, Some("Slack not connected".to_string()))
-                        .await;
-                } else {
-                    pipeline.fail_task(&task_id, e.clone()).await;
-                    errors.push(format!("Slack: {}", e));
-                }
-            }
-        }
-    }
+    // Waits for, or preempts at its next checkpoint, any lower-priority work
+    // on the same sources
+    let only = sources.as_deref();
+    let permit = scheduler
+        .acquire(
+            "Sync now",
+            SyncPriority::UserInitiated,
+            SyncClaim::sources_with_ai(only),
+        )
+        .await;
+
+    // Sync every connected source, or only the requested ones. Paused sources
+    // are synced too, and a success closes their circuit.
+    let ctx = ConnectorContext::new(db.clone(), crypto.clone()).with_circuits(circuits);
+    let channels_processed = registry.connected(&ctx, only).await.len() as i32;
+    let SourcesSynced {
+        items: total_items,
+        mut errors,
+        ..
+    } = sync_connected_sources(&registry, &ctx, &pipeline, only, None).await;
 
     // Run AI batch processing to group and summarize content
     if total_items > 0 {
@@ -137,22 +79,27 @@ pub async fn start_sync(
 
         if let Some(api_key_or_client) = gemini_client {
             // Start AI summarization task
-            let task_id = {
+            let (task_id, cancel) = {
                 let pipeline = pipeline.lock().await;
-                pipeline
+                let task_id = pipeline
                     .start_task(
                         PipelineTaskType::AiSummarize,
                         "Analyzing and grouping content with AI...".to_string(),
                     )
-                    .await
+                    .await;
+                let cancel = pipeline.cancellation_token(&task_id).await;
+                (task_id, cancel)
             };
 
             let ai_pipeline =
-                ProcessingPipeline::new(api_key_or_client, db.clone(), crypto.clone());
+                ProcessingPipeline::new(api_key_or_client, db.clone(), crypto.clone())
+                    .with_cancellation(cancel)
+                    .with_progress(ProgressReporter::new(pipeline.clone(), &task_id));
             // Use batch processing to group related content across channels
             match ai_pipeline.process_daily_batch(timezone_offset).await {
                 Ok(processed) => {
                     tracing::info!("AI batch processed {} groups/items", processed);
+                    ctx.record_outcome(AI_CIRCUIT, None);
                     let pipeline = pipeline.lock().await;
                     pipeline
                         .complete_task(
@@ -163,6 +110,9 @@ pub async fn start_sync(
                 }
                 Err(e) => {
                     tracing::error!("AI batch processing error: {}", e);
+                    if e != CANCELLED {
+                        ctx.record_outcome(AI_CIRCUIT, Some(&e));
+                    }
                     let pipeline = pipeline.lock().await;
                     pipeline.fail_task(&task_id, e.clone()).await;
                     errors.push(format!("AI: {}", e));
@@ -183,12 +133,10 @@ pub async fn start_sync(
         tracing::error!("Failed to save last sync timestamp: {}", e);
     }
 
-    // Drop the guards before draining the queue
-    // _syncing_guard will reset is_syncing to false when dropped
-    drop(_syncing_guard);
-    drop(_sync_guard);
+    // Release the permit before draining the queue
+    drop(permit);
 
-    // Spawn queue drain as a background task so queued historical syncs run
+    // Spawn queue drain as a background task so queued sync jobs run
     let drain_state = app_state.clone();
     tokio::spawn(async move {
         drain_sync_queue(drain_state).await;
@@ -206,17 +154,21 @@ pub async fn start_sync(
 /// Get the current sync status
 #[tauri::command]
 pub async fn get_sync_status(state: State<'_, Arc<Mutex<AppState>>>) -> Result<SyncStatus, String> {
-    let (db, is_syncing, next_sync_at) = {
+    let (db, crypto, registry, is_syncing, is_online, next_sync_at, circuits) = {
         let state = state.lock().await;
         (
             state.db.clone(),
+            Arc::new(state.crypto.clone()),
+            state.sources.clone(),
             state.is_syncing.clone(),
+            state.is_online.clone(),
             state.next_sync_at.clone(),
+            state.circuits.clone(),
         )
     };
 
     let is_currently_syncing = is_syncing.load(std::sync::atomic::Ordering::SeqCst);
-    let last_sync_at = crate::sync::get_last_sync_at(db).await;
+    let last_sync_at = crate::sync::get_last_sync_at(db.clone()).await;
     let next_sync = {
         let val = next_sync_at.load(std::sync::atomic::Ordering::SeqCst);
         if val > 0 {
@@ -226,11 +178,56 @@ pub async fn get_sync_status(state: State<'_, Arc<Mutex<AppState>>>) -> Result<S
         }
     };
 
+    let ctx = ConnectorContext::new(db.clone(), crypto);
+    let mut sources = Vec::new();
+    for connector in registry.all() {
+        let connected = connector.is_connected(&ctx).await;
+
+        let items_synced: (i32,) =
+            sqlx::query_as("SELECT COUNT(*) FROM content_items WHERE source = ?")
+                .bind(connector.id())
+                .fetch_one(db.pool())
+                .await
+                .unwrap_or((0,));
+
+        let last_error: Option<(Option<String>,)> = sqlx::query_as(
+            "SELECT error_message FROM sync_state
+             WHERE source = ? AND error_message IS NOT NULL
+             ORDER BY error_at DESC LIMIT 1",
+        )
+        .bind(connector.id())
+        .fetch_optional(db.pool())
+        .await
+        .unwrap_or(None);
+        let circuit = circuits.get(connector.id());
+        let last_error = last_error
+            .and_then(|r| r.0)
+            .or_else(|| circuit.last_error.clone());
+
+        let status = match (connected, &last_error) {
+            (false, _) => "disconnected",
+            (true, _) if circuit.unreachable => "offline",
+            (true, _) if circuit.state != CircuitState::Closed => "paused",
+            (true, Some(_)) => "error",
+            (true, None) => "connected",
+        };
+
+        sources.push(SourceStatus {
+            name: connector.id().to_string(),
+            status: status.to_string(),
+            items_synced: items_synced.0,
+            last_error,
+            circuit,
+        });
+    }
+
     Ok(SyncStatus {
         is_syncing: is_currently_syncing,
         last_sync_at,
         next_sync_at: next_sync,
-        sources: vec![],
+        is_online: is_online.load(std::sync::atomic::Ordering::SeqCst),
+        sources,
+        ai_circuit: circuits.get(AI_CIRCUIT),
     })
 }
 
@@ -245,30 +242,45 @@ pub async fn resync_historical_day(
 
     let app_state = state.inner().clone();
 
-    let (db, crypto, pipeline, sync_lock, is_syncing) = {
+    let range = SyncRange::day(&date, timezone_offset)?;
+
+    let (db, crypto, registry, pipeline, scheduler) = {
         let state = app_state.lock().await;
         (
             state.db.clone(),
             std::sync::Arc::new(state.crypto.clone()),
+            state.sources.clone(),
             state.pipeline.clone(),
-            state.sync_lock.clone(),
-            state.is_syncing.clone(),
+            state.scheduler.clone(),
         )
     };
 
-    let _sync_guard = match sync_lock.try_lock() {
-        Ok(guard) => guard,
-        Err(_) => {
-            // Enqueue the request instead of dropping it
-            let request =
-                crate::sync::SyncRequest::historical(date.clone(), timezone_offset);
-            let state = app_state.lock().await;
-            state.sync_queue.enqueue(request).await;
+    let permit = match scheduler.try_acquire(
+        format!("Resync {}", date),
+        SyncPriority::UserInitiated,
+        SyncClaim::All,
+    ) {
+        Some(permit) => permit,
+        None => {
+            // Persist the request so it runs as soon as the current work allows
+            let sync_queue = app_state.lock().await.sync_queue.clone();
+            let job = sync_queue
+                .enqueue(SyncJobPayload::HistoricalDay {
+                    date: date.clone(),
+                    timezone_offset,
+                })
+                .await?;
             tracing::info!(
-                "Sync in progress, queued historical resync for {} (queue size: {})",
+                "Sync in progress, queued historical resync for {} as job {}",
                 date,
-                state.sync_queue.len().await
+                job.id
             );
+
+            let drain_state = app_state.clone();
+            tokio::spawn(async move {
+                drain_sync_queue(drain_state).await;
+            });
+
             return Ok(SyncResult {
                 items_synced: 0,
                 channels_processed: 0,
@@ -278,71 +290,34 @@ pub async fn resync_historical_day(
         }
     };
 
-    let _syncing_guard = SyncingGuard::new(is_syncing);
-
-    let mut total_items = 0;
-    let mut errors: Vec<String> = Vec::new();
-
-    let sync_task_id = {
-        let pipeline = pipeline.lock().await;
-        pipeline
-            .start_task(
-                PipelineTaskType::SyncSlack,
-                format!("Syncing Slack messages for {}...", date),
-            )
-            .await
-    };
-
-    match sync_slack_historical_day(db.clone(), crypto.clone(), &date, timezone_offset).await {
-        Ok(result) => {
-            let items = result.items_synced;
-            tracing::info!(
-                "Historical Slack sync completed: {} items for {}",
-                items,
-                date
-            );
-            total_items = items;
-            errors.extend(result.errors.into_iter().map(|e| format!("Slack: {}", e)));
-
-            let pipeline = pipeline.lock().await;
-            let message = if items > 0 {
-                format!("Synced {} messages for {}", items, date)
-            } else {
-                format!("No new messages found for {}", date)
-            };
-            pipeline.complete_task(&sync_task_id, Some(message)).await;
-        }
-        Err(e) => {
-            tracing::error!("Historical Slack sync error for {}: {}", date, e);
-
-            let pipeline = pipeline.lock().await;
-            if e.contains("not connected") {
-                pipeline
-                    .complete_task(&sync_task_id, Some("Slack not connected".to_string()))
-                    .await;
-            } else {
-                pipeline.fail_task(&sync_task_id, e.clone()).await;
-                errors.push(format!("Slack: {}", e));
-            }
-        }
-    }
+    let ctx = ConnectorContext::new(db.clone(), crypto.clone());
+    let channels_processed = registry.connected(&ctx, None).await.len() as i32;
+    let SourcesSynced {
+        items: total_items,
+        mut errors,
+        ..
+    } = sync_connected_sources(&registry, &ctx, &pipeline, None, Some(&range)).await;
 
     if total_items > 0 {
         let gemini_client = get_gemini_client(db.clone(), crypto.clone()).await;
 
         if let Some(api_key_or_client) = gemini_client {
-            let ai_task_id = {
+            let (ai_task_id, cancel) = {
                 let pipeline = pipeline.lock().await;
-                pipeline
+                let task_id = pipeline
                     .start_task(
                         PipelineTaskType::AiSummarize,
                         format!("Analyzing content for {}...", date),
                     )
-                    .await
+                    .await;
+                let cancel = pipeline.cancellation_token(&task_id).await;
+                (task_id, cancel)
             };
 
             let ai_pipeline =
-                ProcessingPipeline::new(api_key_or_client, db.clone(), crypto.clone());
+                ProcessingPipeline::new(api_key_or_client, db.clone(), crypto.clone())
+                    .with_cancellation(cancel)
+                    .with_progress(ProgressReporter::new(pipeline.clone(), &ai_task_id));
             match ai_pipeline
                 .process_batch_for_date(&date, timezone_offset)
                 .await
@@ -376,12 +351,10 @@ pub async fn resync_historical_day(
         errors
     );
 
-    // Drop the guards before draining the queue
-    // _syncing_guard will reset is_syncing to false when dropped
-    drop(_syncing_guard);
-    drop(_sync_guard);
+    // Release the permit before draining the queue
+    drop(permit);
 
-    // Spawn queue drain as a background task so other queued historical syncs run
+    // Spawn queue drain as a background task so other queued sync jobs run
     let drain_state = app_state.clone();
     tokio::spawn(async move {
         drain_sync_queue(drain_state).await;
@@ -389,155 +362,250 @@ pub async fn resync_historical_day(
 
     Ok(SyncResult {
         items_synced: total_items,
-        channels_processed: 1,
+        channels_processed,
         errors,
         queued: false,
     })
 }
 
-/// Drain the sync queue, executing each queued historical resync in order.
-/// Acquires and releases the sync lock for each item so user-initiated syncs
-/// can interleave between queued items.
-pub async fn drain_sync_queue(app_state: Arc<Mutex<AppState>>) {
-    loop {
-        let request = {
-            let state = app_state.lock().await;
-            state.sync_queue.dequeue().await
-        };
+/// Start a multi-day backfill over `start_date..=end_date` for the registered sources.
+///
+/// The job is persisted and queued before it starts, runs in the background, and
+/// resumes on the next launch if the app quits before it finishes.
+#[tauri::command]
+pub async fn start_backfill(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    start_date: String,
+    end_date: String,
+    timezone_offset: i32,
+    sources: Option<Vec<String>>,
+) -> Result<BackfillJob, String> {
+    let app_state = state.inner().clone();
+    let (db, registry, sync_queue) = {
+        let state = app_state.lock().await;
+        (
+            state.db.clone(),
+            state.sources.clone(),
+            state.sync_queue.clone(),
+        )
+    };
 
-        let Some(request) = request else {
-            break;
-        };
+    let job = BackfillJob::new(
+        &start_date,
+        &end_date,
+        timezone_offset,
+        sources,
+        &registry.ids(),
+    )?;
+
+    insert_backfill_job(&db, &job).await?;
+    let queued = sync_queue
+        .enqueue(SyncJobPayload::Backfill {
+            backfill_id: job.id.clone(),
+            start_date: job.start_date.clone(),
+            end_date: job.end_date.clone(),
+        })
+        .await?;
 
-        let (Some(date), Some(tz_offset)) = (request.date.clone(), request.timezone_offset) else {
-            tracing::warn!("Queued sync request missing date/timezone, skipping: {:?}", request.source);
-            continue;
-        };
+    tracing::info!(
+        "Backfill job {} queued as job {}: {} to {} ({:?})",
+        job.id,
+        queued.id,
+        job.start_date,
+        job.end_date,
+        job.sources
+    );
 
-        tracing::info!("Draining queued historical resync for {}", date);
-
-        let (db, crypto, pipeline, sync_lock, is_syncing) = {
-            let state = app_state.lock().await;
-            (
-                state.db.clone(),
-                Arc::new(state.crypto.clone()),
-                state.pipeline.clone(),
-                state.sync_lock.clone(),
-                state.is_syncing.clone(),
-            )
-        };
+    tokio::spawn(async move {
+        drain_sync_queue(app_state).await;
+    });
 
-        // Try to acquire the lock; if someone else grabbed it, re-queue and stop draining
-        let _sync_guard = match sync_lock.try_lock() {
-            Ok(guard) => guard,
-            Err(_) => {
-                tracing::info!("Sync lock busy during queue drain, re-queuing {}", date);
-                let state = app_state.lock().await;
-                state.sync_queue.enqueue(request).await;
-                break;
-            }
-        };
+    Ok(job)
+}
+
+/// List recent backfill jobs, newest first
+#[tauri::command]
+pub async fn get_backfill_jobs(
+    state: State<'_, Arc<Mutex<AppState>>>,
+) -> Result<Vec<BackfillJob>, String> {
+    let db = {
+        let state = state.lock().await;
+        state.db.clone()
+    };
+
+    crate::sync::backfill::list_backfill_jobs(&db).await
+}
 
-        // Use the guard pattern to ensure is_syncing is reset even on error/panic
-        let _syncing_guard = SyncingGuard::new(is_syncing.clone());
+/// Dry-run a sync and project its volume, Gemini requests, time and cost
+/// without storing anything. With `start_date` and `end_date` the estimate
+/// covers a backfill of those days, otherwise the next incremental sync.
+/// `scopes` maps source ids to scopes to estimate instead of the current
+/// selection, e.g. Slack channels that are not enabled yet.
+#[tauri::command]
+pub async fn estimate_sync(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    sources: Option<Vec<String>>,
+    scopes: Option<HashMap<String, Vec<String>>>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    timezone_offset: Option<i32>,
+) -> Result<SyncEstimate, String> {
+    let (db, crypto, registry) = {
+        let state = state.lock().await;
+        (
+            state.db.clone(),
+            Arc::new(state.crypto.clone()),
+            state.sources.clone(),
+        )
+    };
 
-        let mut total_items = 0;
+    let timezone_offset =
+        timezone_offset.unwrap_or_else(|| -chrono::Local::now().offset().local_minus_utc() / 60);
+    let range = match (start_date, end_date) {
+        (Some(start), Some(end)) => Some(SyncRange::new(&start, &end, timezone_offset)?),
+        (None, None) => None,
+        _ => return Err("Both a start and an end date are required".to_string()),
+    };
+    let dry_run = DryRun {
+        range,
+        scopes: scopes.unwrap_or_default(),
+        timezone_offset,
+    };
 
-        let sync_task_id = {
+    let ctx = ConnectorContext::new(db, crypto);
+    let report = estimate::estimate_sync(&registry, &ctx, sources.as_deref(), &dry_run).await;
+    tracing::info!(
+        "Sync estimate: {} items, {} AI requests, ~${:.2}",
+        report.items,
+        report.ai_requests,
+        report.cost_usd
+    );
+    Ok(report)
+}
+
+/// Import a Jira issue export (XML/CSV/JSON) or Confluence space export (HTML/XML)
+/// from disk. `base_url` is the site URL used for deep links the export lacks.
+#[tauri::command]
+pub async fn import_atlassian_export(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    path: String,
+    kind: AtlassianExportKind,
+    base_url: Option<String>,
+) -> Result<AtlassianImportResult, String> {
+    let (db, crypto, pipeline) = {
+        let state = state.lock().await;
+        (
+            state.db.clone(),
+            Arc::new(state.crypto.clone()),
+            state.pipeline.clone(),
+        )
+    };
+
+    let (task_type, label) = match kind {
+        AtlassianExportKind::Jira => (PipelineTaskType::SyncJira, "Jira"),
+        AtlassianExportKind::Confluence => (PipelineTaskType::SyncConfluence, "Confluence"),
+    };
+    let task_id = {
+        let pipeline = pipeline.lock().await;
+        pipeline
+            .start_task(task_type, format!("Importing {} export...", label))
+            .await
+    };
+
+    let importer = AtlassianExportImporter::new(db, crypto).with_base_url(base_url);
+    match importer.import_file(&path, kind).await {
+        Ok(result) => {
+            tracing::info!(
+                "{} export imported: {} issues, {} pages ({} errors)",
+                label,
+                result.issues_imported,
+                result.pages_imported,
+                result.errors.len()
+            );
             let pipeline = pipeline.lock().await;
             pipeline
-                .start_task(
-                    PipelineTaskType::SyncSlack,
-                    format!("Syncing Slack messages for {}...", date),
+                .complete_task(
+                    &task_id,
+                    Some(format!(
+                        "Imported {} items from {} export",
+                        result.issues_imported + result.pages_imported,
+                        label
+                    )),
                 )
-                .await
-        };
+                .await;
+            Ok(result)
+        }
+        Err(e) => {
+            let pipeline = pipeline.lock().await;
+            pipeline.fail_task(&task_id, e.to_string()).await;
+            Err(e.to_string())
+        }
+    }
+}
 
-        match sync_slack_historical_day(db.clone(), crypto.clone(), &date, tz_offset).await {
-            Ok(result) => {
-                let items = result.items_synced;
-                tracing::info!(
-                    "Queued historical Slack sync completed: {} items for {}",
-                    items,
-                    date
-                );
-                total_items = items;
+/// List recent queued sync jobs, newest first
+#[tauri::command]
+pub async fn get_sync_jobs(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    limit: Option<i64>,
+) -> Result<Vec<SyncJob>, String> {
+    let sync_queue = state.lock().await.sync_queue.clone();
+    sync_queue.list(limit.unwrap_or(50)).await
+}
 
-                let pipeline = pipeline.lock().await;
-                let message = if items > 0 {
-                    format!("Synced {} messages for {}", items, date)
-                } else {
-                    format!("No new messages found for {}", date)
-                };
-                pipeline.complete_task(&sync_task_id, Some(message)).await;
-            }
-            Err(e) => {
-                tracing::error!("Queued historical Slack sync error for {}: {}", date, e);
+/// Cancel a pending or running sync job
+#[tauri::command]
+pub async fn cancel_sync_job(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    job_id: String,
+) -> Result<SyncJob, String> {
+    let sync_queue = state.lock().await.sync_queue.clone();
+    sync_queue.cancel(&job_id).await
+}
 
-                let pipeline = pipeline.lock().await;
-                if e.contains("not connected") {
-                    pipeline
-                        .complete_task(&sync_task_id, Some("Slack not connected".to_string()))
-                        .await;
-                } else {
-                    pipeline.fail_task(&sync_task_id, e).await;
-                }
-            }
-        }
+/// Requeue a failed or cancelled sync job and start draining the queue
+#[tauri::command]
+pub async fn retry_sync_job(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    job_id: String,
+) -> Result<SyncJob, String> {
+    let app_state = state.inner().clone();
+    let sync_queue = app_state.lock().await.sync_queue.clone();
+    let job = sync_queue.retry(&job_id).await?;
 
-        if total_items > 0 {
-            let gemini_client = get_gemini_client(db.clone(), crypto.clone()).await;
+    tokio::spawn(async move {
+        drain_sync_queue(app_state).await;
+    });
 
-            if let Some(api_key_or_client) = gemini_client {
-                let ai_task_id = {
-                    let pipeline = pipeline.lock().await;
-                    pipeline
-                        .start_task(
-                            PipelineTaskType::AiSummarize,
-                            format!("Analyzing content for {}...", date),
-                        )
-                        .await
-                };
-
-                let ai_pipeline =
-                    ProcessingPipeline::new(api_key_or_client, db.clone(), crypto.clone());
-                match ai_pipeline
-                    .process_batch_for_date(&date, tz_offset)
-                    .await
-                {
-                    Ok(processed) => {
-                        tracing::info!(
-                            "AI batch processed {} groups/items for {} (queued)",
-                            processed,
-                            date
-                        );
-                        let pipeline = pipeline.lock().await;
-                        pipeline
-                            .complete_task(
-                                &ai_task_id,
-                                Some(format!("Grouped and summarized {} items", processed)),
-                            )
-                            .await;
-                    }
-                    Err(e) => {
-                        tracing::error!(
-                            "AI batch processing error for {} (queued): {}",
-                            date,
-                            e
-                        );
-                        let pipeline = pipeline.lock().await;
-                        pipeline.fail_task(&ai_task_id, e).await;
-                    }
-                }
-            }
-        }
+    Ok(job)
+}
 
-        // _syncing_guard is dropped here, resetting is_syncing to false
-        tracing::info!("Queued historical resync for {} completed", date);
+/// Show which sync jobs hold permits and which are waiting, in start order
+#[tauri::command]
+pub async fn get_sync_schedule(
+    state: State<'_, Arc<Mutex<AppState>>>,
+) -> Result<SyncSchedule, String> {
+    let scheduler = state.lock().await.scheduler.clone();
+    Ok(scheduler.snapshot())
+}
 
-        // Yield briefly to allow user-initiated syncs to interleave
-        tokio::task::yield_now().await;
-    }
+/// Drain due jobs from the sync queue, acquiring a permit for each job so
+/// user-initiated syncs run ahead of the remaining queued jobs.
+pub async fn drain_sync_queue(app_state: Arc<Mutex<AppState>>) {
+    let (db, crypto, registry, pipeline, scheduler, sync_queue) = {
+        let state = app_state.lock().await;
+        (
+            state.db.clone(),
+            Arc::new(state.crypto.clone()),
+            state.sources.clone(),
+            state.pipeline.clone(),
+            state.scheduler.clone(),
+            state.sync_queue.clone(),
+        )
+    };
+
+    let ctx = ConnectorContext::new(db, crypto);
+    background::drain_sync_queue(&ctx, &pipeline, &scheduler, &sync_queue, &registry).await;
 }
 
 #[cfg(test)]
@@ -577,12 +645,17 @@ mod tests {
             is_syncing: false,
             last_sync_at: None,
             next_sync_at: None,
+            is_online: true,
             sources: vec![],
+            ai_circuit: crate::sync::SourceCircuit::default(),
         };
 
         assert!(!status.is_syncing);
         assert!(status.last_sync_at.is_none());
+        assert!(status.is_online);
         assert!(status.sources.is_empty());
+        assert_eq!(status.ai_circuit.state, CircuitState::Closed);
+        assert_eq!(status.ai_circuit.consecutive_failures, 0);
     }
 
     #[test]
@@ -0,0 +1,108 @@
+//! Meeting transcript commands
+
+use super::credentials::get_gemini_client;
+use crate::ai::ProcessingPipeline;
+use crate::pipeline::{PipelineTaskType, ProgressReporter};
+use crate::sync::{TranscriptImportResult, TranscriptImporter};
+use crate::AppState;
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+/// Import a `.vtt`, `.srt` or `.txt` meeting transcript and, when Gemini is
+/// configured, summarize it. `started_at` is epoch milliseconds; `timezone_offset`
+/// (minutes west of UTC) decides which local day the summary belongs to.
+#[tauri::command]
+pub async fn import_meeting_transcript(
+    state: State<'_, Arc<Mutex<AppState>>>,
+    path: String,
+    title: Option<String>,
+    started_at: Option<i64>,
+    timezone_offset: Option<i32>,
+) -> Result<TranscriptImportResult, String> {
+    let (db, crypto, pipeline) = {
+        let state = state.lock().await;
+        (
+            state.db.clone(),
+            Arc::new(state.crypto.clone()),
+            state.pipeline.clone(),
+        )
+    };
+
+    let task_id = {
+        let pipeline = pipeline.lock().await;
+        pipeline
+            .start_task(
+                PipelineTaskType::ImportTranscript,
+                "Importing meeting transcript...".to_string(),
+            )
+            .await
+    };
+
+    let importer = TranscriptImporter::new(db.clone(), crypto.clone());
+    let mut result = match importer.import_file(&path, title, started_at).await {
+        Ok(result) => {
+            let pipeline = pipeline.lock().await;
+            pipeline
+                .complete_task(
+                    &task_id,
+                    Some(format!(
+                        "Imported {} segments from {} speakers",
+                        result.segments_imported,
+                        result.speakers.len()
+                    )),
+                )
+                .await;
+            result
+        }
+        Err(e) => {
+            let pipeline = pipeline.lock().await;
+            pipeline.fail_task(&task_id, e.to_string()).await;
+            return Err(e.to_string());
+        }
+    };
+
+    let Some(api_key_or_client) = get_gemini_client(db.clone(), crypto.clone()).await else {
+        tracing::debug!("No Gemini credentials configured, skipping meeting summary");
+        return Ok(result);
+    };
+
+    let (ai_task_id, cancel) = {
+        let pipeline = pipeline.lock().await;
+        let task_id = pipeline
+            .start_task(
+                PipelineTaskType::AiSummarize,
+                format!("Summarizing {}...", result.title),
+            )
+            .await;
+        let cancel = pipeline.cancellation_token(&task_id).await;
+        (task_id, cancel)
+    };
+
+    let ai_pipeline = ProcessingPipeline::new(api_key_or_client, db, crypto)
+        .with_cancellation(cancel)
+        .with_progress(ProgressReporter::new(pipeline.clone(), &ai_task_id));
+    match ai_pipeline
+        .process_meeting(&result.meeting_id, timezone_offset.unwrap_or(0))
+        .await
+    {
+        Ok(stored) => {
+            result.items_summarized = stored;
+            let pipeline = pipeline.lock().await;
+            pipeline
+                .complete_task(
+                    &ai_task_id,
+                    Some(format!("Grouped and summarized {} items", stored)),
+                )
+                .await;
+        }
+        Err(e) => {
+            // The transcript is stored; the next AI batch will pick it up
+            tracing::error!("Meeting summary failed for {}: {}", result.meeting_id, e);
+            let pipeline = pipeline.lock().await;
+            pipeline.fail_task(&ai_task_id, e).await;
+        }
+    }
+
+    Ok(result)
+}
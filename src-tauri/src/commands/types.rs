@@ -1,3 +1,4 @@
+use crate::sync::{SourceCircuit, SyncScheduleConfig};
 use serde::{Deserialize, Serialize};
 
 /// A single item in the daily/weekly digest
@@ -61,7 +62,11 @@ pub struct SyncStatus {
     pub is_syncing: bool,
     pub last_sync_at: Option<i64>,
     pub next_sync_at: Option<i64>,
+    /// `false` while sources are paused because they could not be reached
+    pub is_online: bool,
     pub sources: Vec<SourceStatus>,
+    /// Circuit breaker of the AI pass that follows each sync
+    pub ai_circuit: SourceCircuit,
 }
 
 /// Status of a single sync source
@@ -72,6 +77,9 @@ pub struct SourceStatus {
     pub status: String,
     pub items_synced: i32,
     pub last_error: Option<String>,
+    /// Backoff and circuit breaker state; `status` is "paused" while it is open
+    /// and "offline" while the source cannot be reached
+    pub circuit: SourceCircuit,
 }
 
 /// Result of a sync operation
@@ -90,7 +98,9 @@ pub struct SyncResult {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Preferences {
-    pub sync_interval_minutes: i32,
+    /// Background sync schedule; replaces the old fixed `syncIntervalMinutes`
+    #[serde(default)]
+    pub sync_schedule: SyncScheduleConfig,
     pub enabled_sources: Vec<String>,
     pub enabled_categories: Vec<String>,
     pub notifications_enabled: bool,
@@ -102,7 +112,7 @@ pub struct Preferences {
 impl Default for Preferences {
     fn default() -> Self {
         Self {
-            sync_interval_minutes: 15,
+            sync_schedule: SyncScheduleConfig::default(),
             enabled_sources: vec![],
             enabled_categories: vec![
                 "sales".to_string(),
@@ -117,6 +127,23 @@ impl Default for Preferences {
     }
 }
 
+impl Preferences {
+    /// Parse stored preferences, carrying a legacy `syncIntervalMinutes` over to a schedule
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let legacy_interval = value
+            .get("syncIntervalMinutes")
+            .and_then(|v| v.as_i64())
+            .filter(|_| value.get("syncSchedule").is_none());
+
+        let mut prefs: Self = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        if let Some(minutes) = legacy_interval {
+            prefs.sync_schedule = SyncScheduleConfig::from_interval_minutes(minutes);
+        }
+        Ok(prefs)
+    }
+}
+
 /// Database statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -264,7 +291,7 @@ mod tests {
     #[test]
     fn test_preferences_default() {
         let prefs = Preferences::default();
-        assert_eq!(prefs.sync_interval_minutes, 15);
+        assert_eq!(prefs.sync_schedule, SyncScheduleConfig::default());
         assert!(prefs.notifications_enabled);
         assert!(prefs
             .enabled_categories
@@ -275,7 +302,7 @@ mod tests {
     #[test]
     fn test_preferences_serialization() {
         let prefs = Preferences {
-            sync_interval_minutes: 30,
+            sync_schedule: SyncScheduleConfig::from_interval_minutes(30),
             enabled_sources: vec!["slack".to_string()],
             enabled_categories: vec!["sales".to_string()],
             notifications_enabled: false,
@@ -283,11 +310,22 @@ mod tests {
         };
 
         let json = serde_json::to_string(&prefs).unwrap();
-        assert!(json.contains("\"syncIntervalMinutes\":30"));
+        assert!(json.contains("\"syncSchedule\":{\"entries\":[\"*/30 * * * *\"]"));
         assert!(json.contains("\"notificationsEnabled\":false"));
         assert!(json.contains("\"userGuidance\":\"Focus on production issues\""));
     }
 
+    #[test]
+    fn test_preferences_from_legacy_interval() {
+        let json = r#"{"syncIntervalMinutes":60,"enabledSources":[],"enabledCategories":[],"notificationsEnabled":true}"#;
+        let prefs = Preferences::from_json(json).unwrap();
+        assert_eq!(prefs.sync_schedule.entries, vec!["0 */1 * * *"]);
+
+        let json = r#"{"syncSchedule":{"entries":["0 9 * * 1-5"]},"enabledSources":[],"enabledCategories":[],"notificationsEnabled":true}"#;
+        let prefs = Preferences::from_json(json).unwrap();
+        assert_eq!(prefs.sync_schedule.entries, vec!["0 9 * * 1-5"]);
+    }
+
     #[test]
     fn test_sync_result_serialization() {
         let result = SyncResult {
@@ -398,16 +436,20 @@ mod tests {
             is_syncing: true,
             last_sync_at: Some(1234567890),
             next_sync_at: None,
+            is_online: true,
             sources: vec![SourceStatus {
                 name: "slack".to_string(),
                 status: "connected".to_string(),
                 items_synced: 50,
                 last_error: None,
+                circuit: SourceCircuit::default(),
             }],
+            ai_circuit: SourceCircuit::default(),
         };
 
         let json = serde_json::to_string(&status).unwrap();
         assert!(json.contains("\"isSyncing\":true"));
         assert!(json.contains("\"lastSyncAt\":1234567890"));
+        assert!(json.contains("\"circuit\":{\"state\":\"closed\",\"consecutiveFailures\":0}"));
     }
 }
@@ -51,6 +51,22 @@ impl Database {
         Ok(Self { pool })
     }
 
+    /// Migrated in-memory database for tests. Each SQLite connection to
+    /// `:memory:` is its own database, so the pool keeps exactly one open.
+    #[cfg(test)]
+    pub async fn in_memory() -> Result<Self, DbError> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await?;
+
+        sqlx::migrate!("./migrations").run(&pool).await?;
+
+        Ok(Self { pool })
+    }
+
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
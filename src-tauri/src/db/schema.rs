@@ -6,6 +6,16 @@ pub enum Source {
     Slack,
     Jira,
     Confluence,
+    Github,
+    Gitlab,
+    Linear,
+    Incident,
+    Email,
+    Calendar,
+    Notes,
+    Rss,
+    Transcript,
+    Webhook,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
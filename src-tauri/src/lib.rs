@@ -16,7 +16,7 @@ use notifications::NotificationService;
 use pipeline::PipelineManager;
 use std::sync::atomic::{AtomicBool, AtomicI64};
 use std::sync::Arc;
-use sync::{BackgroundSyncService, SyncQueue};
+use sync::{BackgroundSyncService, CircuitBreakers, SourceRegistry, SyncQueue, SyncScheduler};
 
 pub struct AppState {
     pub db: Arc<Database>,
@@ -25,9 +25,16 @@ pub struct AppState {
     pub analytics: Option<AnalyticsService>,
     pub pipeline: Arc<tokio::sync::Mutex<PipelineManager>>,
     pub sync_queue: Arc<SyncQueue>,
-    /// Prevents concurrent sync operations from racing on topic updates.
-    pub sync_lock: Arc<tokio::sync::Mutex<()>>,
+    /// Registered data source connectors
+    pub sources: Arc<SourceRegistry>,
+    /// Grants sync work by priority so concurrent syncs never race on the same
+    /// source or on topic updates.
+    pub scheduler: Arc<SyncScheduler>,
+    /// Per-source backoff and circuit breakers, shared with the background sync
+    pub circuits: Arc<CircuitBreakers>,
     pub background_sync: Option<Arc<BackgroundSyncService>>,
     pub is_syncing: Arc<AtomicBool>,
+    /// `false` while the background sync is retrying sources it could not reach
+    pub is_online: Arc<AtomicBool>,
     pub next_sync_at: Arc<AtomicI64>,
 }
@@ -7,7 +7,10 @@ use companion::crypto::CryptoService;
 use companion::db::Database;
 use companion::notifications::NotificationService;
 use companion::pipeline::PipelineManager;
-use companion::sync::{BackgroundSyncService, SyncQueue};
+use companion::sync::{
+    notes, webhook, BackgroundSyncService, CircuitBreakers, ConnectorContext, SourceRegistry,
+    SyncQueue, SyncScheduler, SyncTimetable,
+};
 use companion::tray;
 use companion::AppState;
 use std::sync::Arc;
@@ -48,24 +51,35 @@ fn main() {
                 pipeline.set_app_handle(app_handle.clone());
                 let pipeline_arc = Arc::new(Mutex::new(pipeline));
 
-                let sync_queue = Arc::new(SyncQueue::new());
-                let sync_lock = Arc::new(tokio::sync::Mutex::new(()));
+                let sync_queue = Arc::new(SyncQueue::new(db_arc.clone()));
+                let sources = Arc::new(SourceRegistry::with_defaults());
+                let scheduler = Arc::new(SyncScheduler::new());
+                let circuits = Arc::new(CircuitBreakers::new());
 
-                let sync_interval = load_sync_interval(db_arc.clone()).await;
-                tracing::info!("Loaded sync interval: {} minutes", sync_interval);
+                let sync_timetable = load_sync_timetable(db_arc.clone()).await;
 
                 let background_sync = BackgroundSyncService::new(
                     app_handle.clone(),
                     db_arc.clone(),
                     crypto_arc.clone(),
                     pipeline_arc.clone(),
-                    sync_lock.clone(),
+                    scheduler.clone(),
                     sync_queue.clone(),
-                    sync_interval,
-                );
+                    sync_timetable,
+                )
+                .with_sources(sources.clone())
+                .with_circuits(circuits.clone());
                 let background_sync_arc = Arc::new(background_sync);
                 let is_syncing = background_sync_arc.is_syncing_flag();
                 let next_sync_at = background_sync_arc.next_sync_at_flag();
+                let tray_next_sync_at = next_sync_at.clone();
+                let is_online = background_sync_arc.is_online_flag();
+                let tray_is_online = is_online.clone();
+
+                let notes_ctx = ConnectorContext::new(db_arc.clone(), crypto_arc.clone());
+                let notes_pipeline = pipeline_arc.clone();
+                let notes_scheduler = scheduler.clone();
+                let webhook_ctx = ConnectorContext::new(db_arc.clone(), crypto_arc.clone());
 
                 app.manage(Arc::new(Mutex::new(AppState {
                     db: db_arc.clone(),
@@ -74,9 +88,12 @@ fn main() {
                     analytics: Some(analytics),
                     pipeline: pipeline_arc.clone(),
                     sync_queue,
-                    sync_lock,
+                    sources,
+                    scheduler,
+                    circuits,
                     background_sync: Some(background_sync_arc.clone()),
                     is_syncing,
+                    is_online,
                     next_sync_at,
                 })));
 
@@ -84,13 +101,27 @@ fn main() {
                     tracing::error!("Failed to initialize system tray: {}", e);
                 }
 
-                tray::spawn_tray_updater(app_handle.clone(), pipeline_arc);
+                tray::spawn_tray_updater(
+                    app_handle.clone(),
+                    pipeline_arc,
+                    tray_next_sync_at,
+                    tray_is_online,
+                );
 
                 let bg_sync = background_sync_arc.clone();
                 tauri::async_runtime::spawn(async move {
+                    bg_sync.resume_queued_jobs().await;
                     bg_sync.run_startup_sync_if_needed().await;
                     bg_sync.start();
                 });
+
+                tauri::async_runtime::spawn(notes::watch_notes(
+                    notes_ctx,
+                    notes_pipeline,
+                    notes_scheduler,
+                ));
+
+                tauri::async_runtime::spawn(webhook::serve_webhooks(webhook_ctx));
             });
 
             Ok(())
@@ -102,6 +133,14 @@ fn main() {
             commands::start_sync,
             commands::get_sync_status,
             commands::resync_historical_day,
+            commands::start_backfill,
+            commands::get_backfill_jobs,
+            commands::estimate_sync,
+            commands::get_sync_jobs,
+            commands::cancel_sync_job,
+            commands::retry_sync_job,
+            commands::get_sync_schedule,
+            commands::import_atlassian_export,
             commands::save_api_key,
             commands::has_api_key,
             commands::get_preferences,
@@ -112,6 +151,7 @@ fn main() {
             commands::track_event,
             commands::get_analytics_summary,
             commands::get_pipeline_status,
+            commands::cancel_task,
             commands::list_slack_channels,
             commands::list_slack_users,
             commands::save_slack_channels,
@@ -119,6 +159,20 @@ fn main() {
             commands::remove_slack_channel,
             commands::get_slack_connection_status,
             commands::disconnect_slack,
+            commands::import_slack_export,
+            commands::list_sources,
+            commands::connect_source,
+            commands::list_source_scopes,
+            commands::select_source_scopes,
+            commands::list_sync_intervals,
+            commands::set_sync_interval,
+            commands::get_source_health,
+            commands::get_resource_health,
+            commands::disconnect_source,
+            commands::get_email_sender_filter,
+            commands::save_email_sender_filter,
+            commands::import_email_archive,
+            commands::import_meeting_transcript,
             commands::save_gemini_credentials,
             commands::verify_gemini_connection,
             commands::get_gemini_auth_type,
@@ -130,7 +184,7 @@ fn main() {
         .expect("error while running tauri application");
 }
 
-async fn load_sync_interval(db: Arc<Database>) -> u64 {
+async fn load_sync_timetable(db: Arc<Database>) -> SyncTimetable {
     let result: Option<(String,)> =
         sqlx::query_as("SELECT value FROM preferences WHERE key = 'user_preferences'")
             .fetch_optional(db.pool())
@@ -138,14 +192,19 @@ async fn load_sync_interval(db: Arc<Database>) -> u64 {
             .ok()
             .flatten();
 
-    if let Some((json,)) = result {
-        if let Ok(prefs) = serde_json::from_str::<serde_json::Value>(&json) {
-            // Preferences are serialized with camelCase
-            if let Some(interval) = prefs.get("syncIntervalMinutes").and_then(|v| v.as_i64()) {
-                return interval.max(1) as u64;
-            }
+    let schedule = result
+        .and_then(|(json,)| commands::Preferences::from_json(&json).ok())
+        .map(|prefs| prefs.sync_schedule)
+        .unwrap_or_default();
+
+    match SyncTimetable::compile(&schedule) {
+        Ok(timetable) => {
+            tracing::info!("Loaded sync schedule: {:?}", schedule.entries);
+            timetable
+        }
+        Err(e) => {
+            tracing::warn!("Invalid sync schedule, using default: {}", e);
+            SyncTimetable::default()
         }
     }
-
-    15
 }
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter};
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 
 const MAX_HISTORY_SIZE: usize = 50;
 
@@ -24,10 +25,21 @@ pub enum PipelineTaskType {
     SyncSlack,
     SyncJira,
     SyncConfluence,
+    SyncGithub,
+    SyncGitlab,
+    SyncLinear,
+    SyncIncidents,
+    SyncEmail,
+    SyncCalendar,
+    SyncNotes,
+    SyncFeeds,
+    SyncWebhook,
+    ImportTranscript,
     AiSummarize,
     AiCategorize,
     GenerateDailyDigest,
     GenerateWeeklyDigest,
+    Backfill,
 }
 
 impl PipelineTaskType {
@@ -36,10 +48,21 @@ impl PipelineTaskType {
             Self::SyncSlack => "Syncing Slack",
             Self::SyncJira => "Syncing Jira",
             Self::SyncConfluence => "Syncing Confluence",
+            Self::SyncGithub => "Syncing GitHub",
+            Self::SyncGitlab => "Syncing GitLab",
+            Self::SyncLinear => "Syncing Linear",
+            Self::SyncIncidents => "Syncing incidents",
+            Self::SyncEmail => "Syncing email",
+            Self::SyncCalendar => "Syncing calendar",
+            Self::SyncNotes => "Syncing notes",
+            Self::SyncFeeds => "Syncing feeds",
+            Self::SyncWebhook => "Collecting webhook events",
+            Self::ImportTranscript => "Importing transcript",
             Self::AiSummarize => "Summarizing content",
             Self::AiCategorize => "Categorizing items",
             Self::GenerateDailyDigest => "Generating daily digest",
             Self::GenerateWeeklyDigest => "Generating weekly digest",
+            Self::Backfill => "Backfilling history",
         }
     }
 
@@ -48,10 +71,21 @@ impl PipelineTaskType {
             Self::SyncSlack => "🔄",
             Self::SyncJira => "🔄",
             Self::SyncConfluence => "🔄",
+            Self::SyncGithub => "🔄",
+            Self::SyncGitlab => "🔄",
+            Self::SyncLinear => "🔄",
+            Self::SyncIncidents => "🚨",
+            Self::SyncEmail => "📧",
+            Self::SyncCalendar => "📅",
+            Self::SyncNotes => "📝",
+            Self::SyncFeeds => "📡",
+            Self::SyncWebhook => "🪝",
+            Self::ImportTranscript => "🎙️",
             Self::AiSummarize => "✨",
             Self::AiCategorize => "🏷️",
             Self::GenerateDailyDigest => "📰",
             Self::GenerateWeeklyDigest => "📊",
+            Self::Backfill => "⏪",
         }
     }
 }
@@ -63,6 +97,77 @@ pub enum TaskStatus {
     Running,
     Completed,
     Failed,
+    Cancelled,
+}
+
+/// Cooperative cancellation flag for a running task.
+///
+/// Work checks [`CancellationToken::is_cancelled`] at safe points (between pages,
+/// chunks or days) and stops there, so nothing is left half-written. A child
+/// token is also cancelled when its parent is, which lets a backfill cancel the
+/// per-source syncs it starts.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    parent: Option<Box<CancellationToken>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A token that is cancelled along with this one, but can also be cancelled on its own
+    pub fn child(&self) -> Self {
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            parent: Some(Box::new(self.clone())),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+            || self.parent.as_ref().is_some_and(|p| p.is_cancelled())
+    }
+}
+
+/// Reports progress of one task from inside the work it tracks, so services
+/// deep in a sync or AI pass can update the task without knowing about the
+/// pipeline. The default reporter discards updates, for work run outside a task.
+#[derive(Clone, Default)]
+pub struct ProgressReporter {
+    task: Option<(Arc<Mutex<PipelineManager>>, String)>,
+}
+
+impl ProgressReporter {
+    pub fn new(pipeline: Arc<Mutex<PipelineManager>>, task_id: &str) -> Self {
+        Self {
+            task: Some((pipeline, task_id.to_string())),
+        }
+    }
+
+    /// Set the task's progress (0.0 to 1.0) and message
+    pub async fn report(&self, progress: f32, message: String) {
+        if let Some((pipeline, task_id)) = &self.task {
+            let pipeline = pipeline.lock().await;
+            pipeline
+                .update_progress(task_id, progress, Some(message))
+                .await;
+        }
+    }
+
+    /// Update the message only, for steps whose share of the work is unknown
+    /// or that do not advance it, such as rate-limit waits
+    pub async fn message(&self, message: String) {
+        if let Some((pipeline, task_id)) = &self.task {
+            let pipeline = pipeline.lock().await;
+            pipeline.set_message(task_id, message).await;
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -80,6 +185,8 @@ pub struct PipelineManager {
 struct PipelineStateInner {
     active_tasks: Vec<PipelineTask>,
     history: VecDeque<PipelineTask>,
+    /// Cancellation tokens of active tasks, keyed by task id
+    tokens: HashMap<String, CancellationToken>,
 }
 
 impl PipelineManager {
@@ -88,6 +195,7 @@ impl PipelineManager {
             state: Arc::new(RwLock::new(PipelineStateInner {
                 active_tasks: Vec::new(),
                 history: VecDeque::with_capacity(MAX_HISTORY_SIZE),
+                tokens: HashMap::new(),
             })),
             app_handle: None,
         }
@@ -98,6 +206,26 @@ impl PipelineManager {
     }
 
     pub async fn start_task(&self, task_type: PipelineTaskType, message: String) -> String {
+        self.start(task_type, message, CancellationToken::new())
+            .await
+    }
+
+    /// Start a task that is also cancelled when `parent` is
+    pub async fn start_child_task(
+        &self,
+        task_type: PipelineTaskType,
+        message: String,
+        parent: &CancellationToken,
+    ) -> String {
+        self.start(task_type, message, parent.child()).await
+    }
+
+    async fn start(
+        &self,
+        task_type: PipelineTaskType,
+        message: String,
+        token: CancellationToken,
+    ) -> String {
         let id = uuid::Uuid::new_v4().to_string();
         let task = PipelineTask {
             id: id.clone(),
@@ -113,12 +241,38 @@ impl PipelineManager {
         {
             let mut state = self.state.write().await;
             state.active_tasks.push(task.clone());
+            state.tokens.insert(id.clone(), token);
         }
 
         self.emit_update().await;
         id
     }
 
+    /// The cancellation token of an active task. Unknown or finished tasks get a
+    /// fresh token that is never cancelled.
+    pub async fn cancellation_token(&self, task_id: &str) -> CancellationToken {
+        let state = self.state.read().await;
+        state.tokens.get(task_id).cloned().unwrap_or_default()
+    }
+
+    /// Ask a running task to stop at its next safe point. The task stays active
+    /// until its worker finishes it, at which point it is recorded as cancelled.
+    /// Returns false if the task is not running.
+    pub async fn cancel_task(&self, task_id: &str) -> bool {
+        {
+            let mut state = self.state.write().await;
+            let Some(token) = state.tokens.get(task_id).cloned() else {
+                return false;
+            };
+            token.cancel();
+            if let Some(task) = state.active_tasks.iter_mut().find(|t| t.id == task_id) {
+                task.message = "Cancelling...".to_string();
+            }
+        }
+        self.emit_update().await;
+        true
+    }
+
     pub async fn update_progress(&self, task_id: &str, progress: f32, message: Option<String>) {
         {
             let mut state = self.state.write().await;
@@ -132,11 +286,22 @@ impl PipelineManager {
         self.emit_update().await;
     }
 
+    pub async fn set_message(&self, task_id: &str, message: String) {
+        {
+            let mut state = self.state.write().await;
+            if let Some(task) = state.active_tasks.iter_mut().find(|t| t.id == task_id) {
+                task.message = message;
+            }
+        }
+        self.emit_update().await;
+    }
+
     pub async fn complete_task(&self, task_id: &str, message: Option<String>) {
         self.finish_task(task_id, TaskStatus::Completed, message, None)
             .await;
     }
 
+    /// Record a task as failed, or as cancelled if it stopped because it was cancelled
     pub async fn fail_task(&self, task_id: &str, error: String) {
         self.finish_task(task_id, TaskStatus::Failed, None, Some(error))
             .await;
@@ -151,15 +316,25 @@ impl PipelineManager {
     ) {
         {
             let mut state = self.state.write().await;
+            let cancelled = state
+                .tokens
+                .remove(task_id)
+                .is_some_and(|token| token.is_cancelled());
             if let Some(idx) = state.active_tasks.iter().position(|t| t.id == task_id) {
                 let mut task = state.active_tasks.remove(idx);
-                task.status = status;
                 task.completed_at = Some(chrono::Utc::now().timestamp());
                 task.progress = Some(1.0);
-                if let Some(msg) = message {
-                    task.message = msg;
+                if cancelled && status == TaskStatus::Failed {
+                    task.status = TaskStatus::Cancelled;
+                    task.message = "Cancelled".to_string();
+                    task.error = None;
+                } else {
+                    task.status = status;
+                    if let Some(msg) = message {
+                        task.message = msg;
+                    }
+                    task.error = error;
                 }
-                task.error = error;
 
                 if state.history.len() >= MAX_HISTORY_SIZE {
                     state.history.pop_front();
@@ -259,6 +434,51 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_cancelled_task_finishes_as_cancelled() {
+        let manager = PipelineManager::new();
+        let task_id = manager
+            .start_task(PipelineTaskType::SyncSlack, "Syncing messages".to_string())
+            .await;
+        let token = manager.cancellation_token(&task_id).await;
+        assert!(!token.is_cancelled());
+
+        assert!(manager.cancel_task(&task_id).await);
+        assert!(token.is_cancelled());
+        // Still active until the worker stops
+        assert_eq!(manager.get_state().await.active_tasks.len(), 1);
+
+        manager.fail_task(&task_id, "Cancelled".to_string()).await;
+        let state = manager.get_state().await;
+        assert!(state.active_tasks.is_empty());
+        assert_eq!(state.recent_history[0].status, TaskStatus::Cancelled);
+        assert_eq!(state.recent_history[0].error, None);
+        assert!(!manager.cancel_task(&task_id).await);
+    }
+
+    #[tokio::test]
+    async fn test_child_task_cancelled_with_parent() {
+        let manager = PipelineManager::new();
+        let parent_id = manager
+            .start_task(PipelineTaskType::Backfill, "Backfilling".to_string())
+            .await;
+        let parent = manager.cancellation_token(&parent_id).await;
+        let child_id = manager
+            .start_child_task(PipelineTaskType::SyncJira, "Syncing".to_string(), &parent)
+            .await;
+        let child = manager.cancellation_token(&child_id).await;
+
+        manager.cancel_task(&child_id).await;
+        assert!(child.is_cancelled());
+        assert!(!parent.is_cancelled());
+
+        let other_id = manager
+            .start_child_task(PipelineTaskType::SyncJira, "Syncing".to_string(), &parent)
+            .await;
+        manager.cancel_task(&parent_id).await;
+        assert!(manager.cancellation_token(&other_id).await.is_cancelled());
+    }
+
     #[tokio::test]
     async fn test_status_message_idle() {
         let manager = PipelineManager::new();
@@ -318,6 +538,10 @@ mod tests {
             PipelineTaskType::GenerateWeeklyDigest.display_name(),
             "Generating weekly digest"
         );
+        assert_eq!(
+            PipelineTaskType::Backfill.display_name(),
+            "Backfilling history"
+        );
     }
 
     #[test]
@@ -345,6 +569,29 @@ mod tests {
         assert_eq!(state.active_tasks[0].progress, Some(0.0));
     }
 
+    #[tokio::test]
+    async fn test_progress_reporter_updates_task() {
+        let pipeline = Arc::new(Mutex::new(PipelineManager::new()));
+        let task_id = pipeline
+            .lock()
+            .await
+            .start_task(PipelineTaskType::SyncSlack, "Syncing Slack...".to_string())
+            .await;
+
+        let progress = ProgressReporter::new(pipeline.clone(), &task_id);
+        progress.report(0.25, "#general page 1".to_string()).await;
+        progress
+            .message("Rate limited, waiting 2s".to_string())
+            .await;
+        ProgressReporter::default()
+            .report(0.9, "Ignored".to_string())
+            .await;
+
+        let state = pipeline.lock().await.get_state().await;
+        assert_eq!(state.active_tasks[0].progress, Some(0.25));
+        assert_eq!(state.active_tasks[0].message, "Rate limited, waiting 2s");
+    }
+
     #[tokio::test]
     async fn test_update_nonexistent_task_is_noop() {
         let manager = PipelineManager::new();
@@ -381,6 +628,10 @@ mod tests {
             serde_json::to_string(&TaskStatus::Pending).unwrap(),
             "\"pending\""
         );
+        assert_eq!(
+            serde_json::to_string(&TaskStatus::Cancelled).unwrap(),
+            "\"cancelled\""
+        );
     }
 
     #[test]
@@ -180,6 +180,98 @@ impl AtlassianClient {
             .collect())
     }
 
+    /// List Jira projects visible to the user as (key, name) pairs
+    pub async fn list_projects(&self) -> Result<Vec<(String, String)>, AtlassianError> {
+        let token = self
+            .access_token
+            .as_ref()
+            .ok_or_else(|| AtlassianError::OAuth("Not authenticated".into()))?;
+        let cloud_id = self
+            .cloud_id
+            .as_ref()
+            .ok_or_else(|| AtlassianError::OAuth("No cloud instance selected".into()))?;
+
+        let url = format!(
+            "https://api.atlassian.com/ex/jira/{}/rest/api/3/project/search",
+            cloud_id
+        );
+
+        let response = self
+            .http
+            .get(&url)
+            .bearer_auth(token)
+            .query(&[("maxResults", "100")])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(AtlassianError::Api(format!("HTTP {}", response.status())));
+        }
+
+        let json: serde_json::Value = response.json().await?;
+
+        Ok(json["values"]
+            .as_array()
+            .map(|projects| {
+                projects
+                    .iter()
+                    .filter_map(|p| {
+                        Some((
+                            p["key"].as_str()?.to_string(),
+                            p["name"].as_str().unwrap_or_default().to_string(),
+                        ))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// List Confluence spaces visible to the user as (key, name) pairs
+    pub async fn list_spaces(&self) -> Result<Vec<(String, String)>, AtlassianError> {
+        let token = self
+            .access_token
+            .as_ref()
+            .ok_or_else(|| AtlassianError::OAuth("Not authenticated".into()))?;
+        let cloud_id = self
+            .cloud_id
+            .as_ref()
+            .ok_or_else(|| AtlassianError::OAuth("No cloud instance selected".into()))?;
+
+        let url = format!(
+            "https://api.atlassian.com/ex/confluence/{}/wiki/rest/api/space",
+            cloud_id
+        );
+
+        let response = self
+            .http
+            .get(&url)
+            .bearer_auth(token)
+            .query(&[("limit", "100")])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(AtlassianError::Api(format!("HTTP {}", response.status())));
+        }
+
+        let json: serde_json::Value = response.json().await?;
+
+        Ok(json["results"]
+            .as_array()
+            .map(|spaces| {
+                spaces
+                    .iter()
+                    .filter_map(|s| {
+                        Some((
+                            s["key"].as_str()?.to_string(),
+                            s["name"].as_str().unwrap_or_default().to_string(),
+                        ))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
     /// Search Jira issues using JQL
     pub async fn search_issues(
         &self,
@@ -275,6 +367,28 @@ impl AtlassianClient {
         cql: &str,
         start: i32,
         limit: i32,
+    ) -> Result<Vec<ConfluencePage>, AtlassianError> {
+        self.search_content(cql, start, limit, "body.storage,space,version")
+            .await
+    }
+
+    /// Search Confluence pages using CQL without fetching their bodies
+    pub async fn search_page_headers(
+        &self,
+        cql: &str,
+        start: i32,
+        limit: i32,
+    ) -> Result<Vec<ConfluencePage>, AtlassianError> {
+        self.search_content(cql, start, limit, "space,version")
+            .await
+    }
+
+    async fn search_content(
+        &self,
+        cql: &str,
+        start: i32,
+        limit: i32,
+        expand: &str,
     ) -> Result<Vec<ConfluencePage>, AtlassianError> {
         let token = self
             .access_token
@@ -301,7 +415,7 @@ impl AtlassianClient {
                 ("cql", cql),
                 ("start", &start_str),
                 ("limit", &limit_str),
-                ("expand", "body.storage,space,version"),
+                ("expand", expand),
             ])
             .send()
             .await?;
@@ -0,0 +1,514 @@
+//! Jira and Confluence source connectors
+//!
+//! Both products share one Atlassian OAuth credential and cloud selection, so
+//! connecting or disconnecting either connector affects the other.
+
+use async_trait::async_trait;
+
+use super::client::AtlassianClient;
+use super::sync::{
+    confluence_between_cql, confluence_recent_cql, jira_between_jql, jira_recent_jql,
+    AtlassianSyncService, KeyFilter,
+};
+use super::types::{AtlassianError, AtlassianTokens, CloudResource};
+use crate::pipeline::PipelineTaskType;
+use crate::sync::connector::{
+    clear_sync_state, load_credentials, load_cursor, save_cursor, store_credentials,
+    ConnectorContext, ConnectorError, SourceConnector, SourceScope, SyncRange, INITIAL_SYNC_DAYS,
+    MAX_CATCH_UP_DAYS,
+};
+use crate::sync::estimate::{DryRun, SourceEstimate};
+use crate::sync::intervals::scope_overrides;
+use crate::sync::status::mark_pending;
+use crate::sync::SyncResult;
+
+/// Load stored Atlassian tokens and the selected cloud id, if both are present
+pub(crate) async fn load_atlassian_credentials(
+    ctx: &ConnectorContext,
+) -> Result<Option<(AtlassianTokens, String)>, ConnectorError> {
+    let tokens: Option<AtlassianTokens> = load_credentials(ctx, "atlassian").await?;
+    let cloud_id: Option<(String,)> =
+        sqlx::query_as("SELECT value FROM preferences WHERE key = 'atlassian_cloud_id'")
+            .fetch_optional(ctx.db.pool())
+            .await?;
+
+    let (Some(tokens), Some((cloud_id,))) = (tokens, cloud_id) else {
+        return Ok(None);
+    };
+
+    Ok(Some((tokens, cloud_id)))
+}
+
+async fn load_client(
+    ctx: &ConnectorContext,
+    source: &'static str,
+) -> Result<(AtlassianClient, String), ConnectorError> {
+    let (tokens, cloud_id) = load_atlassian_credentials(ctx)
+        .await?
+        .ok_or(ConnectorError::NotConnected(source))?;
+
+    let client = AtlassianClient::new(String::new(), String::new())
+        .with_token(tokens.access_token, cloud_id.clone());
+    Ok((client, cloud_id))
+}
+
+fn sync_error(e: AtlassianError) -> ConnectorError {
+    match e {
+        AtlassianError::Cancelled => ConnectorError::Cancelled,
+        e => ConnectorError::classify(e),
+    }
+}
+
+/// Build an Atlassian sync service from stored credentials and the selected cloud resource.
+pub(crate) async fn load_atlassian_sync_service(
+    ctx: &ConnectorContext,
+    source: &'static str,
+) -> Result<(AtlassianSyncService, String), ConnectorError> {
+    let (client, cloud_id) = load_client(ctx, source).await?;
+    Ok((
+        AtlassianSyncService::new(client, ctx.db.clone(), ctx.crypto.clone())
+            .with_cancellation(ctx.cancel.clone())
+            .with_progress(ctx.progress.clone()),
+        cloud_id,
+    ))
+}
+
+/// Run the OAuth flow and store the resulting tokens. The caller still has to
+/// pick one of the returned cloud resources.
+pub async fn connect_atlassian_oauth(
+    ctx: &ConnectorContext,
+    client_id: String,
+    client_secret: String,
+) -> Result<(AtlassianTokens, Vec<CloudResource>), ConnectorError> {
+    let client = AtlassianClient::new(client_id, client_secret);
+    let (tokens, resources) = client
+        .start_oauth_flow()
+        .await
+        .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+    store_credentials(ctx, "atlassian", &tokens).await?;
+
+    tracing::info!(
+        "Atlassian connected with {} cloud resources",
+        resources.len()
+    );
+    Ok((tokens, resources))
+}
+
+/// Shared `connect` for both products. Expects `{ "clientId", "clientSecret" }`
+/// and optionally `cloudId` to select a site immediately.
+async fn connect_from_config(
+    ctx: &ConnectorContext,
+    config: serde_json::Value,
+) -> Result<serde_json::Value, ConnectorError> {
+    let field = |name: &str| {
+        config[name]
+            .as_str()
+            .filter(|v| !v.trim().is_empty())
+            .map(String::from)
+            .ok_or_else(|| ConnectorError::InvalidConfig(format!("{} is required", name)))
+    };
+
+    let (_tokens, resources) =
+        connect_atlassian_oauth(ctx, field("clientId")?, field("clientSecret")?).await?;
+
+    let cloud_id = config["cloudId"].as_str().map(String::from).or_else(|| {
+        // A single site needs no selection step
+        (resources.len() == 1).then(|| resources[0].id.clone())
+    });
+    if let Some(cloud_id) = cloud_id {
+        sqlx::query(
+            "INSERT INTO preferences (key, value) VALUES ('atlassian_cloud_id', ?)
+             ON CONFLICT(key) DO UPDATE SET value = ?",
+        )
+        .bind(&cloud_id)
+        .bind(&cloud_id)
+        .execute(ctx.db.pool())
+        .await?;
+    }
+
+    Ok(serde_json::json!({ "resources": resources }))
+}
+
+async fn disconnect_atlassian(ctx: &ConnectorContext) -> Result<(), ConnectorError> {
+    sqlx::query("DELETE FROM credentials WHERE id = 'atlassian'")
+        .execute(ctx.db.pool())
+        .await?;
+
+    sqlx::query("DELETE FROM preferences WHERE key = 'atlassian_cloud_id'")
+        .execute(ctx.db.pool())
+        .await?;
+
+    clear_sync_state(&ctx.db, "jira").await?;
+    clear_sync_state(&ctx.db, "confluence").await?;
+
+    Ok(())
+}
+
+/// Days to look back for an incremental sync given the last synced date
+fn catch_up_days(last_synced: Option<&str>, today: chrono::NaiveDate) -> i32 {
+    let days = last_synced
+        .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .map(|d| (today - d).num_days() + 1)
+        .unwrap_or(INITIAL_SYNC_DAYS);
+    days.clamp(1, MAX_CATCH_UP_DAYS) as i32
+}
+
+/// `[start, end)` dates covering a sync range, as used by JQL/CQL queries
+fn range_bounds(range: &SyncRange) -> (String, String) {
+    let end = range.end_date.succ_opt().unwrap_or(range.end_date);
+    (
+        range.start_date.format("%Y-%m-%d").to_string(),
+        end.format("%Y-%m-%d").to_string(),
+    )
+}
+
+/// Fetch recently updated Jira issues or Confluence pages
+async fn sync_recent(
+    service: &AtlassianSyncService,
+    source: &str,
+    days: i32,
+    filter: KeyFilter<'_>,
+) -> Result<i32, ConnectorError> {
+    let result = if source == "jira" {
+        service.sync_jira(days, filter).await
+    } else {
+        service.sync_confluence(days, filter).await
+    };
+    result.map_err(sync_error)
+}
+
+/// Incremental sync shared by both products. Projects or spaces with their own
+/// sync interval are queried separately under their own cursor; everything else
+/// follows the site cursor.
+async fn sync_incremental_by_scope(
+    ctx: &ConnectorContext,
+    source: &'static str,
+    name: &'static str,
+    resource_type: &str,
+) -> Result<SyncResult, ConnectorError> {
+    let (service, cloud_id) = load_atlassian_sync_service(ctx, name).await?;
+    let today = chrono::Utc::now().date_naive();
+    let cursor = today.format("%Y-%m-%d").to_string();
+    let own_interval = scope_overrides(&ctx.db, source, resource_type).await?;
+
+    let mut due = Vec::new();
+    for key in &own_interval {
+        if ctx.is_scope_due(source, resource_type, key).await {
+            due.push(key);
+        }
+    }
+    let site_due = ctx.is_scope_due(source, "site", &cloud_id).await;
+    mark_pending(&ctx.db, source, resource_type, &due).await;
+    if site_due {
+        mark_pending(&ctx.db, source, "site", &[&cloud_id]).await;
+    }
+    let steps = due.len() + 1;
+
+    let mut items_synced = 0;
+    for (index, key) in due.iter().enumerate() {
+        ctx.progress
+            .report(
+                index as f32 / steps as f32,
+                format!("Syncing {} {} {}...", name, resource_type, key),
+            )
+            .await;
+        let last = load_cursor(&ctx.db, source, resource_type, key).await?;
+        let days = catch_up_days(last.as_deref(), today);
+        let sync = sync_recent(&service, source, days, KeyFilter::Only(key));
+        items_synced += ctx.track_resource(source, resource_type, key, sync).await?;
+        save_cursor(&ctx.db, source, resource_type, key, &cursor).await?;
+    }
+
+    if site_due {
+        ctx.progress
+            .report(
+                due.len() as f32 / steps as f32,
+                format!("Syncing {}...", name),
+            )
+            .await;
+        let last = load_cursor(&ctx.db, source, "site", &cloud_id).await?;
+        let days = catch_up_days(last.as_deref(), today);
+        let sync = sync_recent(&service, source, days, KeyFilter::Excluding(&own_interval));
+        items_synced += ctx.track_resource(source, "site", &cloud_id, sync).await?;
+        save_cursor(&ctx.db, source, "site", &cloud_id, &cursor).await?;
+    }
+
+    Ok(SyncResult {
+        source: source.to_string(),
+        items_synced,
+        errors: vec![],
+    })
+}
+
+/// Dry run shared by both products over what an incremental or range sync
+/// would fetch. Projects or spaces with their own interval are counted from
+/// the site cursor rather than queried separately.
+async fn estimate_by_scope(
+    ctx: &ConnectorContext,
+    source: &'static str,
+    name: &'static str,
+    dry_run: &DryRun,
+) -> Result<Option<SourceEstimate>, ConnectorError> {
+    let (service, cloud_id) = load_atlassian_sync_service(ctx, name).await?;
+    let filter = match dry_run.scopes_for(source) {
+        Some([]) => return Ok(Some(SourceEstimate::new(source))),
+        Some(keys) => KeyFilter::In(keys),
+        None => KeyFilter::Excluding(&[]),
+    };
+
+    let query = match &dry_run.range {
+        Some(range) => {
+            let (start, end) = range_bounds(range);
+            if source == "jira" {
+                jira_between_jql(&start, &end, filter)
+            } else {
+                confluence_between_cql(&start, &end, filter)
+            }
+        }
+        None => {
+            let last = load_cursor(&ctx.db, source, "site", &cloud_id).await?;
+            let days = catch_up_days(last.as_deref(), chrono::Utc::now().date_naive());
+            if source == "jira" {
+                jira_recent_jql(days, filter)
+            } else {
+                confluence_recent_cql(days, filter)
+            }
+        }
+    };
+
+    let result = if source == "jira" {
+        service.estimate_jira(&query, dry_run.timezone_offset).await
+    } else {
+        service
+            .estimate_confluence(&query, dry_run.timezone_offset)
+            .await
+    };
+    result.map(Some).map_err(sync_error)
+}
+
+pub struct JiraConnector;
+
+#[async_trait]
+impl SourceConnector for JiraConnector {
+    fn id(&self) -> &'static str {
+        "jira"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Jira"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sync issues from Atlassian Jira"
+    }
+
+    fn task_type(&self) -> PipelineTaskType {
+        PipelineTaskType::SyncJira
+    }
+
+    fn item_label(&self) -> &'static str {
+        "issues"
+    }
+
+    async fn connect(
+        &self,
+        ctx: &ConnectorContext,
+        config: serde_json::Value,
+    ) -> Result<serde_json::Value, ConnectorError> {
+        connect_from_config(ctx, config).await
+    }
+
+    async fn is_connected(&self, ctx: &ConnectorContext) -> bool {
+        matches!(load_atlassian_credentials(ctx).await, Ok(Some(_)))
+    }
+
+    fn supports_scope_intervals(&self) -> bool {
+        true
+    }
+
+    async fn list_scopes(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<Vec<SourceScope>, ConnectorError> {
+        let (client, _) = load_client(ctx, "Jira").await?;
+        let projects = client
+            .list_projects()
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+        Ok(projects
+            .into_iter()
+            .map(|(key, name)| SourceScope {
+                id: key,
+                name,
+                kind: "project".to_string(),
+                selected: true,
+            })
+            .collect())
+    }
+
+    async fn sync_incremental(&self, ctx: &ConnectorContext) -> Result<SyncResult, ConnectorError> {
+        sync_incremental_by_scope(ctx, self.id(), "Jira", "project").await
+    }
+
+    async fn sync_range(
+        &self,
+        ctx: &ConnectorContext,
+        range: &SyncRange,
+    ) -> Result<SyncResult, ConnectorError> {
+        let (service, _) = load_atlassian_sync_service(ctx, "Jira").await?;
+        let (start, end) = range_bounds(range);
+
+        let items_synced = service
+            .sync_jira_between(&start, &end)
+            .await
+            .map_err(sync_error)?;
+
+        Ok(SyncResult {
+            source: self.id().to_string(),
+            items_synced,
+            errors: vec![],
+        })
+    }
+
+    async fn estimate(
+        &self,
+        ctx: &ConnectorContext,
+        dry_run: &DryRun,
+    ) -> Result<Option<SourceEstimate>, ConnectorError> {
+        estimate_by_scope(ctx, self.id(), self.display_name(), dry_run).await
+    }
+
+    async fn disconnect(&self, ctx: &ConnectorContext) -> Result<(), ConnectorError> {
+        disconnect_atlassian(ctx).await
+    }
+}
+
+pub struct ConfluenceConnector;
+
+#[async_trait]
+impl SourceConnector for ConfluenceConnector {
+    fn id(&self) -> &'static str {
+        "confluence"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Confluence"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sync pages and spaces from Atlassian Confluence"
+    }
+
+    fn task_type(&self) -> PipelineTaskType {
+        PipelineTaskType::SyncConfluence
+    }
+
+    fn item_label(&self) -> &'static str {
+        "pages"
+    }
+
+    async fn connect(
+        &self,
+        ctx: &ConnectorContext,
+        config: serde_json::Value,
+    ) -> Result<serde_json::Value, ConnectorError> {
+        connect_from_config(ctx, config).await
+    }
+
+    async fn is_connected(&self, ctx: &ConnectorContext) -> bool {
+        matches!(load_atlassian_credentials(ctx).await, Ok(Some(_)))
+    }
+
+    fn supports_scope_intervals(&self) -> bool {
+        true
+    }
+
+    async fn list_scopes(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<Vec<SourceScope>, ConnectorError> {
+        let (client, _) = load_client(ctx, "Confluence").await?;
+        let spaces = client
+            .list_spaces()
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+        Ok(spaces
+            .into_iter()
+            .map(|(key, name)| SourceScope {
+                id: key,
+                name,
+                kind: "space".to_string(),
+                selected: true,
+            })
+            .collect())
+    }
+
+    async fn sync_incremental(&self, ctx: &ConnectorContext) -> Result<SyncResult, ConnectorError> {
+        sync_incremental_by_scope(ctx, self.id(), "Confluence", "space").await
+    }
+
+    async fn sync_range(
+        &self,
+        ctx: &ConnectorContext,
+        range: &SyncRange,
+    ) -> Result<SyncResult, ConnectorError> {
+        let (service, _) = load_atlassian_sync_service(ctx, "Confluence").await?;
+        let (start, end) = range_bounds(range);
+
+        let items_synced = service
+            .sync_confluence_between(&start, &end)
+            .await
+            .map_err(sync_error)?;
+
+        Ok(SyncResult {
+            source: self.id().to_string(),
+            items_synced,
+            errors: vec![],
+        })
+    }
+
+    async fn estimate(
+        &self,
+        ctx: &ConnectorContext,
+        dry_run: &DryRun,
+    ) -> Result<Option<SourceEstimate>, ConnectorError> {
+        estimate_by_scope(ctx, self.id(), self.display_name(), dry_run).await
+    }
+
+    async fn disconnect(&self, ctx: &ConnectorContext) -> Result<(), ConnectorError> {
+        disconnect_atlassian(ctx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_up_days() {
+        let today = chrono::NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        assert_eq!(catch_up_days(None, today), INITIAL_SYNC_DAYS as i32);
+        assert_eq!(catch_up_days(Some("2024-03-10"), today), 1);
+        assert_eq!(catch_up_days(Some("2024-03-07"), today), 4);
+        assert_eq!(
+            catch_up_days(Some("2023-01-01"), today),
+            MAX_CATCH_UP_DAYS as i32
+        );
+        assert_eq!(
+            catch_up_days(Some("garbage"), today),
+            INITIAL_SYNC_DAYS as i32
+        );
+    }
+
+    #[test]
+    fn test_range_bounds_are_end_exclusive() {
+        let range = SyncRange::new("2024-01-30", "2024-01-31", 0).unwrap();
+        assert_eq!(
+            range_bounds(&range),
+            ("2024-01-30".to_string(), "2024-02-01".to_string())
+        );
+    }
+}
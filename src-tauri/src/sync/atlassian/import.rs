@@ -0,0 +1,949 @@
+//! Jira and Confluence export import
+//!
+//! Parses issue search exports (XML, CSV or JSON) and Confluence space exports
+//! (HTML or XML) from disk, then stores them through the same path as the live
+//! sync so imported items share its schema and encryption.
+
+use super::sync::{parse_atlassian_timestamp, store_confluence_page, store_jira_issue};
+use super::types::{AtlassianError, ConfluencePage, JiraIssue};
+use crate::crypto::CryptoService;
+use crate::db::Database;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Which product an export file came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AtlassianExportKind {
+    Jira,
+    Confluence,
+}
+
+/// Summary of a completed export import
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AtlassianImportResult {
+    pub issues_imported: i32,
+    pub pages_imported: i32,
+    pub errors: Vec<String>,
+}
+
+fn import_error(e: impl std::fmt::Display) -> AtlassianError {
+    AtlassianError::Import(e.to_string())
+}
+
+/// Convert the timestamp styles found in exports into a form
+/// `parse_atlassian_timestamp` accepts. Naive times are treated as UTC.
+fn normalize_timestamp(value: &str) -> String {
+    let value = value.trim();
+    if value.is_empty() || parse_atlassian_timestamp(value).is_some() {
+        return value.to_string();
+    }
+
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc2822(value) {
+        return dt.to_rfc3339();
+    }
+
+    const NAIVE_FORMATS: [&str; 4] = [
+        "%Y-%m-%d %H:%M:%S%.f", // Confluence entities.xml
+        "%d/%b/%y %I:%M %p",    // Jira CSV
+        "%d/%b/%Y %I:%M %p",
+        "%Y-%m-%d %H:%M",
+    ];
+    for format in NAIVE_FORMATS {
+        if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(value, format) {
+            return dt.and_utc().to_rfc3339();
+        }
+    }
+
+    // Confluence HTML metadata only carries the day
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%b %d, %Y") {
+        return date.and_hms_opt(0, 0, 0).unwrap().and_utc().to_rfc3339();
+    }
+
+    value.to_string()
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+}
+
+fn jira_browse_url(base_url: Option<&str>, key: &str) -> String {
+    base_url
+        .map(|base| format!("{}/browse/{}", base.trim_end_matches('/'), key))
+        .unwrap_or_default()
+}
+
+fn confluence_page_url(base_url: Option<&str>, space_key: &str, id: &str) -> String {
+    base_url
+        .map(|base| {
+            format!(
+                "{}/wiki/spaces/{}/pages/{}",
+                base.trim_end_matches('/'),
+                space_key,
+                id
+            )
+        })
+        .unwrap_or_default()
+}
+
+fn project_from_key(key: &str) -> String {
+    key.split_once('-')
+        .map(|(project, _)| project.to_string())
+        .unwrap_or_default()
+}
+
+/// Parse a Jira issue search export, detecting XML, JSON or CSV from its content.
+pub fn parse_jira_export(
+    contents: &str,
+    base_url: Option<&str>,
+) -> Result<Vec<JiraIssue>, AtlassianError> {
+    let contents = contents.trim_start_matches('\u{feff}');
+    match contents.trim_start().chars().next() {
+        Some('<') => parse_jira_xml(contents, base_url),
+        Some('{') | Some('[') => parse_jira_json(contents, base_url),
+        Some(_) => parse_jira_csv(contents, base_url),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn child_text(node: roxmltree::Node, name: &str) -> Option<String> {
+    node.children()
+        .find(|c| c.has_tag_name(name))
+        .and_then(|c| non_empty(c.text()))
+}
+
+/// Jira's "Export XML" is an RSS document with one `<item>` per issue.
+fn parse_jira_xml(
+    contents: &str,
+    base_url: Option<&str>,
+) -> Result<Vec<JiraIssue>, AtlassianError> {
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let doc = roxmltree::Document::parse_with_options(contents, options).map_err(import_error)?;
+
+    let issues = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("item"))
+        .filter_map(|item| {
+            let key_node = item.children().find(|c| c.has_tag_name("key"))?;
+            let key = non_empty(key_node.text())?;
+
+            let project_key = item
+                .children()
+                .find(|c| c.has_tag_name("project"))
+                .and_then(|p| p.attribute("key"))
+                .map(String::from)
+                .unwrap_or_else(|| project_from_key(&key));
+            let summary = child_text(item, "summary").unwrap_or_else(|| {
+                child_text(item, "title")
+                    .map(|t| {
+                        t.trim_start_matches(&format!("[{}]", key))
+                            .trim()
+                            .to_string()
+                    })
+                    .unwrap_or_default()
+            });
+
+            Some(JiraIssue {
+                id: key_node.attribute("id").unwrap_or(&key).to_string(),
+                summary,
+                description: child_text(item, "description"),
+                status: child_text(item, "status").unwrap_or_default(),
+                assignee: child_text(item, "assignee").filter(|a| a != "Unassigned"),
+                reporter: child_text(item, "reporter").unwrap_or_default(),
+                project_key,
+                created: normalize_timestamp(&child_text(item, "created").unwrap_or_default()),
+                updated: normalize_timestamp(&child_text(item, "updated").unwrap_or_default()),
+                url: child_text(item, "link").unwrap_or_else(|| jira_browse_url(base_url, &key)),
+                key,
+            })
+        })
+        .collect();
+
+    Ok(issues)
+}
+
+/// Flatten an Atlassian Document Format tree into plain text
+fn adf_to_text(node: &serde_json::Value) -> String {
+    if let Some(text) = node["text"].as_str() {
+        return text.to_string();
+    }
+
+    let mut out = String::new();
+    if let Some(children) = node["content"].as_array() {
+        for child in children {
+            out.push_str(&adf_to_text(child));
+        }
+    }
+
+    if matches!(
+        node["type"].as_str(),
+        Some("paragraph" | "heading" | "codeBlock" | "blockquote" | "listItem" | "hardBreak")
+    ) {
+        out.push('\n');
+    }
+    out
+}
+
+/// JSON exports follow the REST search response, either wrapped in `issues` or as a bare array.
+fn parse_jira_json(
+    contents: &str,
+    base_url: Option<&str>,
+) -> Result<Vec<JiraIssue>, AtlassianError> {
+    let json: serde_json::Value = serde_json::from_str(contents).map_err(import_error)?;
+    let items = json["issues"]
+        .as_array()
+        .or_else(|| json.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    let issues = items
+        .iter()
+        .filter_map(|i| {
+            let fields = &i["fields"];
+            let key = i["key"].as_str()?.to_string();
+            let id = i["id"]
+                .as_str()
+                .map(String::from)
+                .or_else(|| i["id"].as_i64().map(|n| n.to_string()))
+                .unwrap_or_else(|| key.clone());
+
+            let description = match &fields["description"] {
+                serde_json::Value::String(s) => non_empty(Some(s)),
+                serde_json::Value::Object(_) => {
+                    non_empty(Some(&adf_to_text(&fields["description"])))
+                }
+                _ => None,
+            };
+
+            // Derive the site from the issue's REST `self` link when no base URL is given
+            let url = match base_url {
+                Some(_) => jira_browse_url(base_url, &key),
+                None => i["self"]
+                    .as_str()
+                    .and_then(|s| s.split_once("/rest/"))
+                    .map(|(site, _)| format!("{}/browse/{}", site, key))
+                    .unwrap_or_default(),
+            };
+
+            Some(JiraIssue {
+                id,
+                summary: fields["summary"].as_str().unwrap_or_default().to_string(),
+                description,
+                status: fields["status"]["name"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                assignee: fields["assignee"]["displayName"].as_str().map(String::from),
+                reporter: fields["reporter"]["displayName"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                project_key: fields["project"]["key"]
+                    .as_str()
+                    .map(String::from)
+                    .unwrap_or_else(|| project_from_key(&key)),
+                created: normalize_timestamp(fields["created"].as_str().unwrap_or_default()),
+                updated: normalize_timestamp(fields["updated"].as_str().unwrap_or_default()),
+                url,
+                key,
+            })
+        })
+        .collect();
+
+    Ok(issues)
+}
+
+/// CSV exports use Jira's display column names; the first matching column wins.
+fn parse_jira_csv(
+    contents: &str,
+    base_url: Option<&str>,
+) -> Result<Vec<JiraIssue>, AtlassianError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(contents.as_bytes());
+    let headers = reader.headers().map_err(import_error)?.clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+
+    let key_col = column("Issue key")
+        .ok_or_else(|| AtlassianError::Import("CSV export has no 'Issue key' column".into()))?;
+    let id_col = column("Issue id");
+    let summary_col = column("Summary");
+    let description_col = column("Description");
+    let status_col = column("Status");
+    let assignee_col = column("Assignee");
+    let reporter_col = column("Reporter");
+    let project_col = column("Project key");
+    let created_col = column("Created");
+    let updated_col = column("Updated");
+
+    let mut issues = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(import_error)?;
+        let field = |col: Option<usize>| non_empty(col.and_then(|c| record.get(c)));
+
+        let Some(key) = field(Some(key_col)) else {
+            continue;
+        };
+
+        issues.push(JiraIssue {
+            id: field(id_col).unwrap_or_else(|| key.clone()),
+            summary: field(summary_col).unwrap_or_default(),
+            description: field(description_col),
+            status: field(status_col).unwrap_or_default(),
+            assignee: field(assignee_col),
+            reporter: field(reporter_col).unwrap_or_default(),
+            project_key: field(project_col).unwrap_or_else(|| project_from_key(&key)),
+            created: normalize_timestamp(&field(created_col).unwrap_or_default()),
+            updated: normalize_timestamp(&field(updated_col).unwrap_or_default()),
+            url: jira_browse_url(base_url, &key),
+            key,
+        });
+    }
+
+    Ok(issues)
+}
+
+/// Parse a Confluence space export ZIP, either the XML (`entities.xml`) or HTML flavour.
+pub fn parse_confluence_export<R: Read + Seek>(
+    reader: R,
+    base_url: Option<&str>,
+) -> Result<Vec<ConfluencePage>, AtlassianError> {
+    let mut archive = zip::ZipArchive::new(reader).map_err(import_error)?;
+
+    let entities = archive
+        .file_names()
+        .find(|name| name.rsplit('/').next() == Some("entities.xml"))
+        .map(String::from);
+    if let Some(name) = entities {
+        let mut contents = String::new();
+        archive
+            .by_name(&name)
+            .map_err(import_error)?
+            .read_to_string(&mut contents)?;
+        return parse_confluence_entities(&contents, base_url);
+    }
+
+    let mut pages = Vec::new();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(import_error)?;
+        let name = entry.name().to_string();
+        if !entry.is_file() || !name.ends_with(".html") || name.ends_with("index.html") {
+            continue;
+        }
+
+        let mut html = String::new();
+        entry.read_to_string(&mut html)?;
+        if let Some(page) = parse_confluence_html_page(&name, &html, base_url) {
+            pages.push(page);
+        }
+    }
+
+    Ok(pages)
+}
+
+fn property<'a, 'input>(
+    object: roxmltree::Node<'a, 'input>,
+    name: &str,
+) -> Option<roxmltree::Node<'a, 'input>> {
+    object
+        .children()
+        .find(|c| c.has_tag_name("property") && c.attribute("name") == Some(name))
+}
+
+fn object_id(node: roxmltree::Node) -> Option<String> {
+    child_text(node, "id")
+}
+
+fn property_text(object: roxmltree::Node, name: &str) -> Option<String> {
+    property(object, name).and_then(|p| non_empty(p.text()))
+}
+
+/// Resolve a property that references another object by id
+fn property_ref(object: roxmltree::Node, name: &str) -> Option<String> {
+    property(object, name).and_then(object_id)
+}
+
+/// The XML export is a Hibernate object dump; pages, bodies, spaces and users
+/// are separate objects linked by id.
+fn parse_confluence_entities(
+    contents: &str,
+    base_url: Option<&str>,
+) -> Result<Vec<ConfluencePage>, AtlassianError> {
+    let doc = roxmltree::Document::parse(contents).map_err(import_error)?;
+    let objects: Vec<_> = doc
+        .root_element()
+        .children()
+        .filter(|n| n.has_tag_name("object"))
+        .collect();
+
+    let mut spaces = HashMap::new();
+    let mut users = HashMap::new();
+    let mut bodies = HashMap::new();
+
+    for object in &objects {
+        match object.attribute("class") {
+            Some("Space") => {
+                if let (Some(id), Some(key)) = (object_id(*object), property_text(*object, "key")) {
+                    spaces.insert(id, key);
+                }
+            }
+            Some("ConfluenceUserImpl") => {
+                if let (Some(id), Some(name)) = (object_id(*object), property_text(*object, "name"))
+                {
+                    users.insert(id, name);
+                }
+            }
+            Some("BodyContent") => {
+                if let (Some(content), Some(body)) = (
+                    property_ref(*object, "content"),
+                    property_text(*object, "body"),
+                ) {
+                    bodies.insert(content, body);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let pages = objects
+        .iter()
+        .filter(|o| matches!(o.attribute("class"), Some("Page") | Some("BlogPost")))
+        .filter(|o| {
+            // Older versions point at their current page; drafts and trashed pages are skipped
+            property_ref(**o, "originalVersion").is_none()
+                && property_text(**o, "contentStatus").is_none_or(|s| s == "current")
+        })
+        .filter_map(|o| {
+            let id = object_id(*o)?;
+            let space_key = property_ref(*o, "space")
+                .and_then(|s| spaces.get(&s).cloned())
+                .unwrap_or_default();
+            let author = property_ref(*o, "creator")
+                .or_else(|| property_ref(*o, "lastModifier"))
+                .and_then(|u| users.get(&u).cloned())
+                .unwrap_or_default();
+
+            Some(ConfluencePage {
+                title: property_text(*o, "title").unwrap_or_default(),
+                body: bodies.get(&id).cloned(),
+                author,
+                created: normalize_timestamp(
+                    &property_text(*o, "creationDate").unwrap_or_default(),
+                ),
+                updated: normalize_timestamp(
+                    &property_text(*o, "lastModificationDate").unwrap_or_default(),
+                ),
+                url: confluence_page_url(base_url, &space_key, &id),
+                space_key,
+                id,
+            })
+        })
+        .collect();
+
+    Ok(pages)
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&out)
+}
+
+/// Inner HTML of the `<div>` carrying the given id, honouring nested divs
+fn div_inner_html<'a>(html: &'a str, id: &str) -> Option<&'a str> {
+    let marker = [format!("id=\"{}\"", id), format!("id='{}'", id)]
+        .into_iter()
+        .find_map(|m| html.find(&m))?;
+    let open_start = html[..marker].rfind("<div")?;
+    let content_start = open_start + html[open_start..].find('>')? + 1;
+
+    let mut depth = 1;
+    let mut pos = content_start;
+    while depth > 0 {
+        let next_open = html[pos..].find("<div").map(|i| pos + i);
+        let next_close = html[pos..].find("</div").map(|i| pos + i)?;
+        match next_open {
+            Some(open) if open < next_close => {
+                depth += 1;
+                pos = open + 4;
+            }
+            _ => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&html[content_start..next_close]);
+                }
+                pos = next_close + 5;
+            }
+        }
+    }
+    None
+}
+
+fn first_between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let from = text.find(start)? + start.len();
+    let to = from + text[from..].find(end)?;
+    Some(&text[from..to])
+}
+
+/// Dates in the page metadata line, e.g. "Created by Alice on Jan 15, 2024, last modified on Jan 16, 2024"
+fn metadata_dates(metadata: &str) -> Vec<String> {
+    metadata
+        .split(" on ")
+        .skip(1)
+        .filter_map(|segment| {
+            // "Jan 15, 2024" is always three whitespace-separated tokens
+            let date: Vec<&str> = segment.split_whitespace().take(3).collect();
+            let date = date.join(" ");
+            let date = date.trim_end_matches([',', '.']);
+            chrono::NaiveDate::parse_from_str(date, "%b %d, %Y")
+                .ok()
+                .map(|_| normalize_timestamp(date))
+        })
+        .collect()
+}
+
+/// Parse one page from the HTML export. Files are named `Title_<id>.html` inside
+/// a folder named after the space key.
+pub fn parse_confluence_html_page(
+    path: &str,
+    html: &str,
+    base_url: Option<&str>,
+) -> Option<ConfluencePage> {
+    let mut segments = path.rsplit('/');
+    let stem = segments.next()?.strip_suffix(".html")?;
+    let space_key = segments.next().unwrap_or_default().to_string();
+    let id = stem
+        .rsplit_once('_')
+        .map(|(_, id)| id)
+        .filter(|id| id.chars().all(|c| c.is_ascii_digit()))
+        .unwrap_or(stem)
+        .to_string();
+
+    // Titles are rendered as "Space Name : Page Title"
+    let title = first_between(html, "<title>", "</title>")
+        .map(decode_entities)
+        .map(|t| {
+            t.split_once(" : ")
+                .map(|(_, page)| page.to_string())
+                .unwrap_or(t.clone())
+        })
+        .map(|t| t.trim().to_string())
+        .unwrap_or_else(|| stem.to_string());
+
+    let body = div_inner_html(html, "main-content")
+        .or_else(|| {
+            first_between(html, "<body", "</body>")
+                .and_then(|b| b.split_once('>').map(|(_, rest)| rest))
+        })
+        .map(|b| b.trim().to_string())
+        .filter(|b| !b.is_empty());
+
+    let metadata = div_inner_html(html, "page-metadata")
+        .or_else(|| first_between(html, "class=\"page-metadata\"", "</div>"))
+        .map(strip_tags)
+        .unwrap_or_default();
+    let author = ["class='author'", "class=\"author\""]
+        .iter()
+        .find_map(|marker| {
+            let after = &html[html.find(marker)?..];
+            first_between(after, ">", "<")
+        })
+        .map(decode_entities)
+        .map(|a| a.trim().to_string())
+        .unwrap_or_default();
+    let dates = metadata_dates(&metadata);
+    let created = dates.first().cloned().unwrap_or_default();
+    let updated = dates.last().cloned().unwrap_or_default();
+
+    Some(ConfluencePage {
+        url: confluence_page_url(base_url, &space_key, &id),
+        id,
+        title,
+        space_key,
+        body,
+        author,
+        created,
+        updated,
+    })
+}
+
+enum ParsedExport {
+    Issues(Vec<JiraIssue>),
+    Pages(Vec<ConfluencePage>),
+}
+
+fn parse_export_file(
+    path: &Path,
+    kind: AtlassianExportKind,
+    base_url: Option<&str>,
+) -> Result<ParsedExport, AtlassianError> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    match kind {
+        AtlassianExportKind::Jira => {
+            let contents = std::fs::read_to_string(path)?;
+            parse_jira_export(&contents, base_url).map(ParsedExport::Issues)
+        }
+        AtlassianExportKind::Confluence => match extension.as_str() {
+            "html" | "htm" => {
+                let html = std::fs::read_to_string(path)?;
+                let name = path.to_string_lossy().replace('\\', "/");
+                Ok(ParsedExport::Pages(
+                    parse_confluence_html_page(&name, &html, base_url)
+                        .into_iter()
+                        .collect(),
+                ))
+            }
+            "xml" => {
+                let contents = std::fs::read_to_string(path)?;
+                parse_confluence_entities(&contents, base_url).map(ParsedExport::Pages)
+            }
+            _ => {
+                let file = std::fs::File::open(path)?;
+                parse_confluence_export(std::io::BufReader::new(file), base_url)
+                    .map(ParsedExport::Pages)
+            }
+        },
+    }
+}
+
+/// Imports Jira and Confluence export files into local storage
+pub struct AtlassianExportImporter {
+    db: Arc<Database>,
+    crypto: Arc<CryptoService>,
+    base_url: Option<String>,
+}
+
+impl AtlassianExportImporter {
+    pub fn new(db: Arc<Database>, crypto: Arc<CryptoService>) -> Self {
+        Self {
+            db,
+            crypto,
+            base_url: None,
+        }
+    }
+
+    /// Site URL (e.g. `https://acme.atlassian.net`) used to build deep links
+    /// when the export does not carry them
+    pub fn with_base_url(mut self, base_url: Option<String>) -> Self {
+        self.base_url = base_url.filter(|u| !u.trim().is_empty());
+        self
+    }
+
+    pub async fn import_file(
+        &self,
+        path: impl AsRef<Path>,
+        kind: AtlassianExportKind,
+    ) -> Result<AtlassianImportResult, AtlassianError> {
+        let path: PathBuf = path.as_ref().to_path_buf();
+        let base_url = self.base_url.clone();
+        let parsed = tokio::task::spawn_blocking(move || {
+            parse_export_file(&path, kind, base_url.as_deref())
+        })
+        .await
+        .map_err(import_error)??;
+
+        let mut result = AtlassianImportResult::default();
+        match parsed {
+            ParsedExport::Issues(issues) => {
+                for issue in &issues {
+                    match store_jira_issue(&self.db, &self.crypto, issue).await {
+                        Ok(()) => result.issues_imported += 1,
+                        Err(e) => result.errors.push(format!("{}: {}", issue.key, e)),
+                    }
+                }
+            }
+            ParsedExport::Pages(pages) => {
+                for page in &pages {
+                    match store_confluence_page(&self.db, &self.crypto, page).await {
+                        Ok(()) => result.pages_imported += 1,
+                        Err(e) => result.errors.push(format!("{}: {}", page.title, e)),
+                    }
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn test_normalize_timestamp() {
+        let jira = "2024-01-15T10:00:00.000+0000";
+        assert_eq!(normalize_timestamp(jira), jira);
+
+        let cases = [
+            "Mon, 15 Jan 2024 10:00:00 +0000",
+            "15/Jan/24 10:00 AM",
+            "2024-01-15 10:00:00.000",
+        ];
+        for case in cases {
+            assert_eq!(
+                parse_atlassian_timestamp(&normalize_timestamp(case)),
+                Some(1705312800000),
+                "{}",
+                case
+            );
+        }
+
+        assert_eq!(
+            parse_atlassian_timestamp(&normalize_timestamp("Jan 15, 2024")),
+            Some(1705276800000)
+        );
+        assert_eq!(normalize_timestamp("not a date"), "not a date");
+    }
+
+    #[test]
+    fn test_parse_jira_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- RSS generated by JIRA -->
+<rss version="0.92">
+  <channel>
+    <title>Jira</title>
+    <item>
+      <title>[ENG-42] Fix login</title>
+      <link>https://acme.atlassian.net/browse/ENG-42</link>
+      <project id="10000" key="ENG">Engineering</project>
+      <description>&lt;p&gt;Users cannot log in&lt;/p&gt;</description>
+      <key id="10042">ENG-42</key>
+      <summary>Fix login</summary>
+      <status>In Progress</status>
+      <assignee accountid="a1">Unassigned</assignee>
+      <reporter accountid="a2">Bob</reporter>
+      <created>Mon, 15 Jan 2024 10:00:00 +0000</created>
+      <updated>Tue, 16 Jan 2024 10:00:00 +0000</updated>
+    </item>
+  </channel>
+</rss>"#;
+
+        let issues = parse_jira_export(xml, None).unwrap();
+
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.id, "10042");
+        assert_eq!(issue.key, "ENG-42");
+        assert_eq!(issue.project_key, "ENG");
+        assert_eq!(issue.summary, "Fix login");
+        assert_eq!(
+            issue.description.as_deref(),
+            Some("<p>Users cannot log in</p>")
+        );
+        assert_eq!(issue.assignee, None);
+        assert_eq!(issue.reporter, "Bob");
+        assert_eq!(issue.url, "https://acme.atlassian.net/browse/ENG-42");
+        assert_eq!(
+            parse_atlassian_timestamp(&issue.created),
+            Some(1705312800000)
+        );
+    }
+
+    #[test]
+    fn test_parse_jira_json_with_adf_description() {
+        let json = r#"{"issues": [{
+            "id": "10001",
+            "key": "OPS-7",
+            "self": "https://acme.atlassian.net/rest/api/3/issue/10001",
+            "fields": {
+                "summary": "Disk full",
+                "description": {"type": "doc", "content": [
+                    {"type": "paragraph", "content": [{"type": "text", "text": "First"}]},
+                    {"type": "paragraph", "content": [{"type": "text", "text": "Second"}]}
+                ]},
+                "status": {"name": "Done"},
+                "assignee": {"displayName": "Alice"},
+                "reporter": {"displayName": "Bob"},
+                "project": {"key": "OPS"},
+                "created": "2024-01-15T10:00:00.000+0000",
+                "updated": "2024-01-15T11:00:00.000+0000"
+            }
+        }]}"#;
+
+        let issues = parse_jira_export(json, None).unwrap();
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].description.as_deref(), Some("First\nSecond"));
+        assert_eq!(issues[0].assignee.as_deref(), Some("Alice"));
+        assert_eq!(issues[0].url, "https://acme.atlassian.net/browse/OPS-7");
+    }
+
+    #[test]
+    fn test_parse_jira_csv() {
+        let csv = "Summary,Issue key,Issue id,Status,Assignee,Reporter,Created,Updated,Description\n\
+                   \"Fix, quickly\",ENG-1,10001,Open,,Bob,15/Jan/24 10:00 AM,16/Jan/24 9:30 AM,\"Multi\nline\"\n\
+                   ,,,,,,,,\n";
+
+        let issues = parse_jira_export(csv, Some("https://acme.atlassian.net/")).unwrap();
+
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.summary, "Fix, quickly");
+        assert_eq!(issue.project_key, "ENG");
+        assert_eq!(issue.assignee, None);
+        assert_eq!(issue.description.as_deref(), Some("Multi\nline"));
+        assert_eq!(issue.url, "https://acme.atlassian.net/browse/ENG-1");
+        assert_eq!(
+            parse_atlassian_timestamp(&issue.created),
+            Some(1705312800000)
+        );
+    }
+
+    #[test]
+    fn test_parse_jira_csv_requires_key_column() {
+        let result = parse_jira_export("Summary,Status\nA,Open\n", None);
+        assert!(matches!(result, Err(AtlassianError::Import(_))));
+    }
+
+    #[test]
+    fn test_parse_confluence_entities() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<hibernate-generic datetime="2024-02-01 00:00:00">
+  <object class="Space" package="com.atlassian.confluence.spaces">
+    <id name="id">98304</id>
+    <property name="key"><![CDATA[ENG]]></property>
+  </object>
+  <object class="ConfluenceUserImpl" package="com.atlassian.confluence.user">
+    <id name="key"><![CDATA[u1]]></id>
+    <property name="name"><![CDATA[alice]]></property>
+  </object>
+  <object class="Page" package="com.atlassian.confluence.pages">
+    <id name="id">123</id>
+    <property name="title"><![CDATA[Runbook]]></property>
+    <property name="space" class="Space"><id name="id">98304</id></property>
+    <property name="creator" class="ConfluenceUserImpl"><id name="key"><![CDATA[u1]]></id></property>
+    <property name="creationDate">2024-01-15 10:00:00.000</property>
+    <property name="lastModificationDate">2024-01-16 10:00:00.000</property>
+    <property name="contentStatus"><![CDATA[current]]></property>
+  </object>
+  <object class="Page" package="com.atlassian.confluence.pages">
+    <id name="id">122</id>
+    <property name="title"><![CDATA[Runbook]]></property>
+    <property name="originalVersion" class="Page"><id name="id">123</id></property>
+  </object>
+  <object class="BodyContent" package="com.atlassian.confluence.core">
+    <id name="id">555</id>
+    <property name="body"><![CDATA[<p>Restart the service</p>]]></property>
+    <property name="content" class="Page"><id name="id">123</id></property>
+  </object>
+</hibernate-generic>"#;
+
+        let pages = parse_confluence_entities(xml, Some("https://acme.atlassian.net")).unwrap();
+
+        assert_eq!(pages.len(), 1);
+        let page = &pages[0];
+        assert_eq!(page.id, "123");
+        assert_eq!(page.title, "Runbook");
+        assert_eq!(page.space_key, "ENG");
+        assert_eq!(page.author, "alice");
+        assert_eq!(page.body.as_deref(), Some("<p>Restart the service</p>"));
+        assert_eq!(
+            page.url,
+            "https://acme.atlassian.net/wiki/spaces/ENG/pages/123"
+        );
+        assert_eq!(
+            parse_atlassian_timestamp(&page.created),
+            Some(1705312800000)
+        );
+    }
+
+    const HTML_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head><title>Engineering : Deploy &amp; Rollback</title></head>
+<body>
+<div id="page">
+  <div class="page-metadata">
+    Created by <span class='author'> Alice</span>, last modified by <span class='editor'> Bob</span> on Jan 16, 2024
+  </div>
+  <div id="main-content" class="wiki-content group">
+    <p>Step one</p>
+    <div class="code"><pre>deploy</pre></div>
+  </div>
+  <div class="pageSection group">Attachments</div>
+</div>
+</body>
+</html>"#;
+
+    #[test]
+    fn test_parse_confluence_html_page() {
+        let page =
+            parse_confluence_html_page("ENG/Deploy-Rollback_65538.html", HTML_PAGE, None).unwrap();
+
+        assert_eq!(page.id, "65538");
+        assert_eq!(page.space_key, "ENG");
+        assert_eq!(page.title, "Deploy & Rollback");
+        assert_eq!(page.author, "Alice");
+        let body = page.body.unwrap();
+        assert!(body.starts_with("<p>Step one</p>"));
+        assert!(body.contains("<pre>deploy</pre>"));
+        assert!(!body.contains("Attachments"));
+        assert_eq!(
+            parse_atlassian_timestamp(&page.updated),
+            Some(1705363200000)
+        );
+        assert!(page.url.is_empty());
+    }
+
+    #[test]
+    fn test_parse_confluence_html_export_zip() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in [
+            ("ENG/index.html", "<html><title>Index</title></html>"),
+            ("ENG/Deploy-Rollback_65538.html", HTML_PAGE),
+        ] {
+            writer
+                .start_file(name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        let mut cursor = writer.finish().unwrap();
+        cursor.set_position(0);
+
+        let pages = parse_confluence_export(cursor, Some("https://acme.atlassian.net")).unwrap();
+
+        assert_eq!(pages.len(), 1);
+        assert_eq!(
+            pages[0].url,
+            "https://acme.atlassian.net/wiki/spaces/ENG/pages/65538"
+        );
+    }
+}
@@ -4,9 +4,16 @@
 //! synchronization for Jira and Confluence.
 
 mod client;
+mod connector;
+mod import;
 mod sync;
 mod types;
 
 pub use client::AtlassianClient;
+pub use connector::{connect_atlassian_oauth, ConfluenceConnector, JiraConnector};
+pub use import::{
+    parse_confluence_export, parse_confluence_html_page, parse_jira_export,
+    AtlassianExportImporter, AtlassianExportKind, AtlassianImportResult,
+};
 pub use sync::AtlassianSyncService;
 pub use types::{AtlassianError, AtlassianTokens, CloudResource, ConfluencePage, JiraIssue};
@@ -4,27 +4,156 @@ use super::client::AtlassianClient;
 use super::types::{AtlassianError, ConfluencePage, JiraIssue};
 use crate::crypto::CryptoService;
 use crate::db::Database;
+use crate::pipeline::{CancellationToken, ProgressReporter};
+use crate::sync::estimate::{ceil_div, local_date, text_tokens, SourceEstimate};
 use std::sync::Arc;
 
+const JIRA_PAGE_SIZE: i32 = 50;
+const CONFLUENCE_PAGE_SIZE: i32 = 25;
+/// Dry runs only read metadata, so they can page through more at once
+const DRY_RUN_PAGE_SIZE: i32 = 100;
+/// Confluence bodies are not fetched in dry runs; a typical page's storage format
+const CONFLUENCE_PAGE_TOKENS: i64 = 1_500;
+
+/// Parse an Atlassian timestamp into epoch milliseconds.
+///
+/// Confluence returns RFC 3339, while Jira uses `2024-01-15T10:00:00.000+0000`
+/// (no colon in the offset), which RFC 3339 parsing rejects.
+pub(super) fn parse_atlassian_timestamp(value: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .or_else(|_| chrono::DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .map(|dt| dt.timestamp_millis())
+        .ok()
+}
+
+/// Which projects or spaces a query covers
+#[derive(Debug, Clone, Copy)]
+pub enum KeyFilter<'a> {
+    Only(&'a str),
+    In(&'a [String]),
+    Excluding(&'a [String]),
+}
+
+impl KeyFilter<'_> {
+    /// JQL/CQL condition on `field` followed by `AND`, or nothing when unfiltered
+    fn clause(&self, field: &str) -> String {
+        let quote = |key: &str| format!("\"{}\"", key.replace('"', "\\\""));
+        let list = |keys: &[String]| keys.iter().map(|k| quote(k)).collect::<Vec<_>>().join(", ");
+        match self {
+            Self::Excluding([]) => String::new(),
+            Self::Only(key) => format!("{} = {} AND ", field, quote(key)),
+            Self::In(keys) => format!("{} in ({}) AND ", field, list(keys)),
+            Self::Excluding(keys) => format!("{} not in ({}) AND ", field, list(keys)),
+        }
+    }
+}
+
+/// JQL for Jira issues in `projects` updated in the last `days` days
+pub fn jira_recent_jql(days: i32, projects: KeyFilter<'_>) -> String {
+    format!(
+        "{}updated >= -{}d ORDER BY updated DESC",
+        projects.clause("project"),
+        days
+    )
+}
+
+/// JQL for Jira issues in `projects` updated within `[start_date, end_date)`
+pub fn jira_between_jql(start_date: &str, end_date: &str, projects: KeyFilter<'_>) -> String {
+    format!(
+        "{}updated >= \"{}\" AND updated < \"{}\" ORDER BY updated DESC",
+        projects.clause("project"),
+        start_date,
+        end_date
+    )
+}
+
+/// CQL for Confluence pages in `spaces` modified in the last `days` days
+pub fn confluence_recent_cql(days: i32, spaces: KeyFilter<'_>) -> String {
+    format!(
+        "{}lastModified >= now('-{}d') ORDER BY lastModified DESC",
+        spaces.clause("space"),
+        days
+    )
+}
+
+/// CQL for Confluence pages in `spaces` modified within `[start_date, end_date)`
+pub fn confluence_between_cql(start_date: &str, end_date: &str, spaces: KeyFilter<'_>) -> String {
+    format!(
+        "{}lastModified >= \"{}\" AND lastModified < \"{}\" ORDER BY lastModified DESC",
+        spaces.clause("space"),
+        start_date,
+        end_date
+    )
+}
+
 pub struct AtlassianSyncService {
     client: AtlassianClient,
     db: Arc<Database>,
     crypto: Arc<CryptoService>,
+    cancel: CancellationToken,
+    progress: ProgressReporter,
 }
 
 impl AtlassianSyncService {
     pub fn new(client: AtlassianClient, db: Arc<Database>, crypto: Arc<CryptoService>) -> Self {
-        Self { client, db, crypto }
+        Self {
+            client,
+            db,
+            crypto,
+            cancel: CancellationToken::new(),
+            progress: ProgressReporter::default(),
+        }
+    }
+
+    /// Stop between result pages once `cancel` is cancelled
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Report each fetched page of results
+    pub fn with_progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = progress;
+        self
     }
 
-    /// Sync Jira issues updated in the last N days
-    pub async fn sync_jira(&self, days: i32) -> Result<i32, AtlassianError> {
-        let jql = format!("updated >= -{}d ORDER BY updated DESC", days);
+    fn check_cancelled(&self) -> Result<(), AtlassianError> {
+        if self.cancel.is_cancelled() {
+            Err(AtlassianError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sync Jira issues updated in the last N days in the projects matching `projects`
+    pub async fn sync_jira(
+        &self,
+        days: i32,
+        projects: KeyFilter<'_>,
+    ) -> Result<i32, AtlassianError> {
+        self.sync_jira_jql(&jira_recent_jql(days, projects)).await
+    }
+
+    /// Sync Jira issues updated within `[start_date, end_date)` (both `YYYY-MM-DD`).
+    pub async fn sync_jira_between(
+        &self,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<i32, AtlassianError> {
+        let jql = jira_between_jql(start_date, end_date, KeyFilter::Excluding(&[]));
+        self.sync_jira_jql(&jql).await
+    }
+
+    async fn sync_jira_jql(&self, jql: &str) -> Result<i32, AtlassianError> {
         let mut total = 0;
         let mut start_at = 0;
 
         loop {
-            let issues = self.client.search_issues(&jql, start_at, 50).await?;
+            self.check_cancelled()?;
+            let issues = self
+                .client
+                .search_issues(jql, start_at, JIRA_PAGE_SIZE)
+                .await?;
 
             if issues.is_empty() {
                 break;
@@ -35,23 +164,49 @@ impl AtlassianSyncService {
                 total += 1;
             }
 
-            start_at += 50;
+            start_at += JIRA_PAGE_SIZE;
+            self.progress
+                .message(format!(
+                    "Fetched {} Jira issues (page {})",
+                    total,
+                    start_at / JIRA_PAGE_SIZE
+                ))
+                .await;
         }
 
         Ok(total)
     }
 
-    /// Sync Confluence pages updated in the last N days
-    pub async fn sync_confluence(&self, days: i32) -> Result<i32, AtlassianError> {
-        let cql = format!(
-            "lastModified >= now('-{}d') ORDER BY lastModified DESC",
-            days
-        );
+    /// Sync Confluence pages updated in the last N days in the spaces matching `spaces`
+    pub async fn sync_confluence(
+        &self,
+        days: i32,
+        spaces: KeyFilter<'_>,
+    ) -> Result<i32, AtlassianError> {
+        self.sync_confluence_cql(&confluence_recent_cql(days, spaces))
+            .await
+    }
+
+    /// Sync Confluence pages modified within `[start_date, end_date)` (both `YYYY-MM-DD`).
+    pub async fn sync_confluence_between(
+        &self,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<i32, AtlassianError> {
+        let cql = confluence_between_cql(start_date, end_date, KeyFilter::Excluding(&[]));
+        self.sync_confluence_cql(&cql).await
+    }
+
+    async fn sync_confluence_cql(&self, cql: &str) -> Result<i32, AtlassianError> {
         let mut total = 0;
         let mut start = 0;
 
         loop {
-            let pages = self.client.search_pages(&cql, start, 25).await?;
+            self.check_cancelled()?;
+            let pages = self
+                .client
+                .search_pages(cql, start, CONFLUENCE_PAGE_SIZE)
+                .await?;
 
             if pages.is_empty() {
                 break;
@@ -62,89 +217,222 @@ impl AtlassianSyncService {
                 total += 1;
             }
 
-            start += 25;
+            start += CONFLUENCE_PAGE_SIZE;
+            self.progress
+                .message(format!(
+                    "Fetched {} Confluence pages (batch {})",
+                    total,
+                    start / CONFLUENCE_PAGE_SIZE
+                ))
+                .await;
         }
 
         Ok(total)
     }
 
+    /// Count the issues `jql` matches per project and local day of creation,
+    /// the day the AI pass files them under, without storing them
+    pub async fn estimate_jira(
+        &self,
+        jql: &str,
+        timezone_offset: i32,
+    ) -> Result<SourceEstimate, AtlassianError> {
+        let mut estimate = SourceEstimate::new("jira");
+        let mut start_at = 0;
+
+        loop {
+            self.check_cancelled()?;
+            let issues = self
+                .client
+                .search_issues(jql, start_at, DRY_RUN_PAGE_SIZE)
+                .await?;
+
+            if issues.is_empty() {
+                break;
+            }
+
+            for issue in &issues {
+                let created_at = parse_atlassian_timestamp(&issue.created).unwrap_or_default();
+                estimate.add(
+                    &issue.project_key,
+                    &local_date(created_at, timezone_offset),
+                    1,
+                    text_tokens(issue.description.as_deref().unwrap_or("")),
+                );
+            }
+
+            // The server may return fewer than asked for, so page by what came back
+            start_at += issues.len() as i32;
+        }
+
+        // Pages of the real sync, which stops at the first empty one
+        estimate.api_requests = ceil_div(estimate.items, JIRA_PAGE_SIZE as i64) + 1;
+        Ok(estimate)
+    }
+
+    /// Count the pages `cql` matches per space and local day, without fetching
+    /// their bodies
+    pub async fn estimate_confluence(
+        &self,
+        cql: &str,
+        timezone_offset: i32,
+    ) -> Result<SourceEstimate, AtlassianError> {
+        let mut estimate = SourceEstimate::new("confluence");
+        let mut start = 0;
+
+        loop {
+            self.check_cancelled()?;
+            let pages = self
+                .client
+                .search_page_headers(cql, start, DRY_RUN_PAGE_SIZE)
+                .await?;
+
+            if pages.is_empty() {
+                break;
+            }
+
+            for page in &pages {
+                let created_at = parse_atlassian_timestamp(&page.created).unwrap_or_default();
+                estimate.add(
+                    &page.space_key,
+                    &local_date(created_at, timezone_offset),
+                    1,
+                    CONFLUENCE_PAGE_TOKENS,
+                );
+            }
+
+            start += pages.len() as i32;
+        }
+
+        estimate.api_requests = ceil_div(estimate.items, CONFLUENCE_PAGE_SIZE as i64) + 1;
+        Ok(estimate)
+    }
+
     async fn store_jira_issue(&self, issue: &JiraIssue) -> Result<(), AtlassianError> {
-        let now = chrono::Utc::now().timestamp();
-        let created_at = chrono::DateTime::parse_from_rfc3339(&issue.created)
-            .map(|dt| dt.timestamp())
-            .unwrap_or(now);
-        let updated_at = chrono::DateTime::parse_from_rfc3339(&issue.updated)
-            .map(|dt| dt.timestamp())
-            .unwrap_or(now);
-
-        let description = issue.description.as_deref().unwrap_or("");
-        let encrypted_body = self
-            .crypto
-            .encrypt_string(description)
-            .map_err(|e| AtlassianError::Crypto(e.to_string()))?;
-
-        sqlx::query(
-            "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, created_at, updated_at, synced_at)
-             VALUES (?, 'jira', ?, ?, 'ticket', ?, ?, ?, ?, ?, ?, ?)
-             ON CONFLICT(source, source_id) DO UPDATE SET title = ?, body = ?, updated_at = ?, synced_at = ?"
-        )
-        .bind(uuid::Uuid::new_v4().to_string())
-        .bind(&issue.key)
-        .bind(&issue.url)
-        .bind(&issue.summary)
-        .bind(&encrypted_body)
-        .bind(&issue.reporter)
-        .bind(&issue.project_key)
-        .bind(created_at)
-        .bind(updated_at)
-        .bind(now)
-        .bind(&issue.summary)
-        .bind(&encrypted_body)
-        .bind(updated_at)
-        .bind(now)
-        .execute(self.db.pool())
-        .await?;
-
-        Ok(())
+        store_jira_issue(&self.db, &self.crypto, issue).await
     }
 
     async fn store_confluence_page(&self, page: &ConfluencePage) -> Result<(), AtlassianError> {
-        let now = chrono::Utc::now().timestamp();
-        let created_at = chrono::DateTime::parse_from_rfc3339(&page.created)
-            .map(|dt| dt.timestamp())
-            .unwrap_or(now);
-        let updated_at = chrono::DateTime::parse_from_rfc3339(&page.updated)
-            .map(|dt| dt.timestamp())
-            .unwrap_or(now);
-
-        let body = page.body.as_deref().unwrap_or("");
-        let encrypted_body = self
-            .crypto
-            .encrypt_string(body)
-            .map_err(|e| AtlassianError::Crypto(e.to_string()))?;
-
-        sqlx::query(
-            "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, created_at, updated_at, synced_at)
-             VALUES (?, 'confluence', ?, ?, 'page', ?, ?, ?, ?, ?, ?, ?)
-             ON CONFLICT(source, source_id) DO UPDATE SET title = ?, body = ?, updated_at = ?, synced_at = ?"
-        )
-        .bind(uuid::Uuid::new_v4().to_string())
-        .bind(&page.id)
-        .bind(&page.url)
-        .bind(&page.title)
-        .bind(&encrypted_body)
-        .bind(&page.author)
-        .bind(&page.space_key)
-        .bind(created_at)
-        .bind(updated_at)
-        .bind(now)
-        .bind(&page.title)
-        .bind(&encrypted_body)
-        .bind(updated_at)
-        .bind(now)
-        .execute(self.db.pool())
-        .await?;
-
-        Ok(())
+        store_confluence_page(&self.db, &self.crypto, page).await
+    }
+}
+
+/// Encrypt and upsert a Jira issue into `content_items`, keyed on its issue key.
+pub(super) async fn store_jira_issue(
+    db: &Database,
+    crypto: &CryptoService,
+    issue: &JiraIssue,
+) -> Result<(), AtlassianError> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let created_at = parse_atlassian_timestamp(&issue.created).unwrap_or(now);
+    let updated_at = parse_atlassian_timestamp(&issue.updated).unwrap_or(now);
+
+    let description = issue.description.as_deref().unwrap_or("");
+    let encrypted_body = crypto
+        .encrypt_string(description)
+        .map_err(|e| AtlassianError::Crypto(e.to_string()))?;
+
+    sqlx::query(
+        "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, created_at, updated_at, synced_at)
+         VALUES (?, 'jira', ?, ?, 'ticket', ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(source, source_id) DO UPDATE SET title = ?, body = ?, updated_at = ?, synced_at = ?"
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(&issue.key)
+    .bind(Some(&issue.url).filter(|u| !u.is_empty()))
+    .bind(&issue.summary)
+    .bind(&encrypted_body)
+    .bind(&issue.reporter)
+    .bind(&issue.project_key)
+    .bind(created_at)
+    .bind(updated_at)
+    .bind(now)
+    .bind(&issue.summary)
+    .bind(&encrypted_body)
+    .bind(updated_at)
+    .bind(now)
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Encrypt and upsert a Confluence page into `content_items`, keyed on its page id.
+pub(super) async fn store_confluence_page(
+    db: &Database,
+    crypto: &CryptoService,
+    page: &ConfluencePage,
+) -> Result<(), AtlassianError> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let created_at = parse_atlassian_timestamp(&page.created).unwrap_or(now);
+    let updated_at = parse_atlassian_timestamp(&page.updated).unwrap_or(now);
+
+    let body = page.body.as_deref().unwrap_or("");
+    let encrypted_body = crypto
+        .encrypt_string(body)
+        .map_err(|e| AtlassianError::Crypto(e.to_string()))?;
+
+    sqlx::query(
+        "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, created_at, updated_at, synced_at)
+         VALUES (?, 'confluence', ?, ?, 'page', ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(source, source_id) DO UPDATE SET title = ?, body = ?, updated_at = ?, synced_at = ?"
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(&page.id)
+    .bind(Some(&page.url).filter(|u| !u.is_empty()))
+    .bind(&page.title)
+    .bind(&encrypted_body)
+    .bind(&page.author)
+    .bind(&page.space_key)
+    .bind(created_at)
+    .bind(updated_at)
+    .bind(now)
+    .bind(&page.title)
+    .bind(&encrypted_body)
+    .bind(updated_at)
+    .bind(now)
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_filter_clause() {
+        assert_eq!(KeyFilter::Excluding(&[]).clause("project"), "");
+        assert_eq!(
+            KeyFilter::Only("OPS").clause("project"),
+            "project = \"OPS\" AND "
+        );
+        assert_eq!(
+            KeyFilter::Excluding(&["OPS".to_string(), "ENG".to_string()]).clause("space"),
+            "space not in (\"OPS\", \"ENG\") AND "
+        );
+        assert_eq!(
+            KeyFilter::In(&["OPS".to_string(), "ENG".to_string()]).clause("project"),
+            "project in (\"OPS\", \"ENG\") AND "
+        );
+    }
+
+    #[test]
+    fn test_parse_atlassian_timestamp_rfc3339() {
+        let ts = parse_atlassian_timestamp("2024-01-15T10:00:00Z").unwrap();
+        assert_eq!(ts, 1705312800000);
+    }
+
+    #[test]
+    fn test_parse_atlassian_timestamp_jira_offset() {
+        let ts = parse_atlassian_timestamp("2024-01-15T10:00:00.000+0000").unwrap();
+        assert_eq!(ts, 1705312800000);
+    }
+
+    #[test]
+    fn test_parse_atlassian_timestamp_invalid() {
+        assert!(parse_atlassian_timestamp("").is_none());
+        assert!(parse_atlassian_timestamp("yesterday").is_none());
     }
 }
@@ -22,6 +22,12 @@ pub enum AtlassianError {
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("Import error: {0}")]
+    Import(String),
+
+    #[error("Cancelled")]
+    Cancelled,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,683 @@
+//! Multi-day historical backfill across Slack, Jira and Confluence
+//!
+//! A backfill job walks a date range one day at a time, syncing each selected
+//! source for that day and then running AI batch processing for it. Jobs run
+//! through the sync queue, which handles retries, cancellation and restarts;
+//! `backfill_jobs` holds the per-day progress, persisted after every day so an
+//! interrupted job resumes where it stopped.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use super::background::get_gemini_client;
+use super::connector::{
+    sync_connected_sources, ConnectorContext, SourceRegistry, SourcesSynced, SyncRange,
+};
+use super::queue::{SyncJob, SyncJobPayload, SyncQueue};
+use super::scheduler::{SyncClaim, SyncPriority, SyncScheduler};
+use crate::ai::pipeline::CANCELLED;
+use crate::db::Database;
+use crate::pipeline::{PipelineManager, PipelineTaskType};
+
+/// Upper bound on a single backfill job, to keep API and AI usage predictable.
+pub const MAX_BACKFILL_DAYS: i64 = 90;
+
+type BackfillJobRow = (
+    String,
+    String,
+    String,
+    Option<String>,
+    i32,
+    String,
+    String,
+    i32,
+    String,
+    i32,
+    Option<String>,
+    i64,
+    i64,
+);
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackfillStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl BackfillStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "running" => Self::Running,
+            "completed" => Self::Completed,
+            "failed" => Self::Failed,
+            "cancelled" => Self::Cancelled,
+            _ => Self::Pending,
+        }
+    }
+}
+
+/// A persisted multi-day backfill job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackfillJob {
+    pub id: String,
+    pub start_date: String,
+    pub end_date: String,
+    /// Next day to process; `None` once every day has been handled
+    pub next_date: Option<String>,
+    pub timezone_offset: i32,
+    pub sources: Vec<String>,
+    pub status: BackfillStatus,
+    pub days_completed: i32,
+    /// Days whose sync or AI pass failed; retried before the job finishes
+    pub failed_dates: Vec<String>,
+    /// Items synced on days that completed
+    pub items_synced: i32,
+    pub last_error: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl BackfillJob {
+    /// Create a job for `start_date..=end_date`. `sources` must be a subset of
+    /// `available_sources` and defaults to all of them.
+    pub fn new(
+        start_date: &str,
+        end_date: &str,
+        timezone_offset: i32,
+        sources: Option<Vec<String>>,
+        available_sources: &[&str],
+    ) -> Result<Self, String> {
+        let start = parse_date(start_date)?;
+        let end = parse_date(end_date)?;
+
+        if end < start {
+            return Err(format!(
+                "End date {} is before start date {}",
+                end_date, start_date
+            ));
+        }
+
+        let days = (end - start).num_days() + 1;
+        if days > MAX_BACKFILL_DAYS {
+            return Err(format!(
+                "Backfill range of {} days exceeds the maximum of {} days",
+                days, MAX_BACKFILL_DAYS
+            ));
+        }
+
+        let sources = match sources {
+            Some(sources) if !sources.is_empty() => {
+                if let Some(unknown) = sources
+                    .iter()
+                    .find(|s| !available_sources.contains(&s.as_str()))
+                {
+                    return Err(format!("Unsupported backfill source: {}", unknown));
+                }
+                sources
+            }
+            _ => available_sources.iter().map(|s| s.to_string()).collect(),
+        };
+
+        let now = chrono::Utc::now().timestamp_millis();
+
+        Ok(Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            start_date: start.format("%Y-%m-%d").to_string(),
+            end_date: end.format("%Y-%m-%d").to_string(),
+            next_date: Some(start.format("%Y-%m-%d").to_string()),
+            timezone_offset,
+            sources,
+            status: BackfillStatus::Pending,
+            days_completed: 0,
+            failed_dates: vec![],
+            items_synced: 0,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub fn total_days(&self) -> i32 {
+        match (parse_date(&self.start_date), parse_date(&self.end_date)) {
+            (Ok(start), Ok(end)) => ((end - start).num_days() + 1).max(0) as i32,
+            _ => 0,
+        }
+    }
+
+    /// Days still to be processed, in chronological order.
+    pub fn remaining_dates(&self) -> Vec<String> {
+        let (Some(next), Ok(end)) = (self.next_date.as_deref(), parse_date(&self.end_date)) else {
+            return vec![];
+        };
+        let Ok(mut current) = parse_date(next) else {
+            return vec![];
+        };
+
+        let mut dates = Vec::new();
+        while current <= end {
+            dates.push(current.format("%Y-%m-%d").to_string());
+            match current.succ_opt() {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+        dates
+    }
+
+    fn from_row(row: BackfillJobRow) -> Self {
+        Self {
+            id: row.0,
+            start_date: row.1,
+            end_date: row.2,
+            next_date: row.3,
+            timezone_offset: row.4,
+            sources: serde_json::from_str(&row.5).unwrap_or_default(),
+            status: BackfillStatus::parse(&row.6),
+            days_completed: row.7,
+            failed_dates: serde_json::from_str(&row.8).unwrap_or_default(),
+            items_synced: row.9,
+            last_error: row.10,
+            created_at: row.11,
+            updated_at: row.12,
+        }
+    }
+}
+
+fn parse_date(date_str: &str) -> Result<chrono::NaiveDate, String> {
+    chrono::NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date format '{}': {}", date_str, e))
+}
+
+fn next_date_after(date_str: &str, end_date: &str) -> Option<String> {
+    let next = parse_date(date_str).ok()?.succ_opt()?;
+    let end = parse_date(end_date).ok()?;
+    (next <= end).then(|| next.format("%Y-%m-%d").to_string())
+}
+
+pub async fn insert_backfill_job(db: &Database, job: &BackfillJob) -> Result<(), String> {
+    let sources_json = serde_json::to_string(&job.sources).map_err(|e| e.to_string())?;
+    let failed_json = serde_json::to_string(&job.failed_dates).map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        "INSERT INTO backfill_jobs (id, start_date, end_date, next_date, timezone_offset, sources, status, days_completed, failed_dates, items_synced, last_error, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&job.id)
+    .bind(&job.start_date)
+    .bind(&job.end_date)
+    .bind(&job.next_date)
+    .bind(job.timezone_offset)
+    .bind(&sources_json)
+    .bind(job.status.as_str())
+    .bind(job.days_completed)
+    .bind(&failed_json)
+    .bind(job.items_synced)
+    .bind(&job.last_error)
+    .bind(job.created_at)
+    .bind(job.updated_at)
+    .execute(db.pool())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+async fn save_backfill_progress(db: &Database, job: &mut BackfillJob) -> Result<(), String> {
+    job.updated_at = chrono::Utc::now().timestamp_millis();
+    let failed_json = serde_json::to_string(&job.failed_dates).map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        "UPDATE backfill_jobs
+         SET next_date = ?, status = ?, days_completed = ?, failed_dates = ?, items_synced = ?, last_error = ?, updated_at = ?
+         WHERE id = ?",
+    )
+    .bind(&job.next_date)
+    .bind(job.status.as_str())
+    .bind(job.days_completed)
+    .bind(&failed_json)
+    .bind(job.items_synced)
+    .bind(&job.last_error)
+    .bind(job.updated_at)
+    .bind(&job.id)
+    .execute(db.pool())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+pub async fn list_backfill_jobs(db: &Database) -> Result<Vec<BackfillJob>, String> {
+    let rows: Vec<BackfillJobRow> = sqlx::query_as(
+        "SELECT id, start_date, end_date, next_date, timezone_offset, sources, status, days_completed, failed_dates, items_synced, last_error, created_at, updated_at
+         FROM backfill_jobs ORDER BY created_at DESC LIMIT 50",
+    )
+    .fetch_all(db.pool())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(rows.into_iter().map(BackfillJob::from_row).collect())
+}
+
+async fn get_backfill_job(db: &Database, id: &str) -> Result<Option<BackfillJob>, String> {
+    let row: Option<BackfillJobRow> = sqlx::query_as(
+        "SELECT id, start_date, end_date, next_date, timezone_offset, sources, status, days_completed, failed_dates, items_synced, last_error, created_at, updated_at
+         FROM backfill_jobs WHERE id = ?",
+    )
+    .bind(id)
+    .fetch_optional(db.pool())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(row.map(BackfillJob::from_row))
+}
+
+/// Run the backfill a queued `Backfill` job points at and record the outcome
+/// on the queue job. A backfill left with failed days fails the queue job, so
+/// the queue retries those days after its backoff.
+pub async fn run_queued_backfill(
+    ctx: ConnectorContext,
+    sources: Arc<SourceRegistry>,
+    pipeline: Arc<tokio::sync::Mutex<PipelineManager>>,
+    scheduler: Arc<SyncScheduler>,
+    sync_queue: Arc<SyncQueue>,
+    queue_job: SyncJob,
+) {
+    let SyncJobPayload::Backfill { backfill_id, .. } = &queue_job.payload else {
+        return;
+    };
+
+    let job = match get_backfill_job(&ctx.db, backfill_id).await {
+        Ok(Some(job)) => job,
+        Ok(None) => {
+            let error = format!("Backfill job not found: {}", backfill_id);
+            sync_queue.finish_job(&queue_job, Err(error)).await;
+            return;
+        }
+        Err(e) => {
+            sync_queue.finish_job(&queue_job, Err(e)).await;
+            return;
+        }
+    };
+
+    tracing::info!(
+        "Running backfill job {} ({} to {}, next {:?}, attempt {})",
+        job.id,
+        job.start_date,
+        job.end_date,
+        job.next_date,
+        queue_job.attempts
+    );
+
+    let job = run_backfill_job(
+        &ctx,
+        &sources,
+        &pipeline,
+        &scheduler,
+        &sync_queue,
+        &queue_job,
+        job,
+    )
+    .await;
+
+    match job.status {
+        BackfillStatus::Completed => {
+            sync_queue.finish_job(&queue_job, Ok(())).await;
+        }
+        BackfillStatus::Cancelled => {
+            // Already cancelled when it was stopped from the queue
+            let _ = sync_queue.cancel(&queue_job.id).await;
+        }
+        _ => {
+            let error = job.last_error.unwrap_or_else(|| "Backfill failed".to_string());
+            sync_queue.finish_job(&queue_job, Err(error)).await;
+        }
+    }
+}
+
+/// Run a backfill job to completion, one day at a time.
+///
+/// The job runs at backfill priority and checkpoints between days, so user-initiated
+/// and incremental syncs on the same sources run before the remaining days.
+/// Cancelling the job's task or its queue job stops it without counting the
+/// interrupted day.
+///
+/// A day whose sync or AI pass fails is recorded in `failed_dates` and retried
+/// once after the remaining days; days that still fail leave the job `Failed`.
+async fn run_backfill_job(
+    ctx: &ConnectorContext,
+    sources: &SourceRegistry,
+    pipeline: &Arc<tokio::sync::Mutex<PipelineManager>>,
+    scheduler: &Arc<SyncScheduler>,
+    sync_queue: &SyncQueue,
+    queue_job: &SyncJob,
+    mut job: BackfillJob,
+) -> BackfillJob {
+    let db = &ctx.db;
+    let total_days = job.total_days().max(1);
+
+    // Days that failed in an earlier run are retried after the remaining ones
+    let mut dates: VecDeque<(String, bool)> = job
+        .remaining_dates()
+        .into_iter()
+        .map(|date| (date, false))
+        .chain(job.failed_dates.iter().map(|date| (date.clone(), true)))
+        .collect();
+
+    let (task_id, cancel) = {
+        let pipeline = pipeline.lock().await;
+        let task_id = pipeline
+            .start_task(
+                PipelineTaskType::Backfill,
+                format!("Backfilling {} to {}...", job.start_date, job.end_date),
+            )
+            .await;
+        let cancel = pipeline.cancellation_token(&task_id).await;
+        (task_id, cancel)
+    };
+
+    {
+        let pipeline = pipeline.lock().await;
+        pipeline
+            .update_progress(
+                &task_id,
+                job.days_completed as f32 / total_days as f32,
+                None,
+            )
+            .await;
+    }
+
+    job.status = BackfillStatus::Running;
+    job.last_error = None;
+    if let Err(e) = save_backfill_progress(db, &mut job).await {
+        tracing::error!("Failed to mark backfill job {} running: {}", job.id, e);
+    }
+
+    let mut permit = scheduler
+        .acquire(
+            format!("Backfill {} to {}", job.start_date, job.end_date),
+            SyncPriority::Backfill,
+            SyncClaim::sources_with_ai(Some(job.sources.as_slice())),
+        )
+        .await;
+
+    let ctx = ctx.clone().with_cancellation(cancel.clone());
+
+    while let Some((date, retry)) = dates.pop_front() {
+        // Cancelling the queue job stops the backfill before its next day
+        if sync_queue.is_cancelled(&queue_job.id).await {
+            cancel.cancel();
+        }
+
+        let SourcesSynced {
+            items,
+            mut errors,
+            cancelled,
+        } = backfill_day(&ctx, sources, pipeline, &job, &date).await;
+
+        if cancel.is_cancelled() {
+            // `next_date` still points at the interrupted day, and an interrupted
+            // retry stays in `failed_dates`, so either is redone if retried
+            job.status = BackfillStatus::Cancelled;
+            if let Err(e) = save_backfill_progress(db, &mut job).await {
+                tracing::error!("Failed to mark backfill job {} cancelled: {}", job.id, e);
+            }
+            let pipeline = pipeline.lock().await;
+            pipeline.fail_task(&task_id, CANCELLED.to_string()).await;
+            tracing::info!("Backfill job {} cancelled while syncing {}", job.id, date);
+            return job;
+        }
+
+        if cancelled {
+            // Only a source's own task was cancelled, so the backfill goes on but
+            // the day is left unfinished like a failed one
+            errors.push(CANCELLED.to_string());
+        }
+        if !retry {
+            job.next_date = next_date_after(&date, &job.end_date);
+        }
+        if errors.is_empty() {
+            job.days_completed += 1;
+            job.items_synced += items;
+            job.failed_dates.retain(|failed| failed != &date);
+        } else {
+            job.last_error = Some(format!("{}: {}", date, errors.join("; ")));
+            if !job.failed_dates.contains(&date) {
+                job.failed_dates.push(date.clone());
+            }
+            if !retry {
+                dates.push_back((date.clone(), true));
+            }
+        }
+
+        if let Err(e) = save_backfill_progress(db, &mut job).await {
+            tracing::error!("Failed to persist backfill progress for {}: {}", date, e);
+            job.status = BackfillStatus::Failed;
+            job.last_error = Some(e.clone());
+            let pipeline = pipeline.lock().await;
+            pipeline.fail_task(&task_id, e).await;
+            return job;
+        }
+
+        {
+            let pipeline = pipeline.lock().await;
+            pipeline
+                .update_progress(
+                    &task_id,
+                    job.days_completed as f32 / total_days as f32,
+                    Some(format!(
+                        "Backfilled {} of {} days ({} items, {} failed)",
+                        job.days_completed,
+                        total_days,
+                        job.items_synced,
+                        job.failed_dates.len()
+                    )),
+                )
+                .await;
+        }
+
+        // Progress is saved, so higher-priority syncs can safely run in between
+        permit.checkpoint().await;
+    }
+    drop(permit);
+
+    job.next_date = None;
+    if !job.failed_dates.is_empty() {
+        job.status = BackfillStatus::Failed;
+        job.last_error = Some(format!(
+            "{} days failed: {}",
+            job.failed_dates.len(),
+            job.failed_dates.join(", ")
+        ));
+        if let Err(e) = save_backfill_progress(db, &mut job).await {
+            tracing::error!("Failed to mark backfill job {} failed: {}", job.id, e);
+        }
+
+        let message = job.last_error.clone().unwrap_or_default();
+        tracing::warn!(
+            "Backfill job {} finished with failures: {}",
+            job.id,
+            message
+        );
+        let pipeline = pipeline.lock().await;
+        pipeline.fail_task(&task_id, message).await;
+        return job;
+    }
+
+    job.status = BackfillStatus::Completed;
+    if let Err(e) = save_backfill_progress(db, &mut job).await {
+        tracing::error!("Failed to mark backfill job {} complete: {}", job.id, e);
+    }
+
+    let pipeline = pipeline.lock().await;
+    pipeline
+        .complete_task(
+            &task_id,
+            Some(format!(
+                "Backfilled {} items across {} days",
+                job.items_synced, job.days_completed
+            )),
+        )
+        .await;
+
+    tracing::info!(
+        "Backfill job {} completed: {} items over {} days",
+        job.id,
+        job.items_synced,
+        job.days_completed
+    );
+
+    job
+}
+
+/// Sync every selected source for one day, then run AI processing for that day.
+/// AI errors are added to the per-source errors.
+async fn backfill_day(
+    ctx: &ConnectorContext,
+    sources: &SourceRegistry,
+    pipeline: &Arc<tokio::sync::Mutex<PipelineManager>>,
+    job: &BackfillJob,
+    date: &str,
+) -> SourcesSynced {
+    use crate::ai::ProcessingPipeline;
+
+    tracing::info!("Backfill job {}: syncing {}", job.id, date);
+
+    let range = match SyncRange::day(date, job.timezone_offset) {
+        Ok(range) => range,
+        Err(e) => {
+            return SourcesSynced {
+                errors: vec![e],
+                ..SourcesSynced::default()
+            }
+        }
+    };
+
+    // Unconnected sources are simply skipped
+    let mut synced =
+        sync_connected_sources(sources, ctx, pipeline, Some(&job.sources), Some(&range)).await;
+    for e in &synced.errors {
+        tracing::error!("Backfill error for {}: {}", date, e);
+    }
+
+    if synced.items > 0 && !ctx.cancel.is_cancelled() {
+        let (db, crypto) = (ctx.db.clone(), ctx.crypto.clone());
+        if let Some(api_key_or_client) = get_gemini_client(db.clone(), crypto.clone()).await {
+            let ai_pipeline = ProcessingPipeline::new(api_key_or_client, db, crypto)
+                .with_cancellation(ctx.cancel.clone());
+            match ai_pipeline
+                .process_batch_for_date(date, job.timezone_offset)
+                .await
+            {
+                Ok(processed) => {
+                    tracing::info!(
+                        "Backfill AI processed {} groups/items for {}",
+                        processed,
+                        date
+                    );
+                }
+                Err(e) => {
+                    tracing::error!("Backfill AI processing error for {}: {}", date, e);
+                    synced.errors.push(format!("AI: {}", e));
+                }
+            }
+        }
+    }
+
+    synced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCES: [&str; 3] = ["slack", "jira", "confluence"];
+
+    #[test]
+    fn test_new_job_defaults_to_all_sources() {
+        let job = BackfillJob::new("2026-01-01", "2026-01-03", 0, None, &SOURCES).unwrap();
+        assert_eq!(job.sources, vec!["slack", "jira", "confluence"]);
+        assert_eq!(job.status, BackfillStatus::Pending);
+        assert_eq!(job.next_date.as_deref(), Some("2026-01-01"));
+        assert!(job.failed_dates.is_empty());
+        assert_eq!(job.total_days(), 3);
+    }
+
+    #[test]
+    fn test_new_job_rejects_reversed_range() {
+        assert!(BackfillJob::new("2026-01-05", "2026-01-01", 0, None, &SOURCES).is_err());
+    }
+
+    #[test]
+    fn test_new_job_rejects_oversized_range() {
+        assert!(BackfillJob::new("2025-01-01", "2025-12-31", 0, None, &SOURCES).is_err());
+    }
+
+    #[test]
+    fn test_new_job_rejects_unknown_source() {
+        let result = BackfillJob::new(
+            "2026-01-01",
+            "2026-01-02",
+            0,
+            Some(vec!["slack".to_string(), "teams".to_string()]),
+            &SOURCES,
+        );
+        assert!(result.unwrap_err().contains("teams"));
+    }
+
+    #[test]
+    fn test_remaining_dates_spans_month_boundary() {
+        let job = BackfillJob::new("2026-01-30", "2026-02-02", 0, None, &SOURCES).unwrap();
+        assert_eq!(
+            job.remaining_dates(),
+            vec!["2026-01-30", "2026-01-31", "2026-02-01", "2026-02-02"]
+        );
+    }
+
+    #[test]
+    fn test_remaining_dates_resumes_from_next_date() {
+        let mut job = BackfillJob::new("2026-01-01", "2026-01-05", 0, None, &SOURCES).unwrap();
+        job.next_date = Some("2026-01-04".to_string());
+        assert_eq!(job.remaining_dates(), vec!["2026-01-04", "2026-01-05"]);
+
+        job.next_date = None;
+        assert!(job.remaining_dates().is_empty());
+    }
+
+    #[test]
+    fn test_next_date_after_stops_at_end() {
+        assert_eq!(
+            next_date_after("2026-01-01", "2026-01-02").as_deref(),
+            Some("2026-01-02")
+        );
+        assert!(next_date_after("2026-01-02", "2026-01-02").is_none());
+    }
+
+    #[test]
+    fn test_backfill_status_roundtrip() {
+        for status in [
+            BackfillStatus::Pending,
+            BackfillStatus::Running,
+            BackfillStatus::Completed,
+            BackfillStatus::Failed,
+            BackfillStatus::Cancelled,
+        ] {
+            assert_eq!(BackfillStatus::parse(status.as_str()), status);
+        }
+    }
+}
@@ -4,24 +4,45 @@ use std::time::Duration;
 use tauri::{AppHandle, Emitter};
 use tokio::sync::{Mutex, Notify};
 
-use super::queue::SyncQueue;
+use super::backfill;
+use super::circuit::{CircuitBreakers, AI_CIRCUIT};
+use super::connectivity::OFFLINE_POLL_INTERVAL;
+use super::connector::{
+    sync_connected_sources, ConnectorContext, ConnectorError, SourceRegistry, SourcesSynced,
+    SyncRange,
+};
+use super::queue::{SyncJob, SyncJobPayload, SyncQueue};
+use super::schedule::{
+    local_time_from_millis, local_timestamp_millis, ScheduledRun, SyncTimetable,
+};
+use super::scheduler::{SyncClaim, SyncPriority, SyncScheduler, AI_RESOURCE};
 use super::slack::{SlackClient, SlackSyncService, SlackTokens};
+use super::status;
+use crate::ai::pipeline::CANCELLED;
 use crate::crypto::CryptoService;
 use crate::db::Database;
-use crate::pipeline::PipelineManager;
+use crate::notifications::NotificationService;
+use crate::pipeline::{CancellationToken, PipelineManager, ProgressReporter};
+
+/// Longest single sleep in the schedule loop, so clock changes and system
+/// sleep are picked up without waiting out a long gap between runs
+const MAX_SCHEDULE_SLEEP: Duration = Duration::from_secs(5 * 60);
 
 pub struct BackgroundSyncService {
     app_handle: AppHandle,
     db: Arc<Database>,
     crypto: Arc<CryptoService>,
     pipeline: Arc<tokio::sync::Mutex<PipelineManager>>,
-    sync_lock: Arc<tokio::sync::Mutex<()>>,
+    scheduler: Arc<SyncScheduler>,
     sync_queue: Arc<SyncQueue>,
-    interval_minutes: Arc<Mutex<u64>>,
+    sources: Arc<SourceRegistry>,
+    circuits: Arc<CircuitBreakers>,
+    timetable: Arc<Mutex<SyncTimetable>>,
     is_running: Arc<AtomicBool>,
     is_syncing: Arc<AtomicBool>,
+    is_online: Arc<AtomicBool>,
     next_sync_at: Arc<AtomicI64>,
-    interval_changed: Arc<Notify>,
+    schedule_changed: Arc<Notify>,
 }
 
 impl BackgroundSyncService {
@@ -30,25 +51,41 @@ impl BackgroundSyncService {
         db: Arc<Database>,
         crypto: Arc<CryptoService>,
         pipeline: Arc<tokio::sync::Mutex<PipelineManager>>,
-        sync_lock: Arc<tokio::sync::Mutex<()>>,
+        scheduler: Arc<SyncScheduler>,
         sync_queue: Arc<SyncQueue>,
-        interval_minutes: u64,
+        timetable: SyncTimetable,
     ) -> Self {
+        let is_syncing = scheduler.is_syncing_flag();
         Self {
             app_handle,
             db,
             crypto,
             pipeline,
-            sync_lock,
+            scheduler,
             sync_queue,
-            interval_minutes: Arc::new(Mutex::new(interval_minutes)),
+            sources: Arc::new(SourceRegistry::with_defaults()),
+            circuits: Arc::new(CircuitBreakers::new()),
+            timetable: Arc::new(Mutex::new(timetable)),
             is_running: Arc::new(AtomicBool::new(false)),
-            is_syncing: Arc::new(AtomicBool::new(false)),
+            is_syncing,
+            is_online: Arc::new(AtomicBool::new(true)),
             next_sync_at: Arc::new(AtomicI64::new(0)),
-            interval_changed: Arc::new(Notify::new()),
+            schedule_changed: Arc::new(Notify::new()),
         }
     }
 
+    /// Share the app-wide source registry instead of the built-in default
+    pub fn with_sources(mut self, sources: Arc<SourceRegistry>) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Share the app-wide circuit breakers so sync status can report them
+    pub fn with_circuits(mut self, circuits: Arc<CircuitBreakers>) -> Self {
+        self.circuits = circuits;
+        self
+    }
+
     pub fn is_syncing(&self) -> bool {
         self.is_syncing.load(Ordering::SeqCst)
     }
@@ -57,6 +94,11 @@ impl BackgroundSyncService {
         self.is_syncing.clone()
     }
 
+    /// `false` while some sources are paused because they could not be reached
+    pub fn is_online_flag(&self) -> Arc<AtomicBool> {
+        self.is_online.clone()
+    }
+
     pub fn next_sync_at(&self) -> Option<i64> {
         let val = self.next_sync_at.load(Ordering::SeqCst);
         if val > 0 {
@@ -70,28 +112,28 @@ impl BackgroundSyncService {
         self.next_sync_at.clone()
     }
 
-    pub async fn set_interval(&self, interval_minutes: u64) {
+    pub async fn set_schedule(&self, timetable: SyncTimetable) {
         {
-            let mut interval = self.interval_minutes.lock().await;
-            *interval = interval_minutes;
+            let mut current = self.timetable.lock().await;
+            *current = timetable;
         }
-        tracing::info!(
-            "Updated sync interval to {} minutes, resetting timer",
-            interval_minutes
-        );
-        self.interval_changed.notify_one();
+        tracing::info!("Updated sync schedule, recomputing next run");
+        self.schedule_changed.notify_one();
     }
 
+    /// A sync is needed at startup when none has run yet or a scheduled one was
+    /// missed while the app was closed, unless it is currently a quiet period
     pub async fn check_sync_needed(&self) -> bool {
-        let interval_minutes = *self.interval_minutes.lock().await;
         let last_sync = get_last_sync_at(self.db.clone()).await;
+        let timetable = self.timetable.lock().await;
+        let now = chrono::Local::now().naive_local();
 
-        match last_sync {
-            Some(last_sync_ms) => {
-                let now = chrono::Utc::now().timestamp_millis();
-                let elapsed_minutes = (now - last_sync_ms) / 1000 / 60;
-                elapsed_minutes >= interval_minutes as i64
-            }
+        if timetable.is_quiet(now) {
+            return false;
+        }
+
+        match last_sync.and_then(local_time_from_millis) {
+            Some(last_sync) => timetable.missed_sync(last_sync, now),
             None => true,
         }
     }
@@ -101,17 +143,49 @@ impl BackgroundSyncService {
             tracing::info!("Running startup sync");
             Self::run_sync_cycle(
                 &self.app_handle,
-                self.db.clone(),
-                self.crypto.clone(),
+                ConnectorContext::new(self.db.clone(), self.crypto.clone())
+                    .with_circuits(self.circuits.clone())
+                    .due_only(),
                 self.pipeline.clone(),
-                self.sync_lock.clone(),
+                self.scheduler.clone(),
                 self.sync_queue.clone(),
-                self.is_syncing.clone(),
+                self.sources.clone(),
+                None,
             )
             .await;
+            set_online(
+                &self.app_handle,
+                &self.is_online,
+                self.circuits.unreachable().is_empty(),
+            );
         }
     }
 
+    /// Requeue jobs and reset resources interrupted by the previous session,
+    /// then run any jobs that are due
+    pub async fn resume_queued_jobs(&self) {
+        match self.sync_queue.recover_interrupted().await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Requeued {} interrupted sync jobs", count),
+            Err(e) => tracing::error!("Failed to recover interrupted sync jobs: {}", e),
+        }
+        match status::recover_interrupted(&self.db).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Reset {} resources left syncing", count),
+            Err(e) => tracing::error!("Failed to reset interrupted resources: {}", e),
+        }
+
+        drain_sync_queue(
+            &ConnectorContext::new(self.db.clone(), self.crypto.clone())
+                .with_circuits(self.circuits.clone()),
+            &self.pipeline,
+            &self.scheduler,
+            &self.sync_queue,
+            &self.sources,
+        )
+        .await;
+    }
+
     pub fn start(&self) {
         if self.is_running.swap(true, Ordering::SeqCst) {
             return;
@@ -121,35 +195,104 @@ impl BackgroundSyncService {
         let db = self.db.clone();
         let crypto = self.crypto.clone();
         let pipeline = self.pipeline.clone();
-        let sync_lock = self.sync_lock.clone();
+        let scheduler = self.scheduler.clone();
         let sync_queue = self.sync_queue.clone();
-        let interval_minutes = self.interval_minutes.clone();
+        let sources = self.sources.clone();
+        let circuits = self.circuits.clone();
+        let timetable = self.timetable.clone();
         let is_running = self.is_running.clone();
-        let is_syncing = self.is_syncing.clone();
+        let is_online = self.is_online.clone();
         let next_sync_at = self.next_sync_at.clone();
-        let interval_changed = self.interval_changed.clone();
+        let schedule_changed = self.schedule_changed.clone();
 
         tokio::spawn(async move {
+            // Sources that could not be reached are retried on their own, more
+            // often than the schedule runs, backing off while they stay down
+            let mut retry_interval = OFFLINE_POLL_INTERVAL;
+            let mut retry_at: Option<i64> = None;
+
             loop {
                 if !is_running.load(Ordering::SeqCst) {
                     break;
                 }
 
-                let interval = *interval_minutes.lock().await;
-                let next_sync =
-                    chrono::Utc::now().timestamp_millis() + (interval as i64 * 60 * 1000);
-                next_sync_at.store(next_sync, Ordering::SeqCst);
+                let next_run = {
+                    let timetable = timetable.lock().await;
+                    let now = chrono::Local::now().naive_local();
+                    let next_sync = timetable
+                        .next_sync_after(now)
+                        .map(local_timestamp_millis)
+                        .unwrap_or(0);
+                    next_sync_at.store(next_sync, Ordering::SeqCst);
+                    timetable.next_run_after(now)
+                };
+
+                let unreachable = circuits.unreachable();
+                if unreachable.is_empty() {
+                    retry_interval = OFFLINE_POLL_INTERVAL;
+                    retry_at = None;
+                } else if retry_at.is_none() {
+                    retry_at = Some(
+                        chrono::Utc::now().timestamp_millis() + retry_interval.as_millis() as i64,
+                    );
+                }
+
+                let due_at = next_run.map(|(run_at, _)| local_timestamp_millis(run_at));
+                let Some(wake_at) = due_at.into_iter().chain(retry_at).min() else {
+                    tracing::info!("Sync schedule has no upcoming runs, waiting for a change");
+                    schedule_changed.notified().await;
+                    continue;
+                };
+                let wait_ms = (wake_at - chrono::Utc::now().timestamp_millis()).max(0) as u64;
 
                 tokio::select! {
-                    _ = tokio::time::sleep(Duration::from_secs(interval * 60)) => {
+                    _ = tokio::time::sleep(Duration::from_millis(wait_ms).min(MAX_SCHEDULE_SLEEP)) => {
                         if !is_running.load(Ordering::SeqCst) {
                             break;
                         }
-                        next_sync_at.store(0, Ordering::SeqCst);
-                        Self::run_sync_cycle(&app_handle, db.clone(), crypto.clone(), pipeline.clone(), sync_lock.clone(), sync_queue.clone(), is_syncing.clone()).await;
+                        let now = chrono::Utc::now().timestamp_millis();
+                        match next_run {
+                            Some((_, run)) if due_at.is_some_and(|at| now >= at) => {
+                                next_sync_at.store(0, Ordering::SeqCst);
+                                let ctx = ConnectorContext::new(db.clone(), crypto.clone())
+                                    .with_circuits(circuits.clone())
+                                    .due_only();
+                                match run {
+                                    ScheduledRun::Sync => {
+                                        Self::run_sync_cycle(&app_handle, ctx, pipeline.clone(), scheduler.clone(), sync_queue.clone(), sources.clone(), None).await;
+                                    }
+                                    ScheduledRun::Digest => {
+                                        Self::run_sync_cycle(&app_handle, ctx.clone(), pipeline.clone(), scheduler.clone(), sync_queue.clone(), sources.clone(), None).await;
+                                        Self::run_digest(&app_handle, &ctx, &pipeline, &scheduler).await;
+                                        notify_paused_sources(&app_handle, &ctx, &sources);
+                                    }
+                                }
+                            }
+                            _ if retry_at.is_some_and(|at| now >= at) => {
+                                tracing::info!("Retrying unreachable sources: {}", unreachable.join(", "));
+                                // Not limited to what is due, so a source that
+                                // connects again catches up in this run
+                                let ctx = ConnectorContext::new(db.clone(), crypto.clone())
+                                    .with_circuits(circuits.clone());
+                                Self::run_sync_cycle(
+                                    &app_handle,
+                                    ctx,
+                                    pipeline.clone(),
+                                    scheduler.clone(),
+                                    sync_queue.clone(),
+                                    sources.clone(),
+                                    Some(unreachable.as_slice()),
+                                )
+                                .await;
+                                retry_interval = (retry_interval * 2).min(MAX_SCHEDULE_SLEEP);
+                                retry_at = None;
+                            }
+                            _ => continue,
+                        }
+                        set_online(&app_handle, &is_online, circuits.unreachable().is_empty());
                     }
-                    _ = interval_changed.notified() => {
-                        tracing::info!("Sync interval changed, resetting timer");
+                    _ = schedule_changed.notified() => {
+                        tracing::info!("Sync schedule changed, recomputing next run");
                         continue;
                     }
                 }
@@ -165,87 +308,62 @@ impl BackgroundSyncService {
         self.is_running.store(false, Ordering::SeqCst);
     }
 
+    /// Sync every due source, or only the sources in `only`, and run the AI pass
     async fn run_sync_cycle(
         app_handle: &AppHandle,
-        db: Arc<Database>,
-        crypto: Arc<CryptoService>,
+        ctx: ConnectorContext,
         pipeline: Arc<tokio::sync::Mutex<PipelineManager>>,
-        sync_lock: Arc<tokio::sync::Mutex<()>>,
+        scheduler: Arc<SyncScheduler>,
         sync_queue: Arc<SyncQueue>,
-        is_syncing: Arc<AtomicBool>,
+        sources: Arc<SourceRegistry>,
+        only: Option<&[String]>,
     ) {
         use crate::ai::ProcessingPipeline;
         use crate::pipeline::PipelineTaskType;
         use std::time::Instant;
 
-        let Ok(_guard) = sync_lock.try_lock() else {
-            tracing::debug!("Skipping background sync, another sync is in progress");
-            return;
-        };
+        let permit = scheduler
+            .acquire("Background sync", SyncPriority::Incremental, SyncClaim::All)
+            .await;
 
-        is_syncing.store(true, Ordering::SeqCst);
         tracing::info!("Starting background sync cycle");
         let start = Instant::now();
         let _ = app_handle.emit("sync:started", ());
 
-        let mut total_items = 0;
-        let mut errors: Vec<String> = Vec::new();
-
-        let task_id = {
-            let pipeline = pipeline.lock().await;
-            pipeline
-                .start_task(
-                    PipelineTaskType::SyncSlack,
-                    "Syncing Slack messages...".to_string(),
-                )
-                .await
-        };
-
-        match sync_slack_now(db.clone(), crypto.clone()).await {
-            Ok(items) => {
-                total_items = items;
-                let pipeline = pipeline.lock().await;
-                let message = if items > 0 {
-                    format!("Synced {} messages from Slack", items)
-                } else {
-                    "Slack sync complete (no new messages)".to_string()
-                };
-                pipeline.complete_task(&task_id, Some(message)).await;
-            }
-            Err(e) => {
-                let pipeline = pipeline.lock().await;
-                if e.contains("not connected") {
-                    pipeline
-                        .complete_task(&task_id, Some("Slack not connected".to_string()))
-                        .await;
-                } else {
-                    tracing::error!("Slack sync error: {}", e);
-                    pipeline.fail_task(&task_id, e.clone()).await;
-                    errors.push(format!("Slack: {}", e));
-                }
-            }
-        }
+        let (db, crypto) = (ctx.db.clone(), ctx.crypto.clone());
+        let SourcesSynced {
+            items: total_items,
+            mut errors,
+            ..
+        } = sync_connected_sources(&sources, &ctx, &pipeline, only, None).await;
 
-        if total_items > 0 {
+        // Items left unprocessed while the AI circuit is open are picked up by
+        // the first batch after it closes
+        if total_items > 0 && ctx.circuit_allows(AI_CIRCUIT) {
             if let Some(api_key_or_client) = get_gemini_client(db.clone(), crypto.clone()).await {
-                let ai_task_id = {
+                let (ai_task_id, cancel) = {
                     let pipeline = pipeline.lock().await;
-                    pipeline
+                    let task_id = pipeline
                         .start_task(
                             PipelineTaskType::AiSummarize,
                             "Analyzing content with AI...".to_string(),
                         )
-                        .await
+                        .await;
+                    let cancel = pipeline.cancellation_token(&task_id).await;
+                    (task_id, cancel)
                 };
 
                 let local_offset = chrono::Local::now().offset().local_minus_utc() / 60;
                 let timezone_offset = Some(-local_offset);
 
                 let ai_pipeline =
-                    ProcessingPipeline::new(api_key_or_client, db.clone(), crypto.clone());
+                    ProcessingPipeline::new(api_key_or_client, db.clone(), crypto.clone())
+                        .with_cancellation(cancel)
+                        .with_progress(ProgressReporter::new(pipeline.clone(), &ai_task_id));
                 match ai_pipeline.process_daily_batch(timezone_offset).await {
                     Ok(processed) => {
                         tracing::info!("AI batch processed {} groups/items", processed);
+                        ctx.record_outcome(AI_CIRCUIT, None);
                         let pipeline = pipeline.lock().await;
                         pipeline
                             .complete_task(
@@ -256,6 +374,9 @@ impl BackgroundSyncService {
                     }
                     Err(e) => {
                         tracing::error!("AI batch processing error: {}", e);
+                        if e != CANCELLED {
+                            ctx.record_outcome(AI_CIRCUIT, Some(&e));
+                        }
                         let pipeline = pipeline.lock().await;
                         pipeline.fail_task(&ai_task_id, e.clone()).await;
                         errors.push(format!("AI: {}", e));
@@ -266,12 +387,12 @@ impl BackgroundSyncService {
 
         let duration_ms = start.elapsed().as_millis() as u64;
 
+        notify_paused_sources(app_handle, &ctx, &sources);
+
         if let Err(e) = save_last_sync_at(db.clone()).await {
             tracing::error!("Failed to save last sync timestamp: {}", e);
         }
 
-        is_syncing.store(false, Ordering::SeqCst);
-
         let _ = app_handle.emit(
             "sync:completed",
             serde_json::json!({
@@ -287,180 +408,276 @@ impl BackgroundSyncService {
             duration_ms
         );
 
-        // Drop the sync guard before draining the queue
-        drop(_guard);
+        // Release the permit before draining the queue
+        drop(permit);
 
-        // Drain any queued historical syncs
-        Self::drain_queue(
-            db,
-            crypto,
-            pipeline,
-            sync_lock,
-            sync_queue,
-            is_syncing,
-        )
-        .await;
+        drain_sync_queue(&ctx, &pipeline, &scheduler, &sync_queue, &sources).await;
     }
 
-    /// Drain the sync queue, executing each queued historical resync in order.
-    async fn drain_queue(
-        db: Arc<Database>,
-        crypto: Arc<CryptoService>,
-        pipeline: Arc<tokio::sync::Mutex<PipelineManager>>,
-        sync_lock: Arc<tokio::sync::Mutex<()>>,
-        sync_queue: Arc<SyncQueue>,
-        is_syncing: Arc<AtomicBool>,
+    /// Generate the end-of-day digest for the local date
+    async fn run_digest(
+        app_handle: &AppHandle,
+        ctx: &ConnectorContext,
+        pipeline: &Arc<tokio::sync::Mutex<PipelineManager>>,
+        scheduler: &Arc<SyncScheduler>,
     ) {
         use crate::ai::ProcessingPipeline;
         use crate::pipeline::PipelineTaskType;
 
-        loop {
-            let request = sync_queue.dequeue().await;
+        let Some(api_key_or_client) = get_gemini_client(ctx.db.clone(), ctx.crypto.clone()).await
+        else {
+            tracing::info!("Skipping daily digest: Gemini is not configured");
+            return;
+        };
 
-            let Some(request) = request else {
-                break;
-            };
+        if !ctx.circuit_allows(AI_CIRCUIT) {
+            tracing::info!("Skipping daily digest: AI processing is paused after failures");
+            return;
+        }
 
-            let (Some(date), Some(tz_offset)) =
-                (request.date.clone(), request.timezone_offset)
-            else {
-                tracing::warn!(
-                    "Queued sync request missing date/timezone, skipping: {:?}",
-                    request.source
-                );
-                continue;
-            };
+        let _permit = scheduler
+            .acquire(
+                "Daily digest",
+                SyncPriority::Incremental,
+                SyncClaim::Resources(vec![AI_RESOURCE.to_string()]),
+            )
+            .await;
+
+        let date = chrono::Local::now()
+            .date_naive()
+            .format("%Y-%m-%d")
+            .to_string();
+        let task_id = {
+            let pipeline = pipeline.lock().await;
+            pipeline
+                .start_task(
+                    PipelineTaskType::GenerateDailyDigest,
+                    format!("Generating digest for {}...", date),
+                )
+                .await
+        };
+
+        let ai_pipeline =
+            ProcessingPipeline::new(api_key_or_client, ctx.db.clone(), ctx.crypto.clone());
+        match ai_pipeline.generate_daily_digest(&date).await {
+            Ok(_) => {
+                tracing::info!("Generated daily digest for {}", date);
+                ctx.record_outcome(AI_CIRCUIT, None);
+                let pipeline = pipeline.lock().await;
+                pipeline
+                    .complete_task(&task_id, Some(format!("Digest ready for {}", date)))
+                    .await;
+                let _ = app_handle.emit("digest:generated", serde_json::json!({ "date": date }));
+            }
+            Err(e) => {
+                tracing::error!("Daily digest generation error for {}: {}", date, e);
+                ctx.record_outcome(AI_CIRCUIT, Some(&e));
+                let pipeline = pipeline.lock().await;
+                pipeline.fail_task(&task_id, e).await;
+            }
+        }
+    }
+}
 
-            tracing::info!("Background: draining queued historical resync for {}", date);
+/// Record whether every source could be reached and tell the UI when it changes
+fn set_online(app_handle: &AppHandle, is_online: &AtomicBool, online: bool) {
+    if is_online.swap(online, Ordering::SeqCst) == online {
+        return;
+    }
+    if online {
+        tracing::info!("All sources reachable again");
+    } else {
+        tracing::warn!("Some sources are unreachable, retrying them until they connect");
+    }
+    let _ = app_handle.emit(
+        "connectivity:changed",
+        serde_json::json!({ "online": online }),
+    );
+}
 
-            let _sync_guard = match sync_lock.try_lock() {
-                Ok(guard) => guard,
-                Err(_) => {
-                    tracing::info!(
-                        "Sync lock busy during background queue drain, re-queuing {}",
-                        date
-                    );
-                    sync_queue.enqueue(request).await;
-                    break;
+/// Send one notification for each source whose circuit opened during a run,
+/// instead of one per failed sync
+fn notify_paused_sources(app_handle: &AppHandle, ctx: &ConnectorContext, sources: &SourceRegistry) {
+    let Some(circuits) = &ctx.circuits else {
+        return;
+    };
+
+    let notifications = NotificationService::new(app_handle.clone());
+    for (source, circuit) in circuits.take_opened() {
+        let name = match sources.get(&source) {
+            Some(connector) => connector.display_name(),
+            None if source == AI_CIRCUIT => "AI processing",
+            None => source.as_str(),
+        };
+        let retry = circuit
+            .retry_at
+            .and_then(local_time_from_millis)
+            .map(|at| format!(" Retrying at {}.", at.format("%H:%M")))
+            .unwrap_or_default();
+        let message = format!(
+            "Paused after {} failed syncs: {}.{}",
+            circuit.consecutive_failures,
+            circuit.last_error.as_deref().unwrap_or("unknown error"),
+            retry
+        );
+        if let Err(e) = notifications.notify_error(name, &message) {
+            tracing::warn!("Failed to show sync error notification: {}", e);
+        }
+    }
+}
+
+/// Drain due jobs from the sync queue in order. A permit is acquired per job, so
+/// user-initiated syncs run ahead of the remaining queued jobs.
+///
+/// Backfills are handed to their own task: they take a backfill-priority permit
+/// per day and can run for a long time, so they must not hold up the jobs
+/// behind them or the caller.
+pub(crate) async fn drain_sync_queue(
+    ctx: &ConnectorContext,
+    pipeline: &Arc<tokio::sync::Mutex<PipelineManager>>,
+    scheduler: &Arc<SyncScheduler>,
+    sync_queue: &Arc<SyncQueue>,
+    sources: &Arc<SourceRegistry>,
+) {
+    loop {
+        let job = match sync_queue.dequeue().await {
+            Ok(Some(job)) => job,
+            Ok(None) => break,
+            Err(e) => {
+                tracing::error!("Failed to read sync queue: {}", e);
+                break;
+            }
+        };
+
+        match job.payload {
+            SyncJobPayload::HistoricalDay {
+                ref date,
+                timezone_offset,
+            } => {
+                let _permit = scheduler
+                    .acquire("Queued sync job", SyncPriority::Incremental, SyncClaim::All)
+                    .await;
+
+                if sync_queue.is_cancelled(&job.id).await {
+                    tracing::info!("Skipping cancelled job {}", job.payload.describe());
+                    continue;
                 }
-            };
 
-            is_syncing.store(true, Ordering::SeqCst);
+                let result =
+                    run_historical_day(ctx, pipeline, sources, &job, date, timezone_offset).await;
+                sync_queue.finish_job(&job, result.map(|_| ())).await;
+            }
+            SyncJobPayload::Backfill { .. } => {
+                tokio::spawn(backfill::run_queued_backfill(
+                    ctx.clone(),
+                    sources.clone(),
+                    pipeline.clone(),
+                    scheduler.clone(),
+                    sync_queue.clone(),
+                    job,
+                ));
+            }
+        }
+    }
+}
+
+/// Run one queued historical day resync. The caller holds a permit claiming
+/// every source.
+///
+/// Returns the number of items synced, or the joined errors when any source or
+/// the AI pass failed, or a source's sync was cancelled, so the queue can retry
+/// the job.
+async fn run_historical_day(
+    ctx: &ConnectorContext,
+    pipeline: &Arc<tokio::sync::Mutex<PipelineManager>>,
+    sources: &SourceRegistry,
+    job: &SyncJob,
+    date: &str,
+    timezone_offset: i32,
+) -> Result<i32, String> {
+    use crate::ai::ProcessingPipeline;
+    use crate::pipeline::PipelineTaskType;
+
+    tracing::info!(
+        "Running queued job {} (attempt {})",
+        job.payload.describe(),
+        job.attempts
+    );
 
-            let mut total_items = 0;
+    let range = SyncRange::day(date, timezone_offset)?;
+    let SourcesSynced {
+        items: total_items,
+        mut errors,
+        cancelled,
+    } = sync_connected_sources(sources, ctx, pipeline, None, Some(&range)).await;
+    if cancelled {
+        // A source's sync was cancelled on its own, so the day is not complete
+        // and the job is retried like any other failure
+        errors.push(CANCELLED.to_string());
+    }
 
-            let sync_task_id = {
+    if total_items > 0 {
+        let (db, crypto) = (ctx.db.clone(), ctx.crypto.clone());
+        if let Some(api_key_or_client) = get_gemini_client(db.clone(), crypto.clone()).await {
+            let (ai_task_id, cancel) = {
                 let pipeline = pipeline.lock().await;
-                pipeline
+                let task_id = pipeline
                     .start_task(
-                        PipelineTaskType::SyncSlack,
-                        format!("Syncing Slack messages for {}...", date),
+                        PipelineTaskType::AiSummarize,
+                        format!("Analyzing content for {}...", date),
                     )
-                    .await
+                    .await;
+                let cancel = pipeline.cancellation_token(&task_id).await;
+                (task_id, cancel)
             };
 
-            match sync_slack_historical_day(db.clone(), crypto.clone(), &date, tz_offset).await {
-                Ok(result) => {
-                    let items = result.items_synced;
+            let ai_pipeline = ProcessingPipeline::new(api_key_or_client, db, crypto)
+                .with_cancellation(cancel)
+                .with_progress(ProgressReporter::new(pipeline.clone(), &ai_task_id));
+            match ai_pipeline
+                .process_batch_for_date(date, timezone_offset)
+                .await
+            {
+                Ok(processed) => {
                     tracing::info!(
-                        "Background queued historical sync completed: {} items for {}",
-                        items,
+                        "AI batch processed {} groups/items for {} (queued)",
+                        processed,
                         date
                     );
-                    total_items = items;
-
                     let pipeline = pipeline.lock().await;
-                    let message = if items > 0 {
-                        format!("Synced {} messages for {}", items, date)
-                    } else {
-                        format!("No new messages found for {}", date)
-                    };
-                    pipeline.complete_task(&sync_task_id, Some(message)).await;
+                    pipeline
+                        .complete_task(
+                            &ai_task_id,
+                            Some(format!("Grouped and summarized {} items", processed)),
+                        )
+                        .await;
                 }
                 Err(e) => {
-                    tracing::error!(
-                        "Background queued historical sync error for {}: {}",
-                        date,
-                        e
-                    );
+                    tracing::error!("AI batch processing error for {} (queued): {}", date, e);
                     let pipeline = pipeline.lock().await;
-                    if e.contains("not connected") {
-                        pipeline
-                            .complete_task(
-                                &sync_task_id,
-                                Some("Slack not connected".to_string()),
-                            )
-                            .await;
-                    } else {
-                        pipeline.fail_task(&sync_task_id, e).await;
-                    }
+                    pipeline.fail_task(&ai_task_id, e.clone()).await;
+                    errors.push(format!("AI: {}", e));
                 }
             }
-
-            if total_items > 0 {
-                if let Some(api_key_or_client) =
-                    get_gemini_client(db.clone(), crypto.clone()).await
-                {
-                    let ai_task_id = {
-                        let pipeline = pipeline.lock().await;
-                        pipeline
-                            .start_task(
-                                PipelineTaskType::AiSummarize,
-                                format!("Analyzing content for {}...", date),
-                            )
-                            .await
-                    };
-
-                    let ai_pipeline = ProcessingPipeline::new(
-                        api_key_or_client,
-                        db.clone(),
-                        crypto.clone(),
-                    );
-                    match ai_pipeline.process_batch_for_date(&date, tz_offset).await {
-                        Ok(processed) => {
-                            tracing::info!(
-                                "AI batch processed {} groups/items for {} (background queued)",
-                                processed,
-                                date
-                            );
-                            let pipeline = pipeline.lock().await;
-                            pipeline
-                                .complete_task(
-                                    &ai_task_id,
-                                    Some(format!(
-                                        "Grouped and summarized {} items",
-                                        processed
-                                    )),
-                                )
-                                .await;
-                        }
-                        Err(e) => {
-                            tracing::error!(
-                                "AI batch processing error for {} (background queued): {}",
-                                date,
-                                e
-                            );
-                            let pipeline = pipeline.lock().await;
-                            pipeline.fail_task(&ai_task_id, e).await;
-                        }
-                    }
-                }
-            }
-
-            is_syncing.store(false, Ordering::SeqCst);
-            tracing::info!(
-                "Background queued historical resync for {} completed",
-                date
-            );
-
-            // Drop guard and yield to allow user-initiated syncs to interleave
-            drop(_sync_guard);
-            tokio::task::yield_now().await;
         }
     }
+
+    if errors.is_empty() {
+        tracing::info!(
+            "Queued job {} completed: {} items",
+            job.payload.describe(),
+            total_items
+        );
+        Ok(total_items)
+    } else {
+        Err(errors.join("; "))
+    }
 }
 
-async fn get_gemini_client(db: Arc<Database>, crypto: Arc<CryptoService>) -> Option<String> {
+pub(crate) async fn get_gemini_client(
+    db: Arc<Database>,
+    crypto: Arc<CryptoService>,
+) -> Option<String> {
     use crate::ai::ServiceAccountCredentials;
 
     let service_account: Option<(String,)> = sqlx::query_as(
@@ -517,16 +734,22 @@ async fn save_last_sync_at(db: Arc<Database>) -> Result<(), String> {
     Ok(())
 }
 
-/// Public function to sync Slack data, can be called from commands
-pub async fn sync_slack_now(db: Arc<Database>, crypto: Arc<CryptoService>) -> Result<i32, String> {
+/// Public function to sync Slack data, can be called from commands.
+/// With `due_only`, channels whose sync interval has not elapsed are skipped.
+pub async fn sync_slack_now(
+    db: Arc<Database>,
+    crypto: Arc<CryptoService>,
+    cancel: &CancellationToken,
+    progress: &ProgressReporter,
+    due_only: bool,
+) -> Result<i32, ConnectorError> {
     // Check for Slack credentials
     let result: Option<(String,)> =
         sqlx::query_as("SELECT encrypted_data FROM credentials WHERE id = 'slack'")
             .fetch_optional(db.pool())
-            .await
-            .map_err(|e| e.to_string())?;
+            .await?;
 
-    let encrypted = result.ok_or("Slack not connected")?;
+    let encrypted = result.ok_or(ConnectorError::NotConnected("Slack"))?;
 
     let tokens_json = crypto
         .decrypt_string(&encrypted.0)
@@ -560,10 +783,16 @@ pub async fn sync_slack_now(db: Arc<Database>, crypto: Arc<CryptoService>) -> Re
         }
     }
 
-    let sync_service =
-        SlackSyncService::new(client, db.clone(), crypto).with_team_domain(tokens.team_domain);
+    let sync_service = SlackSyncService::new(client, db.clone(), crypto)
+        .with_team_domain(tokens.team_domain)
+        .with_cancellation(cancel.clone())
+        .with_progress(progress.clone())
+        .with_due_only(due_only);
 
-    let result = sync_service.sync_all().await.map_err(|e| e.to_string())?;
+    let result = sync_service
+        .sync_all()
+        .await
+        .map_err(ConnectorError::classify)?;
 
     tracing::info!("Slack sync completed: {} items synced", result.items_synced);
     Ok(result.items_synced)
@@ -575,14 +804,14 @@ pub async fn sync_slack_historical_day(
     crypto: Arc<CryptoService>,
     date_str: &str,
     timezone_offset_minutes: i32,
-) -> Result<crate::sync::SyncResult, String> {
+    cancel: &CancellationToken,
+) -> Result<crate::sync::SyncResult, ConnectorError> {
     let result: Option<(String,)> =
         sqlx::query_as("SELECT encrypted_data FROM credentials WHERE id = 'slack'")
             .fetch_optional(db.pool())
-            .await
-            .map_err(|e| e.to_string())?;
+            .await?;
 
-    let encrypted = result.ok_or("Slack not connected")?;
+    let encrypted = result.ok_or(ConnectorError::NotConnected("Slack"))?;
 
     let tokens_json = crypto
         .decrypt_string(&encrypted.0)
@@ -599,13 +828,14 @@ pub async fn sync_slack_historical_day(
         .with_token(tokens.access_token.clone())
         .with_team_id(tokens.team_id.clone());
 
-    let sync_service =
-        SlackSyncService::new(client, db.clone(), crypto).with_team_domain(tokens.team_domain);
+    let sync_service = SlackSyncService::new(client, db.clone(), crypto)
+        .with_team_domain(tokens.team_domain)
+        .with_cancellation(cancel.clone());
 
     let result = sync_service
         .sync_historical_day(date_str, timezone_offset_minutes)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(ConnectorError::classify)?;
 
     tracing::info!(
         "Historical Slack sync completed for {}: {} items synced",
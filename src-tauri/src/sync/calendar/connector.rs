@@ -0,0 +1,257 @@
+//! Calendar source connector
+//!
+//! Reads one ICS feed, a subscription URL or a local `.ics` file, and stores
+//! its events so digests can place discussions around meetings.
+
+use async_trait::async_trait;
+
+use super::sync::{normalize_feed_url, CalendarSyncService};
+use super::types::CalendarFeed;
+use crate::pipeline::PipelineTaskType;
+use crate::sync::connector::{
+    clear_sync_state, incremental_since, last_sync_at, load_credentials, load_cursor, save_cursor,
+    store_credentials, ConnectorContext, ConnectorError, SourceConnector, SourceHealth,
+    SourceScope, SyncRange,
+};
+use crate::sync::SyncResult;
+
+/// `sync_state` resource for the single configured feed
+const FEED_RESOURCE: &str = "feed";
+/// Upcoming days synced ahead so today's and tomorrow's meetings are known
+const LOOKAHEAD_DAYS: i64 = 2;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+pub(crate) async fn load_calendar_feed(
+    ctx: &ConnectorContext,
+) -> Result<Option<CalendarFeed>, ConnectorError> {
+    load_credentials(ctx, "calendar").await
+}
+
+/// Incremental window `[start, end)` in epoch milliseconds. Today is always
+/// re-read since meetings are often moved on the day.
+fn incremental_window(cursor: Option<&str>, now_ms: i64) -> (i64, i64) {
+    let since = incremental_since(cursor, now_ms).min(now_ms - DAY_MS);
+
+    (since, now_ms + LOOKAHEAD_DAYS * DAY_MS)
+}
+
+fn config_str<'a>(config: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    config[key]
+        .as_str()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+pub struct CalendarConnector;
+
+impl CalendarConnector {
+    async fn feed(&self, ctx: &ConnectorContext) -> Result<CalendarFeed, ConnectorError> {
+        load_calendar_feed(ctx)
+            .await?
+            .ok_or(ConnectorError::NotConnected("Calendar"))
+    }
+}
+
+#[async_trait]
+impl SourceConnector for CalendarConnector {
+    fn id(&self) -> &'static str {
+        "calendar"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Calendar"
+    }
+
+    fn description(&self) -> &'static str {
+        "Add meetings from an ICS feed or file as context for digests"
+    }
+
+    fn task_type(&self) -> PipelineTaskType {
+        PipelineTaskType::SyncCalendar
+    }
+
+    fn item_label(&self) -> &'static str {
+        "events"
+    }
+
+    /// Accepts `{ "url" }` or `{ "path" }`, plus an optional `name`
+    async fn connect(
+        &self,
+        ctx: &ConnectorContext,
+        config: serde_json::Value,
+    ) -> Result<serde_json::Value, ConnectorError> {
+        let url = config_str(&config, "url").map(normalize_feed_url);
+        let path = config_str(&config, "path").map(String::from);
+        match (&url, &path) {
+            (Some(_), Some(_)) => {
+                return Err(ConnectorError::InvalidConfig(
+                    "provide either a calendar URL or a file path, not both".into(),
+                ))
+            }
+            (None, None) => {
+                return Err(ConnectorError::InvalidConfig(
+                    "a calendar URL or file path is required".into(),
+                ))
+            }
+            (Some(url), None) if !url.starts_with("https://") && !url.starts_with("http://") => {
+                return Err(ConnectorError::InvalidConfig(format!(
+                    "Unsupported calendar URL: {}",
+                    url
+                )))
+            }
+            _ => {}
+        }
+
+        let mut feed = CalendarFeed {
+            url,
+            path,
+            name: String::new(),
+        };
+        let calendar = CalendarSyncService::new(ctx.db.clone(), ctx.crypto.clone())
+            .load(&feed)
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+        feed.name = config_str(&config, "name")
+            .map(String::from)
+            .or(calendar.name.clone())
+            .unwrap_or_else(|| "Calendar".to_string());
+        store_credentials(ctx, "calendar", &feed).await?;
+
+        tracing::info!(
+            "Calendar {} connected with {} events",
+            feed.name,
+            calendar.event_count()
+        );
+        Ok(serde_json::json!({ "name": feed.name, "events": calendar.event_count() }))
+    }
+
+    async fn is_connected(&self, ctx: &ConnectorContext) -> bool {
+        matches!(load_calendar_feed(ctx).await, Ok(Some(_)))
+    }
+
+    async fn list_scopes(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<Vec<SourceScope>, ConnectorError> {
+        let feed = self.feed(ctx).await?;
+        Ok(vec![SourceScope {
+            id: FEED_RESOURCE.to_string(),
+            name: feed.name,
+            kind: "calendar".to_string(),
+            selected: true,
+        }])
+    }
+
+    async fn sync_incremental(&self, ctx: &ConnectorContext) -> Result<SyncResult, ConnectorError> {
+        let feed = self.feed(ctx).await?;
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let cursor = load_cursor(&ctx.db, self.id(), "calendar", FEED_RESOURCE).await?;
+        let (start, end) = incremental_window(cursor.as_deref(), now_ms);
+
+        let service = CalendarSyncService::new(ctx.db.clone(), ctx.crypto.clone());
+        let sync = service.sync_window(&feed, start, end);
+        let result = ctx
+            .track_resource(self.id(), "calendar", FEED_RESOURCE, sync)
+            .await
+            .map_err(ConnectorError::classify)?;
+        ctx.check_cancelled()?;
+
+        let cursor = chrono::DateTime::from_timestamp_millis(now_ms)
+            .unwrap_or_default()
+            .to_rfc3339();
+        save_cursor(&ctx.db, self.id(), "calendar", FEED_RESOURCE, &cursor).await?;
+
+        Ok(result)
+    }
+
+    async fn sync_range(
+        &self,
+        ctx: &ConnectorContext,
+        range: &SyncRange,
+    ) -> Result<SyncResult, ConnectorError> {
+        let feed = self.feed(ctx).await?;
+        let (start, end) = range.utc_bounds();
+
+        CalendarSyncService::new(ctx.db.clone(), ctx.crypto.clone())
+            .sync_window(&feed, start * 1000, end * 1000)
+            .await
+            .map_err(ConnectorError::classify)
+    }
+
+    async fn health(&self, ctx: &ConnectorContext) -> SourceHealth {
+        let last_sync_at = last_sync_at(&ctx.db, self.id()).await;
+        let mut health = SourceHealth {
+            source: self.id().to_string(),
+            connected: false,
+            healthy: false,
+            message: None,
+            last_sync_at,
+        };
+
+        let feed = match load_calendar_feed(ctx).await {
+            Ok(Some(feed)) => feed,
+            Ok(None) => return health,
+            Err(e) => {
+                health.connected = true;
+                health.message = Some(e.to_string());
+                return health;
+            }
+        };
+        health.connected = true;
+
+        match CalendarSyncService::new(ctx.db.clone(), ctx.crypto.clone())
+            .load(&feed)
+            .await
+        {
+            Ok(calendar) => {
+                health.healthy = true;
+                health.message = Some(format!(
+                    "{}: {} events in feed",
+                    feed.name,
+                    calendar.event_count()
+                ));
+            }
+            Err(e) => health.message = Some(e.to_string()),
+        }
+
+        health
+    }
+
+    async fn disconnect(&self, ctx: &ConnectorContext) -> Result<(), ConnectorError> {
+        sqlx::query("DELETE FROM credentials WHERE id = 'calendar'")
+            .execute(ctx.db.pool())
+            .await?;
+
+        clear_sync_state(&ctx.db, self.id()).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incremental_window() {
+        let now = chrono::DateTime::parse_from_rfc3339("2024-03-01T12:00:00Z")
+            .unwrap()
+            .timestamp_millis();
+
+        assert_eq!(
+            incremental_window(None, now),
+            (now - 7 * DAY_MS, now + 2 * DAY_MS)
+        );
+        // A recent cursor still re-reads the last day
+        assert_eq!(
+            incremental_window(Some("2024-03-01T11:00:00Z"), now).0,
+            now - DAY_MS
+        );
+        assert_eq!(
+            incremental_window(Some("2023-01-01T00:00:00Z"), now).0,
+            now - 30 * DAY_MS
+        );
+    }
+}
@@ -0,0 +1,994 @@
+//! iCalendar (RFC 5545) parsing and recurrence expansion
+//!
+//! Covers what calendar exports use in practice: folded lines, `VTIMEZONE`
+//! definitions, DAILY/WEEKLY/MONTHLY/YEARLY rules with INTERVAL, COUNT, UNTIL,
+//! BYDAY, BYMONTHDAY and BYMONTH, `EXDATE` and `RECURRENCE-ID` overrides.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Weekday};
+use std::collections::HashMap;
+
+use super::types::{CalendarError, CalendarEvent};
+
+/// Stop expanding a rule after this many periods (days, weeks, months or years)
+const MAX_RECURRENCE_PERIODS: i64 = 5000;
+
+#[derive(Debug, Clone)]
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Component {
+    name: String,
+    properties: Vec<Property>,
+    children: Vec<Component>,
+}
+
+impl Component {
+    fn property(&self, name: &str) -> Option<&Property> {
+        self.properties.iter().find(|p| p.name == name)
+    }
+
+    fn all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Property> {
+        self.properties.iter().filter(move |p| p.name == name)
+    }
+
+    fn text(&self, name: &str) -> Option<String> {
+        self.property(name)
+            .map(|p| unescape_text(&p.value))
+            .filter(|s| !s.trim().is_empty())
+    }
+}
+
+/// Join folded continuation lines onto the line they continue
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if let Some(rest) = line.strip_prefix([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        if !line.is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+    lines
+}
+
+/// Split on `separator` outside double quotes
+fn split_unquoted(s: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c == separator && !in_quotes {
+            parts.push(&s[start..i]);
+            start = i + 1;
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+fn parse_property(line: &str) -> Option<Property> {
+    let mut in_quotes = false;
+    let colon = line.char_indices().find_map(|(i, c)| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        (c == ':' && !in_quotes).then_some(i)
+    })?;
+
+    let mut head = split_unquoted(&line[..colon], ';').into_iter();
+    let name = head.next()?.trim().to_ascii_uppercase();
+    let params = head
+        .filter_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            Some((
+                key.to_ascii_uppercase(),
+                value.trim_matches('"').to_string(),
+            ))
+        })
+        .collect();
+
+    Some(Property {
+        name,
+        params,
+        value: line[colon + 1..].to_string(),
+    })
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out.trim().to_string()
+}
+
+fn parse_components(text: &str) -> Result<Vec<Component>, CalendarError> {
+    let mut stack = vec![Component::default()];
+
+    for line in unfold(text) {
+        let Some(property) = parse_property(&line) else {
+            continue;
+        };
+        match property.name.as_str() {
+            "BEGIN" => stack.push(Component {
+                name: property.value.trim().to_ascii_uppercase(),
+                ..Default::default()
+            }),
+            "END" => {
+                let name = property.value.trim().to_ascii_uppercase();
+                if stack.len() < 2 || stack.last().is_some_and(|c| c.name != name) {
+                    return Err(CalendarError::Parse(format!("unexpected END:{}", name)));
+                }
+                let component = stack.pop().unwrap();
+                stack.last_mut().unwrap().children.push(component);
+            }
+            _ => stack.last_mut().unwrap().properties.push(property),
+        }
+    }
+
+    if stack.len() != 1 {
+        return Err(CalendarError::Parse(format!(
+            "{} is not closed",
+            stack.last().unwrap().name
+        )));
+    }
+    Ok(stack.pop().unwrap().children)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Zone {
+    Utc,
+    /// Floating times and dates; treated as UTC
+    Floating,
+    Named(String),
+}
+
+#[derive(Debug, Clone)]
+struct DateValue {
+    local: NaiveDateTime,
+    zone: Zone,
+    all_day: bool,
+}
+
+fn parse_date_value(value: &str, tzid: Option<&str>) -> Option<DateValue> {
+    let value = value.trim();
+    if value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some(DateValue {
+            local: date.and_hms_opt(0, 0, 0)?,
+            zone: Zone::Floating,
+            all_day: true,
+        });
+    }
+
+    let (value, utc) = match value.strip_suffix(['Z', 'z']) {
+        Some(value) => (value, true),
+        None => (value, false),
+    };
+    let local = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    let zone = match tzid {
+        _ if utc => Zone::Utc,
+        Some(tzid) => Zone::Named(tzid.to_string()),
+        None => Zone::Floating,
+    };
+    Some(DateValue {
+        local,
+        zone,
+        all_day: false,
+    })
+}
+
+fn property_dates(property: &Property) -> Vec<DateValue> {
+    let tzid = property.param("TZID");
+    property
+        .value
+        .split(',')
+        .filter_map(|value| parse_date_value(value, tzid))
+        .collect()
+}
+
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let value = value.strip_prefix('P')?;
+
+    let mut seconds = 0i64;
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in value.chars() {
+        match c {
+            'T' => in_time = true,
+            '0'..='9' => number.push(c),
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                seconds += n * match (unit, in_time) {
+                    ('W', false) => 7 * 86400,
+                    ('D', false) => 86400,
+                    ('H', true) => 3600,
+                    ('M', true) => 60,
+                    ('S', true) => 1,
+                    _ => return None,
+                };
+            }
+        }
+    }
+
+    Some(Duration::seconds(if negative { -seconds } else { seconds }))
+}
+
+/// UTC offset such as `+0100`, `-0500` or `+053000`, in seconds
+fn parse_utc_offset(value: &str) -> Option<i32> {
+    let value = value.trim();
+    let sign = match value.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let digits = &value[1..];
+    if digits.len() < 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i32 = digits[0..2].parse().ok()?;
+    let minutes: i32 = digits[2..4].parse().ok()?;
+    let seconds: i32 = digits.get(4..6).and_then(|s| s.parse().ok()).unwrap_or(0);
+    Some(sign * (hours * 3600 + minutes * 60 + seconds))
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    Some(match value {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+fn last_day_of_month(year: i32, month: u32) -> Option<NaiveDate> {
+    let (year, month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)?.pred_opt()
+}
+
+/// The `nth` `weekday` of a month; negative `nth` counts from the end
+fn nth_weekday(year: i32, month: u32, nth: i32, weekday: Weekday) -> Option<NaiveDate> {
+    if nth > 0 {
+        return NaiveDate::from_weekday_of_month_opt(year, month, weekday, u8::try_from(nth).ok()?);
+    }
+    if nth == 0 {
+        return None;
+    }
+
+    let last = last_day_of_month(year, month)?;
+    let back = (last.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+    let date = last - Duration::days(back as i64 + 7 * (-nth as i64 - 1));
+    (date.month() == month).then_some(date)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Debug, Clone)]
+struct RecurrenceRule {
+    frequency: Frequency,
+    interval: i64,
+    count: Option<u32>,
+    until: Option<DateValue>,
+    /// `(ordinal, weekday)`; ordinal 0 means every such weekday
+    by_day: Vec<(i32, Weekday)>,
+    by_month_day: Vec<i32>,
+    by_month: Vec<u32>,
+}
+
+fn parse_rrule(value: &str) -> Option<RecurrenceRule> {
+    let mut rule = RecurrenceRule {
+        frequency: Frequency::Daily,
+        interval: 1,
+        count: None,
+        until: None,
+        by_day: Vec::new(),
+        by_month_day: Vec::new(),
+        by_month: Vec::new(),
+    };
+    let mut frequency = None;
+
+    for part in value.split(';') {
+        let Some((key, value)) = part.split_once('=') else {
+            continue;
+        };
+        let list = || value.split(',').map(str::trim);
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => {
+                frequency = match value.to_ascii_uppercase().as_str() {
+                    "DAILY" => Some(Frequency::Daily),
+                    "WEEKLY" => Some(Frequency::Weekly),
+                    "MONTHLY" => Some(Frequency::Monthly),
+                    "YEARLY" => Some(Frequency::Yearly),
+                    _ => return None,
+                }
+            }
+            "INTERVAL" => rule.interval = value.parse().ok().filter(|i| *i > 0).unwrap_or(1),
+            "COUNT" => rule.count = value.parse().ok(),
+            "UNTIL" => rule.until = parse_date_value(value, None),
+            "BYDAY" => {
+                rule.by_day = list()
+                    .filter(|day| day.len() >= 2)
+                    .filter_map(|day| {
+                        let (ordinal, weekday) = day.split_at(day.len() - 2);
+                        let ordinal = if ordinal.is_empty() {
+                            0
+                        } else {
+                            ordinal.trim_start_matches('+').parse().ok()?
+                        };
+                        Some((ordinal, parse_weekday(&weekday.to_ascii_uppercase())?))
+                    })
+                    .collect()
+            }
+            "BYMONTHDAY" => rule.by_month_day = list().filter_map(|d| d.parse().ok()).collect(),
+            "BYMONTH" => rule.by_month = list().filter_map(|m| m.parse().ok()).collect(),
+            _ => {}
+        }
+    }
+
+    rule.frequency = frequency?;
+    Some(rule)
+}
+
+impl RecurrenceRule {
+    /// Candidate days of a month for MONTHLY and YEARLY rules
+    fn month_days(&self, year: i32, month: u32, default_day: u32) -> Vec<NaiveDate> {
+        if !self.by_day.is_empty() {
+            return self
+                .by_day
+                .iter()
+                .flat_map(|&(nth, weekday)| {
+                    if nth != 0 {
+                        return nth_weekday(year, month, nth, weekday).into_iter().collect();
+                    }
+                    (1..=5)
+                        .filter_map(|n| nth_weekday(year, month, n, weekday))
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+        }
+
+        if !self.by_month_day.is_empty() {
+            return self
+                .by_month_day
+                .iter()
+                .filter_map(|&day| {
+                    if day > 0 {
+                        NaiveDate::from_ymd_opt(year, month, day as u32)
+                    } else {
+                        let last = last_day_of_month(year, month)?;
+                        let date = last - Duration::days(-day as i64 - 1);
+                        (date.month() == month).then_some(date)
+                    }
+                })
+                .collect();
+        }
+
+        NaiveDate::from_ymd_opt(year, month, default_day)
+            .into_iter()
+            .collect()
+    }
+
+    /// Candidate days for the `period`-th interval after `dtstart`
+    fn period_days(&self, dtstart: NaiveDateTime, period: i64) -> Vec<NaiveDate> {
+        let step = period * self.interval;
+        let start = dtstart.date();
+        match self.frequency {
+            Frequency::Daily => {
+                let day = start + Duration::days(step);
+                let matches =
+                    self.by_day.is_empty() || self.by_day.iter().any(|(_, w)| *w == day.weekday());
+                matches.then_some(day).into_iter().collect()
+            }
+            Frequency::Weekly => {
+                let week_start = start
+                    - Duration::days(start.weekday().num_days_from_monday() as i64)
+                    + Duration::weeks(step);
+                if self.by_day.is_empty() {
+                    return vec![
+                        week_start + Duration::days(start.weekday().num_days_from_monday() as i64),
+                    ];
+                }
+                self.by_day
+                    .iter()
+                    .map(|(_, w)| week_start + Duration::days(w.num_days_from_monday() as i64))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let months = start.year() as i64 * 12 + start.month0() as i64 + step;
+                let (year, month) = (
+                    months.div_euclid(12) as i32,
+                    months.rem_euclid(12) as u32 + 1,
+                );
+                self.month_days(year, month, start.day())
+            }
+            Frequency::Yearly => {
+                let year = start.year() + step as i32;
+                if self.by_month.is_empty() {
+                    return self.month_days(year, start.month(), start.day());
+                }
+                self.by_month
+                    .iter()
+                    .flat_map(|&month| self.month_days(year, month, start.day()))
+                    .collect()
+            }
+        }
+    }
+
+    /// Local start times of occurrences up to `horizon`; `past_until` reports
+    /// whether a local time is after the rule's `UNTIL`
+    fn expand(
+        &self,
+        dtstart: NaiveDateTime,
+        horizon: NaiveDateTime,
+        past_until: impl Fn(NaiveDateTime) -> bool,
+    ) -> Vec<NaiveDateTime> {
+        let mut occurrences = Vec::new();
+
+        for period in 0..MAX_RECURRENCE_PERIODS {
+            let mut days = self.period_days(dtstart, period);
+            days.sort();
+            days.dedup();
+
+            for day in days {
+                let occurrence = day.and_time(dtstart.time());
+                if occurrence < dtstart {
+                    continue;
+                }
+                if occurrence > horizon || past_until(occurrence) {
+                    return occurrences;
+                }
+                occurrences.push(occurrence);
+                if self
+                    .count
+                    .is_some_and(|count| occurrences.len() >= count as usize)
+                {
+                    return occurrences;
+                }
+            }
+        }
+
+        occurrences
+    }
+}
+
+/// One `STANDARD` or `DAYLIGHT` block of a `VTIMEZONE`
+#[derive(Debug, Clone)]
+struct Observance {
+    onset: NaiveDateTime,
+    offset_from: i32,
+    offset_to: i32,
+    /// Yearly `(month, nth, weekday)` transition and its optional end
+    rule: Option<(u32, i32, Weekday)>,
+    until: Option<NaiveDateTime>,
+}
+
+impl Observance {
+    fn parse(component: &Component) -> Option<Self> {
+        let onset = parse_date_value(&component.property("DTSTART")?.value, None)?.local;
+        let offset_to = parse_utc_offset(&component.property("TZOFFSETTO")?.value)?;
+        let offset_from = component
+            .property("TZOFFSETFROM")
+            .and_then(|p| parse_utc_offset(&p.value))
+            .unwrap_or(offset_to);
+
+        let rrule = component
+            .property("RRULE")
+            .and_then(|p| parse_rrule(&p.value))
+            .filter(|r| r.frequency == Frequency::Yearly);
+        let rule = rrule.as_ref().and_then(|r| {
+            let month = r.by_month.first().copied().unwrap_or(onset.month());
+            let (nth, weekday) = r.by_day.first().copied()?;
+            Some((month, nth, weekday))
+        });
+        let until = rrule.and_then(|r| r.until).map(|u| u.local);
+
+        Some(Self {
+            onset,
+            offset_from,
+            offset_to,
+            rule,
+            until,
+        })
+    }
+
+    /// Latest transition into this observance at or before `local`
+    fn last_onset(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        let Some((month, nth, weekday)) = self.rule else {
+            return Some(self.onset).filter(|onset| *onset <= local);
+        };
+
+        [local.year(), local.year() - 1]
+            .into_iter()
+            .filter_map(|year| nth_weekday(year, month, nth, weekday))
+            .map(|day| day.and_time(self.onset.time()))
+            .find(|onset| *onset <= local)
+            .filter(|onset| *onset >= self.onset)
+            .filter(|onset| self.until.is_none_or(|until| *onset <= until))
+    }
+}
+
+/// `VTIMEZONE` definitions by `TZID`
+#[derive(Debug, Default)]
+struct Timezones {
+    zones: HashMap<String, Vec<Observance>>,
+}
+
+impl Timezones {
+    fn parse(components: &[Component]) -> Self {
+        let zones = components
+            .iter()
+            .filter(|c| c.name == "VTIMEZONE")
+            .filter_map(|zone| {
+                let tzid = zone.property("TZID")?.value.trim().to_string();
+                let observances = zone
+                    .children
+                    .iter()
+                    .filter(|c| c.name == "STANDARD" || c.name == "DAYLIGHT")
+                    .filter_map(Observance::parse)
+                    .collect();
+                Some((tzid, observances))
+            })
+            .collect();
+        Self { zones }
+    }
+
+    /// Offset from UTC in seconds at a local time; unknown zones are UTC
+    fn offset_seconds(&self, tzid: &str, local: NaiveDateTime) -> i32 {
+        let Some(observances) = self.zones.get(tzid) else {
+            return 0;
+        };
+
+        observances
+            .iter()
+            .filter_map(|o| o.last_onset(local).map(|onset| (onset, o.offset_to)))
+            .max_by_key(|(onset, _)| *onset)
+            .map(|(_, offset)| offset)
+            .or_else(|| {
+                observances
+                    .iter()
+                    .min_by_key(|o| o.onset)
+                    .map(|o| o.offset_from)
+            })
+            .unwrap_or(0)
+    }
+
+    fn to_utc_ms(&self, local: NaiveDateTime, zone: &Zone) -> i64 {
+        let offset = match zone {
+            Zone::Named(tzid) => self.offset_seconds(tzid, local),
+            Zone::Utc | Zone::Floating => 0,
+        };
+        (local - Duration::seconds(offset as i64))
+            .and_utc()
+            .timestamp_millis()
+    }
+}
+
+/// A `VEVENT` before recurrence expansion
+#[derive(Debug)]
+struct VEvent {
+    uid: String,
+    summary: String,
+    description: Option<String>,
+    location: Option<String>,
+    url: Option<String>,
+    organizer: Option<String>,
+    attendees: Vec<String>,
+    cancelled: bool,
+    start: DateValue,
+    end: Option<DateValue>,
+    duration: Option<Duration>,
+    rrule: Option<RecurrenceRule>,
+    exdates: Vec<DateValue>,
+    recurrence_id: Option<DateValue>,
+}
+
+/// Display name of an `ORGANIZER` or `ATTENDEE`: `CN`, else the address
+fn person(property: &Property) -> Option<String> {
+    property
+        .param("CN")
+        .map(String::from)
+        .or_else(|| {
+            let value = property.value.trim();
+            let address = if value.len() > 7 && value[..7].eq_ignore_ascii_case("mailto:") {
+                &value[7..]
+            } else {
+                value
+            };
+            Some(address.to_string())
+        })
+        .filter(|name| !name.is_empty())
+}
+
+impl VEvent {
+    fn parse(component: &Component) -> Option<Self> {
+        let start = component
+            .property("DTSTART")
+            .and_then(|p| property_dates(p).into_iter().next())?;
+
+        Some(Self {
+            uid: component.text("UID")?,
+            summary: component
+                .text("SUMMARY")
+                .unwrap_or_else(|| "(untitled)".to_string()),
+            description: component.text("DESCRIPTION"),
+            location: component.text("LOCATION"),
+            url: component.text("URL"),
+            organizer: component.property("ORGANIZER").and_then(person),
+            attendees: component.all("ATTENDEE").filter_map(person).collect(),
+            cancelled: component
+                .property("STATUS")
+                .is_some_and(|p| p.value.trim().eq_ignore_ascii_case("CANCELLED")),
+            end: component
+                .property("DTEND")
+                .and_then(|p| property_dates(p).into_iter().next()),
+            duration: component
+                .property("DURATION")
+                .and_then(|p| parse_duration(&p.value)),
+            rrule: component
+                .property("RRULE")
+                .and_then(|p| parse_rrule(&p.value)),
+            exdates: component.all("EXDATE").flat_map(property_dates).collect(),
+            recurrence_id: component
+                .property("RECURRENCE-ID")
+                .and_then(|p| property_dates(p).into_iter().next()),
+            start,
+        })
+    }
+}
+
+/// A parsed `VCALENDAR`
+pub struct Calendar {
+    /// `X-WR-CALNAME`, when the export sets one
+    pub name: Option<String>,
+    events: Vec<VEvent>,
+    timezones: Timezones,
+}
+
+/// `YYYYMMDDTHHMMSSZ` key identifying an instance of a recurring event
+fn instance_key(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .map(|dt| dt.format("%Y%m%dT%H%M%SZ").to_string())
+        .unwrap_or_else(|| ms.to_string())
+}
+
+impl Calendar {
+    pub fn parse(text: &str) -> Result<Self, CalendarError> {
+        let components = parse_components(text)?;
+        let calendar = components
+            .into_iter()
+            .find(|c| c.name == "VCALENDAR")
+            .ok_or_else(|| CalendarError::Parse("no VCALENDAR found".into()))?;
+
+        Ok(Self {
+            name: calendar.text("X-WR-CALNAME"),
+            events: calendar
+                .children
+                .iter()
+                .filter(|c| c.name == "VEVENT")
+                .filter_map(VEvent::parse)
+                .collect(),
+            timezones: Timezones::parse(&calendar.children),
+        })
+    }
+
+    pub fn event_count(&self) -> usize {
+        self.events.len()
+    }
+
+    fn start_ms(&self, value: &DateValue) -> i64 {
+        self.timezones.to_utc_ms(value.local, &value.zone)
+    }
+
+    /// Length of an event: `DTEND`, else `DURATION`, else a day for all-day events
+    fn duration_ms(&self, event: &VEvent) -> i64 {
+        if let Some(end) = &event.end {
+            return (self.start_ms(end) - self.start_ms(&event.start)).max(0);
+        }
+        match event.duration {
+            Some(duration) => duration.num_milliseconds().max(0),
+            None if event.start.all_day => 86_400_000,
+            None => 0,
+        }
+    }
+
+    fn occurrence(
+        &self,
+        event: &VEvent,
+        id: String,
+        series_id: Option<String>,
+        start_ms: i64,
+    ) -> CalendarEvent {
+        CalendarEvent {
+            id,
+            series_id,
+            title: event.summary.clone(),
+            description: event.description.clone(),
+            location: event.location.clone(),
+            url: event.url.clone(),
+            organizer: event.organizer.clone(),
+            attendees: event.attendees.clone(),
+            start_ms,
+            end_ms: start_ms + self.duration_ms(event),
+            all_day: event.start.all_day,
+            cancelled: event.cancelled,
+        }
+    }
+
+    /// Occurrences starting within `[start_ms, end_ms)`, in start order.
+    /// Cancelled occurrences are included so callers can remove them.
+    pub fn events_between(&self, start_ms: i64, end_ms: i64) -> Vec<CalendarEvent> {
+        let mut overrides: HashMap<(&str, i64), &VEvent> = self
+            .events
+            .iter()
+            .filter_map(|e| {
+                let recurrence_id = e.recurrence_id.as_ref()?;
+                Some(((e.uid.as_str(), self.start_ms(recurrence_id)), e))
+            })
+            .collect();
+
+        // Expand in local time so DST changes keep the wall-clock time
+        let horizon = chrono::DateTime::from_timestamp_millis(end_ms)
+            .map(|dt| dt.naive_utc() + Duration::days(1))
+            .unwrap_or(NaiveDateTime::MAX);
+
+        let mut occurrences = Vec::new();
+        for event in self.events.iter().filter(|e| e.recurrence_id.is_none()) {
+            let Some(rule) = &event.rrule else {
+                occurrences.push(self.occurrence(
+                    event,
+                    event.uid.clone(),
+                    None,
+                    self.start_ms(&event.start),
+                ));
+                continue;
+            };
+
+            let until_ms = rule.until.as_ref().map(|u| self.start_ms(u));
+            let zone = &event.start.zone;
+            let past_until =
+                |local| until_ms.is_some_and(|until| self.timezones.to_utc_ms(local, zone) > until);
+            let excluded: Vec<i64> = event.exdates.iter().map(|d| self.start_ms(d)).collect();
+
+            for local in rule.expand(event.start.local, horizon, past_until) {
+                let original_ms = self.timezones.to_utc_ms(local, zone);
+                if excluded.contains(&original_ms) {
+                    continue;
+                }
+
+                let id = format!("{}/{}", event.uid, instance_key(original_ms));
+                let series_id = Some(event.uid.clone());
+                occurrences.push(match overrides.remove(&(event.uid.as_str(), original_ms)) {
+                    Some(moved) => {
+                        self.occurrence(moved, id, series_id, self.start_ms(&moved.start))
+                    }
+                    None => self.occurrence(event, id, series_id, original_ms),
+                });
+            }
+        }
+
+        // Overrides whose original instance was not expanded, e.g. moved into the window
+        for ((uid, original_ms), moved) in overrides {
+            occurrences.push(self.occurrence(
+                moved,
+                format!("{}/{}", uid, instance_key(original_ms)),
+                Some(uid.to_string()),
+                self.start_ms(&moved.start),
+            ));
+        }
+
+        occurrences.retain(|e| e.start_ms >= start_ms && e.start_ms < end_ms);
+        occurrences.sort_by(|a, b| a.start_ms.cmp(&b.start_ms).then(a.id.cmp(&b.id)));
+        occurrences
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(s: &str) -> i64 {
+        chrono::DateTime::parse_from_rfc3339(s)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    const NEW_YORK: &str = "BEGIN:VTIMEZONE\r
+TZID:America/New_York\r
+BEGIN:DAYLIGHT\r
+TZOFFSETFROM:-0500\r
+TZOFFSETTO:-0400\r
+DTSTART:20070311T020000\r
+RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU\r
+END:DAYLIGHT\r
+BEGIN:STANDARD\r
+TZOFFSETFROM:-0400\r
+TZOFFSETTO:-0500\r
+DTSTART:20071104T020000\r
+RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU\r
+END:STANDARD\r
+END:VTIMEZONE\r
+";
+
+    fn calendar(events: &str) -> Calendar {
+        Calendar::parse(&format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nX-WR-CALNAME:Work\r\n{}{}END:VCALENDAR\r\n",
+            NEW_YORK, events
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_unfolds_and_unescapes_properties() {
+        let calendar = calendar(
+            "BEGIN:VEVENT\r
+UID:abc@example.com\r
+DTSTART:20240115T150000Z\r
+DTEND:20240115T160000Z\r
+SUMMARY:Architecture review\\, Q1\r
+DESCRIPTION:Agenda:\\n1. Storage\r
+  layer\r
+ORGANIZER;CN=\"Lee, Sam\":mailto:sam@example.com\r
+ATTENDEE;PARTSTAT=ACCEPTED:MAILTO:ana@example.com\r
+LOCATION:Room 4\r
+END:VEVENT\r
+",
+        );
+        assert_eq!(calendar.name.as_deref(), Some("Work"));
+
+        let events =
+            calendar.events_between(ms("2024-01-15T00:00:00Z"), ms("2024-01-16T00:00:00Z"));
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.id, "abc@example.com");
+        assert_eq!(event.title, "Architecture review, Q1");
+        assert_eq!(
+            event.description.as_deref(),
+            Some("Agenda:\n1. Storage layer")
+        );
+        assert_eq!(event.organizer.as_deref(), Some("Lee, Sam"));
+        assert_eq!(event.attendees, vec!["ana@example.com"]);
+        assert_eq!(event.end_ms - event.start_ms, 3_600_000);
+    }
+
+    #[test]
+    fn test_timezone_offsets_follow_dst() {
+        let calendar = calendar(
+            "BEGIN:VEVENT\r
+UID:winter\r
+DTSTART;TZID=America/New_York:20240115T090000\r
+DURATION:PT30M\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:summer\r
+DTSTART;TZID=America/New_York:20240715T090000\r
+DURATION:PT30M\r
+END:VEVENT\r
+",
+        );
+        let events =
+            calendar.events_between(ms("2024-01-01T00:00:00Z"), ms("2025-01-01T00:00:00Z"));
+        assert_eq!(events[0].start_ms, ms("2024-01-15T14:00:00Z"));
+        assert_eq!(events[1].start_ms, ms("2024-07-15T13:00:00Z"));
+        assert_eq!(events[1].end_ms - events[1].start_ms, 1_800_000);
+    }
+
+    #[test]
+    fn test_weekly_recurrence_with_exdate_and_override() {
+        let calendar = calendar(
+            "BEGIN:VEVENT\r
+UID:standup\r
+DTSTART;TZID=America/New_York:20240304T093000\r
+DTEND;TZID=America/New_York:20240304T094500\r
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE;COUNT=6\r
+EXDATE;TZID=America/New_York:20240306T093000\r
+SUMMARY:Standup\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:standup\r
+RECURRENCE-ID;TZID=America/New_York:20240313T093000\r
+DTSTART;TZID=America/New_York:20240313T110000\r
+DTEND;TZID=America/New_York:20240313T111500\r
+SUMMARY:Standup (moved)\r
+END:VEVENT\r
+",
+        );
+        let events =
+            calendar.events_between(ms("2024-03-01T00:00:00Z"), ms("2024-04-01T00:00:00Z"));
+        let starts: Vec<i64> = events.iter().map(|e| e.start_ms).collect();
+
+        // DST starts on March 10: 09:30 local is 14:30 UTC before, 13:30 UTC after
+        assert_eq!(
+            starts,
+            vec![
+                ms("2024-03-04T14:30:00Z"),
+                ms("2024-03-11T13:30:00Z"),
+                ms("2024-03-13T15:00:00Z"),
+                ms("2024-03-18T13:30:00Z"),
+                ms("2024-03-20T13:30:00Z"),
+            ]
+        );
+        assert_eq!(events[2].title, "Standup (moved)");
+        assert_eq!(events[2].id, "standup/20240313T133000Z");
+        assert!(events
+            .iter()
+            .all(|e| e.series_id.as_deref() == Some("standup")));
+    }
+
+    #[test]
+    fn test_monthly_rule_and_all_day_events() {
+        let calendar = calendar(
+            "BEGIN:VEVENT\r
+UID:review\r
+DTSTART:20240105T160000Z\r
+RRULE:FREQ=MONTHLY;BYDAY=-1FR;UNTIL=20240401T000000Z\r
+STATUS:CANCELLED\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:offsite\r
+DTSTART;VALUE=DATE:20240320\r
+END:VEVENT\r
+",
+        );
+        let events =
+            calendar.events_between(ms("2024-01-01T00:00:00Z"), ms("2024-12-01T00:00:00Z"));
+        let reviews: Vec<&CalendarEvent> =
+            events.iter().filter(|e| e.series_id.is_some()).collect();
+        assert_eq!(reviews.len(), 3);
+        assert_eq!(reviews[0].start_ms, ms("2024-01-26T16:00:00Z"));
+        assert!(reviews.iter().all(|e| e.cancelled));
+
+        let offsite = events.iter().find(|e| e.id == "offsite").unwrap();
+        assert!(offsite.all_day);
+        assert_eq!(offsite.start_ms, ms("2024-03-20T00:00:00Z"));
+        assert_eq!(offsite.end_ms - offsite.start_ms, 86_400_000);
+    }
+}
@@ -0,0 +1,14 @@
+//! Calendar integration module
+//!
+//! This module reads ICS feeds and files, expands recurring events and stores
+//! meetings as context for AI analysis and digests.
+
+mod connector;
+mod ics;
+mod sync;
+mod types;
+
+pub use connector::CalendarConnector;
+pub use ics::Calendar;
+pub use sync::CalendarSyncService;
+pub use types::{CalendarError, CalendarEvent, CalendarFeed};
@@ -0,0 +1,207 @@
+//! Calendar synchronization service
+
+use reqwest::Client;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use super::ics::Calendar;
+use super::types::{CalendarError, CalendarEvent, CalendarFeed};
+use crate::crypto::CryptoService;
+use crate::db::Database;
+use crate::sync::SyncResult;
+
+/// `webcal://` is a hint for calendar apps; the feed itself is served over HTTPS
+pub(super) fn normalize_feed_url(url: &str) -> String {
+    let url = url.trim();
+    match url.get(..9) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("webcal://") => {
+            format!("https://{}", &url[9..])
+        }
+        _ => url.to_string(),
+    }
+}
+
+fn event_text(event: &CalendarEvent) -> String {
+    let mut text = event.title.clone();
+    if let Some(location) = &event.location {
+        text.push_str(&format!("\nLocation: {}", location));
+    }
+    if !event.attendees.is_empty() {
+        text.push_str(&format!("\nAttendees: {}", event.attendees.join(", ")));
+    }
+    if let Some(description) = &event.description {
+        text.push_str(&format!("\n\n{}", description));
+    }
+    text
+}
+
+/// Encrypt and upsert an event; `created_at` is the event start so day-window
+/// queries find meetings on the day they happen
+async fn store_event(
+    db: &Database,
+    crypto: &CryptoService,
+    calendar: &str,
+    event: &CalendarEvent,
+) -> Result<(), CalendarError> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let encrypted_body = crypto
+        .encrypt_string(&event_text(event))
+        .map_err(|e| CalendarError::Crypto(e.to_string()))?;
+    let metadata = serde_json::json!({
+        "start": event.start_ms,
+        "end": event.end_ms,
+        "all_day": event.all_day,
+        "location": event.location,
+        "organizer": event.organizer,
+        "attendees": event.attendees,
+    })
+    .to_string();
+
+    sqlx::query(
+        "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, parent_id, created_at, updated_at, synced_at, metadata)
+         VALUES (?, 'calendar', ?, ?, 'event', ?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(source, source_id) DO UPDATE SET source_url = ?, title = ?, body = ?, author_id = ?, channel_or_project = ?, created_at = ?, updated_at = ?, synced_at = ?, metadata = ?"
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(&event.id)
+    .bind(&event.url)
+    .bind(&event.title)
+    .bind(&encrypted_body)
+    .bind(&event.organizer)
+    .bind(calendar)
+    .bind(&event.series_id)
+    .bind(event.start_ms)
+    .bind(event.start_ms)
+    .bind(now)
+    .bind(&metadata)
+    .bind(&event.url)
+    .bind(&event.title)
+    .bind(&encrypted_body)
+    .bind(&event.organizer)
+    .bind(calendar)
+    .bind(event.start_ms)
+    .bind(event.start_ms)
+    .bind(now)
+    .bind(&metadata)
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+pub struct CalendarSyncService {
+    http: Client,
+    db: Arc<Database>,
+    crypto: Arc<CryptoService>,
+}
+
+impl CalendarSyncService {
+    pub fn new(db: Arc<Database>, crypto: Arc<CryptoService>) -> Self {
+        Self {
+            http: Client::new(),
+            db,
+            crypto,
+        }
+    }
+
+    /// Download or read the feed and parse it
+    pub async fn load(&self, feed: &CalendarFeed) -> Result<Calendar, CalendarError> {
+        let text = match (&feed.url, &feed.path) {
+            (Some(url), _) => {
+                self.http
+                    .get(normalize_feed_url(url))
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .text()
+                    .await?
+            }
+            (None, Some(path)) => std::fs::read_to_string(path)?,
+            (None, None) => {
+                return Err(CalendarError::Parse(
+                    "no calendar URL or file configured".into(),
+                ))
+            }
+        };
+
+        Calendar::parse(&text)
+    }
+
+    /// Store events starting within `[start_ms, end_ms)`.
+    ///
+    /// The feed is a full snapshot, so stored events in the window that were
+    /// cancelled or removed from it are deleted.
+    pub async fn sync_window(
+        &self,
+        feed: &CalendarFeed,
+        start_ms: i64,
+        end_ms: i64,
+    ) -> Result<SyncResult, CalendarError> {
+        let calendar = self.load(feed).await?;
+        let mut items_synced = 0;
+        let mut errors = Vec::new();
+        let mut current = HashSet::new();
+
+        for event in calendar.events_between(start_ms, end_ms) {
+            if event.cancelled {
+                continue;
+            }
+            match store_event(&self.db, &self.crypto, &feed.name, &event).await {
+                Ok(()) => items_synced += 1,
+                Err(e) => errors.push(format!("{}: {}", event.title, e)),
+            }
+            current.insert(event.id);
+        }
+
+        let stored: Vec<(String,)> = sqlx::query_as(
+            "SELECT source_id FROM content_items
+             WHERE source = 'calendar' AND created_at >= ? AND created_at < ?",
+        )
+        .bind(start_ms)
+        .bind(end_ms)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut removed = 0;
+        for (source_id,) in stored {
+            if current.contains(&source_id) {
+                continue;
+            }
+            sqlx::query("DELETE FROM content_items WHERE source = 'calendar' AND source_id = ?")
+                .bind(&source_id)
+                .execute(self.db.pool())
+                .await?;
+            removed += 1;
+        }
+
+        tracing::info!(
+            "Synced {} events from {} ({} removed)",
+            items_synced,
+            feed.name,
+            removed
+        );
+
+        Ok(SyncResult {
+            source: "calendar".to_string(),
+            items_synced,
+            errors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_feed_url() {
+        assert_eq!(
+            normalize_feed_url("webcal://calendar.example.com/private/basic.ics"),
+            "https://calendar.example.com/private/basic.ics"
+        );
+        assert_eq!(
+            normalize_feed_url(" https://example.com/cal.ics "),
+            "https://example.com/cal.ics"
+        );
+    }
+}
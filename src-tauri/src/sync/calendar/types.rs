@@ -0,0 +1,57 @@
+//! Calendar data types and error definitions
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CalendarError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid calendar: {0}")]
+    Parse(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Crypto error: {0}")]
+    Crypto(String),
+}
+
+/// Where the calendar is read from; stored encrypted since subscription URLs
+/// usually embed a private token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarFeed {
+    /// `http(s)://` or `webcal://` subscription URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Local `.ics` file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Used as `channel_or_project` for stored events
+    pub name: String,
+}
+
+/// One occurrence of an event, with recurrences already expanded
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    /// `UID`, or `UID/<original start>` for an instance of a recurring event
+    pub id: String,
+    /// `UID` of the recurring series this occurrence belongs to
+    pub series_id: Option<String>,
+    pub title: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub url: Option<String>,
+    pub organizer: Option<String>,
+    pub attendees: Vec<String>,
+    /// Epoch milliseconds; all-day events start at UTC midnight
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub all_day: bool,
+    pub cancelled: bool,
+}
@@ -0,0 +1,289 @@
+//! Per-source circuit breakers for background sync
+//!
+//! Each failed sync pushes a source's next attempt back exponentially, with
+//! jitter so sources that failed together do not retry together. After
+//! [`FAILURE_THRESHOLD`] consecutive failures the circuit opens: scheduled runs
+//! skip the source entirely until a half-open probe is due, and a single
+//! successful sync closes it again. User-initiated syncs are never blocked, but
+//! their results are recorded, so "Sync now" doubles as a manual probe.
+//!
+//! A source whose host could not be reached is paused separately: the failure
+//! says nothing about the source, so it is not counted, and the background
+//! sync retries the source until it connects again.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Circuit key for the Gemini batch pass that follows each sync
+pub const AI_CIRCUIT: &str = "ai";
+
+/// Consecutive failures before the circuit opens
+pub const FAILURE_THRESHOLD: u32 = 3;
+
+const BASE_BACKOFF: Duration = Duration::from_secs(60);
+const MAX_BACKOFF: Duration = Duration::from_secs(2 * 60 * 60);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Syncing normally, possibly backing off after a recent failure
+    #[default]
+    Closed,
+    /// Paused after repeated failures until `retry_at`
+    Open,
+    /// A probe sync is running to test whether the source has recovered
+    HalfOpen,
+}
+
+/// Health of one source as seen by the background sync
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceCircuit {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Epoch ms before which scheduled runs skip the source
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_at: Option<i64>,
+    /// The last sync could not reach the source; scheduled runs skip it until
+    /// a retry connects
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unreachable: bool,
+}
+
+impl SourceCircuit {
+    /// Whether scheduled runs are currently skipping the source
+    pub fn is_paused(&self, now: i64) -> bool {
+        self.unreachable || self.retry_at.is_some_and(|at| now < at)
+    }
+}
+
+/// Delay before the next attempt after `failures` consecutive failures, scaled
+/// by `jitter` (expected in `0.8..1.2`)
+pub fn backoff_delay(failures: u32, jitter: f64) -> Duration {
+    let exponent = failures.saturating_sub(1).min(16);
+    let delay = BASE_BACKOFF.saturating_mul(1 << exponent).min(MAX_BACKOFF);
+    delay.mul_f64(jitter)
+}
+
+/// Circuit state for every source, shared by all sync paths
+#[derive(Debug, Default)]
+pub struct CircuitBreakers {
+    circuits: Mutex<HashMap<String, SourceCircuit>>,
+    /// Sources whose circuit opened since the last `take_opened`
+    opened: Mutex<Vec<String>>,
+}
+
+impl CircuitBreakers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a scheduled run may sync `source` now. An open circuit whose
+    /// retry time has passed turns half-open and lets this one attempt through
+    /// as the probe; the retry time moves on so a lost probe is retried later.
+    pub fn allow(&self, source: &str, now: i64) -> bool {
+        let mut circuits = self.circuits.lock().unwrap();
+        let Some(circuit) = circuits.get_mut(source) else {
+            return true;
+        };
+
+        if circuit.is_paused(now) {
+            return false;
+        }
+
+        if circuit.state != CircuitState::Closed {
+            circuit.state = CircuitState::HalfOpen;
+            circuit.retry_at = Some(now + next_delay_ms(circuit.consecutive_failures));
+            tracing::info!(
+                "Probing {} after {} failures",
+                source,
+                circuit.consecutive_failures
+            );
+        }
+        true
+    }
+
+    pub fn record_success(&self, source: &str) {
+        let mut circuits = self.circuits.lock().unwrap();
+        if let Some(circuit) = circuits.remove(source) {
+            if circuit.state != CircuitState::Closed {
+                tracing::info!("{} recovered, closing circuit", source);
+            }
+        }
+    }
+
+    /// Record a failed sync; returns `true` when this failure opened the circuit
+    pub fn record_failure(&self, source: &str, error: &str, now: i64) -> bool {
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(source.to_string()).or_default();
+        let was_open = circuit.state != CircuitState::Closed;
+
+        circuit.consecutive_failures += 1;
+        circuit.unreachable = false;
+        circuit.last_error = Some(error.to_string());
+        circuit.retry_at = Some(now + next_delay_ms(circuit.consecutive_failures));
+        if circuit.consecutive_failures >= FAILURE_THRESHOLD {
+            circuit.state = CircuitState::Open;
+        }
+
+        let opened = !was_open && circuit.state == CircuitState::Open;
+        if opened {
+            tracing::warn!(
+                "Pausing {} after {} consecutive failures: {}",
+                source,
+                circuit.consecutive_failures,
+                error
+            );
+            self.opened.lock().unwrap().push(source.to_string());
+        }
+        opened
+    }
+
+    /// Record a sync that could not reach `source`. It is paused until a sync
+    /// succeeds or fails with an API error; its failure count is left alone.
+    pub fn record_unreachable(&self, source: &str, error: &str) {
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(source.to_string()).or_default();
+        if !circuit.unreachable {
+            tracing::warn!(
+                "Cannot reach {}, pausing it until it connects: {}",
+                source,
+                error
+            );
+        }
+        circuit.unreachable = true;
+        circuit.last_error = Some(error.to_string());
+    }
+
+    /// Sources paused because they could not be reached
+    pub fn unreachable(&self) -> Vec<String> {
+        let circuits = self.circuits.lock().unwrap();
+        let mut sources: Vec<_> = circuits
+            .iter()
+            .filter(|(_, circuit)| circuit.unreachable)
+            .map(|(source, _)| source.clone())
+            .collect();
+        sources.sort();
+        sources
+    }
+
+    /// Sources whose circuit opened since the last call, to notify about once
+    pub fn take_opened(&self) -> Vec<(String, SourceCircuit)> {
+        let opened = std::mem::take(&mut *self.opened.lock().unwrap());
+        opened
+            .into_iter()
+            .map(|source| {
+                let circuit = self.get(&source);
+                (source, circuit)
+            })
+            .collect()
+    }
+
+    /// Current state of a source; closed with no failures if never seen
+    pub fn get(&self, source: &str) -> SourceCircuit {
+        self.circuits
+            .lock()
+            .unwrap()
+            .get(source)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+fn next_delay_ms(failures: u32) -> i64 {
+    let jitter = rand::thread_rng().gen_range(0.8..1.2);
+    backoff_delay(failures, jitter).as_millis() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay() {
+        assert_eq!(backoff_delay(1, 1.0), Duration::from_secs(60));
+        assert_eq!(backoff_delay(2, 1.0), Duration::from_secs(120));
+        assert_eq!(backoff_delay(4, 1.0), Duration::from_secs(480));
+        assert_eq!(backoff_delay(40, 1.0), MAX_BACKOFF);
+        assert_eq!(backoff_delay(1, 0.8), Duration::from_secs(48));
+    }
+
+    #[test]
+    fn test_circuit_opens_after_threshold() {
+        let breakers = CircuitBreakers::new();
+        let mut now = 0;
+
+        for failure in 1..FAILURE_THRESHOLD {
+            assert!(breakers.allow("slack", now));
+            assert!(!breakers.record_failure("slack", "invalid_auth", now));
+            assert_eq!(breakers.get("slack").consecutive_failures, failure);
+            assert!(!breakers.allow("slack", now + 1));
+            now = breakers.get("slack").retry_at.unwrap();
+        }
+
+        assert!(breakers.allow("slack", now));
+        assert!(breakers.record_failure("slack", "invalid_auth", now));
+        let circuit = breakers.get("slack");
+        assert_eq!(circuit.state, CircuitState::Open);
+        assert_eq!(circuit.last_error.as_deref(), Some("invalid_auth"));
+        assert!(circuit.is_paused(now));
+
+        let opened = breakers.take_opened();
+        assert_eq!(opened.len(), 1);
+        assert_eq!(opened[0].0, "slack");
+        assert!(breakers.take_opened().is_empty());
+    }
+
+    #[test]
+    fn test_half_open_probe() {
+        let breakers = CircuitBreakers::new();
+        for _ in 0..FAILURE_THRESHOLD {
+            breakers.record_failure("jira", "503", 0);
+        }
+
+        let retry_at = breakers.get("jira").retry_at.unwrap();
+        assert!(!breakers.allow("jira", retry_at - 1));
+        assert!(breakers.allow("jira", retry_at));
+        assert_eq!(breakers.get("jira").state, CircuitState::HalfOpen);
+        // Only one probe at a time
+        assert!(!breakers.allow("jira", retry_at));
+
+        // A failed probe reopens without notifying again
+        assert!(!breakers.record_failure("jira", "503", retry_at));
+        assert_eq!(breakers.get("jira").state, CircuitState::Open);
+
+        breakers.record_success("jira");
+        assert_eq!(breakers.get("jira"), SourceCircuit::default());
+        assert!(breakers.allow("jira", 0));
+    }
+
+    #[test]
+    fn test_unreachable_source() {
+        let breakers = CircuitBreakers::new();
+        breakers.record_failure("gitlab", "500", 0);
+        breakers.record_unreachable("gitlab", "connection timed out");
+        breakers.record_unreachable("gitlab", "connection timed out");
+
+        let circuit = breakers.get("gitlab");
+        assert!(circuit.unreachable);
+        assert_eq!(circuit.consecutive_failures, 1);
+        assert!(!breakers.allow("gitlab", i64::MAX));
+        assert_eq!(breakers.unreachable(), vec!["gitlab".to_string()]);
+        assert!(breakers.take_opened().is_empty());
+
+        // Reaching the source with an API error counts as a normal failure
+        breakers.record_failure("gitlab", "500", 0);
+        assert!(!breakers.get("gitlab").unreachable);
+        assert_eq!(breakers.get("gitlab").consecutive_failures, 2);
+
+        breakers.record_unreachable("gitlab", "connection timed out");
+        breakers.record_success("gitlab");
+        assert!(breakers.unreachable().is_empty());
+        assert!(breakers.allow("gitlab", 0));
+    }
+}
@@ -0,0 +1,80 @@
+//! Network connectivity detection
+//!
+//! A failed sync on its own does not say whether a service is down or cannot
+//! be reached. Each connector's errors are classified instead: a failed DNS
+//! lookup, connect or timeout means the source's host is unreachable, anything
+//! else is an API error. An unreachable source is paused without counting the
+//! failure against its circuit, retried every [`OFFLINE_POLL_INTERVAL`], and
+//! caught up once it connects again. Local sources never report unreachable,
+//! so they keep syncing on schedule.
+
+use std::time::Duration;
+
+/// How often sources that could not be reached are retried
+pub const OFFLINE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Whether `error`, or any error it wraps, is a failure to reach the host
+/// (DNS, connect or timeout) rather than an error reported by the service
+pub fn is_unreachable(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut current = Some(error);
+    while let Some(error) = current {
+        if let Some(e) = error.downcast_ref::<reqwest::Error>() {
+            if e.is_connect() || e.is_timeout() {
+                return true;
+            }
+        }
+        if let Some(e) = error.downcast_ref::<std::io::Error>() {
+            if is_unreachable_kind(e.kind()) {
+                return true;
+            }
+        }
+        current = error.source();
+    }
+    false
+}
+
+fn is_unreachable_kind(kind: std::io::ErrorKind) -> bool {
+    use std::io::ErrorKind;
+
+    matches!(
+        kind,
+        ErrorKind::TimedOut
+            | ErrorKind::ConnectionRefused
+            | ErrorKind::NotConnected
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NetworkUnreachable
+            | ErrorKind::HostUnreachable
+            | ErrorKind::NetworkDown
+            | ErrorKind::AddrNotAvailable
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::email::EmailError;
+    use std::io::{Error, ErrorKind};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_io_errors() {
+        let unreachable = EmailError::Io(Error::new(ErrorKind::HostUnreachable, "no route"));
+        assert!(is_unreachable(&unreachable));
+        assert!(is_unreachable(&Error::from(ErrorKind::TimedOut)));
+
+        let not_found = EmailError::Io(Error::from(ErrorKind::NotFound));
+        assert!(!is_unreachable(&not_found));
+        assert!(!is_unreachable(&Error::from(ErrorKind::PermissionDenied)));
+    }
+
+    #[tokio::test]
+    async fn test_reqwest_connect_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let error = reqwest::get(format!("http://{}", addr)).await.unwrap_err();
+        assert!(is_unreachable(&error));
+    }
+}
@@ -0,0 +1,969 @@
+//! Source connector abstraction
+//!
+//! Every data source implements [`SourceConnector`] and is registered in a
+//! [`SourceRegistry`]. The background loop, sync commands, backfill jobs and the
+//! settings UI iterate over the registry instead of naming sources directly.
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::sync::Mutex;
+
+use super::circuit::CircuitBreakers;
+use super::connectivity::is_unreachable;
+use super::estimate::{DryRun, SourceEstimate};
+use super::status;
+use super::SyncResult;
+use crate::ai::pipeline::CANCELLED;
+use crate::crypto::CryptoService;
+use crate::db::Database;
+use crate::pipeline::{CancellationToken, PipelineManager, PipelineTaskType, ProgressReporter};
+
+/// Look-back window for a resource's first incremental sync
+pub const INITIAL_SYNC_DAYS: i64 = 7;
+/// Furthest back an incremental sync catches up after a long gap
+pub const MAX_CATCH_UP_DAYS: i64 = 30;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Error, Debug)]
+pub enum ConnectorError {
+    #[error("{0} not connected")]
+    NotConnected(&'static str),
+
+    #[error("Unknown source: {0}")]
+    UnknownSource(String),
+
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+
+    #[error("Cancelled")]
+    Cancelled,
+
+    /// The source's host could not be reached; see [`super::connectivity`]
+    #[error("{0}")]
+    Unreachable(String),
+
+    #[error("{0}")]
+    Failed(String),
+}
+
+impl ConnectorError {
+    /// Wrap a client error, telling connection failures apart from API errors
+    pub fn classify<E: std::error::Error + 'static>(e: E) -> Self {
+        if is_unreachable(&e) {
+            Self::Unreachable(e.to_string())
+        } else {
+            Self::Failed(e.to_string())
+        }
+    }
+}
+
+impl From<String> for ConnectorError {
+    fn from(e: String) -> Self {
+        Self::Failed(e)
+    }
+}
+
+impl From<sqlx::Error> for ConnectorError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Failed(e.to_string())
+    }
+}
+
+/// Shared handles every connector needs
+#[derive(Clone)]
+pub struct ConnectorContext {
+    pub db: Arc<Database>,
+    pub crypto: Arc<CryptoService>,
+    /// Cancellation of the task this sync runs under
+    pub cancel: CancellationToken,
+    /// Progress of the task this sync runs under
+    pub progress: ProgressReporter,
+    /// Skip sources and scopes whose sync interval has not elapsed (scheduled runs)
+    pub due_only: bool,
+    /// Per-source failure tracking; scheduled runs also skip paused sources
+    pub circuits: Option<Arc<CircuitBreakers>>,
+}
+
+impl ConnectorContext {
+    pub fn new(db: Arc<Database>, crypto: Arc<CryptoService>) -> Self {
+        Self {
+            db,
+            crypto,
+            cancel: CancellationToken::new(),
+            progress: ProgressReporter::default(),
+            due_only: false,
+            circuits: None,
+        }
+    }
+
+    /// Only sync what is due under the configured sync intervals
+    pub fn due_only(mut self) -> Self {
+        self.due_only = true;
+        self
+    }
+
+    /// Whether a scope should be synced in this run
+    pub async fn is_scope_due(&self, source: &str, resource_type: &str, resource_id: &str) -> bool {
+        !self.due_only
+            || super::intervals::is_scope_due(&self.db, source, resource_type, resource_id).await
+    }
+
+    /// Record sync results against the given circuit breakers
+    pub fn with_circuits(mut self, circuits: Arc<CircuitBreakers>) -> Self {
+        self.circuits = Some(circuits);
+        self
+    }
+
+    /// Whether `key`'s circuit lets a sync through now; always true without breakers
+    pub fn circuit_allows(&self, key: &str) -> bool {
+        match &self.circuits {
+            Some(circuits) => circuits.allow(key, chrono::Utc::now().timestamp_millis()),
+            None => true,
+        }
+    }
+
+    /// Record the outcome of a sync of `key`; `error` is `None` on success
+    pub fn record_outcome(&self, key: &str, error: Option<&str>) {
+        let Some(circuits) = &self.circuits else {
+            return;
+        };
+        match error {
+            None => circuits.record_success(key),
+            Some(error) => {
+                circuits.record_failure(key, error, chrono::Utc::now().timestamp_millis());
+            }
+        }
+    }
+
+    /// Pause `key` until it can be reached again, without counting a failure
+    pub fn record_unreachable(&self, key: &str, error: &str) {
+        if let Some(circuits) = &self.circuits {
+            circuits.record_unreachable(key, error);
+        }
+    }
+
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    pub fn with_progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Run the sync of one resource, moving it to `syncing` and, if it fails, to
+    /// `error`. Success is recorded by `save_cursor` or `mark_complete`; a sync
+    /// interrupted by cancelling the task goes back to `pending`.
+    pub async fn track_resource<T, E: std::fmt::Display>(
+        &self,
+        source: &str,
+        resource_type: &str,
+        resource_id: &str,
+        sync: impl std::future::Future<Output = Result<T, E>>,
+    ) -> Result<T, E> {
+        status::mark_syncing(&self.db, source, resource_type, resource_id).await;
+        let result = sync.await;
+        if self.cancel.is_cancelled() {
+            status::mark_pending(&self.db, source, resource_type, &[resource_id]).await;
+        } else if let Err(e) = &result {
+            status::mark_failed(&self.db, source, resource_type, resource_id, &e.to_string()).await;
+        }
+        result
+    }
+
+    /// `Err(Cancelled)` once the task has been cancelled, for use at safe stopping points
+    pub fn check_cancelled(&self) -> Result<(), ConnectorError> {
+        if self.cancel.is_cancelled() {
+            Err(ConnectorError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A syncable unit within a source: a Slack channel, Jira project, Confluence space, ...
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceScope {
+    pub id: String,
+    pub name: String,
+    /// Matches `sync_state.resource_type`, e.g. `channel`, `project`, `space`
+    pub kind: String,
+    pub selected: bool,
+}
+
+/// Connection health reported to the settings UI and sync status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceHealth {
+    pub source: String,
+    pub connected: bool,
+    pub healthy: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_sync_at: Option<i64>,
+}
+
+/// Registry entry as listed in the settings UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceInfo {
+    pub id: String,
+    pub display_name: String,
+    pub description: String,
+    pub connected: bool,
+    /// Whether scopes can be chosen through `select_scopes`
+    pub scope_selection: bool,
+    /// Whether scopes can have their own sync interval
+    pub scope_intervals: bool,
+}
+
+/// An inclusive range of local calendar days to sync historically
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncRange {
+    pub start_date: chrono::NaiveDate,
+    pub end_date: chrono::NaiveDate,
+    /// Minutes west of UTC (JS `getTimezoneOffset` convention)
+    pub timezone_offset: i32,
+}
+
+impl SyncRange {
+    pub fn new(start_date: &str, end_date: &str, timezone_offset: i32) -> Result<Self, String> {
+        let parse = |s: &str| {
+            chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid date format: {}", e))
+        };
+        let (start_date, end_date) = (parse(start_date)?, parse(end_date)?);
+        if end_date < start_date {
+            return Err("End date must not be before start date".to_string());
+        }
+
+        Ok(Self {
+            start_date,
+            end_date,
+            timezone_offset,
+        })
+    }
+
+    /// A range covering a single local day
+    pub fn day(date: &str, timezone_offset: i32) -> Result<Self, String> {
+        Self::new(date, date, timezone_offset)
+    }
+
+    /// Every day in the range as `YYYY-MM-DD`, in order
+    pub fn dates(&self) -> Vec<String> {
+        self.start_date
+            .iter_days()
+            .take_while(|d| *d <= self.end_date)
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .collect()
+    }
+
+    /// UTC epoch-second bounds `[start, end)` of the local days in this range
+    pub fn utc_bounds(&self) -> (i64, i64) {
+        let offset = chrono::FixedOffset::west_opt(self.timezone_offset * 60)
+            .unwrap_or(chrono::FixedOffset::east_opt(0).unwrap());
+        let local_midnight = |date: chrono::NaiveDate| {
+            date.and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_local_timezone(offset)
+                .single()
+                .map(|dt| dt.timestamp())
+                .unwrap_or_else(|| date.and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp())
+        };
+
+        let end = self.end_date.succ_opt().unwrap_or(self.end_date);
+        (local_midnight(self.start_date), local_midnight(end))
+    }
+}
+
+/// A data source that can be connected, enumerated and synced.
+#[async_trait]
+pub trait SourceConnector: Send + Sync {
+    /// Stable identifier, also used as `content_items.source`
+    fn id(&self) -> &'static str;
+
+    fn display_name(&self) -> &'static str;
+
+    fn description(&self) -> &'static str;
+
+    /// Pipeline task shown while this source syncs
+    fn task_type(&self) -> PipelineTaskType;
+
+    /// Noun for synced items in progress messages, e.g. "messages"
+    fn item_label(&self) -> &'static str {
+        "items"
+    }
+
+    /// Store credentials from a source-specific JSON config and return
+    /// connection details for the UI
+    async fn connect(
+        &self,
+        ctx: &ConnectorContext,
+        config: serde_json::Value,
+    ) -> Result<serde_json::Value, ConnectorError>;
+
+    async fn is_connected(&self, ctx: &ConnectorContext) -> bool;
+
+    async fn list_scopes(&self, ctx: &ConnectorContext)
+        -> Result<Vec<SourceScope>, ConnectorError>;
+
+    /// Whether this source syncs a user-chosen subset of its scopes
+    fn supports_scope_selection(&self) -> bool {
+        false
+    }
+
+    /// Whether incremental syncs skip scopes whose own sync interval has not
+    /// elapsed; other sources only honour the source interval
+    fn supports_scope_intervals(&self) -> bool {
+        false
+    }
+
+    /// Replace the set of scopes to sync
+    async fn select_scopes(
+        &self,
+        _ctx: &ConnectorContext,
+        _scope_ids: Vec<String>,
+    ) -> Result<(), ConnectorError> {
+        Err(ConnectorError::InvalidConfig(format!(
+            "{} does not support scope selection",
+            self.display_name()
+        )))
+    }
+
+    /// Fetch everything new since the last sync
+    async fn sync_incremental(&self, ctx: &ConnectorContext) -> Result<SyncResult, ConnectorError>;
+
+    /// Fetch content for a historical range without moving incremental cursors
+    async fn sync_range(
+        &self,
+        ctx: &ConnectorContext,
+        range: &SyncRange,
+    ) -> Result<SyncResult, ConnectorError>;
+
+    /// Count what `sync_incremental` or `sync_range` would fetch without
+    /// storing anything; `None` for sources that cannot estimate their volume
+    async fn estimate(
+        &self,
+        _ctx: &ConnectorContext,
+        _dry_run: &DryRun,
+    ) -> Result<Option<SourceEstimate>, ConnectorError> {
+        Ok(None)
+    }
+
+    async fn health(&self, ctx: &ConnectorContext) -> SourceHealth {
+        let connected = self.is_connected(ctx).await;
+        SourceHealth {
+            source: self.id().to_string(),
+            connected,
+            healthy: connected,
+            message: None,
+            last_sync_at: last_sync_at(&ctx.db, self.id()).await,
+        }
+    }
+
+    /// Remove credentials, scope selections and sync state
+    async fn disconnect(&self, ctx: &ConnectorContext) -> Result<(), ConnectorError>;
+}
+
+/// The set of available source connectors
+#[derive(Default, Clone)]
+pub struct SourceRegistry {
+    connectors: Vec<Arc<dyn SourceConnector>>,
+}
+
+impl SourceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with every built-in source
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register(Arc::new(super::slack::SlackConnector));
+        registry.register(Arc::new(super::atlassian::JiraConnector));
+        registry.register(Arc::new(super::atlassian::ConfluenceConnector));
+        registry.register(Arc::new(super::github::GitHubConnector));
+        registry.register(Arc::new(super::gitlab::GitLabConnector));
+        registry.register(Arc::new(super::linear::LinearConnector));
+        registry.register(Arc::new(super::incident::IncidentConnector));
+        registry.register(Arc::new(super::email::EmailConnector));
+        registry.register(Arc::new(super::calendar::CalendarConnector));
+        registry.register(Arc::new(super::notes::NotesConnector));
+        registry.register(Arc::new(super::feeds::FeedConnector));
+        registry.register(Arc::new(super::webhook::WebhookConnector));
+        registry
+    }
+
+    /// Add a connector, replacing any existing one with the same id
+    pub fn register(&mut self, connector: Arc<dyn SourceConnector>) {
+        self.connectors.retain(|c| c.id() != connector.id());
+        self.connectors.push(connector);
+    }
+
+    pub fn get(&self, id: &str) -> Option<Arc<dyn SourceConnector>> {
+        self.connectors.iter().find(|c| c.id() == id).cloned()
+    }
+
+    pub fn require(&self, id: &str) -> Result<Arc<dyn SourceConnector>, ConnectorError> {
+        self.get(id)
+            .ok_or_else(|| ConnectorError::UnknownSource(id.to_string()))
+    }
+
+    pub fn all(&self) -> &[Arc<dyn SourceConnector>] {
+        &self.connectors
+    }
+
+    pub fn ids(&self) -> Vec<&'static str> {
+        self.connectors.iter().map(|c| c.id()).collect()
+    }
+
+    /// Connectors with stored credentials, optionally restricted to `only`
+    pub async fn connected(
+        &self,
+        ctx: &ConnectorContext,
+        only: Option<&[String]>,
+    ) -> Vec<Arc<dyn SourceConnector>> {
+        let mut connected = Vec::new();
+        for connector in &self.connectors {
+            if only.is_some_and(|ids| !ids.iter().any(|id| id == connector.id())) {
+                continue;
+            }
+            if connector.is_connected(ctx).await {
+                connected.push(connector.clone());
+            }
+        }
+        connected
+    }
+}
+
+/// Most recent successful sync of any resource of this source
+pub async fn last_sync_at(db: &Database, source: &str) -> Option<i64> {
+    sqlx::query_as::<_, (Option<i64>,)>("SELECT MAX(last_sync_at) FROM sync_state WHERE source = ?")
+        .bind(source)
+        .fetch_one(db.pool())
+        .await
+        .ok()
+        .and_then(|r| r.0)
+}
+
+/// Read a connector's incremental cursor from `sync_state`
+pub async fn load_cursor(
+    db: &Database,
+    source: &str,
+    resource_type: &str,
+    resource_id: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    let row: Option<(Option<String>,)> = sqlx::query_as(
+        "SELECT cursor FROM sync_state WHERE source = ? AND resource_type = ? AND resource_id = ?",
+    )
+    .bind(source)
+    .bind(resource_type)
+    .bind(resource_id)
+    .fetch_optional(db.pool())
+    .await?;
+
+    Ok(row.and_then(|r| r.0))
+}
+
+/// Record a completed incremental sync and its new cursor in `sync_state`,
+/// clearing any earlier error
+pub async fn save_cursor(
+    db: &Database,
+    source: &str,
+    resource_type: &str,
+    resource_id: &str,
+    cursor: &str,
+) -> Result<(), sqlx::Error> {
+    let now = chrono::Utc::now().timestamp_millis();
+
+    sqlx::query(
+        "INSERT INTO sync_state (id, source, resource_type, resource_id, last_sync_at, cursor, status, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, 'complete', ?)
+         ON CONFLICT(source, resource_type, resource_id)
+         DO UPDATE SET last_sync_at = ?, cursor = ?, status = 'complete', updated_at = ?,
+             error_message = NULL, error_at = NULL",
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(source)
+    .bind(resource_type)
+    .bind(resource_id)
+    .bind(now)
+    .bind(cursor)
+    .bind(now)
+    .bind(now)
+    .bind(cursor)
+    .bind(now)
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Remove all `sync_state` rows for a source
+pub async fn clear_sync_state(db: &Database, source: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM sync_state WHERE source = ?")
+        .bind(source)
+        .execute(db.pool())
+        .await?;
+    Ok(())
+}
+
+/// Forget the sync state and interval overrides of a source's scopes of one kind
+/// that are no longer selected. Their last sync times would otherwise keep the
+/// source due on every run, and a scope selected again starts with a fresh sync.
+pub async fn clear_deselected_scopes(
+    db: &Database,
+    source: &str,
+    resource_type: &str,
+    selected: &[String],
+) -> Result<(), sqlx::Error> {
+    let selected = serde_json::to_string(selected).unwrap_or_else(|_| "[]".to_string());
+
+    for table in ["sync_state", "sync_intervals"] {
+        sqlx::query(&format!(
+            "DELETE FROM {} WHERE source = ? AND resource_type = ?
+             AND resource_id NOT IN (SELECT value FROM json_each(?))",
+            table
+        ))
+        .bind(source)
+        .bind(resource_type)
+        .bind(&selected)
+        .execute(db.pool())
+        .await?;
+    }
+    Ok(())
+}
+
+/// Parse an RFC 3339 timestamp into epoch milliseconds, the unit Slack items
+/// are stored in, so day-window AI queries pick up items from every source
+pub fn parse_timestamp_ms(value: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.timestamp_millis())
+}
+
+/// Start of an incremental sync in epoch milliseconds, from an RFC 3339
+/// cursor. A first sync looks back `INITIAL_SYNC_DAYS`; a long-idle resource
+/// catches up at most `MAX_CATCH_UP_DAYS` and leaves older history to backfill.
+pub fn incremental_since(cursor: Option<&str>, now_ms: i64) -> i64 {
+    cursor
+        .and_then(parse_timestamp_ms)
+        .unwrap_or(now_ms - INITIAL_SYNC_DAYS * DAY_MS)
+        .max(now_ms - MAX_CATCH_UP_DAYS * DAY_MS)
+}
+
+/// Read a connector's encrypted settings from `credentials`
+pub async fn load_credentials<T: DeserializeOwned>(
+    ctx: &ConnectorContext,
+    id: &str,
+) -> Result<Option<T>, ConnectorError> {
+    let result: Option<(String,)> =
+        sqlx::query_as("SELECT encrypted_data FROM credentials WHERE id = ?")
+            .bind(id)
+            .fetch_optional(ctx.db.pool())
+            .await?;
+
+    let Some((encrypted,)) = result else {
+        return Ok(None);
+    };
+
+    let json = ctx
+        .crypto
+        .decrypt_string(&encrypted)
+        .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|e| ConnectorError::Failed(e.to_string()))
+}
+
+/// Encrypt and store a connector's settings in `credentials`, replacing any
+/// earlier ones
+pub async fn store_credentials<T: Serialize>(
+    ctx: &ConnectorContext,
+    id: &str,
+    value: &T,
+) -> Result<(), ConnectorError> {
+    let json = serde_json::to_string(value).map_err(|e| e.to_string())?;
+    let encrypted = ctx
+        .crypto
+        .encrypt_string(&json)
+        .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+    let now = chrono::Utc::now().timestamp();
+    sqlx::query(
+        "INSERT INTO credentials (id, service, encrypted_data, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT(id) DO UPDATE SET encrypted_data = ?, updated_at = ?",
+    )
+    .bind(id)
+    .bind(id)
+    .bind(&encrypted)
+    .bind(now)
+    .bind(now)
+    .bind(&encrypted)
+    .bind(now)
+    .execute(ctx.db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Read a JSON preference, falling back to the default when it is missing or
+/// unreadable
+pub async fn load_preference<T: DeserializeOwned + Default>(
+    db: &Database,
+    key: &str,
+) -> Result<T, ConnectorError> {
+    let result: Option<(String,)> = sqlx::query_as("SELECT value FROM preferences WHERE key = ?")
+        .bind(key)
+        .fetch_optional(db.pool())
+        .await?;
+
+    Ok(result
+        .and_then(|(json,)| serde_json::from_str(&json).ok())
+        .unwrap_or_default())
+}
+
+pub async fn save_preference<T: Serialize>(
+    db: &Database,
+    key: &str,
+    value: &T,
+) -> Result<(), ConnectorError> {
+    let json = serde_json::to_string(value).map_err(|e| e.to_string())?;
+    sqlx::query(
+        "INSERT INTO preferences (key, value) VALUES (?, ?)
+         ON CONFLICT(key) DO UPDATE SET value = ?",
+    )
+    .bind(key)
+    .bind(&json)
+    .bind(&json)
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Run one connector's sync as a pipeline task. A source that turns out not to
+/// be connected completes quietly with zero items instead of failing.
+///
+/// The task can be cancelled on its own or through `ctx.cancel`; connectors stop
+/// before saving a cursor, so a cancelled sync is simply picked up next time.
+pub async fn run_sync_task(
+    connector: &dyn SourceConnector,
+    ctx: &ConnectorContext,
+    pipeline: &Arc<Mutex<PipelineManager>>,
+    range: Option<&SyncRange>,
+) -> Result<SyncResult, ConnectorError> {
+    let name = connector.display_name();
+    let label = connector.item_label();
+    let day = range.map(|r| r.dates().join(", "));
+
+    let (task_id, cancel) = {
+        let pipeline = pipeline.lock().await;
+        let message = match &day {
+            Some(day) => format!("Syncing {} {} for {}...", name, label, day),
+            None => format!("Syncing {} {}...", name, label),
+        };
+        let task_id = pipeline
+            .start_child_task(connector.task_type(), message, &ctx.cancel)
+            .await;
+        let cancel = pipeline.cancellation_token(&task_id).await;
+        (task_id, cancel)
+    };
+
+    let ctx = ctx
+        .clone()
+        .with_cancellation(cancel)
+        .with_progress(ProgressReporter::new(pipeline.clone(), &task_id));
+    let result = match range {
+        Some(range) => connector.sync_range(&ctx, range).await,
+        None => connector.sync_incremental(&ctx).await,
+    };
+
+    match &result {
+        Ok(_) => ctx.record_outcome(connector.id(), None),
+        Err(ConnectorError::Cancelled | ConnectorError::NotConnected(_)) => {}
+        Err(ConnectorError::Unreachable(e)) => ctx.record_unreachable(connector.id(), e),
+        Err(e) => ctx.record_outcome(connector.id(), Some(&e.to_string())),
+    }
+
+    let pipeline = pipeline.lock().await;
+    match result {
+        Ok(result) => {
+            let items = result.items_synced;
+            tracing::info!("{} sync completed: {} items", name, items);
+            let message = match (&day, items > 0) {
+                (Some(day), true) => format!("Synced {} {} for {}", items, label, day),
+                (Some(day), false) => format!("No new {} found for {}", label, day),
+                (None, true) => format!("Synced {} {} from {}", items, label, name),
+                (None, false) => format!("{} sync complete (no new {})", name, label),
+            };
+            pipeline.complete_task(&task_id, Some(message)).await;
+            Ok(result)
+        }
+        Err(ConnectorError::NotConnected(source)) => {
+            pipeline
+                .complete_task(&task_id, Some(format!("{} not connected", source)))
+                .await;
+            Ok(SyncResult {
+                source: connector.id().to_string(),
+                items_synced: 0,
+                errors: vec![],
+            })
+        }
+        Err(ConnectorError::Cancelled) => {
+            tracing::info!("{} sync cancelled", name);
+            pipeline.fail_task(&task_id, CANCELLED.to_string()).await;
+            Err(ConnectorError::Cancelled)
+        }
+        Err(e) => {
+            tracing::error!("{} sync error: {}", name, e);
+            pipeline.fail_task(&task_id, e.to_string()).await;
+            Err(e)
+        }
+    }
+}
+
+/// Outcome of one pass over the connected sources
+#[derive(Debug, Default)]
+pub struct SourcesSynced {
+    pub items: i32,
+    /// Per-source errors, prefixed with the source's name
+    pub errors: Vec<String>,
+    /// Whether any source's sync was cancelled before it finished, so the pass
+    /// did not cover everything it was asked to
+    pub cancelled: bool,
+}
+
+/// Sync every connected source (optionally restricted to `only`) for one pass.
+pub async fn sync_connected_sources(
+    registry: &SourceRegistry,
+    ctx: &ConnectorContext,
+    pipeline: &Arc<Mutex<PipelineManager>>,
+    only: Option<&[String]>,
+    range: Option<&SyncRange>,
+) -> SourcesSynced {
+    let mut synced = SourcesSynced::default();
+
+    for connector in registry.connected(ctx, only).await {
+        if ctx.cancel.is_cancelled() {
+            break;
+        }
+        if ctx.due_only && range.is_none() {
+            if !super::intervals::is_source_due(&ctx.db, connector.id()).await {
+                tracing::debug!("Skipping {}: not due yet", connector.display_name());
+                continue;
+            }
+            if !ctx.circuit_allows(connector.id()) {
+                tracing::debug!(
+                    "Skipping {}: paused after failures",
+                    connector.display_name()
+                );
+                continue;
+            }
+        }
+        match run_sync_task(connector.as_ref(), ctx, pipeline, range).await {
+            Ok(result) => {
+                synced.items += result.items_synced;
+                synced.errors.extend(
+                    result
+                        .errors
+                        .into_iter()
+                        .map(|e| format!("{}: {}", connector.display_name(), e)),
+                );
+            }
+            // A cancelled sync is not a failure of the source, but the caller
+            // must not treat the pass as complete. Only this source's task may
+            // have been cancelled, so the rest still sync.
+            Err(ConnectorError::Cancelled) => synced.cancelled = true,
+            Err(e) => synced
+                .errors
+                .push(format!("{}: {}", connector.display_name(), e)),
+        }
+    }
+
+    synced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeConnector(&'static str);
+
+    #[async_trait]
+    impl SourceConnector for FakeConnector {
+        fn id(&self) -> &'static str {
+            self.0
+        }
+
+        fn display_name(&self) -> &'static str {
+            "Fake"
+        }
+
+        fn description(&self) -> &'static str {
+            "A fake source"
+        }
+
+        fn task_type(&self) -> PipelineTaskType {
+            PipelineTaskType::SyncSlack
+        }
+
+        async fn connect(
+            &self,
+            _ctx: &ConnectorContext,
+            _config: serde_json::Value,
+        ) -> Result<serde_json::Value, ConnectorError> {
+            Ok(serde_json::Value::Null)
+        }
+
+        async fn is_connected(&self, _ctx: &ConnectorContext) -> bool {
+            false
+        }
+
+        async fn list_scopes(
+            &self,
+            _ctx: &ConnectorContext,
+        ) -> Result<Vec<SourceScope>, ConnectorError> {
+            Ok(vec![])
+        }
+
+        async fn sync_incremental(
+            &self,
+            _ctx: &ConnectorContext,
+        ) -> Result<SyncResult, ConnectorError> {
+            Err(ConnectorError::NotConnected("Fake"))
+        }
+
+        async fn sync_range(
+            &self,
+            _ctx: &ConnectorContext,
+            _range: &SyncRange,
+        ) -> Result<SyncResult, ConnectorError> {
+            Err(ConnectorError::NotConnected("Fake"))
+        }
+
+        async fn disconnect(&self, _ctx: &ConnectorContext) -> Result<(), ConnectorError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_incremental_since() {
+        let now = parse_timestamp_ms("2024-03-01T00:00:00Z").unwrap();
+
+        assert_eq!(incremental_since(None, now), now - 7 * DAY_MS);
+        assert_eq!(
+            incremental_since(Some("2024-02-29T12:00:00.000Z"), now),
+            now - DAY_MS / 2
+        );
+        // Long-idle resources are capped rather than syncing all history
+        assert_eq!(
+            incremental_since(Some("2023-01-01T00:00:00Z"), now),
+            now - 30 * DAY_MS
+        );
+        assert_eq!(incremental_since(Some("garbage"), now), now - 7 * DAY_MS);
+    }
+
+    #[test]
+    fn test_scope_interval_support() {
+        let registry = SourceRegistry::with_defaults();
+        let supported: Vec<_> = registry
+            .all()
+            .iter()
+            .filter(|c| c.supports_scope_intervals())
+            .map(|c| c.id())
+            .collect();
+        assert_eq!(supported, vec!["slack", "jira", "confluence"]);
+    }
+
+    #[test]
+    fn test_registry_defaults() {
+        let registry = SourceRegistry::with_defaults();
+        assert_eq!(
+            registry.ids(),
+            vec![
+                "slack",
+                "jira",
+                "confluence",
+                "github",
+                "gitlab",
+                "linear",
+                "incident",
+                "email",
+                "calendar",
+                "notes",
+                "rss",
+                "webhook"
+            ]
+        );
+        assert!(registry.get("slack").is_some());
+        assert!(matches!(
+            registry.require("myspace"),
+            Err(ConnectorError::UnknownSource(_))
+        ));
+    }
+
+    #[test]
+    fn test_registry_register_replaces_same_id() {
+        let mut registry = SourceRegistry::new();
+        registry.register(Arc::new(FakeConnector("fake")));
+        registry.register(Arc::new(FakeConnector("other")));
+        registry.register(Arc::new(FakeConnector("fake")));
+
+        assert_eq!(registry.ids(), vec!["other", "fake"]);
+    }
+
+    #[test]
+    fn test_sync_range_dates() {
+        let range = SyncRange::new("2024-02-28", "2024-03-01", 0).unwrap();
+        assert_eq!(
+            range.dates(),
+            vec!["2024-02-28", "2024-02-29", "2024-03-01"]
+        );
+
+        assert!(SyncRange::new("2024-03-02", "2024-03-01", 0).is_err());
+        assert!(SyncRange::day("2024/03/01", 0).is_err());
+    }
+
+    #[test]
+    fn test_sync_range_utc_bounds() {
+        // UTC-5 (getTimezoneOffset = 300): local midnight is 05:00 UTC
+        let range = SyncRange::day("2024-01-15", 300).unwrap();
+        let (start, end) = range.utc_bounds();
+        assert_eq!(start, 1705294800);
+        assert_eq!(end - start, 86400);
+    }
+
+    #[test]
+    fn test_connector_error_display() {
+        assert_eq!(
+            ConnectorError::NotConnected("Slack").to_string(),
+            "Slack not connected"
+        );
+    }
+
+    #[test]
+    fn test_classify_connector_error() {
+        use crate::sync::email::EmailError;
+        use std::io::{Error, ErrorKind};
+
+        let offline = EmailError::Io(Error::new(ErrorKind::NetworkUnreachable, "offline"));
+        assert!(matches!(
+            ConnectorError::classify(offline),
+            ConnectorError::Unreachable(_)
+        ));
+        let auth = EmailError::Auth("bad password".to_string());
+        assert!(matches!(
+            ConnectorError::classify(auth),
+            ConnectorError::Failed(_)
+        ));
+    }
+}
@@ -0,0 +1,393 @@
+//! Email source connector
+//!
+//! Connects to an IMAP account with a username and (app) password and syncs the
+//! folders selected in settings, each with its own `sync_state` cursor.
+
+use async_trait::async_trait;
+use std::collections::HashSet;
+
+use super::imap::ImapSession;
+use super::sync::{EmailSyncService, FolderCursor, FolderWindow};
+use super::types::{EmailAccount, SenderFilter};
+use crate::db::Database;
+use crate::pipeline::PipelineTaskType;
+use crate::sync::connectivity::is_unreachable;
+use crate::sync::connector::{
+    clear_deselected_scopes, clear_sync_state, last_sync_at, load_credentials, load_cursor,
+    load_preference, save_cursor, save_preference, store_credentials, ConnectorContext,
+    ConnectorError, SourceConnector, SourceHealth, SourceScope, SyncRange, INITIAL_SYNC_DAYS,
+};
+use crate::sync::status::{mark_failed, mark_pending};
+use crate::sync::SyncResult;
+
+const SELECTED_FOLDERS_KEY: &str = "email_selected_folders";
+const SENDER_FILTER_KEY: &str = "email_sender_filter";
+
+pub async fn load_sender_filter(db: &Database) -> Result<SenderFilter, ConnectorError> {
+    load_preference(db, SENDER_FILTER_KEY).await
+}
+
+pub async fn save_sender_filter(
+    db: &Database,
+    filter: &SenderFilter,
+) -> Result<(), ConnectorError> {
+    save_preference(db, SENDER_FILTER_KEY, filter).await
+}
+
+pub(crate) async fn load_email_account(
+    ctx: &ConnectorContext,
+) -> Result<Option<EmailAccount>, ConnectorError> {
+    load_credentials(ctx, "email").await
+}
+
+/// Build account settings from connect config. `port` defaults to 993 with TLS
+/// and 143 without; `tls` defaults to true.
+fn account_from_config(config: &serde_json::Value) -> Result<EmailAccount, ConnectorError> {
+    let field = |name: &str| {
+        config[name]
+            .as_str()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(String::from)
+    };
+
+    let tls = match &config["tls"] {
+        serde_json::Value::Bool(tls) => *tls,
+        serde_json::Value::String(tls) => tls.trim() != "false",
+        _ => true,
+    };
+    let port = match &config["port"] {
+        serde_json::Value::Number(port) => port.as_u64().and_then(|p| u16::try_from(p).ok()),
+        serde_json::Value::String(port) if !port.trim().is_empty() => Some(
+            port.trim()
+                .parse()
+                .map_err(|_| ConnectorError::InvalidConfig(format!("Invalid port: {}", port)))?,
+        ),
+        _ => None,
+    }
+    .unwrap_or(if tls { 993 } else { 143 });
+
+    Ok(EmailAccount {
+        host: field("host")
+            .ok_or_else(|| ConnectorError::InvalidConfig("host is required".into()))?,
+        port,
+        username: field("username")
+            .ok_or_else(|| ConnectorError::InvalidConfig("username is required".into()))?,
+        password: config["password"]
+            .as_str()
+            .filter(|p| !p.is_empty())
+            .map(String::from)
+            .ok_or_else(|| ConnectorError::InvalidConfig("password is required".into()))?,
+        tls,
+    })
+}
+
+pub struct EmailConnector;
+
+impl EmailConnector {
+    async fn service(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<(EmailSyncService, Vec<String>), ConnectorError> {
+        let account = load_email_account(ctx)
+            .await?
+            .ok_or(ConnectorError::NotConnected("Email"))?;
+        let folders: Vec<String> = load_preference(&ctx.db, SELECTED_FOLDERS_KEY).await?;
+        let filter = load_sender_filter(&ctx.db).await?;
+
+        let service =
+            EmailSyncService::connect(&account, ctx.db.clone(), ctx.crypto.clone(), filter)
+                .await
+                .map_err(ConnectorError::classify)?;
+        Ok((service, folders))
+    }
+}
+
+#[async_trait]
+impl SourceConnector for EmailConnector {
+    fn id(&self) -> &'static str {
+        "email"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Email"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sync email threads from selected IMAP folders"
+    }
+
+    fn task_type(&self) -> PipelineTaskType {
+        PipelineTaskType::SyncEmail
+    }
+
+    fn item_label(&self) -> &'static str {
+        "emails"
+    }
+
+    /// Accepts `{ "host", "port"?, "username", "password", "tls"?, "allowSenders"?,
+    /// "denySenders"? }`; sender lists are comma separated addresses or domains
+    async fn connect(
+        &self,
+        ctx: &ConnectorContext,
+        config: serde_json::Value,
+    ) -> Result<serde_json::Value, ConnectorError> {
+        let account = account_from_config(&config)?;
+
+        let mut session = ImapSession::open(&account)
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+        let folders = session
+            .list_folders()
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+        let _ = session.logout().await;
+
+        store_credentials(ctx, "email", &account).await?;
+
+        let filter = SenderFilter {
+            allow: SenderFilter::parse_list(config["allowSenders"].as_str().unwrap_or("")),
+            deny: SenderFilter::parse_list(config["denySenders"].as_str().unwrap_or("")),
+        };
+        save_sender_filter(&ctx.db, &filter).await?;
+
+        let selected: Vec<String> = load_preference(&ctx.db, SELECTED_FOLDERS_KEY).await?;
+        if selected.is_empty() && folders.iter().any(|f| f.name == "INBOX") {
+            save_preference(&ctx.db, SELECTED_FOLDERS_KEY, &vec!["INBOX"]).await?;
+        }
+
+        tracing::info!("Email connected: {}@{}", account.username, account.host);
+        Ok(serde_json::json!({ "username": account.username, "folders": folders.len() }))
+    }
+
+    async fn is_connected(&self, ctx: &ConnectorContext) -> bool {
+        matches!(load_email_account(ctx).await, Ok(Some(_)))
+    }
+
+    async fn list_scopes(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<Vec<SourceScope>, ConnectorError> {
+        let account = load_email_account(ctx)
+            .await?
+            .ok_or(ConnectorError::NotConnected("Email"))?;
+        let selected: HashSet<String> =
+            load_preference::<Vec<String>>(&ctx.db, SELECTED_FOLDERS_KEY)
+                .await?
+                .into_iter()
+                .collect();
+
+        let mut session = ImapSession::open(&account)
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+        let folders = session
+            .list_folders()
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+        let _ = session.logout().await;
+
+        Ok(folders
+            .into_iter()
+            .filter(|folder| folder.selectable)
+            .map(|folder| SourceScope {
+                selected: selected.contains(&folder.name),
+                id: folder.name.clone(),
+                name: folder.name,
+                kind: "folder".to_string(),
+            })
+            .collect())
+    }
+
+    fn supports_scope_selection(&self) -> bool {
+        true
+    }
+
+    async fn select_scopes(
+        &self,
+        ctx: &ConnectorContext,
+        scope_ids: Vec<String>,
+    ) -> Result<(), ConnectorError> {
+        save_preference(&ctx.db, SELECTED_FOLDERS_KEY, &scope_ids).await?;
+        clear_deselected_scopes(&ctx.db, self.id(), "folder", &scope_ids).await?;
+        tracing::info!("Selected {} email folders", scope_ids.len());
+        Ok(())
+    }
+
+    async fn sync_incremental(&self, ctx: &ConnectorContext) -> Result<SyncResult, ConnectorError> {
+        let (mut service, folders) = self.service(ctx).await?;
+        let mut total = SyncResult {
+            source: self.id().to_string(),
+            items_synced: 0,
+            errors: vec![],
+        };
+
+        let since_ms =
+            chrono::Utc::now().timestamp_millis() - INITIAL_SYNC_DAYS * 24 * 60 * 60 * 1000;
+
+        mark_pending(&ctx.db, self.id(), "folder", &folders).await;
+        for folder in folders {
+            let cursor = load_cursor(&ctx.db, self.id(), "folder", &folder)
+                .await?
+                .and_then(|c| FolderCursor::parse(&c));
+            let window = FolderWindow::Incremental { cursor, since_ms };
+
+            let sync = service.sync_folder(&folder, window);
+            let result = ctx.track_resource(self.id(), "folder", &folder, sync).await;
+            if ctx.cancel.is_cancelled() {
+                break;
+            }
+            match result {
+                Ok((result, cursor)) => {
+                    total.items_synced += result.items_synced;
+                    save_cursor(&ctx.db, self.id(), "folder", &folder, &cursor.format()).await?;
+                    if !result.errors.is_empty() {
+                        let error = result.errors.join("; ");
+                        mark_failed(&ctx.db, self.id(), "folder", &folder, &error).await;
+                        total.errors.extend(result.errors);
+                    }
+                }
+                // The folders share one connection, which is gone
+                Err(e) if is_unreachable(&e) => {
+                    return Err(ConnectorError::Unreachable(e.to_string()))
+                }
+                Err(e) => {
+                    tracing::error!("Email sync error for {}: {}", folder, e);
+                    total.errors.push(format!("{}: {}", folder, e));
+                }
+            }
+        }
+
+        // Log out before reporting a cancellation
+        service.close().await;
+        ctx.check_cancelled()?;
+        Ok(total)
+    }
+
+    async fn sync_range(
+        &self,
+        ctx: &ConnectorContext,
+        range: &SyncRange,
+    ) -> Result<SyncResult, ConnectorError> {
+        let (mut service, folders) = self.service(ctx).await?;
+        let (start, end) = range.utc_bounds();
+        let mut total = SyncResult {
+            source: self.id().to_string(),
+            items_synced: 0,
+            errors: vec![],
+        };
+
+        for folder in folders {
+            let window = FolderWindow::Range {
+                start_ms: start * 1000,
+                end_ms: end * 1000,
+            };
+            let result = service.sync_folder(&folder, window).await;
+            if ctx.cancel.is_cancelled() {
+                break;
+            }
+            match result {
+                Ok((result, _)) => {
+                    total.items_synced += result.items_synced;
+                    total.errors.extend(result.errors);
+                }
+                // The folders share one connection, which is gone
+                Err(e) if is_unreachable(&e) => {
+                    return Err(ConnectorError::Unreachable(e.to_string()))
+                }
+                Err(e) => total.errors.push(format!("{}: {}", folder, e)),
+            }
+        }
+
+        // Log out before reporting a cancellation
+        service.close().await;
+        ctx.check_cancelled()?;
+        Ok(total)
+    }
+
+    async fn health(&self, ctx: &ConnectorContext) -> SourceHealth {
+        let last_sync_at = last_sync_at(&ctx.db, self.id()).await;
+        let mut health = SourceHealth {
+            source: self.id().to_string(),
+            connected: false,
+            healthy: false,
+            message: None,
+            last_sync_at,
+        };
+
+        let account = match load_email_account(ctx).await {
+            Ok(Some(account)) => account,
+            Ok(None) => return health,
+            Err(e) => {
+                health.connected = true;
+                health.message = Some(e.to_string());
+                return health;
+            }
+        };
+        health.connected = true;
+
+        match ImapSession::open(&account).await {
+            Ok(session) => {
+                let _ = session.logout().await;
+                let folders: Vec<String> = load_preference(&ctx.db, SELECTED_FOLDERS_KEY)
+                    .await
+                    .unwrap_or_default();
+                health.healthy = true;
+                health.message = Some(format!(
+                    "Signed in to {} as {}; {} folders",
+                    account.host,
+                    account.username,
+                    folders.len()
+                ));
+            }
+            Err(e) => health.message = Some(e.to_string()),
+        }
+
+        health
+    }
+
+    async fn disconnect(&self, ctx: &ConnectorContext) -> Result<(), ConnectorError> {
+        sqlx::query("DELETE FROM credentials WHERE id = 'email'")
+            .execute(ctx.db.pool())
+            .await?;
+
+        sqlx::query("DELETE FROM preferences WHERE key IN (?, ?)")
+            .bind(SELECTED_FOLDERS_KEY)
+            .bind(SENDER_FILTER_KEY)
+            .execute(ctx.db.pool())
+            .await?;
+
+        clear_sync_state(&ctx.db, self.id()).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_from_config_defaults() {
+        let account = account_from_config(&serde_json::json!({
+            "host": "imap.example.com",
+            "username": "jane@example.com",
+            "password": "app-password",
+        }))
+        .unwrap();
+        assert_eq!(account.port, 993);
+        assert!(account.tls);
+
+        let bridge = account_from_config(&serde_json::json!({
+            "host": "127.0.0.1",
+            "port": "1143",
+            "tls": "false",
+            "username": "jane",
+            "password": "bridge",
+        }))
+        .unwrap();
+        assert_eq!(bridge.port, 1143);
+        assert!(!bridge.tls);
+
+        assert!(account_from_config(&serde_json::json!({ "host": "imap.example.com" })).is_err());
+    }
+}
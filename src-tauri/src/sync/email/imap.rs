@@ -0,0 +1,476 @@
+//! Minimal IMAP4rev1 client covering the commands email sync needs
+//!
+//! Supports implicit TLS and plaintext connections (to loopback only), `LOGIN`,
+//! `LIST`, `SELECT`, `UID SEARCH` and `UID FETCH` with literal responses.
+
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{lookup_host, TcpStream};
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+
+use super::types::{EmailAccount, EmailError};
+
+/// UIDs per `UID FETCH`, to keep responses reasonably sized
+const FETCH_BATCH: usize = 50;
+
+trait ImapStream: AsyncRead + AsyncWrite + Unpin + Send {}
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> ImapStream for T {}
+
+/// One untagged response with any literals it carried
+#[derive(Debug, Default)]
+struct ResponseLine {
+    text: String,
+    literals: Vec<Vec<u8>>,
+}
+
+/// A selectable mailbox from `LIST`
+#[derive(Debug, Clone)]
+pub struct ImapFolder {
+    pub name: String,
+    pub selectable: bool,
+}
+
+/// A message body from `UID FETCH`
+#[derive(Debug, Clone)]
+pub struct FetchedMessage {
+    pub uid: u32,
+    pub raw: Vec<u8>,
+}
+
+pub struct ImapSession {
+    stream: BufReader<Box<dyn ImapStream>>,
+    next_tag: u32,
+}
+
+/// Format a string argument. Printable ASCII is quoted, escaping backslashes
+/// and double quotes; anything else is sent as a `{n}` literal so CR, LF or
+/// non-ASCII bytes cannot break out of the command.
+fn astring(value: &str) -> String {
+    if value.bytes().all(|b| (0x20..0x7f).contains(&b)) {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        format!("{{{}}}\r\n{}", value.len(), value)
+    }
+}
+
+/// The length of the `{n}` literal announced at the end of a line, if any
+fn literal_len(line: &str) -> Option<usize> {
+    line.strip_suffix('}')
+        .and_then(|t| t.rsplit_once('{'))
+        .and_then(|(_, n)| n.parse().ok())
+}
+
+/// Format a date for `SEARCH SINCE`/`BEFORE`, e.g. `5-Mar-2024`
+pub(super) fn imap_date(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .unwrap_or_default()
+        .format("%-d-%b-%Y")
+        .to_string()
+}
+
+/// Parse the mailbox name and flags out of a `LIST` response
+fn parse_list_line(line: &ResponseLine) -> Option<ImapFolder> {
+    let rest = line.text.strip_prefix("* LIST ")?;
+    let flags_end = rest.find(')')?;
+    let flags = rest[..flags_end].to_lowercase();
+    let after_flags = rest[flags_end + 1..].trim_start();
+
+    // Skip the hierarchy delimiter: a quoted character or NIL
+    let after_delimiter = if let Some(quoted) = after_flags.strip_prefix('"') {
+        let end = if quoted.starts_with('\\') { 2 } else { 1 };
+        quoted.get(end + 1..)?.trim_start()
+    } else {
+        after_flags.strip_prefix("NIL")?.trim_start()
+    };
+
+    let name = if let Some(quoted) = after_delimiter.strip_prefix('"') {
+        quoted
+            .strip_suffix('"')?
+            .replace("\\\"", "\"")
+            .replace("\\\\", "\\")
+    } else if after_delimiter.starts_with('{') {
+        String::from_utf8_lossy(line.literals.first()?).into_owned()
+    } else {
+        after_delimiter.to_string()
+    };
+
+    Some(ImapFolder {
+        name,
+        selectable: !flags.contains("\\noselect") && !flags.contains("\\nonexistent"),
+    })
+}
+
+/// Extract `UID n` from a `FETCH` response
+fn parse_fetch_uid(text: &str) -> Option<u32> {
+    let start = text.find("UID ")? + 4;
+    text[start..]
+        .split(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()
+}
+
+impl ImapSession {
+    /// Open a connection and read the server greeting. Plaintext connections
+    /// are refused unless `host` resolves to a loopback address.
+    pub async fn connect(host: &str, port: u16, tls: bool) -> Result<Self, EmailError> {
+        // A failed lookup is reported as unreachable so it is not taken for a
+        // server error; see `crate::sync::connectivity`
+        let addrs: Vec<_> = lookup_host((host, port))
+            .await
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::HostUnreachable, e))?
+            .collect();
+
+        // LOGIN sends the password as is, so without TLS it must not leave the
+        // machine
+        if !tls && !addrs.iter().all(|addr| addr.ip().is_loopback()) {
+            return Err(EmailError::Tls(format!(
+                "{} is not a local address; plaintext IMAP is only allowed for local bridges",
+                host
+            )));
+        }
+        let tcp = TcpStream::connect(addrs.as_slice()).await?;
+
+        let stream: Box<dyn ImapStream> = if tls {
+            let mut roots = RootCertStore::empty();
+            roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+            let config = ClientConfig::builder()
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+            let server_name = ServerName::try_from(host.to_string())
+                .map_err(|e| EmailError::Tls(e.to_string()))?;
+            let tls_stream = TlsConnector::from(Arc::new(config))
+                .connect(server_name, tcp)
+                .await
+                .map_err(|e| EmailError::Tls(e.to_string()))?;
+            Box::new(tls_stream)
+        } else {
+            Box::new(tcp)
+        };
+
+        let mut session = Self {
+            stream: BufReader::new(stream),
+            next_tag: 1,
+        };
+
+        let greeting = session.read_response().await?;
+        if !greeting.text.starts_with("* OK") && !greeting.text.starts_with("* PREAUTH") {
+            return Err(EmailError::Imap(format!(
+                "Unexpected greeting: {}",
+                greeting.text
+            )));
+        }
+
+        Ok(session)
+    }
+
+    /// Connect and log in to an account
+    pub async fn open(account: &EmailAccount) -> Result<Self, EmailError> {
+        let mut session = Self::connect(&account.host, account.port, account.tls).await?;
+        session.login(&account.username, &account.password).await?;
+        Ok(session)
+    }
+
+    /// Read one response line, pulling in any `{n}` literals it announces
+    async fn read_response(&mut self) -> Result<ResponseLine, EmailError> {
+        let mut response = ResponseLine::default();
+
+        loop {
+            let mut line = Vec::new();
+            let read = self.stream.read_until(b'\n', &mut line).await?;
+            if read == 0 {
+                return Err(EmailError::Imap("Connection closed by server".into()));
+            }
+
+            let text = String::from_utf8_lossy(&line);
+            let text = text.trim_end_matches(['\r', '\n']);
+            response.text.push_str(text);
+
+            let Some(len) = literal_len(text) else {
+                return Ok(response);
+            };
+
+            let mut literal = vec![0; len];
+            self.stream.read_exact(&mut literal).await?;
+            response.literals.push(literal);
+        }
+    }
+
+    /// Send a tagged command and collect untagged responses until it completes
+    async fn command(&mut self, command: &str) -> Result<Vec<ResponseLine>, EmailError> {
+        let tag = format!("A{:04}", self.next_tag);
+        self.next_tag += 1;
+
+        let line = format!("{} {}\r\n", tag, command);
+        let mut untagged = Vec::new();
+
+        // Each literal goes out only after the server's `+` continuation
+        let mut rest = line.as_bytes();
+        while let Some(end) = rest.windows(2).position(|w| w == b"\r\n") {
+            let (segment, after) = rest.split_at(end + 2);
+            let stream = self.stream.get_mut();
+            stream.write_all(segment).await?;
+            stream.flush().await?;
+
+            let Some(len) = literal_len(&String::from_utf8_lossy(&segment[..end])) else {
+                break;
+            };
+            loop {
+                let response = self.read_response().await?;
+                if response.text.starts_with('+') {
+                    break;
+                }
+                if let Some(status) = response.text.strip_prefix(&format!("{} ", tag)) {
+                    return Err(EmailError::Imap(status.to_string()));
+                }
+                untagged.push(response);
+            }
+
+            let (literal, after) = after.split_at(len);
+            self.stream.get_mut().write_all(literal).await?;
+            rest = after;
+        }
+
+        loop {
+            let response = self.read_response().await?;
+            let Some(status) = response.text.strip_prefix(&format!("{} ", tag)) else {
+                untagged.push(response);
+                continue;
+            };
+
+            if status.starts_with("OK") {
+                return Ok(untagged);
+            }
+            return Err(EmailError::Imap(status.to_string()));
+        }
+    }
+
+    pub async fn login(&mut self, username: &str, password: &str) -> Result<(), EmailError> {
+        self.command(&format!(
+            "LOGIN {} {}",
+            astring(username),
+            astring(password)
+        ))
+        .await
+        .map(|_| ())
+        .map_err(|e| match e {
+            EmailError::Imap(message) => EmailError::Auth(message),
+            other => other,
+        })
+    }
+
+    /// All mailboxes visible to the account
+    pub async fn list_folders(&mut self) -> Result<Vec<ImapFolder>, EmailError> {
+        let responses = self.command("LIST \"\" \"*\"").await?;
+        Ok(responses.iter().filter_map(parse_list_line).collect())
+    }
+
+    /// Open a mailbox read-only, returning its `UIDVALIDITY`
+    pub async fn examine(&mut self, folder: &str) -> Result<u32, EmailError> {
+        let responses = self
+            .command(&format!("EXAMINE {}", astring(folder)))
+            .await?;
+
+        responses
+            .iter()
+            .find_map(|r| {
+                let start = r.text.find("[UIDVALIDITY ")? + 13;
+                r.text[start..].split(']').next()?.trim().parse().ok()
+            })
+            .ok_or_else(|| EmailError::Imap(format!("No UIDVALIDITY for {}", folder)))
+    }
+
+    /// Run `UID SEARCH` with the given criteria
+    pub async fn uid_search(&mut self, criteria: &str) -> Result<Vec<u32>, EmailError> {
+        let responses = self.command(&format!("UID SEARCH {}", criteria)).await?;
+
+        let mut uids: Vec<u32> = responses
+            .iter()
+            .filter_map(|r| r.text.strip_prefix("* SEARCH"))
+            .flat_map(|ids| ids.split_whitespace().filter_map(|id| id.parse().ok()))
+            .collect();
+        uids.sort_unstable();
+        Ok(uids)
+    }
+
+    /// Fetch full messages without marking them as read
+    pub async fn uid_fetch(&mut self, uids: &[u32]) -> Result<Vec<FetchedMessage>, EmailError> {
+        let mut messages = Vec::new();
+
+        for batch in uids.chunks(FETCH_BATCH) {
+            let set = batch
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            let responses = self
+                .command(&format!("UID FETCH {} (UID BODY.PEEK[])", set))
+                .await?;
+
+            messages.extend(responses.into_iter().filter_map(|mut r| {
+                if !r.text.contains(" FETCH ") || r.literals.is_empty() {
+                    return None;
+                }
+                Some(FetchedMessage {
+                    uid: parse_fetch_uid(&r.text)?,
+                    raw: r.literals.swap_remove(0),
+                })
+            }));
+        }
+
+        Ok(messages)
+    }
+
+    pub async fn logout(mut self) -> Result<(), EmailError> {
+        self.command("LOGOUT").await.map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    const MESSAGE: &str = "Message-ID: <a@acme.com>\r\nSubject: Renewal\r\n\r\nHi\r\n";
+    /// A password that would end the command early if it were quoted
+    const LITERAL_PASSWORD: &str = "s\u{e9}cret\r\nA9 LOGOUT";
+
+    /// Scripted IMAP server answering one session on localhost
+    async fn spawn_server() -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let (read, mut write) = socket.into_split();
+            let mut lines = BufReader::new(read).lines();
+
+            write.write_all(b"* OK IMAP4rev1 ready\r\n").await.unwrap();
+
+            while let Some(line) = lines.next_line().await.unwrap() {
+                let (tag, command) = line.split_once(' ').unwrap();
+                let mut command = command.to_string();
+                while let Some(len) = literal_len(&command) {
+                    write.write_all(b"+ Ready\r\n").await.unwrap();
+                    let mut literal = vec![0; len];
+                    lines.get_mut().read_exact(&mut literal).await.unwrap();
+                    command.push_str("\r\n");
+                    command.push_str(&String::from_utf8(literal).unwrap());
+                    command.push_str(&lines.next_line().await.unwrap().unwrap());
+                }
+
+                let reply = if command.starts_with("LOGIN") {
+                    if command.ends_with("\"secret\"")
+                        || command.ends_with(&format!("}}\r\n{}", LITERAL_PASSWORD))
+                    {
+                        format!("{} OK LOGIN completed\r\n", tag)
+                    } else {
+                        format!("{} NO [AUTHENTICATIONFAILED] Invalid credentials\r\n", tag)
+                    }
+                } else if command.starts_with("LIST") {
+                    format!(
+                        "* LIST (\\HasNoChildren) \"/\" \"INBOX\"\r\n\
+                         * LIST (\\Noselect \\HasChildren) \"/\" \"[Gmail]\"\r\n\
+                         * LIST (\\HasNoChildren) \"/\" \"Clients/Acme \\\"Corp\\\"\"\r\n\
+                         {} OK LIST completed\r\n",
+                        tag
+                    )
+                } else if command.starts_with("EXAMINE") {
+                    format!(
+                        "* 2 EXISTS\r\n* OK [UIDVALIDITY 3857529045] UIDs valid\r\n\
+                         {} OK [READ-ONLY] EXAMINE completed\r\n",
+                        tag
+                    )
+                } else if command.starts_with("UID SEARCH") {
+                    format!("* SEARCH 12 7\r\n{} OK SEARCH completed\r\n", tag)
+                } else if command.starts_with("UID FETCH") {
+                    format!(
+                        "* 1 FETCH (UID 7 BODY[] {{{}}}\r\n{})\r\n{} OK FETCH completed\r\n",
+                        MESSAGE.len(),
+                        MESSAGE,
+                        tag
+                    )
+                } else {
+                    format!("* BYE\r\n{} OK LOGOUT completed\r\n", tag)
+                };
+                write.write_all(reply.as_bytes()).await.unwrap();
+            }
+        });
+
+        port
+    }
+
+    #[tokio::test]
+    async fn test_session_against_local_server() {
+        let port = spawn_server().await;
+        let mut session = ImapSession::connect("127.0.0.1", port, false)
+            .await
+            .unwrap();
+
+        session.login("jane@acme.com", "secret").await.unwrap();
+
+        let folders = session.list_folders().await.unwrap();
+        assert_eq!(folders.len(), 3);
+        assert_eq!(folders[0].name, "INBOX");
+        assert!(!folders[1].selectable);
+        assert_eq!(folders[2].name, "Clients/Acme \"Corp\"");
+
+        assert_eq!(session.examine("INBOX").await.unwrap(), 3857529045);
+        assert_eq!(session.uid_search("ALL").await.unwrap(), vec![7, 12]);
+
+        let messages = session.uid_fetch(&[7]).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].uid, 7);
+        assert_eq!(messages[0].raw, MESSAGE.as_bytes());
+
+        session.logout().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejected_login_is_auth_error() {
+        let port = spawn_server().await;
+        let mut session = ImapSession::connect("127.0.0.1", port, false)
+            .await
+            .unwrap();
+
+        let err = session.login("jane@acme.com", "wrong").await.unwrap_err();
+        assert!(matches!(err, EmailError::Auth(_)));
+    }
+
+    #[tokio::test]
+    async fn test_login_sends_unsafe_password_as_literal() {
+        let port = spawn_server().await;
+        let mut session = ImapSession::connect("127.0.0.1", port, false)
+            .await
+            .unwrap();
+
+        session
+            .login("jane@acme.com", LITERAL_PASSWORD)
+            .await
+            .unwrap();
+        assert_eq!(session.uid_search("ALL").await.unwrap(), vec![7, 12]);
+    }
+
+    #[test]
+    fn test_astring() {
+        assert_eq!(astring("a \"b\" \\c"), "\"a \\\"b\\\" \\\\c\"");
+        assert_eq!(astring("p\r\nw"), "{4}\r\np\r\nw");
+        assert_eq!(astring("s\u{e9}"), "{3}\r\ns\u{e9}");
+    }
+
+    #[tokio::test]
+    async fn test_plaintext_refused_for_remote_host() {
+        let err = ImapSession::connect("192.0.2.1", 143, false)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, EmailError::Tls(_)));
+    }
+
+    #[test]
+    fn test_imap_date() {
+        assert_eq!(imap_date(1709596800000), "5-Mar-2024");
+    }
+}
@@ -0,0 +1,249 @@
+//! mbox and `.eml` archive import
+//!
+//! Reads an mbox file, a single `.eml` file or a folder of them from disk and
+//! stores the messages through the same path as IMAP sync, so imported threads
+//! share its schema, encryption and `parent_id` threading.
+
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::message::parse_message;
+use super::sync::store_message;
+use super::types::{EmailError, EmailMessage};
+use crate::crypto::CryptoService;
+use crate::db::Database;
+
+/// Summary of a completed archive import
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailImportResult {
+    pub messages_imported: i32,
+    pub threads_imported: i32,
+    pub files_read: i32,
+    pub errors: Vec<String>,
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(extension))
+}
+
+/// Archive files under `path`: `.eml` and `.mbox` files in a folder (recursively),
+/// or the file itself
+fn collect_files(path: &Path) -> Result<Vec<PathBuf>, EmailError> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files = Vec::new();
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry_path = entry?.path();
+            if entry_path.is_dir() {
+                pending.push(entry_path);
+            } else if has_extension(&entry_path, "eml") || has_extension(&entry_path, "mbox") {
+                files.push(entry_path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Split an mbox into raw messages, undoing `>From ` quoting
+fn split_mbox(data: &[u8]) -> Vec<Vec<u8>> {
+    let mut messages = Vec::new();
+    let mut current: Option<Vec<u8>> = None;
+    let mut previous_blank = true;
+
+    for line in data.split_inclusive(|&b| b == b'\n') {
+        if previous_blank && line.starts_with(b"From ") {
+            if let Some(message) = current.take() {
+                messages.push(message);
+            }
+            current = Some(Vec::new());
+            previous_blank = false;
+            continue;
+        }
+
+        previous_blank = line == b"\n" || line == b"\r\n";
+        let Some(message) = current.as_mut() else {
+            continue;
+        };
+
+        // mboxrd: one leading '>' was added to every ">*From " line
+        let quoted = line.iter().take_while(|&&b| b == b'>').count();
+        if quoted > 0 && line[quoted..].starts_with(b"From ") {
+            message.extend_from_slice(&line[1..]);
+        } else {
+            message.extend_from_slice(line);
+        }
+    }
+
+    messages.extend(current);
+    messages
+}
+
+/// Parent messages must be stored before their replies so replies can pick up
+/// the stored thread root; dated messages go in date order, undated ones last
+fn sort_for_threading(messages: &mut [(String, EmailMessage)]) {
+    messages.sort_by_key(|(_, message)| (message.date.is_none(), message.date));
+}
+
+pub struct EmailArchiveImporter {
+    db: Arc<Database>,
+    crypto: Arc<CryptoService>,
+}
+
+impl EmailArchiveImporter {
+    pub fn new(db: Arc<Database>, crypto: Arc<CryptoService>) -> Self {
+        Self { db, crypto }
+    }
+
+    /// Import an mbox file, an `.eml` file or a folder containing either
+    pub async fn import_path(&self, path: &str) -> Result<EmailImportResult, EmailError> {
+        let path = Path::new(path);
+        if !path.exists() {
+            return Err(EmailError::Parse(format!(
+                "{} does not exist",
+                path.display()
+            )));
+        }
+
+        let mut result = EmailImportResult::default();
+        let mut messages = Vec::new();
+
+        for file in collect_files(path)? {
+            let data = match std::fs::read(&file) {
+                Ok(data) => data,
+                Err(e) => {
+                    result.errors.push(format!("{}: {}", file.display(), e));
+                    continue;
+                }
+            };
+            result.files_read += 1;
+
+            // Messages are grouped under the archive they came from
+            let archive = file
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("archive");
+            let archive = if has_extension(&file, "eml") {
+                file.parent()
+                    .and_then(|p| p.file_name())
+                    .and_then(|s| s.to_str())
+                    .unwrap_or(archive)
+            } else {
+                archive
+            };
+
+            let raws = if has_extension(&file, "eml") {
+                vec![data]
+            } else {
+                split_mbox(&data)
+            };
+
+            for (index, raw) in raws.iter().enumerate() {
+                match parse_message(raw) {
+                    Ok(message) => messages.push((archive.to_string(), message)),
+                    Err(e) => result.errors.push(format!(
+                        "{} message {}: {}",
+                        file.display(),
+                        index + 1,
+                        e
+                    )),
+                }
+            }
+        }
+
+        sort_for_threading(&mut messages);
+
+        let mut threads = HashSet::new();
+        for (archive, message) in &messages {
+            match store_message(&self.db, &self.crypto, archive, None, message).await {
+                Ok(()) => {
+                    result.messages_imported += 1;
+                    threads.insert(message.thread_root().unwrap_or(&message.message_id));
+                }
+                Err(e) => result.errors.push(format!("{}: {}", message.message_id, e)),
+            }
+        }
+        result.threads_imported = threads.len() as i32;
+
+        tracing::info!(
+            "Imported {} emails in {} threads from {} files",
+            result.messages_imported,
+            result.threads_imported,
+            result.files_read
+        );
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MBOX: &[u8] = b"From jane@acme.com Mon Mar  4 09:15:00 2024\n\
+Message-ID: <b@acme.com>\n\
+In-Reply-To: <a@vendor.io>\n\
+From: jane@acme.com\n\
+Date: Mon, 4 Mar 2024 09:15:00 +0000\n\
+Subject: Re: Renewal\n\
+\n\
+Agreed.\n\
+>From the contract: net 30.\n\
+\n\
+From sales@vendor.io Sun Mar  3 17:00:00 2024\n\
+Message-ID: <a@vendor.io>\n\
+From: sales@vendor.io\n\
+Date: Sun, 3 Mar 2024 17:00:00 +0000\n\
+Subject: Renewal\n\
+\n\
+Can you confirm terms?\n";
+
+    #[test]
+    fn test_split_mbox_unescapes_from_lines() {
+        let messages = split_mbox(MBOX);
+        assert_eq!(messages.len(), 2);
+
+        let reply = parse_message(&messages[0]).unwrap();
+        assert_eq!(reply.message_id, "b@acme.com");
+        assert_eq!(reply.text, "Agreed.\nFrom the contract: net 30.");
+
+        let original = parse_message(&messages[1]).unwrap();
+        assert_eq!(original.text, "Can you confirm terms?");
+    }
+
+    #[test]
+    fn test_parents_sorted_before_replies() {
+        let mut messages: Vec<(String, EmailMessage)> = split_mbox(MBOX)
+            .iter()
+            .map(|raw| ("archive".to_string(), parse_message(raw).unwrap()))
+            .collect();
+        sort_for_threading(&mut messages);
+
+        assert_eq!(messages[0].1.message_id, "a@vendor.io");
+        assert_eq!(messages[1].1.thread_root(), Some("a@vendor.io"));
+    }
+
+    #[test]
+    fn test_collect_files_finds_eml_recursively() {
+        let dir = std::env::temp_dir().join(format!("companion-eml-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("2024")).unwrap();
+        std::fs::write(dir.join("a.eml"), b"Subject: a\n\nA\n").unwrap();
+        std::fs::write(dir.join("2024").join("b.EML"), b"Subject: b\n\nB\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), b"ignored").unwrap();
+
+        let files = collect_files(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().all(|f| !has_extension(f, "txt")));
+    }
+}
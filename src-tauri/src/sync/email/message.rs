@@ -0,0 +1,539 @@
+//! RFC 5322/MIME parsing, quoted-reply and signature stripping
+
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+use super::types::{EmailError, EmailMessage};
+
+/// Lines that start the quoted history of a reply in common clients
+const REPLY_MARKERS: &[&str] = &[
+    "-----original message-----",
+    "________________________________",
+    "begin forwarded message:",
+];
+
+/// Lines that end the authored part of a message
+const SIGNATURE_MARKERS: &[&str] = &[
+    "sent from my ",
+    "get outlook for ",
+    "sent from mail for windows",
+];
+
+type Headers = Vec<(String, String)>;
+
+fn split_header_body(raw: &[u8]) -> (&[u8], &[u8]) {
+    for (separator, len) in [(&b"\r\n\r\n"[..], 4), (&b"\n\n"[..], 2)] {
+        if let Some(pos) = raw.windows(len).position(|w| w == separator) {
+            return (&raw[..pos], &raw[pos + len..]);
+        }
+    }
+    (raw, &[])
+}
+
+/// Unfold and split headers into lowercase names and raw values
+fn parse_headers(header: &[u8]) -> Headers {
+    let text = String::from_utf8_lossy(header);
+    let mut headers: Headers = Vec::new();
+
+    for line in text.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_lowercase(), value.trim().to_string()));
+        }
+    }
+
+    headers
+}
+
+fn header<'a>(headers: &'a Headers, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.as_str())
+}
+
+/// Split `text/plain; charset="utf-8"` into the lowercase type and its parameters
+fn parse_content_type(value: &str) -> (String, Vec<(String, String)>) {
+    let mut parts = value.split(';');
+    let mime = parts.next().unwrap_or("").trim().to_lowercase();
+    let params = parts
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| {
+            (
+                k.trim().to_lowercase(),
+                v.trim().trim_matches('"').to_string(),
+            )
+        })
+        .collect();
+    (mime, params)
+}
+
+fn param<'a>(params: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    params
+        .iter()
+        .find(|(k, _)| k == name)
+        .map(|(_, v)| v.as_str())
+}
+
+fn decode_charset(bytes: &[u8], charset: Option<&str>) -> String {
+    let encoding = charset
+        .and_then(|c| encoding_rs::Encoding::for_label(c.as_bytes()))
+        .unwrap_or(encoding_rs::UTF_8);
+    encoding.decode(bytes).0.into_owned()
+}
+
+fn decode_quoted_printable(input: &[u8], header_mode: bool) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut i = 0;
+
+    while i < input.len() {
+        match input[i] {
+            b'=' if input.get(i + 1) == Some(&b'\r') && input.get(i + 2) == Some(&b'\n') => i += 3,
+            b'=' if input.get(i + 1) == Some(&b'\n') => i += 2,
+            b'=' => {
+                let hex = input
+                    .get(i + 1..i + 3)
+                    .and_then(|h| std::str::from_utf8(h).ok());
+                match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 3;
+                    }
+                    None => {
+                        out.push(b'=');
+                        i += 1;
+                    }
+                }
+            }
+            b'_' if header_mode => {
+                out.push(b' ');
+                i += 1;
+            }
+            byte => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+fn decode_base64(input: &[u8]) -> Vec<u8> {
+    let cleaned: Vec<u8> = input
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    base64::engine::general_purpose::STANDARD
+        .decode(&cleaned)
+        .unwrap_or_default()
+}
+
+/// Decode RFC 2047 encoded words, e.g. `=?UTF-8?Q?Caf=C3=A9?=`
+pub(super) fn decode_encoded_words(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    let mut last_was_word = false;
+
+    while let Some(start) = rest.find("=?") {
+        let decoded = rest[start + 2..].splitn(3, '?').collect::<Vec<_>>();
+        let word = match decoded.as_slice() {
+            [charset, encoding, tail] => tail.find("?=").map(|end| {
+                let text = &tail[..end];
+                let bytes = match encoding.to_ascii_uppercase().as_str() {
+                    "B" => decode_base64(text.as_bytes()),
+                    _ => decode_quoted_printable(text.as_bytes(), true),
+                };
+                let consumed = start + 2 + charset.len() + encoding.len() + 2 + end + 2;
+                (decode_charset(&bytes, Some(charset)), consumed)
+            }),
+            _ => None,
+        };
+
+        let Some((text, consumed)) = word else {
+            break;
+        };
+
+        // Whitespace between adjacent encoded words is not part of the text
+        let between = &rest[..start];
+        if !(last_was_word && between.trim().is_empty()) {
+            out.push_str(between);
+        }
+        out.push_str(&text);
+        rest = &rest[consumed..];
+        last_was_word = true;
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Pull `<id>` values out of a Message-ID style header
+fn parse_message_ids(value: &str) -> Vec<String> {
+    value
+        .split('<')
+        .skip(1)
+        .filter_map(|part| part.split_once('>'))
+        .map(|(id, _)| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+/// Split `"Jane Doe" <jane@acme.com>` into display name and lowercase address
+fn parse_mailbox(value: &str) -> (Option<String>, String) {
+    match (value.rfind('<'), value.rfind('>')) {
+        (Some(start), Some(end)) if start < end => {
+            let name = value[..start].trim().trim_matches('"').trim();
+            (
+                Some(name.to_string()).filter(|n| !n.is_empty()),
+                value[start + 1..end].trim().to_lowercase(),
+            )
+        }
+        _ => (None, value.trim().to_lowercase()),
+    }
+}
+
+fn parse_date(value: &str) -> Option<i64> {
+    // Drop trailing comments such as `(UTC)` that chrono rejects
+    let value = match value.find('(') {
+        Some(pos) => &value[..pos],
+        None => value,
+    };
+    chrono::DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|dt| dt.timestamp_millis())
+}
+
+fn strip_html(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    // ASCII lowercasing keeps byte offsets aligned with `html`
+    let lower = html.to_ascii_lowercase();
+    let mut skip_until: Option<&str> = None;
+
+    for (i, c) in html.char_indices() {
+        if let Some(end) = skip_until {
+            if lower[i..].starts_with(end) {
+                skip_until = None;
+                in_tag = true;
+            }
+            continue;
+        }
+        match c {
+            '<' => {
+                in_tag = true;
+                let tag = &lower[i..];
+                if tag.starts_with("<style") {
+                    skip_until = Some("</style");
+                } else if tag.starts_with("<script") {
+                    skip_until = Some("</script");
+                } else if tag.starts_with("<br")
+                    || tag.starts_with("<p")
+                    || tag.starts_with("</p")
+                    || tag.starts_with("<div")
+                    || tag.starts_with("</div")
+                    || tag.starts_with("<blockquote")
+                {
+                    text.push('\n');
+                }
+                if tag.starts_with("<blockquote") {
+                    // Quoted history in HTML replies
+                    skip_until = Some("</blockquote");
+                }
+            }
+            '>' if in_tag => in_tag = false,
+            _ if in_tag => {}
+            _ => text.push(c),
+        }
+    }
+
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Decode a MIME part, preferring `text/plain` over `text/html`. Returns the
+/// text and whether it came from HTML.
+fn extract_text(headers: &Headers, body: &[u8]) -> Option<(String, bool)> {
+    let (mime, params) =
+        parse_content_type(header(headers, "content-type").unwrap_or("text/plain"));
+
+    if header(headers, "content-disposition")
+        .is_some_and(|d| d.to_lowercase().starts_with("attachment"))
+    {
+        return None;
+    }
+
+    if mime.starts_with("multipart/") {
+        let boundary = format!("--{}", param(&params, "boundary")?);
+        let mut html = None;
+
+        for part in split_parts(body, boundary.as_bytes()) {
+            let (part_header, part_body) = split_header_body(part);
+            match extract_text(&parse_headers(part_header), part_body) {
+                Some((text, false)) => return Some((text, false)),
+                Some((text, true)) => html = html.or(Some(text)),
+                None => {}
+            }
+        }
+        return html.map(|text| (text, true));
+    }
+
+    if mime != "text/plain" && mime != "text/html" {
+        return None;
+    }
+
+    let encoding = header(headers, "content-transfer-encoding")
+        .unwrap_or("")
+        .to_lowercase();
+    let bytes = match encoding.trim() {
+        "base64" => decode_base64(body),
+        "quoted-printable" => decode_quoted_printable(body, false),
+        _ => body.to_vec(),
+    };
+    let text = decode_charset(&bytes, param(&params, "charset"));
+
+    if mime == "text/html" {
+        Some((strip_html(&text), true))
+    } else {
+        Some((text, false))
+    }
+}
+
+/// Bodies of the parts between multipart boundary lines, stopping at the closing boundary
+fn split_parts<'a>(body: &'a [u8], boundary: &[u8]) -> Vec<&'a [u8]> {
+    let mut parts = Vec::new();
+    let mut rest = body;
+    let mut started = false;
+
+    while let Some(pos) = rest.windows(boundary.len()).position(|w| w == boundary) {
+        if started {
+            parts.push(&rest[..pos]);
+        }
+        started = true;
+        rest = &rest[pos + boundary.len()..];
+        if rest.starts_with(b"--") {
+            break;
+        }
+        let line_end = rest
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(rest.len(), |p| p + 1);
+        rest = &rest[line_end..];
+    }
+
+    parts
+}
+
+fn is_reply_header(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    (lower.starts_with("on ") && lower.ends_with("wrote:"))
+        || REPLY_MARKERS.iter().any(|m| lower.starts_with(m))
+}
+
+/// Keep only what the sender wrote: drop quoted lines, the quoted history that
+/// follows an attribution line, and the signature
+pub(super) fn strip_quotes_and_signature(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    let mut kept: Vec<&str> = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let lower = line.trim().to_lowercase();
+
+        // Attribution lines are often wrapped: "On Mon, 4 Mar 2024, Jane Doe\n<jane@acme.com> wrote:"
+        let wrapped_attribution = lower.starts_with("on ")
+            && lines
+                .get(i + 1)
+                .is_some_and(|next| next.trim().to_lowercase().ends_with("wrote:"));
+        // Outlook puts "From:/Sent:" headers above the quoted message
+        let outlook_header = lower.starts_with("from:")
+            && lines
+                .get(i + 1)
+                .is_some_and(|next| next.trim().to_lowercase().starts_with("sent:"));
+
+        if is_reply_header(line.trim()) || wrapped_attribution || outlook_header {
+            break;
+        }
+        if *line == "-- " || line.trim() == "--" {
+            break;
+        }
+        if SIGNATURE_MARKERS.iter().any(|m| lower.starts_with(m)) {
+            break;
+        }
+        if line.trim_start().starts_with('>') {
+            continue;
+        }
+        kept.push(line);
+    }
+
+    let text = kept.join("\n");
+    let mut result = String::new();
+    let mut blank_run = 0;
+    for line in text.trim().lines() {
+        if line.trim().is_empty() {
+            blank_run += 1;
+            if blank_run > 1 {
+                continue;
+            }
+        } else {
+            blank_run = 0;
+        }
+        result.push_str(line);
+        result.push('\n');
+    }
+    result.trim_end().to_string()
+}
+
+/// Parse a raw message into threading headers and its authored text
+pub fn parse_message(raw: &[u8]) -> Result<EmailMessage, EmailError> {
+    let (header_bytes, body) = split_header_body(raw);
+    let headers = parse_headers(header_bytes);
+    if headers.is_empty() {
+        return Err(EmailError::Parse("Message has no headers".into()));
+    }
+
+    let message_id = header(&headers, "message-id")
+        .and_then(|v| parse_message_ids(v).into_iter().next())
+        .unwrap_or_else(|| {
+            // Without a Message-ID, a content hash keeps re-syncs idempotent
+            let hash = Sha256::digest(raw);
+            format!("{:x}@local", hash)
+        });
+
+    let (from_name, from_address) = header(&headers, "from")
+        .map(|v| parse_mailbox(&decode_encoded_words(v)))
+        .unwrap_or((None, String::new()));
+
+    let to = header(&headers, "to")
+        .map(|v| {
+            v.split(',')
+                .map(|mailbox| parse_mailbox(mailbox).1)
+                .filter(|address| !address.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let text = extract_text(&headers, body)
+        .map(|(text, _)| strip_quotes_and_signature(&text))
+        .unwrap_or_default();
+
+    Ok(EmailMessage {
+        message_id,
+        in_reply_to: header(&headers, "in-reply-to")
+            .and_then(|v| parse_message_ids(v).into_iter().next()),
+        references: header(&headers, "references")
+            .map(parse_message_ids)
+            .unwrap_or_default(),
+        subject: header(&headers, "subject")
+            .map(decode_encoded_words)
+            .unwrap_or_default(),
+        from_name,
+        from_address,
+        to,
+        date: header(&headers, "date").and_then(parse_date),
+        text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reply_with_threading_headers() {
+        let raw = b"Message-ID: <c@acme.com>\r\n\
+In-Reply-To: <b@vendor.io>\r\n\
+References: <a@acme.com>\r\n\x20<b@vendor.io>\r\n\
+From: \"Jane Doe\" <Jane@Acme.com>\r\n\
+To: sales@vendor.io, Bob <bob@vendor.io>\r\n\
+Subject: =?UTF-8?Q?Re:_Renewal_pricing_=E2=80=93_Q3?=\r\n\
+Date: Mon, 4 Mar 2024 09:15:00 +0100 (CET)\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+\r\n\
+We can accept 8% if we sign by Friday.\r\n\
+\r\n\
+-- \r\n\
+Jane Doe | Procurement\r\n";
+
+        let message = parse_message(raw).unwrap();
+        assert_eq!(message.message_id, "c@acme.com");
+        assert_eq!(message.in_reply_to.as_deref(), Some("b@vendor.io"));
+        assert_eq!(message.references, vec!["a@acme.com", "b@vendor.io"]);
+        assert_eq!(message.thread_root(), Some("a@acme.com"));
+        assert_eq!(message.from_name.as_deref(), Some("Jane Doe"));
+        assert_eq!(message.from_address, "jane@acme.com");
+        assert_eq!(message.to, vec!["sales@vendor.io", "bob@vendor.io"]);
+        assert_eq!(message.subject, "Re: Renewal pricing – Q3");
+        assert_eq!(message.date, Some(1709540100000));
+        assert_eq!(message.text, "We can accept 8% if we sign by Friday.");
+    }
+
+    #[test]
+    fn test_multipart_prefers_plain_text() {
+        let raw = b"Message-ID: <m@x>\r\n\
+From: ops@vendor.io\r\n\
+Content-Type: multipart/alternative; boundary=\"b1\"\r\n\
+\r\n\
+--b1\r\n\
+Content-Type: text/html; charset=utf-8\r\n\
+\r\n\
+<p>HTML version</p>\r\n\
+--b1\r\n\
+Content-Type: text/plain; charset=iso-8859-1\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+\r\n\
+Caf=E9 outage resolved.=\r\n\
+\x20Postmortem to follow.\r\n\
+--b1--\r\n";
+
+        let message = parse_message(raw).unwrap();
+        assert_eq!(message.text, "Café outage resolved. Postmortem to follow.");
+    }
+
+    #[test]
+    fn test_html_only_strips_tags_and_quotes() {
+        let raw = b"Message-ID: <h@x>\r\n\
+From: ops@vendor.io\r\n\
+Content-Type: text/html\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+PGRpdj5TaGlwcGVkICZhbXA7IGRvbmU8L2Rpdj48YmxvY2txdW90ZT5vbGQgc3R1ZmY8L2Jsb2NrcXVvdGU+\r\n";
+
+        let message = parse_message(raw).unwrap();
+        assert_eq!(message.text, "Shipped & done");
+    }
+
+    #[test]
+    fn test_strip_quoted_history() {
+        let text = "Sounds good, ship it.\n\
+\n\
+On Mon, 4 Mar 2024 at 09:15, Jane Doe\n\
+<jane@acme.com> wrote:\n\
+> Can we ship today?\n";
+        assert_eq!(strip_quotes_and_signature(text), "Sounds good, ship it.");
+
+        let outlook = "Approved.\n\nFrom: Bob\nSent: Monday\nSubject: PO\n\nOld text";
+        assert_eq!(strip_quotes_and_signature(outlook), "Approved.");
+
+        let inline =
+            "> question one\nanswer one\n> question two\nanswer two\n\nSent from my iPhone";
+        assert_eq!(strip_quotes_and_signature(inline), "answer one\nanswer two");
+    }
+
+    #[test]
+    fn test_missing_message_id_is_stable() {
+        let raw = b"From: a@b.com\r\nSubject: hi\r\n\r\nbody\r\n";
+        let first = parse_message(raw).unwrap();
+        let second = parse_message(raw).unwrap();
+        assert_eq!(first.message_id, second.message_id);
+        assert!(first.message_id.ends_with("@local"));
+    }
+}
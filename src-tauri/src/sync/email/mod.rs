@@ -0,0 +1,19 @@
+//! Email integration module
+//!
+//! This module provides IMAP access to selected folders, reconstruction of threads
+//! from `Message-ID`/`In-Reply-To`/`References`, quoted-reply and signature
+//! stripping, a sender allow/deny list, and import of mbox and `.eml` archives.
+
+mod connector;
+mod imap;
+mod import;
+mod message;
+mod sync;
+mod types;
+
+pub use connector::{load_sender_filter, save_sender_filter, EmailConnector};
+pub use imap::ImapSession;
+pub use import::{EmailArchiveImporter, EmailImportResult};
+pub use message::parse_message;
+pub use sync::EmailSyncService;
+pub use types::{EmailAccount, EmailError, EmailMessage, SenderFilter};
@@ -0,0 +1,281 @@
+//! Email synchronization service
+
+use std::sync::Arc;
+
+use super::imap::{imap_date, ImapSession};
+use super::message::parse_message;
+use super::types::{EmailAccount, EmailError, EmailMessage, SenderFilter};
+use crate::crypto::CryptoService;
+use crate::db::Database;
+use crate::sync::SyncResult;
+
+/// Position in a folder: UIDs are only comparable within one `UIDVALIDITY`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct FolderCursor {
+    pub uid_validity: u32,
+    pub last_uid: u32,
+}
+
+impl FolderCursor {
+    pub fn parse(cursor: &str) -> Option<Self> {
+        let (validity, uid) = cursor.split_once(':')?;
+        Some(Self {
+            uid_validity: validity.parse().ok()?,
+            last_uid: uid.parse().ok()?,
+        })
+    }
+
+    pub fn format(&self) -> String {
+        format!("{}:{}", self.uid_validity, self.last_uid)
+    }
+}
+
+/// Where a folder sync starts
+pub(super) enum FolderWindow {
+    /// New messages after a stored cursor, or since a date when the cursor is stale
+    Incremental {
+        cursor: Option<FolderCursor>,
+        since_ms: i64,
+    },
+    /// Messages dated within `[start_ms, end_ms)`
+    Range { start_ms: i64, end_ms: i64 },
+}
+
+/// Thread root for a message: an already stored ancestor's root wins over the
+/// message's own headers, so threads survive truncated `References`
+async fn resolve_thread_root(
+    db: &Database,
+    message: &EmailMessage,
+) -> Result<Option<String>, EmailError> {
+    if let Some(parent) = &message.in_reply_to {
+        let stored: Option<(Option<String>,)> = sqlx::query_as(
+            "SELECT parent_id FROM content_items WHERE source = 'email' AND source_id = ?",
+        )
+        .bind(parent)
+        .fetch_optional(db.pool())
+        .await?;
+
+        if let Some((root,)) = stored {
+            return Ok(Some(root.unwrap_or_else(|| parent.clone())));
+        }
+    }
+
+    Ok(message.thread_root().map(String::from))
+}
+
+fn message_text(message: &EmailMessage) -> String {
+    let subject = if message.subject.is_empty() {
+        "(no subject)"
+    } else {
+        &message.subject
+    };
+    if message.text.is_empty() {
+        subject.to_string()
+    } else {
+        format!("{}\n\n{}", subject, message.text)
+    }
+}
+
+/// Encrypt and upsert an email into `content_items`. `folder` is the IMAP folder
+/// or archive name; `uid` is only known for messages fetched over IMAP.
+pub(super) async fn store_message(
+    db: &Database,
+    crypto: &CryptoService,
+    folder: &str,
+    uid: Option<u32>,
+    message: &EmailMessage,
+) -> Result<(), EmailError> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let created_at = message.date.unwrap_or(now);
+    let parent_id = resolve_thread_root(db, message).await?;
+
+    let encrypted_body = crypto
+        .encrypt_string(&message_text(message))
+        .map_err(|e| EmailError::Crypto(e.to_string()))?;
+    let metadata = serde_json::json!({
+        "folder": folder,
+        "uid": uid,
+        "from_name": message.from_name,
+        "to": message.to,
+        "in_reply_to": message.in_reply_to,
+    })
+    .to_string();
+
+    sqlx::query(
+        "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, parent_id, created_at, updated_at, synced_at, metadata)
+         VALUES (?, 'email', ?, NULL, 'email', ?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(source, source_id) DO UPDATE SET title = ?, body = ?, parent_id = ?, synced_at = ?, metadata = ?"
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(&message.message_id)
+    .bind(&message.subject)
+    .bind(&encrypted_body)
+    .bind(&message.from_address)
+    .bind(folder)
+    .bind(&parent_id)
+    .bind(created_at)
+    .bind(created_at)
+    .bind(now)
+    .bind(&metadata)
+    .bind(&message.subject)
+    .bind(&encrypted_body)
+    .bind(&parent_id)
+    .bind(now)
+    .bind(&metadata)
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+pub struct EmailSyncService {
+    session: ImapSession,
+    db: Arc<Database>,
+    crypto: Arc<CryptoService>,
+    filter: SenderFilter,
+}
+
+impl EmailSyncService {
+    /// Log in to the account; call [`EmailSyncService::close`] when done
+    pub async fn connect(
+        account: &EmailAccount,
+        db: Arc<Database>,
+        crypto: Arc<CryptoService>,
+        filter: SenderFilter,
+    ) -> Result<Self, EmailError> {
+        Ok(Self {
+            session: ImapSession::open(account).await?,
+            db,
+            crypto,
+            filter,
+        })
+    }
+
+    /// Sync one folder, returning the result and the cursor to store. The cursor
+    /// stops short of the first message that failed to store, so it is fetched
+    /// again next time; unparseable messages would fail the same way and are
+    /// passed over.
+    pub(super) async fn sync_folder(
+        &mut self,
+        folder: &str,
+        window: FolderWindow,
+    ) -> Result<(SyncResult, FolderCursor), EmailError> {
+        let uid_validity = self.session.examine(folder).await?;
+        let previous_uid = match &window {
+            FolderWindow::Incremental {
+                cursor: Some(cursor),
+                ..
+            } if cursor.uid_validity == uid_validity => cursor.last_uid,
+            _ => 0,
+        };
+
+        let (uids, bounds) = match window {
+            FolderWindow::Incremental {
+                cursor: Some(cursor),
+                ..
+            } if cursor.uid_validity == uid_validity => {
+                // `n:*` always matches the newest message, even below `n`
+                let uids = self
+                    .session
+                    .uid_search(&format!("UID {}:*", cursor.last_uid + 1))
+                    .await?
+                    .into_iter()
+                    .filter(|uid| *uid > cursor.last_uid)
+                    .collect::<Vec<_>>();
+                (uids, None)
+            }
+            FolderWindow::Incremental { since_ms, .. } => {
+                let criteria = format!("SINCE {}", imap_date(since_ms));
+                (self.session.uid_search(&criteria).await?, None)
+            }
+            FolderWindow::Range { start_ms, end_ms } => {
+                // IMAP dates have day granularity; pad a day and filter on `Date`
+                let day_ms = 24 * 60 * 60 * 1000;
+                let criteria = format!(
+                    "SINCE {} BEFORE {}",
+                    imap_date(start_ms - day_ms),
+                    imap_date(end_ms + day_ms)
+                );
+                (
+                    self.session.uid_search(&criteria).await?,
+                    Some((start_ms, end_ms)),
+                )
+            }
+        };
+
+        let mut last_uid = uids.last().copied().unwrap_or(previous_uid);
+        let mut first_failed: Option<u32> = None;
+        let mut items_synced = 0;
+        let mut errors = Vec::new();
+
+        for fetched in self.session.uid_fetch(&uids).await? {
+            last_uid = last_uid.max(fetched.uid);
+
+            let message = match parse_message(&fetched.raw) {
+                Ok(message) => message,
+                Err(e) => {
+                    errors.push(format!("{} UID {}: {}", folder, fetched.uid, e));
+                    continue;
+                }
+            };
+
+            if !self.filter.allows(&message.from_address) {
+                continue;
+            }
+            if let (Some((start, end)), Some(date)) = (bounds, message.date) {
+                if date < start || date >= end {
+                    continue;
+                }
+            }
+
+            match store_message(&self.db, &self.crypto, folder, Some(fetched.uid), &message).await {
+                Ok(()) => items_synced += 1,
+                Err(e) => {
+                    errors.push(format!("{}: {}", message.message_id, e));
+                    first_failed =
+                        Some(first_failed.map_or(fetched.uid, |uid| uid.min(fetched.uid)));
+                }
+            }
+        }
+
+        let cursor = FolderCursor {
+            uid_validity,
+            last_uid: match first_failed {
+                Some(uid) => (uid - 1).max(previous_uid),
+                None => last_uid,
+            },
+        };
+
+        tracing::info!("Synced {} emails from {}", items_synced, folder);
+
+        Ok((
+            SyncResult {
+                source: "email".to_string(),
+                items_synced,
+                errors,
+            },
+            cursor,
+        ))
+    }
+
+    pub async fn close(self) {
+        if let Err(e) = self.session.logout().await {
+            tracing::debug!("IMAP logout failed: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_folder_cursor_roundtrip() {
+        let cursor = FolderCursor {
+            uid_validity: 3857529045,
+            last_uid: 4821,
+        };
+        assert_eq!(FolderCursor::parse(&cursor.format()), Some(cursor));
+        assert_eq!(FolderCursor::parse("garbage"), None);
+    }
+}
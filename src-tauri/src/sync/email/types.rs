@@ -0,0 +1,170 @@
+//! Email data types and error definitions
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum EmailError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("TLS error: {0}")]
+    Tls(String),
+
+    #[error("Authentication failed: {0}")]
+    Auth(String),
+
+    #[error("IMAP error: {0}")]
+    Imap(String),
+
+    #[error("Parse error: {0}")]
+    Parse(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Crypto error: {0}")]
+    Crypto(String),
+}
+
+/// Stored IMAP account credentials
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailAccount {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+    /// Implicit TLS (port 993); plaintext is only allowed to loopback hosts,
+    /// for local bridges
+    #[serde(default = "default_tls")]
+    pub tls: bool,
+}
+
+fn default_tls() -> bool {
+    true
+}
+
+/// Sender allow/deny list. Entries are full addresses (`ceo@vendor.com`) or
+/// domains (`vendor.com`, `@vendor.com`), which also match subdomains.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SenderFilter {
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl SenderFilter {
+    /// Parse a comma, semicolon or newline separated list
+    pub fn parse_list(list: &str) -> Vec<String> {
+        list.split([',', ';', '\n'])
+            .map(|entry| entry.trim().to_lowercase())
+            .filter(|entry| !entry.is_empty())
+            .collect()
+    }
+
+    fn matches(pattern: &str, address: &str) -> bool {
+        let pattern = pattern.trim().to_lowercase();
+        if pattern.contains('@') && !pattern.starts_with('@') {
+            return pattern == address;
+        }
+
+        let domain = pattern.trim_start_matches('@');
+        let Some((_, address_domain)) = address.rsplit_once('@') else {
+            return false;
+        };
+        address_domain == domain || address_domain.ends_with(&format!(".{}", domain))
+    }
+
+    /// Denied senders are always dropped; with a non-empty allow list only
+    /// matching senders are kept
+    pub fn allows(&self, address: &str) -> bool {
+        let address = address.trim().to_lowercase();
+        if self.deny.iter().any(|p| Self::matches(p, &address)) {
+            return false;
+        }
+        self.allow.is_empty() || self.allow.iter().any(|p| Self::matches(p, &address))
+    }
+}
+
+/// An RFC 5322 message reduced to what threading and summarization need
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailMessage {
+    /// `Message-ID` without angle brackets
+    pub message_id: String,
+    pub in_reply_to: Option<String>,
+    /// `References`, oldest first
+    pub references: Vec<String>,
+    pub subject: String,
+    pub from_name: Option<String>,
+    pub from_address: String,
+    pub to: Vec<String>,
+    /// `Date` header in epoch milliseconds
+    pub date: Option<i64>,
+    /// Plain-text body with quoted replies and signatures removed
+    pub text: String,
+}
+
+impl EmailMessage {
+    /// Message-ID of the first message in the thread, if this is a reply
+    pub fn thread_root(&self) -> Option<&str> {
+        self.references
+            .first()
+            .or(self.in_reply_to.as_ref())
+            .map(String::as_str)
+            .filter(|root| *root != self.message_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sender_filter() {
+        let filter = SenderFilter {
+            allow: SenderFilter::parse_list("acme.com, @vendor.io; boss@gmail.com"),
+            deny: SenderFilter::parse_list("noreply@acme.com"),
+        };
+
+        assert!(filter.allows("jane@acme.com"));
+        assert!(filter.allows("ops@eu.acme.com"));
+        assert!(filter.allows("sales@vendor.io"));
+        assert!(filter.allows("Boss@Gmail.com"));
+        assert!(!filter.allows("friend@gmail.com"));
+        assert!(!filter.allows("noreply@acme.com"));
+        assert!(!filter.allows("someone@notacme.com"));
+
+        let open = SenderFilter {
+            allow: vec![],
+            deny: vec!["newsletter.com".into()],
+        };
+        assert!(open.allows("anyone@example.com"));
+        assert!(!open.allows("digest@newsletter.com"));
+    }
+
+    #[test]
+    fn test_thread_root_prefers_references() {
+        let message = EmailMessage {
+            message_id: "c@x".into(),
+            in_reply_to: Some("b@x".into()),
+            references: vec!["a@x".into(), "b@x".into()],
+            subject: "Re: Contract".into(),
+            from_name: None,
+            from_address: "jane@acme.com".into(),
+            to: vec![],
+            date: None,
+            text: String::new(),
+        };
+        assert_eq!(message.thread_root(), Some("a@x"));
+
+        let root = EmailMessage {
+            message_id: "a@x".into(),
+            in_reply_to: None,
+            references: vec![],
+            ..message
+        };
+        assert_eq!(root.thread_root(), None);
+    }
+}
@@ -0,0 +1,419 @@
+//! Sync dry runs
+//!
+//! A dry run walks the same pagination a sync would, but only counts what it
+//! sees: nothing is stored and no cursor moves. The counts are turned into the
+//! Gemini requests the AI pass would make, following the same batching and
+//! hierarchical thresholds as `ProcessingPipeline`, and into a projected time
+//! and cost. Tokens come from a characters-per-token heuristic and prices and
+//! timings are rough averages, so the report is for comparing options (25 busy
+//! channels, a 30-day backfill) rather than a bill.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use super::connector::{ConnectorContext, SourceRegistry, SyncRange};
+use crate::ai::pipeline::{
+    HIERARCHICAL_CHANNEL_CHUNK_SIZE, HIERARCHICAL_CHANNEL_THRESHOLD, HIERARCHICAL_TOTAL_THRESHOLD,
+    HISTORICAL_AI_CHUNK_SIZE,
+};
+
+const CHARS_PER_TOKEN: i64 = 4;
+/// Id, channel, author, timestamp and url around each message in prompt JSON
+const MESSAGE_OVERHEAD_TOKENS: i64 = 40;
+/// Instructions and schema of a batch, channel summary or grouping prompt
+const PROMPT_TEMPLATE_TOKENS: i64 = 1_000;
+/// One channel summary as fed into the cross-channel grouping prompt
+const CHANNEL_SUMMARY_TOKENS: i64 = 300;
+const OUTPUT_TOKENS_PER_REQUEST: i64 = 1_500;
+
+/// List prices of the default Gemini model, in USD per million tokens
+const INPUT_USD_PER_MILLION: f64 = 2.0;
+const OUTPUT_USD_PER_MILLION: f64 = 12.0;
+
+/// Average time per source API request, including the delay between pages
+const SECONDS_PER_API_REQUEST: f64 = 0.75;
+const SECONDS_PER_AI_REQUEST: f64 = 30.0;
+
+/// What a dry run covers
+#[derive(Debug, Clone, Default)]
+pub struct DryRun {
+    /// Historical days to estimate; `None` estimates the next incremental sync
+    pub range: Option<SyncRange>,
+    /// Scopes to estimate per source id instead of the current selection, e.g.
+    /// Slack channels that are not enabled yet
+    pub scopes: HashMap<String, Vec<String>>,
+    /// Minutes west of UTC, for grouping items into local days
+    pub timezone_offset: i32,
+}
+
+impl DryRun {
+    /// Scopes requested for `source`, if any
+    pub fn scopes_for(&self, source: &str) -> Option<&[String]> {
+        self.scopes.get(source).map(Vec::as_slice)
+    }
+}
+
+/// Items with text that the AI pass would analyze, for one scope on one local day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopeVolume {
+    /// Grouping used in prompts: channel name, project or space key
+    pub scope: String,
+    pub date: String,
+    pub items: i64,
+    pub tokens: i64,
+}
+
+/// What a dry run found for one source
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceEstimate {
+    pub source: String,
+    /// Items the sync would store
+    pub items: i64,
+    /// Requests the sync itself would make to the source's API
+    pub api_requests: i64,
+    pub volumes: Vec<ScopeVolume>,
+}
+
+impl SourceEstimate {
+    pub fn new(source: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Count `items` stored under `scope` on `date`, carrying `tokens` of text
+    /// for the AI pass
+    pub fn add(&mut self, scope: &str, date: &str, items: i64, tokens: i64) {
+        self.items += items;
+        if tokens == 0 {
+            return;
+        }
+        match self
+            .volumes
+            .iter_mut()
+            .find(|v| v.scope == scope && v.date == date)
+        {
+            Some(volume) => {
+                volume.items += items;
+                volume.tokens += tokens;
+            }
+            None => self.volumes.push(ScopeVolume {
+                scope: scope.to_string(),
+                date: date.to_string(),
+                items,
+                tokens,
+            }),
+        }
+    }
+}
+
+/// Projected volume, time and cost of a sync and the AI pass after it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncEstimate {
+    pub sources: Vec<SourceEstimate>,
+    /// Connected sources that cannot be estimated
+    pub skipped: Vec<String>,
+    pub errors: Vec<String>,
+    pub items: i64,
+    pub api_requests: i64,
+    pub ai_requests: i64,
+    pub prompt_tokens: i64,
+    pub output_tokens: i64,
+    /// Whether any AI batch is large enough for hierarchical summarization
+    pub hierarchical: bool,
+    pub sync_seconds: i64,
+    pub ai_seconds: i64,
+    pub cost_usd: f64,
+}
+
+/// `n / d` rounded up, for counting pages and chunks; `i64::div_ceil` is
+/// not stable
+pub fn ceil_div(n: i64, d: i64) -> i64 {
+    (n.max(0) as u64).div_ceil(d.max(1) as u64) as i64
+}
+
+/// Approximate prompt tokens for one item with `text`; empty items are
+/// skipped by the AI pass and cost nothing
+pub fn text_tokens(text: &str) -> i64 {
+    if text.trim().is_empty() {
+        return 0;
+    }
+    MESSAGE_OVERHEAD_TOKENS + ceil_div(text.chars().count() as i64, CHARS_PER_TOKEN)
+}
+
+/// Local `YYYY-MM-DD` of an epoch-millisecond time, `timezone_offset` minutes west of UTC
+pub fn local_date(epoch_ms: i64, timezone_offset: i32) -> String {
+    let offset = chrono::FixedOffset::west_opt(timezone_offset * 60)
+        .unwrap_or(chrono::FixedOffset::east_opt(0).unwrap());
+    chrono::DateTime::from_timestamp_millis(epoch_ms)
+        .map(|dt| dt.with_timezone(&offset).format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Gemini requests and prompt tokens for some batches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct AiPlan {
+    requests: i64,
+    prompt_tokens: i64,
+    hierarchical: bool,
+}
+
+impl AiPlan {
+    fn add(&mut self, other: AiPlan) {
+        self.requests += other.requests;
+        self.prompt_tokens += other.prompt_tokens;
+        self.hierarchical |= other.hierarchical;
+    }
+}
+
+/// One batch over `(items, tokens)` per channel, choosing direct or
+/// hierarchical processing the way `ProcessingPipeline` does
+fn plan_batch(channels: &[(i64, i64)]) -> AiPlan {
+    let items: i64 = channels.iter().map(|(items, _)| items).sum();
+    let tokens: i64 = channels.iter().map(|(_, tokens)| tokens).sum();
+    if items == 0 {
+        return AiPlan::default();
+    }
+
+    if items < HIERARCHICAL_TOTAL_THRESHOLD as i64 {
+        return AiPlan {
+            requests: 1,
+            prompt_tokens: PROMPT_TEMPLATE_TOKENS + tokens,
+            hierarchical: false,
+        };
+    }
+
+    let mut plan = AiPlan {
+        hierarchical: true,
+        ..Default::default()
+    };
+    let mut summaries = 0;
+    let mut direct_tokens = 0;
+    for &(items, tokens) in channels {
+        if items >= HIERARCHICAL_CHANNEL_THRESHOLD as i64 {
+            let chunks = ceil_div(items, HIERARCHICAL_CHANNEL_CHUNK_SIZE as i64);
+            plan.requests += chunks;
+            plan.prompt_tokens += chunks * PROMPT_TEMPLATE_TOKENS + tokens;
+            summaries += chunks;
+        } else {
+            direct_tokens += tokens;
+        }
+    }
+
+    // Cross-channel grouping over the summaries and the smaller channels
+    plan.requests += 1;
+    plan.prompt_tokens +=
+        PROMPT_TEMPLATE_TOKENS + summaries * CHANNEL_SUMMARY_TOKENS + direct_tokens;
+    plan
+}
+
+/// `process_batch_for_date` over one day: items are taken in chunks of
+/// [`HISTORICAL_AI_CHUNK_SIZE`] by creation time, so each chunk is assumed to
+/// carry an even share of every channel
+fn plan_historical_day(channels: &[(i64, i64)]) -> AiPlan {
+    let items: i64 = channels.iter().map(|(items, _)| items).sum();
+    let chunks = ceil_div(items, HISTORICAL_AI_CHUNK_SIZE);
+    if chunks == 0 {
+        return AiPlan::default();
+    }
+
+    let share: Vec<(i64, i64)> = channels
+        .iter()
+        .map(|&(items, tokens)| (ceil_div(items, chunks), ceil_div(tokens, chunks)))
+        .collect();
+    let chunk = plan_batch(&share);
+    AiPlan {
+        requests: chunk.requests * chunks,
+        prompt_tokens: chunk.prompt_tokens * chunks,
+        hierarchical: chunk.hierarchical,
+    }
+}
+
+/// Plan the AI pass over every source's volumes: a batch per day after a
+/// historical sync, or after an incremental one the daily batch, which only
+/// covers `today`
+fn plan_ai(sources: &[SourceEstimate], today: Option<&str>) -> AiPlan {
+    let mut by_day: BTreeMap<&str, BTreeMap<&str, (i64, i64)>> = BTreeMap::new();
+    for volume in sources.iter().flat_map(|s| &s.volumes) {
+        if today.is_some_and(|today| volume.date != today) {
+            continue;
+        }
+        let channel = by_day
+            .entry(volume.date.as_str())
+            .or_default()
+            .entry(volume.scope.as_str())
+            .or_default();
+        channel.0 += volume.items;
+        channel.1 += volume.tokens;
+    }
+
+    let mut plan = AiPlan::default();
+    for channels in by_day.values() {
+        let channels: Vec<(i64, i64)> = channels.values().copied().collect();
+        plan.add(match today {
+            Some(_) => plan_batch(&channels),
+            None => plan_historical_day(&channels),
+        });
+    }
+    plan
+}
+
+/// Totals, AI plan, time and cost for the estimates of each source. `today`
+/// is set for incremental syncs, whose AI pass only covers the current day.
+pub fn build_report(
+    sources: Vec<SourceEstimate>,
+    skipped: Vec<String>,
+    errors: Vec<String>,
+    today: Option<&str>,
+) -> SyncEstimate {
+    let ai = plan_ai(&sources, today);
+    let items = sources.iter().map(|s| s.items).sum();
+    let api_requests = sources.iter().map(|s| s.api_requests).sum();
+    let output_tokens = ai.requests * OUTPUT_TOKENS_PER_REQUEST;
+    let cost_usd = (ai.prompt_tokens as f64 * INPUT_USD_PER_MILLION
+        + output_tokens as f64 * OUTPUT_USD_PER_MILLION)
+        / 1_000_000.0;
+
+    SyncEstimate {
+        sources,
+        skipped,
+        errors,
+        items,
+        api_requests,
+        ai_requests: ai.requests,
+        prompt_tokens: ai.prompt_tokens,
+        output_tokens,
+        hierarchical: ai.hierarchical,
+        sync_seconds: (api_requests as f64 * SECONDS_PER_API_REQUEST).ceil() as i64,
+        ai_seconds: (ai.requests as f64 * SECONDS_PER_AI_REQUEST).ceil() as i64,
+        cost_usd,
+    }
+}
+
+/// Dry-run every connected source (optionally only those in `only`) and
+/// project the sync and AI pass that would follow
+pub async fn estimate_sync(
+    registry: &SourceRegistry,
+    ctx: &ConnectorContext,
+    only: Option<&[String]>,
+    dry_run: &DryRun,
+) -> SyncEstimate {
+    let mut sources = Vec::new();
+    let mut skipped = Vec::new();
+    let mut errors = Vec::new();
+
+    for connector in registry.connected(ctx, only).await {
+        if ctx.check_cancelled().is_err() {
+            break;
+        }
+        match connector.estimate(ctx, dry_run).await {
+            Ok(Some(estimate)) => sources.push(estimate),
+            Ok(None) => skipped.push(connector.display_name().to_string()),
+            Err(e) => {
+                tracing::warn!("Dry run of {} failed: {}", connector.display_name(), e);
+                errors.push(format!("{}: {}", connector.display_name(), e));
+            }
+        }
+    }
+
+    let today = dry_run.range.is_none().then(|| {
+        local_date(
+            chrono::Utc::now().timestamp_millis(),
+            dry_run.timezone_offset,
+        )
+    });
+    build_report(sources, skipped, errors, today.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_tokens() {
+        assert_eq!(text_tokens("   "), 0);
+        assert_eq!(text_tokens("abcd"), MESSAGE_OVERHEAD_TOKENS + 1);
+        assert_eq!(text_tokens("abcde"), MESSAGE_OVERHEAD_TOKENS + 2);
+    }
+
+    #[test]
+    fn test_local_date() {
+        // 2024-01-15T02:00:00Z is still the 14th five hours west of UTC
+        assert_eq!(local_date(1705284000000, 0), "2024-01-15");
+        assert_eq!(local_date(1705284000000, 300), "2024-01-14");
+    }
+
+    #[test]
+    fn test_plan_batch_direct_below_threshold() {
+        let plan = plan_batch(&[(100, 5_000), (50, 2_000)]);
+        assert_eq!(
+            plan,
+            AiPlan {
+                requests: 1,
+                prompt_tokens: PROMPT_TEMPLATE_TOKENS + 7_000,
+                hierarchical: false,
+            }
+        );
+        assert_eq!(plan_batch(&[]), AiPlan::default());
+    }
+
+    #[test]
+    fn test_plan_batch_hierarchical() {
+        // 170 messages in one channel summarize in 3 chunks; the small channel
+        // goes straight into the grouping prompt
+        let plan = plan_batch(&[(170, 10_000), (40, 1_000)]);
+        assert!(plan.hierarchical);
+        assert_eq!(plan.requests, 3 + 1);
+        assert_eq!(
+            plan.prompt_tokens,
+            3 * PROMPT_TEMPLATE_TOKENS
+                + 10_000
+                + PROMPT_TEMPLATE_TOKENS
+                + 3 * CHANNEL_SUMMARY_TOKENS
+                + 1_000
+        );
+    }
+
+    #[test]
+    fn test_build_report_daily_batch() {
+        let mut slack = SourceEstimate::new("slack");
+        slack.add("eng", "2024-01-15", 170, 10_000);
+        slack.add("random", "2024-01-15", 40, 1_000);
+        let mut jira = SourceEstimate::new("jira");
+        jira.add("OPS", "2024-01-15", 5, 500);
+
+        let report = build_report(vec![slack, jira], vec![], vec![], Some("2024-01-15"));
+        assert_eq!(report.items, 215);
+        assert!(report.hierarchical);
+        assert_eq!(report.ai_requests, 4);
+        assert_eq!(report.ai_seconds, 120);
+    }
+
+    #[test]
+    fn test_build_report_historical_days() {
+        let mut slack = SourceEstimate::new("slack");
+        slack.api_requests = 40;
+        slack.add("eng", "2024-01-14", 400, 40_000);
+        slack.add("eng", "2024-01-15", 10, 500);
+        slack.add("eng", "2024-01-15", 0, 0);
+
+        let report = build_report(vec![slack], vec![], vec![], None);
+        assert_eq!(report.items, 410);
+        // 400 messages need 3 historical chunks, the quiet day one
+        assert_eq!(report.ai_requests, 4);
+        assert!(!report.hierarchical);
+        assert_eq!(report.output_tokens, 4 * OUTPUT_TOKENS_PER_REQUEST);
+        assert_eq!(report.sync_seconds, 30);
+        assert!(report.cost_usd > 0.0);
+
+        // An incremental sync's daily batch only covers today
+        let incremental = build_report(report.sources, vec![], vec![], Some("2024-01-15"));
+        assert_eq!(incremental.items, 410);
+        assert_eq!(incremental.ai_requests, 1);
+        assert!(!incremental.hierarchical);
+    }
+}
@@ -0,0 +1,379 @@
+//! RSS/Atom source connector
+//!
+//! Follows any number of feeds, each polled no more often than its own
+//! interval. The `sync_state` row of a feed holds its ETag/Last-Modified
+//! validators as the cursor and its last fetch time.
+
+use async_trait::async_trait;
+
+use super::sync::{FeedSyncService, FetchOutcome};
+use super::types::{FeedSubscription, FeedValidators, DEFAULT_FEED_INTERVAL_MINUTES};
+use crate::pipeline::PipelineTaskType;
+use crate::sync::connectivity::is_unreachable;
+use crate::sync::connector::{
+    clear_sync_state, last_sync_at, load_credentials, load_cursor, save_cursor, store_credentials,
+    ConnectorContext, ConnectorError, SourceConnector, SourceHealth, SourceScope, SyncRange,
+};
+use crate::sync::status::mark_pending;
+use crate::sync::SyncResult;
+
+/// `sync_state` resource type; the resource id is the feed URL
+const FEED_RESOURCE: &str = "feed";
+
+pub(crate) async fn load_feed_subscriptions(
+    ctx: &ConnectorContext,
+) -> Result<Vec<FeedSubscription>, ConnectorError> {
+    Ok(load_credentials(ctx, "rss").await?.unwrap_or_default())
+}
+
+/// When a feed was last fetched, in epoch milliseconds
+async fn last_fetched_at(ctx: &ConnectorContext, url: &str) -> Option<i64> {
+    sqlx::query_as::<_, (Option<i64>,)>(
+        "SELECT last_sync_at FROM sync_state WHERE source = 'rss' AND resource_type = ? AND resource_id = ?",
+    )
+    .bind(FEED_RESOURCE)
+    .bind(url)
+    .fetch_optional(ctx.db.pool())
+    .await
+    .ok()
+    .flatten()
+    .and_then(|r| r.0)
+}
+
+fn is_due(feed: &FeedSubscription, last_fetched_at: Option<i64>, now_ms: i64) -> bool {
+    let interval_ms = i64::from(feed.interval_minutes.max(1)) * 60 * 1000;
+    last_fetched_at.is_none_or(|last| now_ms - last >= interval_ms)
+}
+
+fn config_str<'a>(config: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    config[key]
+        .as_str()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+pub struct FeedConnector;
+
+impl FeedConnector {
+    async fn feeds(&self, ctx: &ConnectorContext) -> Result<Vec<FeedSubscription>, ConnectorError> {
+        let feeds = load_feed_subscriptions(ctx).await?;
+        if feeds.is_empty() {
+            return Err(ConnectorError::NotConnected("RSS"));
+        }
+        Ok(feeds)
+    }
+}
+
+#[async_trait]
+impl SourceConnector for FeedConnector {
+    fn id(&self) -> &'static str {
+        "rss"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "RSS & Atom"
+    }
+
+    fn description(&self) -> &'static str {
+        "Follow status pages, blogs and release notes"
+    }
+
+    fn task_type(&self) -> PipelineTaskType {
+        PipelineTaskType::SyncFeeds
+    }
+
+    fn item_label(&self) -> &'static str {
+        "entries"
+    }
+
+    /// Accepts `{ "url", "name"?, "intervalMinutes"? }`. Connecting a URL that
+    /// is already followed updates its name and interval.
+    async fn connect(
+        &self,
+        ctx: &ConnectorContext,
+        config: serde_json::Value,
+    ) -> Result<serde_json::Value, ConnectorError> {
+        let url = config_str(&config, "url")
+            .ok_or_else(|| ConnectorError::InvalidConfig("feed URL is required".into()))?;
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(ConnectorError::InvalidConfig(format!(
+                "Unsupported feed URL: {}",
+                url
+            )));
+        }
+        // The settings form sends the interval as text, API callers as a number
+        let interval = config["intervalMinutes"]
+            .as_u64()
+            .map(|i| i.to_string())
+            .or_else(|| config_str(&config, "intervalMinutes").map(String::from));
+        let interval_minutes = match interval {
+            Some(interval) => interval
+                .parse::<u32>()
+                .ok()
+                .filter(|i| *i >= 1)
+                .ok_or_else(|| {
+                    ConnectorError::InvalidConfig(format!("invalid interval {}", interval))
+                })?,
+            None => DEFAULT_FEED_INTERVAL_MINUTES,
+        };
+
+        let feed = match FeedSyncService::new(ctx.db.clone(), ctx.crypto.clone())
+            .fetch(url, &FeedValidators::default())
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?
+        {
+            FetchOutcome::Fetched(feed, _) => feed,
+            FetchOutcome::NotModified => {
+                return Err(ConnectorError::Failed("feed returned no content".into()))
+            }
+        };
+
+        let subscription = FeedSubscription {
+            url: url.to_string(),
+            name: config_str(&config, "name").map(String::from),
+            interval_minutes,
+        };
+        let name = subscription
+            .name
+            .clone()
+            .or(feed.title.clone())
+            .unwrap_or_else(|| url.to_string());
+
+        let mut feeds = load_feed_subscriptions(ctx).await?;
+        feeds.retain(|f| f.url != subscription.url);
+        feeds.push(subscription);
+        store_credentials(ctx, "rss", &feeds).await?;
+
+        tracing::info!(
+            "Following feed {} ({} entries, every {} min)",
+            name,
+            feed.entries.len(),
+            interval_minutes
+        );
+        Ok(serde_json::json!({
+            "name": name,
+            "entries": feed.entries.len(),
+            "feeds": feeds.len(),
+        }))
+    }
+
+    async fn is_connected(&self, ctx: &ConnectorContext) -> bool {
+        load_feed_subscriptions(ctx)
+            .await
+            .is_ok_and(|feeds| !feeds.is_empty())
+    }
+
+    async fn list_scopes(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<Vec<SourceScope>, ConnectorError> {
+        Ok(self
+            .feeds(ctx)
+            .await?
+            .into_iter()
+            .map(|feed| SourceScope {
+                name: feed.name.unwrap_or_else(|| feed.url.clone()),
+                id: feed.url,
+                kind: "feed".to_string(),
+                selected: true,
+            })
+            .collect())
+    }
+
+    /// Fetch each feed whose interval has elapsed. A failing feed is reported
+    /// in `errors` and retried next time without holding up the others.
+    async fn sync_incremental(&self, ctx: &ConnectorContext) -> Result<SyncResult, ConnectorError> {
+        let feeds = self.feeds(ctx).await?;
+        let service = FeedSyncService::new(ctx.db.clone(), ctx.crypto.clone());
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut result = SyncResult {
+            source: self.id().to_string(),
+            items_synced: 0,
+            errors: vec![],
+        };
+
+        let mut due = Vec::new();
+        for subscription in &feeds {
+            if is_due(
+                subscription,
+                last_fetched_at(ctx, &subscription.url).await,
+                now_ms,
+            ) {
+                due.push(subscription);
+            }
+        }
+        let due_urls: Vec<String> = due.iter().map(|s| s.url.clone()).collect();
+        mark_pending(&ctx.db, self.id(), FEED_RESOURCE, &due_urls).await;
+
+        // Feeds live on different hosts, so the source only counts as
+        // unreachable when none of them could be reached
+        let mut unreachable = None;
+        let mut reached = false;
+        for subscription in due {
+            let validators: FeedValidators =
+                load_cursor(&ctx.db, self.id(), FEED_RESOURCE, &subscription.url)
+                    .await?
+                    .and_then(|c| serde_json::from_str(&c).ok())
+                    .unwrap_or_default();
+
+            let fetch = service.fetch(&subscription.url, &validators);
+            let fetched = ctx
+                .track_resource(self.id(), FEED_RESOURCE, &subscription.url, fetch)
+                .await;
+            ctx.check_cancelled()?;
+            reached |= fetched.is_ok();
+            let validators = match fetched {
+                Ok(FetchOutcome::NotModified) => validators,
+                Ok(FetchOutcome::Fetched(feed, validators)) => {
+                    for entry in &feed.entries {
+                        match service
+                            .store_entry(subscription, feed.title.as_deref(), entry)
+                            .await
+                        {
+                            Ok(true) => result.items_synced += 1,
+                            Ok(false) => {}
+                            Err(e) => result.errors.push(format!("{}: {}", entry.guid, e)),
+                        }
+                    }
+                    validators
+                }
+                Err(e) => {
+                    tracing::warn!("Feed {} failed: {}", subscription.url, e);
+                    if is_unreachable(&e) {
+                        unreachable = Some(e.to_string());
+                    }
+                    result.errors.push(format!("{}: {}", subscription.url, e));
+                    continue;
+                }
+            };
+
+            let cursor = serde_json::to_string(&validators).unwrap_or_default();
+            save_cursor(
+                &ctx.db,
+                self.id(),
+                FEED_RESOURCE,
+                &subscription.url,
+                &cursor,
+            )
+            .await?;
+        }
+
+        match unreachable {
+            Some(error) if !reached => Err(ConnectorError::Unreachable(error)),
+            _ => Ok(result),
+        }
+    }
+
+    /// Feeds only carry their latest entries, so this stores those dated in
+    /// the range
+    async fn sync_range(
+        &self,
+        ctx: &ConnectorContext,
+        range: &SyncRange,
+    ) -> Result<SyncResult, ConnectorError> {
+        let feeds = self.feeds(ctx).await?;
+        let service = FeedSyncService::new(ctx.db.clone(), ctx.crypto.clone());
+        let (start, end) = range.utc_bounds();
+        let (start, end) = (start * 1000, end * 1000);
+        let mut result = SyncResult {
+            source: self.id().to_string(),
+            items_synced: 0,
+            errors: vec![],
+        };
+
+        let mut unreachable = None;
+        let mut reached = false;
+        for subscription in &feeds {
+            let fetched = service
+                .fetch(&subscription.url, &FeedValidators::default())
+                .await;
+            ctx.check_cancelled()?;
+            reached |= fetched.is_ok();
+            let feed = match fetched {
+                Ok(FetchOutcome::Fetched(feed, _)) => feed,
+                Ok(FetchOutcome::NotModified) => continue,
+                Err(e) => {
+                    if is_unreachable(&e) {
+                        unreachable = Some(e.to_string());
+                    }
+                    result.errors.push(format!("{}: {}", subscription.url, e));
+                    continue;
+                }
+            };
+
+            for entry in feed.entries.iter().filter(|entry| {
+                entry
+                    .published
+                    .or(entry.updated)
+                    .is_some_and(|at| at >= start && at < end)
+            }) {
+                match service
+                    .store_entry(subscription, feed.title.as_deref(), entry)
+                    .await
+                {
+                    Ok(_) => result.items_synced += 1,
+                    Err(e) => result.errors.push(format!("{}: {}", entry.guid, e)),
+                }
+            }
+        }
+
+        match unreachable {
+            Some(error) if !reached => Err(ConnectorError::Unreachable(error)),
+            _ => Ok(result),
+        }
+    }
+
+    async fn health(&self, ctx: &ConnectorContext) -> SourceHealth {
+        let mut health = SourceHealth {
+            source: self.id().to_string(),
+            connected: false,
+            healthy: false,
+            message: None,
+            last_sync_at: last_sync_at(&ctx.db, self.id()).await,
+        };
+
+        match load_feed_subscriptions(ctx).await {
+            Ok(feeds) if feeds.is_empty() => {}
+            Ok(feeds) => {
+                health.connected = true;
+                health.healthy = true;
+                health.message = Some(format!("Following {} feeds", feeds.len()));
+            }
+            Err(e) => {
+                health.connected = true;
+                health.message = Some(e.to_string());
+            }
+        }
+
+        health
+    }
+
+    async fn disconnect(&self, ctx: &ConnectorContext) -> Result<(), ConnectorError> {
+        sqlx::query("DELETE FROM credentials WHERE id = 'rss'")
+            .execute(ctx.db.pool())
+            .await?;
+
+        clear_sync_state(&ctx.db, self.id()).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_due() {
+        let feed = FeedSubscription {
+            url: "https://status.acme.com/history.rss".into(),
+            name: None,
+            interval_minutes: 15,
+        };
+        let now = 1_000_000_000;
+
+        assert!(is_due(&feed, None, now));
+        assert!(!is_due(&feed, Some(now - 14 * 60 * 1000), now));
+        assert!(is_due(&feed, Some(now - 15 * 60 * 1000), now));
+    }
+}
@@ -0,0 +1,14 @@
+//! RSS and Atom feed integration module
+//!
+//! This module follows RSS 2.0, RSS 1.0 and Atom feeds with per-feed polling
+//! intervals and conditional fetches, storing entries as plain-text articles.
+
+mod connector;
+mod parse;
+mod sync;
+mod types;
+
+pub use connector::FeedConnector;
+pub use parse::{html_to_text, parse_feed};
+pub use sync::{FeedSyncService, FetchOutcome};
+pub use types::{Feed, FeedEntry, FeedError, FeedSubscription, FeedValidators};
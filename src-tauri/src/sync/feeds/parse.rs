@@ -0,0 +1,342 @@
+//! RSS 2.0, RSS 1.0 and Atom parsing with HTML-to-text cleanup
+
+use roxmltree::Node;
+use sha2::{Digest, Sha256};
+
+use super::types::{Feed, FeedEntry, FeedError};
+
+/// Block-level tags that start a new line in the text version
+const BLOCK_TAGS: &[&str] = &[
+    "p",
+    "br",
+    "div",
+    "li",
+    "tr",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "blockquote",
+    "pre",
+    "ul",
+    "ol",
+    "table",
+    "hr",
+];
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        "mdash" => Some('—'),
+        "ndash" => Some('–'),
+        "hellip" => Some('…'),
+        "rsquo" | "lsquo" => Some('\''),
+        "rdquo" | "ldquo" => Some('"'),
+        _ => {
+            let code = entity.strip_prefix('#')?;
+            let value = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(value)
+        }
+    }
+}
+
+/// Convert an HTML fragment to readable text: block tags become line breaks,
+/// list items get bullets, scripts and styles are dropped, entities decoded
+pub fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(pos) = rest.find(['<', '&']) {
+        text.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+
+        if rest.starts_with('&') {
+            let entity = rest[1..]
+                .find(';')
+                .filter(|end| *end <= 10)
+                .and_then(|end| Some((decode_entity(&rest[1..end + 1])?, end + 2)));
+            match entity {
+                Some((c, len)) => {
+                    text.push(c);
+                    rest = &rest[len..];
+                }
+                None => {
+                    text.push('&');
+                    rest = &rest[1..];
+                }
+            }
+            continue;
+        }
+
+        let Some(end) = rest.find('>') else {
+            text.push_str(rest);
+            rest = "";
+            break;
+        };
+        let closing = rest[1..end].starts_with('/');
+        let tag = rest[1..end].trim_start_matches('/').to_ascii_lowercase();
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        rest = &rest[end + 1..];
+
+        if !closing && (name == "script" || name == "style") {
+            let close = format!("</{}", name);
+            rest = match rest.to_ascii_lowercase().find(&close) {
+                Some(pos) => &rest[pos..],
+                None => "",
+            };
+            continue;
+        }
+        if BLOCK_TAGS.contains(&name) {
+            text.push('\n');
+        }
+        if name == "li" && !closing {
+            text.push_str("• ");
+        }
+    }
+    text.push_str(rest);
+
+    // Collapse runs of spaces and the empty lines left by nested block tags
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// All text under a node, including CDATA sections and XHTML content
+fn node_text(node: Node) -> Option<String> {
+    let text: String = node
+        .descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect();
+    let text = text.trim().to_string();
+    Some(text).filter(|t| !t.is_empty())
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|c| c.has_tag_name(name))
+}
+
+fn child_text(node: Node, name: &str) -> Option<String> {
+    child(node, name).and_then(node_text)
+}
+
+fn parse_date(value: &str) -> Option<i64> {
+    let value = value.trim();
+    chrono::DateTime::parse_from_rfc2822(value)
+        .or_else(|_| chrono::DateTime::parse_from_rfc3339(value))
+        .ok()
+        .map(|dt| dt.timestamp_millis())
+        .or_else(|| {
+            // Some feeds write `GMT`-less or single-digit-day RFC 822 dates
+            chrono::NaiveDateTime::parse_from_str(value, "%a, %d %b %Y %H:%M:%S")
+                .ok()
+                .map(|dt| dt.and_utc().timestamp_millis())
+        })
+}
+
+/// Stable id for entries without a guid, id or link
+fn fallback_guid(title: Option<&str>, text: &str) -> String {
+    let digest = Sha256::digest(format!("{}\n{}", title.unwrap_or_default(), text));
+    format!("{:x}", digest)[..16].to_string()
+}
+
+fn rss_item(item: Node) -> FeedEntry {
+    let title = child_text(item, "title").map(|t| html_to_text(&t));
+    let link = child_text(item, "link");
+    let html = child_text(item, "encoded").or_else(|| child_text(item, "description"));
+    let text = html.map(|h| html_to_text(&h)).unwrap_or_default();
+    let published = child_text(item, "pubDate")
+        .or_else(|| child_text(item, "date"))
+        .and_then(|d| parse_date(&d));
+
+    FeedEntry {
+        guid: child_text(item, "guid")
+            .or_else(|| {
+                item.attribute(("http://www.w3.org/1999/02/22-rdf-syntax-ns#", "about"))
+                    .map(String::from)
+            })
+            .or_else(|| link.clone())
+            .unwrap_or_else(|| fallback_guid(title.as_deref(), &text)),
+        title,
+        link,
+        author: child_text(item, "creator").or_else(|| child_text(item, "author")),
+        text,
+        published,
+        updated: None,
+    }
+}
+
+/// The `alternate` link of an Atom entry, or its first link
+fn atom_link(entry: Node) -> Option<String> {
+    let links: Vec<Node> = entry
+        .children()
+        .filter(|c| c.has_tag_name("link"))
+        .collect();
+    links
+        .iter()
+        .find(|l| l.attribute("rel").is_none_or(|rel| rel == "alternate"))
+        .or(links.first())
+        .and_then(|l| l.attribute("href"))
+        .map(String::from)
+}
+
+fn atom_entry(entry: Node) -> FeedEntry {
+    let title = child_text(entry, "title").map(|t| html_to_text(&t));
+    let link = atom_link(entry);
+    let text = child(entry, "content")
+        .or_else(|| child(entry, "summary"))
+        .and_then(|content| {
+            let raw = node_text(content)?;
+            Some(match content.attribute("type") {
+                Some("html") | Some("xhtml") | Some("text/html") => html_to_text(&raw),
+                _ => raw,
+            })
+        })
+        .unwrap_or_default();
+    let updated = child_text(entry, "updated").and_then(|d| parse_date(&d));
+
+    FeedEntry {
+        guid: child_text(entry, "id")
+            .or_else(|| link.clone())
+            .unwrap_or_else(|| fallback_guid(title.as_deref(), &text)),
+        title,
+        link,
+        author: child(entry, "author").and_then(|a| child_text(a, "name")),
+        text,
+        published: child_text(entry, "published")
+            .and_then(|d| parse_date(&d))
+            .or(updated),
+        updated,
+    }
+}
+
+/// Parse an RSS 2.0, RSS 1.0 (RDF) or Atom document
+pub fn parse_feed(xml: &str) -> Result<Feed, FeedError> {
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let doc = roxmltree::Document::parse_with_options(xml.trim_start_matches('\u{feff}'), options)
+        .map_err(|e| FeedError::Parse(e.to_string()))?;
+    let root = doc.root_element();
+
+    match root.tag_name().name() {
+        "feed" => Ok(Feed {
+            title: child_text(root, "title").map(|t| html_to_text(&t)),
+            entries: root
+                .children()
+                .filter(|c| c.has_tag_name("entry"))
+                .map(atom_entry)
+                .collect(),
+        }),
+        "rss" | "RDF" => {
+            let channel = child(root, "channel");
+            // RSS 2.0 nests items in <channel>; RSS 1.0 puts them beside it
+            let items = channel
+                .filter(|c| c.children().any(|i| i.has_tag_name("item")))
+                .unwrap_or(root);
+            Ok(Feed {
+                title: channel.and_then(|c| child_text(c, "title")),
+                entries: items
+                    .children()
+                    .filter(|c| c.has_tag_name("item"))
+                    .map(rss_item)
+                    .collect(),
+            })
+        }
+        other => Err(FeedError::Parse(format!(
+            "expected an RSS or Atom document, found <{}>",
+            other
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rss() {
+        let xml = r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <channel>
+    <title>Acme Status</title>
+    <item>
+      <title>Degraded API latency</title>
+      <link>https://status.acme.com/incidents/1</link>
+      <guid isPermaLink="false">incident-1</guid>
+      <dc:creator>Ops</dc:creator>
+      <pubDate>Tue, 05 Mar 2024 10:00:00 GMT</pubDate>
+      <description>short</description>
+      <content:encoded><![CDATA[<p>We are <b>investigating</b> &amp; monitoring.</p><ul><li>EU</li><li>US</li></ul>]]></content:encoded>
+    </item>
+    <item>
+      <title>Resolved</title>
+      <link>https://status.acme.com/incidents/2</link>
+    </item>
+  </channel>
+</rss>"#;
+        let feed = parse_feed(xml).unwrap();
+
+        assert_eq!(feed.title.as_deref(), Some("Acme Status"));
+        assert_eq!(feed.entries.len(), 2);
+        let entry = &feed.entries[0];
+        assert_eq!(entry.guid, "incident-1");
+        assert_eq!(entry.author.as_deref(), Some("Ops"));
+        assert_eq!(entry.text, "We are investigating & monitoring.\n• EU\n• US");
+        assert_eq!(entry.published, Some(1_709_632_800_000));
+        assert_eq!(feed.entries[1].guid, "https://status.acme.com/incidents/2");
+    }
+
+    #[test]
+    fn test_parse_atom() {
+        let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+  <title type="text">Engineering Blog</title>
+  <entry>
+    <id>tag:blog.acme.com,2024:42</id>
+    <title>Migrating to Postgres 16</title>
+    <link rel="replies" href="https://blog.acme.com/42#comments"/>
+    <link rel="alternate" href="https://blog.acme.com/42"/>
+    <author><name>Riley</name></author>
+    <updated>2024-03-06T09:30:00Z</updated>
+    <content type="html">&lt;h2&gt;Why&lt;/h2&gt;&lt;p&gt;Faster&amp;nbsp;vacuum.&lt;/p&gt;</content>
+  </entry>
+</feed>"#;
+        let feed = parse_feed(xml).unwrap();
+
+        assert_eq!(feed.title.as_deref(), Some("Engineering Blog"));
+        let entry = &feed.entries[0];
+        assert_eq!(entry.guid, "tag:blog.acme.com,2024:42");
+        assert_eq!(entry.link.as_deref(), Some("https://blog.acme.com/42"));
+        assert_eq!(entry.author.as_deref(), Some("Riley"));
+        assert_eq!(entry.text, "Why\nFaster vacuum.");
+        assert_eq!(entry.published, entry.updated);
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        assert_eq!(
+            html_to_text(
+                "<div>Release&#x20;<em>1.2</em></div><script>x()</script><p>Fixes &#8212; many</p>"
+            ),
+            "Release 1.2\nFixes — many"
+        );
+        assert_eq!(html_to_text("AT&T < 5"), "AT&T < 5");
+        assert!(parse_feed("<html><body/></html>").is_err());
+    }
+}
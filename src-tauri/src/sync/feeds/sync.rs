@@ -0,0 +1,159 @@
+//! Feed fetching and storage
+
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{Client, StatusCode};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+use super::parse::parse_feed;
+use super::types::{Feed, FeedEntry, FeedError, FeedSubscription, FeedValidators};
+use crate::crypto::CryptoService;
+use crate::db::Database;
+
+/// Result of a conditional fetch
+pub enum FetchOutcome {
+    NotModified,
+    Fetched(Feed, FeedValidators),
+}
+
+/// Short stable key for a feed URL, prefixed to entry ids so GUIDs only need to
+/// be unique within their feed
+pub(super) fn feed_key(url: &str) -> String {
+    format!("{:x}", Sha256::digest(url.trim().as_bytes()))[..12].to_string()
+}
+
+pub struct FeedSyncService {
+    http: Client,
+    db: Arc<Database>,
+    crypto: Arc<CryptoService>,
+}
+
+impl FeedSyncService {
+    pub fn new(db: Arc<Database>, crypto: Arc<CryptoService>) -> Self {
+        Self {
+            http: Client::new(),
+            db,
+            crypto,
+        }
+    }
+
+    /// Fetch a feed, sending `If-None-Match`/`If-Modified-Since` from the
+    /// previous fetch so unchanged feeds cost a `304`
+    pub async fn fetch(
+        &self,
+        url: &str,
+        validators: &FeedValidators,
+    ) -> Result<FetchOutcome, FeedError> {
+        let mut request = self.http.get(url).header(
+            reqwest::header::ACCEPT,
+            "application/rss+xml, application/atom+xml, application/xml;q=0.9, */*;q=0.8",
+        );
+        if let Some(etag) = &validators.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+
+        let response = request.send().await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(FetchOutcome::NotModified);
+        }
+        let response = response.error_for_status()?;
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v: &reqwest::header::HeaderValue| v.to_str().ok())
+                .map(String::from)
+        };
+        let validators = FeedValidators {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        };
+        let text = response.text().await?;
+
+        Ok(FetchOutcome::Fetched(parse_feed(&text)?, validators))
+    }
+
+    /// Store an entry; returns whether it was new. Entries are deduplicated by
+    /// GUID, and a republished entry only updates the stored copy.
+    pub async fn store_entry(
+        &self,
+        subscription: &FeedSubscription,
+        feed_title: Option<&str>,
+        entry: &FeedEntry,
+    ) -> Result<bool, FeedError> {
+        let source_id = format!("{}:{}", feed_key(&subscription.url), entry.guid);
+        let existing: Option<(String,)> =
+            sqlx::query_as("SELECT id FROM content_items WHERE source = 'rss' AND source_id = ?")
+                .bind(&source_id)
+                .fetch_optional(self.db.pool())
+                .await?;
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let channel = subscription
+            .name
+            .as_deref()
+            .or(feed_title)
+            .unwrap_or(&subscription.url);
+        let body = match &entry.title {
+            Some(title) if !entry.text.is_empty() => format!("{}\n\n{}", title, entry.text),
+            Some(title) => title.clone(),
+            None => entry.text.clone(),
+        };
+        let encrypted_body = self
+            .crypto
+            .encrypt_string(&body)
+            .map_err(|e| FeedError::Crypto(e.to_string()))?;
+        let created_at = entry.published.or(entry.updated).unwrap_or(now);
+        let updated_at = entry.updated.unwrap_or(created_at);
+        let metadata = serde_json::json!({
+            "feed": subscription.url,
+            "guid": entry.guid,
+        })
+        .to_string();
+
+        sqlx::query(
+            "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, parent_id, created_at, updated_at, synced_at, metadata)
+             VALUES (?, 'rss', ?, ?, 'article', ?, ?, ?, ?, NULL, ?, ?, ?, ?)
+             ON CONFLICT(source, source_id) DO UPDATE SET source_url = ?, title = ?, body = ?, author_id = ?, channel_or_project = ?, updated_at = ?, metadata = ?"
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(&source_id)
+        .bind(&entry.link)
+        .bind(&entry.title)
+        .bind(&encrypted_body)
+        .bind(&entry.author)
+        .bind(channel)
+        .bind(created_at)
+        .bind(updated_at)
+        .bind(now)
+        .bind(&metadata)
+        .bind(&entry.link)
+        .bind(&entry.title)
+        .bind(&encrypted_body)
+        .bind(&entry.author)
+        .bind(channel)
+        .bind(updated_at)
+        .bind(&metadata)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(existing.is_none())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_key_is_stable() {
+        let key = feed_key("https://status.acme.com/history.rss");
+        assert_eq!(key.len(), 12);
+        assert_eq!(key, feed_key(" https://status.acme.com/history.rss "));
+        assert_ne!(key, feed_key("https://blog.acme.com/feed.xml"));
+    }
+}
@@ -0,0 +1,70 @@
+//! RSS/Atom feed data types and error definitions
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Polling interval for feeds that don't set one
+pub const DEFAULT_FEED_INTERVAL_MINUTES: u32 = 60;
+
+#[derive(Error, Debug)]
+pub enum FeedError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Invalid feed: {0}")]
+    Parse(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Crypto error: {0}")]
+    Crypto(String),
+}
+
+/// A followed feed; stored encrypted since private feed URLs often embed a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedSubscription {
+    pub url: String,
+    /// Used as `channel_or_project`; defaults to the feed's own title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Minimum time between fetches
+    #[serde(default = "default_interval")]
+    pub interval_minutes: u32,
+}
+
+fn default_interval() -> u32 {
+    DEFAULT_FEED_INTERVAL_MINUTES
+}
+
+/// Conditional-fetch validators kept as the feed's `sync_state` cursor
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedValidators {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+}
+
+/// A parsed RSS 2.0, RSS 1.0 or Atom document
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feed {
+    pub title: Option<String>,
+    pub entries: Vec<FeedEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedEntry {
+    /// RSS `guid`, Atom `id`, or the link when neither is present
+    pub guid: String,
+    pub title: Option<String>,
+    pub link: Option<String>,
+    pub author: Option<String>,
+    /// Plain text converted from the entry's HTML content or summary
+    pub text: String,
+    /// Epoch milliseconds
+    pub published: Option<i64>,
+    pub updated: Option<i64>,
+}
@@ -0,0 +1,416 @@
+//! GitHub REST/GraphQL client with token and device-flow authentication
+
+use reqwest::{Client, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+use super::types::{
+    GitHubComment, GitHubDeviceCode, GitHubDiscussion, GitHubError, GitHubIssue, GitHubRepo,
+    GitHubReview, GitHubTokens, GitHubUser,
+};
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+const GITHUB_DEVICE_CODE_URL: &str = "https://github.com/login/device/code";
+const GITHUB_ACCESS_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
+const DEVICE_FLOW_SCOPES: &str = "repo read:discussion read:user";
+const USER_AGENT: &str = "companion-app";
+const PER_PAGE: usize = 100;
+const DISCUSSIONS_PAGE_SIZE: usize = 50;
+
+const DISCUSSIONS_QUERY: &str = r#"
+query($owner: String!, $name: String!, $first: Int!, $after: String) {
+  repository(owner: $owner, name: $name) {
+    discussions(first: $first, after: $after, orderBy: {field: UPDATED_AT, direction: DESC}) {
+      nodes {
+        id number title body url createdAt updatedAt
+        author { login }
+        category { name }
+      }
+      pageInfo { hasNextPage endCursor }
+    }
+  }
+}"#;
+
+pub struct GitHubClient {
+    http: Client,
+    api_url: String,
+    access_token: Option<String>,
+}
+
+impl Default for GitHubClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GitHubClient {
+    pub fn new() -> Self {
+        Self {
+            http: Client::new(),
+            api_url: GITHUB_API_URL.to_string(),
+            access_token: None,
+        }
+    }
+
+    pub fn with_token(mut self, access_token: String) -> Self {
+        self.access_token = Some(access_token);
+        self
+    }
+
+    /// Use a GitHub Enterprise Server REST endpoint, e.g. `https://ghe.example.com/api/v3`
+    pub fn with_api_url(mut self, api_url: Option<String>) -> Self {
+        if let Some(url) = api_url.filter(|u| !u.trim().is_empty()) {
+            self.api_url = url.trim().trim_end_matches('/').to_string();
+        }
+        self
+    }
+
+    /// Client for stored credentials
+    pub fn from_tokens(tokens: &GitHubTokens) -> Self {
+        Self::new()
+            .with_token(tokens.access_token.clone())
+            .with_api_url(tokens.api_url.clone())
+    }
+
+    fn graphql_url(&self) -> String {
+        match self.api_url.strip_suffix("/api/v3") {
+            Some(host) => format!("{}/api/graphql", host),
+            None => format!("{}/graphql", self.api_url),
+        }
+    }
+
+    fn authed(&self, request: RequestBuilder) -> Result<RequestBuilder, GitHubError> {
+        let token = self
+            .access_token
+            .as_ref()
+            .ok_or_else(|| GitHubError::Auth("Not authenticated".into()))?;
+
+        Ok(request
+            .bearer_auth(token)
+            .header("User-Agent", USER_AGENT)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28"))
+    }
+
+    async fn check(response: Response) -> Result<Response, GitHubError> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let remaining = response
+            .headers()
+            .get("x-ratelimit-remaining")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<i64>().ok());
+        let reset = response
+            .headers()
+            .get("x-ratelimit-reset")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<i64>().ok());
+
+        if status.as_u16() == 401 {
+            return Err(GitHubError::Auth("Token rejected by GitHub".into()));
+        }
+        if status.as_u16() == 429 || (status.as_u16() == 403 && remaining == Some(0)) {
+            return Err(GitHubError::RateLimited(reset.unwrap_or_default()));
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        Err(GitHubError::Api(format!("HTTP {}: {}", status, body)))
+    }
+
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<T, GitHubError> {
+        let url = format!("{}{}", self.api_url, path);
+        let request = self.authed(self.http.get(&url).query(query))?;
+        let response = Self::check(request.send().await?).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Fetch every page of a list endpoint. Incremental listings are bounded by
+    /// their `since` filter, so nothing older than the cursor is paged through.
+    async fn get_paginated<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<Vec<T>, GitHubError> {
+        self.get_paginated_while(path, query, |_| true).await
+    }
+
+    /// Fetch pages of a list endpoint until the first item `keep` rejects
+    async fn get_paginated_while<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+        keep: impl Fn(&T) -> bool,
+    ) -> Result<Vec<T>, GitHubError> {
+        let mut all = Vec::new();
+
+        for page in 1.. {
+            let mut page_query = query.to_vec();
+            page_query.push(("per_page", PER_PAGE.to_string()));
+            page_query.push(("page", page.to_string()));
+
+            let items: Vec<T> = self.get_json(path, &page_query).await?;
+            let len = items.len();
+            let before = all.len();
+            all.extend(items.into_iter().take_while(&keep));
+            if len < PER_PAGE || all.len() - before < len {
+                break;
+            }
+        }
+
+        Ok(all)
+    }
+
+    /// The authenticated user
+    pub async fn get_viewer(&self) -> Result<GitHubUser, GitHubError> {
+        self.get_json("/user", &[]).await
+    }
+
+    /// Repositories the user owns, collaborates on or can see through an organization
+    pub async fn list_repos(&self) -> Result<Vec<GitHubRepo>, GitHubError> {
+        self.get_paginated(
+            "/user/repos",
+            &[
+                (
+                    "affiliation",
+                    "owner,collaborator,organization_member".to_string(),
+                ),
+                ("sort", "pushed".to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// Newest first, or oldest first when bounded by `until` so paging can stop
+    /// at the end of the range instead of walking everything updated since
+    fn since_query(since: Option<&str>, until: Option<&str>) -> Vec<(&'static str, String)> {
+        let direction = if until.is_some() { "asc" } else { "desc" };
+        let mut query = vec![
+            ("sort", "updated".to_string()),
+            ("direction", direction.to_string()),
+        ];
+        if let Some(since) = since {
+            query.push(("since", since.to_string()));
+        }
+        query
+    }
+
+    /// Issues and pull requests updated since `since` and before `until` (RFC 3339)
+    pub async fn list_issues(
+        &self,
+        repo: &str,
+        since: Option<&str>,
+        until: Option<&str>,
+    ) -> Result<Vec<GitHubIssue>, GitHubError> {
+        let mut query = Self::since_query(since, until);
+        query.push(("state", "all".to_string()));
+        self.get_paginated_while(
+            &format!("/repos/{}/issues", repo),
+            &query,
+            |issue: &GitHubIssue| until.is_none_or(|until| issue.updated_at.as_str() < until),
+        )
+        .await
+    }
+
+    /// Conversation comments on issues and pull requests updated since `since`
+    /// and before `until`
+    pub async fn list_issue_comments(
+        &self,
+        repo: &str,
+        since: Option<&str>,
+        until: Option<&str>,
+    ) -> Result<Vec<GitHubComment>, GitHubError> {
+        self.get_paginated_while(
+            &format!("/repos/{}/issues/comments", repo),
+            &Self::since_query(since, until),
+            |comment: &GitHubComment| until.is_none_or(|until| comment.updated_at.as_str() < until),
+        )
+        .await
+    }
+
+    /// Inline pull request review comments updated since `since` and before
+    /// `until`
+    pub async fn list_review_comments(
+        &self,
+        repo: &str,
+        since: Option<&str>,
+        until: Option<&str>,
+    ) -> Result<Vec<GitHubComment>, GitHubError> {
+        self.get_paginated_while(
+            &format!("/repos/{}/pulls/comments", repo),
+            &Self::since_query(since, until),
+            |comment: &GitHubComment| until.is_none_or(|until| comment.updated_at.as_str() < until),
+        )
+        .await
+    }
+
+    /// Submitted reviews on a pull request
+    pub async fn list_reviews(
+        &self,
+        repo: &str,
+        number: i64,
+    ) -> Result<Vec<GitHubReview>, GitHubError> {
+        self.get_paginated(&format!("/repos/{}/pulls/{}/reviews", repo, number), &[])
+            .await
+    }
+
+    /// Discussions updated since `since`, newest first, paging until the first
+    /// older one. Repositories without discussions enabled return an empty list.
+    pub async fn list_discussions(
+        &self,
+        repo: &str,
+        since: Option<&str>,
+    ) -> Result<Vec<GitHubDiscussion>, GitHubError> {
+        let (owner, name) = repo
+            .split_once('/')
+            .ok_or_else(|| GitHubError::Api(format!("Invalid repository name: {}", repo)))?;
+
+        let mut discussions = Vec::new();
+        let mut after: Option<String> = None;
+
+        loop {
+            let body = serde_json::json!({
+                "query": DISCUSSIONS_QUERY,
+                "variables": {
+                    "owner": owner,
+                    "name": name,
+                    "first": DISCUSSIONS_PAGE_SIZE,
+                    "after": after,
+                },
+            });
+
+            let request = self.authed(self.http.post(self.graphql_url()).json(&body))?;
+            let response = Self::check(request.send().await?).await?;
+            let json: serde_json::Value = response.json().await?;
+
+            if let Some(errors) = json["errors"].as_array().filter(|e| !e.is_empty()) {
+                let message = errors[0]["message"].as_str().unwrap_or("GraphQL error");
+                return Err(GitHubError::Api(message.to_string()));
+            }
+
+            let connection = &json["data"]["repository"]["discussions"];
+            let nodes: Vec<GitHubDiscussion> =
+                serde_json::from_value(connection["nodes"].clone()).unwrap_or_default();
+
+            let mut reached_since = false;
+            for discussion in nodes {
+                if since.is_some_and(|since| discussion.updated_at.as_str() < since) {
+                    reached_since = true;
+                    break;
+                }
+                discussions.push(discussion);
+            }
+
+            let has_next = connection["pageInfo"]["hasNextPage"]
+                .as_bool()
+                .unwrap_or(false);
+            if reached_since || !has_next {
+                break;
+            }
+            after = connection["pageInfo"]["endCursor"]
+                .as_str()
+                .map(String::from);
+        }
+
+        Ok(discussions)
+    }
+
+    /// Begin the OAuth device flow for an OAuth app with device flow enabled
+    pub async fn start_device_flow(
+        &self,
+        client_id: &str,
+    ) -> Result<GitHubDeviceCode, GitHubError> {
+        let response = self
+            .http
+            .post(GITHUB_DEVICE_CODE_URL)
+            .header("Accept", "application/json")
+            .header("User-Agent", USER_AGENT)
+            .form(&[("client_id", client_id), ("scope", DEVICE_FLOW_SCOPES)])
+            .send()
+            .await?;
+
+        let response = Self::check(response).await?;
+        let json: serde_json::Value = response.json().await?;
+        if let Some(error) = json["error"].as_str() {
+            return Err(GitHubError::Auth(error.to_string()));
+        }
+
+        serde_json::from_value(json).map_err(|e| GitHubError::Api(e.to_string()))
+    }
+
+    /// Poll until the user approves the device code, returning the access token and scopes
+    pub async fn poll_device_flow(
+        &self,
+        client_id: &str,
+        device_code: &str,
+        interval_secs: u64,
+        expires_in_secs: u64,
+    ) -> Result<(String, String), GitHubError> {
+        let deadline = std::time::Instant::now() + Duration::from_secs(expires_in_secs);
+        let mut interval = interval_secs.max(1);
+
+        while std::time::Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+
+            let response = self
+                .http
+                .post(GITHUB_ACCESS_TOKEN_URL)
+                .header("Accept", "application/json")
+                .header("User-Agent", USER_AGENT)
+                .form(&[
+                    ("client_id", client_id),
+                    ("device_code", device_code),
+                    ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ])
+                .send()
+                .await?;
+
+            let json: serde_json::Value = Self::check(response).await?.json().await?;
+
+            if let Some(token) = json["access_token"].as_str() {
+                let scope = json["scope"].as_str().unwrap_or_default().to_string();
+                return Ok((token.to_string(), scope));
+            }
+
+            match json["error"].as_str() {
+                Some("authorization_pending") => {}
+                Some("slow_down") => interval += 5,
+                Some(error) => return Err(GitHubError::Auth(error.to_string())),
+                None => return Err(GitHubError::Auth("Unexpected token response".into())),
+            }
+        }
+
+        Err(GitHubError::Auth("Device code expired".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graphql_url() {
+        assert_eq!(
+            GitHubClient::new().graphql_url(),
+            "https://api.github.com/graphql"
+        );
+        assert_eq!(
+            GitHubClient::new()
+                .with_api_url(Some("https://ghe.example.com/api/v3/".into()))
+                .graphql_url(),
+            "https://ghe.example.com/api/graphql"
+        );
+    }
+
+    #[test]
+    fn test_blank_api_url_keeps_default() {
+        let client = GitHubClient::new().with_api_url(Some("  ".into()));
+        assert_eq!(client.api_url, GITHUB_API_URL);
+    }
+}
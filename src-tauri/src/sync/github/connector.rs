@@ -0,0 +1,339 @@
+//! GitHub source connector
+//!
+//! Connects with a personal access token or the OAuth device flow and syncs the
+//! repositories selected in settings, each with its own `sync_state` cursor.
+
+use async_trait::async_trait;
+use std::collections::HashSet;
+
+use super::client::GitHubClient;
+use super::sync::{format_github_timestamp, GitHubSyncService};
+use super::types::GitHubTokens;
+use crate::pipeline::PipelineTaskType;
+use crate::sync::connectivity::is_unreachable;
+use crate::sync::connector::{
+    clear_deselected_scopes, clear_sync_state, incremental_since, last_sync_at, load_credentials,
+    load_cursor, load_preference, save_cursor, save_preference, store_credentials,
+    ConnectorContext, ConnectorError, SourceConnector, SourceHealth, SourceScope, SyncRange,
+};
+use crate::sync::status::{mark_failed, mark_pending};
+use crate::sync::SyncResult;
+
+const SELECTED_REPOS_KEY: &str = "github_selected_repos";
+
+pub(crate) async fn load_github_tokens(
+    ctx: &ConnectorContext,
+) -> Result<Option<GitHubTokens>, ConnectorError> {
+    load_credentials(ctx, "github").await
+}
+
+async fn load_selected_repos(ctx: &ConnectorContext) -> Result<Vec<String>, ConnectorError> {
+    load_preference(&ctx.db, SELECTED_REPOS_KEY).await
+}
+
+pub struct GitHubConnector;
+
+impl GitHubConnector {
+    async fn service(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<(GitHubSyncService, Vec<String>), ConnectorError> {
+        let tokens = load_github_tokens(ctx)
+            .await?
+            .ok_or(ConnectorError::NotConnected("GitHub"))?;
+        let repos = load_selected_repos(ctx).await?;
+
+        let service = GitHubSyncService::new(
+            GitHubClient::from_tokens(&tokens),
+            ctx.db.clone(),
+            ctx.crypto.clone(),
+        );
+        Ok((service, repos))
+    }
+
+    /// Verify a token against `/user` and store it
+    async fn connect_token(
+        &self,
+        ctx: &ConnectorContext,
+        token: String,
+        scope: String,
+        api_url: Option<String>,
+    ) -> Result<GitHubTokens, ConnectorError> {
+        let viewer = GitHubClient::new()
+            .with_token(token.clone())
+            .with_api_url(api_url.clone())
+            .get_viewer()
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+        let tokens = GitHubTokens {
+            access_token: token,
+            token_type: "bearer".to_string(),
+            scope,
+            login: viewer.login,
+            api_url: api_url.filter(|u| !u.trim().is_empty()),
+        };
+        store_credentials(ctx, "github", &tokens).await?;
+
+        tracing::info!("GitHub connected as {}", tokens.login);
+        Ok(tokens)
+    }
+}
+
+#[async_trait]
+impl SourceConnector for GitHubConnector {
+    fn id(&self) -> &'static str {
+        "github"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "GitHub"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sync pull requests, reviews, issues and discussions from GitHub"
+    }
+
+    fn task_type(&self) -> PipelineTaskType {
+        PipelineTaskType::SyncGithub
+    }
+
+    /// Accepts `{ "token", "apiUrl"? }` for a personal access token. For the device
+    /// flow, send `{ "clientId" }` to receive a user code, show it to the user, then
+    /// send `{ "clientId", "deviceCode", "interval", "expiresIn" }` to wait for approval.
+    async fn connect(
+        &self,
+        ctx: &ConnectorContext,
+        config: serde_json::Value,
+    ) -> Result<serde_json::Value, ConnectorError> {
+        let field = |name: &str| {
+            config[name]
+                .as_str()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(String::from)
+        };
+        let api_url = field("apiUrl");
+
+        if let Some(token) = field("token") {
+            let tokens = self
+                .connect_token(ctx, token, String::new(), api_url)
+                .await?;
+            return Ok(serde_json::json!({ "login": tokens.login }));
+        }
+
+        let client_id = field("clientId")
+            .ok_or_else(|| ConnectorError::InvalidConfig("token or clientId is required".into()))?;
+        let client = GitHubClient::new();
+
+        let Some(device_code) = field("deviceCode") else {
+            let device = client
+                .start_device_flow(&client_id)
+                .await
+                .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+            return serde_json::to_value(device).map_err(|e| ConnectorError::Failed(e.to_string()));
+        };
+
+        let interval = config["interval"].as_u64().unwrap_or(5);
+        let expires_in = config["expiresIn"].as_u64().unwrap_or(900);
+        let (token, scope) = client
+            .poll_device_flow(&client_id, &device_code, interval, expires_in)
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+        let tokens = self.connect_token(ctx, token, scope, None).await?;
+        Ok(serde_json::json!({ "login": tokens.login }))
+    }
+
+    async fn is_connected(&self, ctx: &ConnectorContext) -> bool {
+        matches!(load_github_tokens(ctx).await, Ok(Some(_)))
+    }
+
+    async fn list_scopes(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<Vec<SourceScope>, ConnectorError> {
+        let tokens = load_github_tokens(ctx)
+            .await?
+            .ok_or(ConnectorError::NotConnected("GitHub"))?;
+        let selected: HashSet<String> = load_selected_repos(ctx).await?.into_iter().collect();
+
+        let repos = GitHubClient::from_tokens(&tokens)
+            .list_repos()
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+        Ok(repos
+            .into_iter()
+            .map(|repo| SourceScope {
+                selected: selected.contains(&repo.full_name),
+                id: repo.full_name.clone(),
+                name: repo.full_name,
+                kind: "repo".to_string(),
+            })
+            .collect())
+    }
+
+    fn supports_scope_selection(&self) -> bool {
+        true
+    }
+
+    async fn select_scopes(
+        &self,
+        ctx: &ConnectorContext,
+        scope_ids: Vec<String>,
+    ) -> Result<(), ConnectorError> {
+        if let Some(invalid) = scope_ids.iter().find(|id| !id.contains('/')) {
+            return Err(ConnectorError::InvalidConfig(format!(
+                "Invalid repository name: {}",
+                invalid
+            )));
+        }
+
+        save_preference(&ctx.db, SELECTED_REPOS_KEY, &scope_ids).await?;
+        clear_deselected_scopes(&ctx.db, self.id(), "repo", &scope_ids).await?;
+
+        tracing::info!("Selected {} GitHub repositories", scope_ids.len());
+        Ok(())
+    }
+
+    async fn sync_incremental(&self, ctx: &ConnectorContext) -> Result<SyncResult, ConnectorError> {
+        let (service, repos) = self.service(ctx).await?;
+        let mut total = SyncResult {
+            source: self.id().to_string(),
+            items_synced: 0,
+            errors: vec![],
+        };
+
+        mark_pending(&ctx.db, self.id(), "repo", &repos).await;
+        for repo in repos {
+            let now_ms = chrono::Utc::now().timestamp_millis();
+            let cursor = load_cursor(&ctx.db, self.id(), "repo", &repo).await?;
+            let since = incremental_since(cursor.as_deref(), now_ms);
+
+            let sync = service.sync_repo(&repo, since, None);
+            let result = ctx.track_resource(self.id(), "repo", &repo, sync).await;
+            ctx.check_cancelled()?;
+            match result {
+                Ok(result) => {
+                    total.items_synced += result.items_synced;
+                    if result.errors.is_empty() {
+                        save_cursor(
+                            &ctx.db,
+                            self.id(),
+                            "repo",
+                            &repo,
+                            &format_github_timestamp(now_ms),
+                        )
+                        .await?;
+                    } else {
+                        // Keep the cursor so what failed is fetched again next run
+                        let error = result.errors.join("; ");
+                        mark_failed(&ctx.db, self.id(), "repo", &repo, &error).await;
+                        total.errors.extend(result.errors);
+                    }
+                }
+                // The other repositories are on the same host
+                Err(e) if is_unreachable(&e) => {
+                    return Err(ConnectorError::Unreachable(e.to_string()))
+                }
+                Err(e) => {
+                    tracing::error!("GitHub sync error for {}: {}", repo, e);
+                    total.errors.push(format!("{}: {}", repo, e));
+                }
+            }
+        }
+
+        Ok(total)
+    }
+
+    async fn sync_range(
+        &self,
+        ctx: &ConnectorContext,
+        range: &SyncRange,
+    ) -> Result<SyncResult, ConnectorError> {
+        let (service, repos) = self.service(ctx).await?;
+        let (start, end) = range.utc_bounds();
+        let mut total = SyncResult {
+            source: self.id().to_string(),
+            items_synced: 0,
+            errors: vec![],
+        };
+
+        for repo in repos {
+            let result = service
+                .sync_repo(&repo, start * 1000, Some(end * 1000))
+                .await;
+            ctx.check_cancelled()?;
+            match result {
+                Ok(result) => {
+                    total.items_synced += result.items_synced;
+                    total.errors.extend(result.errors);
+                }
+                // The other repositories are on the same host
+                Err(e) if is_unreachable(&e) => {
+                    return Err(ConnectorError::Unreachable(e.to_string()))
+                }
+                Err(e) => total.errors.push(format!("{}: {}", repo, e)),
+            }
+        }
+
+        Ok(total)
+    }
+
+    async fn health(&self, ctx: &ConnectorContext) -> SourceHealth {
+        let last_sync_at = last_sync_at(&ctx.db, self.id()).await;
+        let mut health = SourceHealth {
+            source: self.id().to_string(),
+            connected: false,
+            healthy: false,
+            message: None,
+            last_sync_at,
+        };
+
+        let tokens = match load_github_tokens(ctx).await {
+            Ok(Some(tokens)) => tokens,
+            Ok(None) => return health,
+            Err(e) => {
+                health.connected = true;
+                health.message = Some(e.to_string());
+                return health;
+            }
+        };
+        health.connected = true;
+
+        match GitHubClient::from_tokens(&tokens).get_viewer().await {
+            Ok(viewer) => {
+                let repos = load_selected_repos(ctx).await.unwrap_or_default();
+                health.healthy = true;
+                health.message = Some(if repos.is_empty() {
+                    format!("Signed in as {}; no repositories selected", viewer.login)
+                } else {
+                    format!(
+                        "Signed in as {}; {} repositories",
+                        viewer.login,
+                        repos.len()
+                    )
+                });
+            }
+            Err(e) => health.message = Some(e.to_string()),
+        }
+
+        health
+    }
+
+    async fn disconnect(&self, ctx: &ConnectorContext) -> Result<(), ConnectorError> {
+        sqlx::query("DELETE FROM credentials WHERE id = 'github'")
+            .execute(ctx.db.pool())
+            .await?;
+
+        sqlx::query("DELETE FROM preferences WHERE key = ?")
+            .bind(SELECTED_REPOS_KEY)
+            .execute(ctx.db.pool())
+            .await?;
+
+        clear_sync_state(&ctx.db, self.id()).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+//! GitHub integration module
+//!
+//! This module provides token and device-flow authentication and
+//! synchronization of pull requests, reviews, issues and discussions.
+
+mod client;
+mod connector;
+mod sync;
+mod types;
+
+pub use client::GitHubClient;
+pub use connector::GitHubConnector;
+pub use sync::GitHubSyncService;
+pub use types::{
+    GitHubComment, GitHubDeviceCode, GitHubDiscussion, GitHubError, GitHubIssue, GitHubRepo,
+    GitHubReview, GitHubTokens, GitHubUser,
+};
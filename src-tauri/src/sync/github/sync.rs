@@ -0,0 +1,387 @@
+//! GitHub data synchronization service
+
+use std::sync::Arc;
+
+use super::client::GitHubClient;
+use super::types::{GitHubComment, GitHubDiscussion, GitHubError, GitHubIssue, GitHubReview};
+use crate::crypto::CryptoService;
+use crate::db::Database;
+use crate::sync::connector::parse_timestamp_ms;
+use crate::sync::SyncResult;
+
+/// A GitHub object normalized for `content_items`
+struct GitHubContent {
+    source_id: String,
+    url: String,
+    content_type: &'static str,
+    title: String,
+    text: String,
+    author: Option<String>,
+    repo: String,
+    parent_id: Option<String>,
+    created_at: String,
+    updated_at: String,
+    metadata: serde_json::Value,
+}
+
+/// Format epoch milliseconds the way GitHub's `since` parameter expects
+pub(super) fn format_github_timestamp(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .unwrap_or_default()
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string()
+}
+
+/// `owner/repo#812`, the key shared by a PR or issue and everything attached to it
+fn issue_key(repo: &str, number: i64) -> String {
+    format!("{}#{}", repo, number)
+}
+
+fn issue_content(repo: &str, issue: &GitHubIssue) -> GitHubContent {
+    let (kind, content_type) = if issue.is_pull_request() {
+        ("PR", "pull_request")
+    } else {
+        ("Issue", "ticket")
+    };
+    let title = format!("{} #{}: {}", kind, issue.number, issue.title);
+    let body = issue.body.as_deref().unwrap_or("").trim();
+
+    GitHubContent {
+        source_id: issue_key(repo, issue.number),
+        url: issue.html_url.clone(),
+        content_type,
+        text: if body.is_empty() {
+            format!("{} [{}]", title, issue.state)
+        } else {
+            format!("{} [{}]\n\n{}", title, issue.state, body)
+        },
+        title,
+        author: issue.user.as_ref().map(|u| u.login.clone()),
+        repo: repo.to_string(),
+        parent_id: None,
+        created_at: issue.created_at.clone(),
+        updated_at: issue.updated_at.clone(),
+        metadata: serde_json::json!({
+            "repo": repo,
+            "number": issue.number,
+            "state": issue.state,
+            "merged": issue.is_merged(),
+            "labels": issue.labels.iter().map(|l| &l.name).collect::<Vec<_>>(),
+        }),
+    }
+}
+
+fn comment_content(
+    repo: &str,
+    comment: &GitHubComment,
+    review_comment: bool,
+) -> Option<GitHubContent> {
+    let number = comment.parent_number()?;
+    let body = comment.body.as_deref().unwrap_or("").trim();
+    if body.is_empty() {
+        return None;
+    }
+
+    let (label, kind) = if review_comment {
+        ("Review comment on PR", "review-comment")
+    } else {
+        ("Comment on", "comment")
+    };
+    let location = comment
+        .path
+        .as_deref()
+        .map(|path| format!(" ({})", path))
+        .unwrap_or_default();
+
+    Some(GitHubContent {
+        source_id: format!("{}/{}/{}", issue_key(repo, number), kind, comment.id),
+        url: comment.html_url.clone(),
+        content_type: "comment",
+        title: format!("{} #{}", label, number),
+        text: format!("{} #{}{}: {}", label, number, location, body),
+        author: comment.user.as_ref().map(|u| u.login.clone()),
+        repo: repo.to_string(),
+        parent_id: Some(issue_key(repo, number)),
+        created_at: comment.created_at.clone(),
+        updated_at: comment.updated_at.clone(),
+        metadata: serde_json::json!({ "repo": repo, "number": number, "path": comment.path }),
+    })
+}
+
+fn review_content(repo: &str, number: i64, review: &GitHubReview) -> Option<GitHubContent> {
+    let submitted_at = review.submitted_at.clone()?;
+    let state = review.state.to_lowercase().replace('_', " ");
+    let body = review.body.as_deref().unwrap_or("").trim();
+
+    // Plain "commented" reviews without a body only wrap inline comments
+    if body.is_empty() && review.state == "COMMENTED" {
+        return None;
+    }
+
+    let title = format!("Review ({}) on PR #{}", state, number);
+    Some(GitHubContent {
+        source_id: format!("{}/review/{}", issue_key(repo, number), review.id),
+        url: review.html_url.clone(),
+        content_type: "comment",
+        text: if body.is_empty() {
+            title.clone()
+        } else {
+            format!("{}: {}", title, body)
+        },
+        title,
+        author: review.user.as_ref().map(|u| u.login.clone()),
+        repo: repo.to_string(),
+        parent_id: Some(issue_key(repo, number)),
+        created_at: submitted_at.clone(),
+        updated_at: submitted_at,
+        metadata: serde_json::json!({ "repo": repo, "number": number, "state": review.state }),
+    })
+}
+
+fn discussion_content(repo: &str, discussion: &GitHubDiscussion) -> GitHubContent {
+    let title = format!("Discussion #{}: {}", discussion.number, discussion.title);
+    let body = discussion.body.as_deref().unwrap_or("").trim();
+
+    GitHubContent {
+        source_id: format!("{}/discussions/{}", repo, discussion.number),
+        url: discussion.url.clone(),
+        content_type: "discussion",
+        text: if body.is_empty() {
+            title.clone()
+        } else {
+            format!("{}\n\n{}", title, body)
+        },
+        title,
+        author: discussion.author.as_ref().map(|u| u.login.clone()),
+        repo: repo.to_string(),
+        parent_id: None,
+        created_at: discussion.created_at.clone(),
+        updated_at: discussion.updated_at.clone(),
+        metadata: serde_json::json!({
+            "repo": repo,
+            "number": discussion.number,
+            "category": discussion.category.as_ref().map(|c| &c.name),
+        }),
+    }
+}
+
+/// Encrypt and upsert a GitHub object into `content_items`
+async fn store_content(
+    db: &Database,
+    crypto: &CryptoService,
+    item: &GitHubContent,
+) -> Result<(), GitHubError> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let created_at = parse_timestamp_ms(&item.created_at).unwrap_or(now);
+    let updated_at = parse_timestamp_ms(&item.updated_at).unwrap_or(now);
+
+    let encrypted_body = crypto
+        .encrypt_string(&item.text)
+        .map_err(|e| GitHubError::Crypto(e.to_string()))?;
+    let metadata = item.metadata.to_string();
+
+    sqlx::query(
+        "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, parent_id, created_at, updated_at, synced_at, metadata)
+         VALUES (?, 'github', ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(source, source_id) DO UPDATE SET title = ?, body = ?, updated_at = ?, synced_at = ?, metadata = ?"
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(&item.source_id)
+    .bind(&item.url)
+    .bind(item.content_type)
+    .bind(&item.title)
+    .bind(&encrypted_body)
+    .bind(&item.author)
+    .bind(&item.repo)
+    .bind(&item.parent_id)
+    .bind(created_at)
+    .bind(updated_at)
+    .bind(now)
+    .bind(&metadata)
+    .bind(&item.title)
+    .bind(&encrypted_body)
+    .bind(updated_at)
+    .bind(now)
+    .bind(&metadata)
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+pub struct GitHubSyncService {
+    client: GitHubClient,
+    db: Arc<Database>,
+    crypto: Arc<CryptoService>,
+}
+
+impl GitHubSyncService {
+    pub fn new(client: GitHubClient, db: Arc<Database>, crypto: Arc<CryptoService>) -> Self {
+        Self { client, db, crypto }
+    }
+
+    /// Sync pull requests, reviews, comments, issues and discussions of one repository
+    /// updated at or after `since_ms`. With `until_ms`, only objects updated before it
+    /// are synced, and listings stop paging there, bounding historical syncs.
+    pub async fn sync_repo(
+        &self,
+        repo: &str,
+        since_ms: i64,
+        until_ms: Option<i64>,
+    ) -> Result<SyncResult, GitHubError> {
+        let since = format_github_timestamp(since_ms);
+        let until = until_ms.map(format_github_timestamp);
+        let until = until.as_deref();
+        let mut contents = Vec::new();
+        let mut errors = Vec::new();
+
+        let issues = self.client.list_issues(repo, Some(&since), until).await?;
+        contents.extend(issues.iter().map(|issue| issue_content(repo, issue)));
+
+        let pull_numbers = issues
+            .iter()
+            .filter(|issue| issue.is_pull_request())
+            .map(|issue| issue.number);
+        for number in pull_numbers {
+            match self.client.list_reviews(repo, number).await {
+                Ok(reviews) => contents.extend(
+                    reviews
+                        .iter()
+                        .filter(|r| r.submitted_at.as_deref() >= Some(since.as_str()))
+                        .filter_map(|r| review_content(repo, number, r)),
+                ),
+                Err(e) => errors.push(format!("{}#{} reviews: {}", repo, number, e)),
+            }
+        }
+
+        let comments = self
+            .client
+            .list_issue_comments(repo, Some(&since), until)
+            .await?;
+        contents.extend(
+            comments
+                .iter()
+                .filter_map(|c| comment_content(repo, c, false)),
+        );
+
+        let review_comments = self
+            .client
+            .list_review_comments(repo, Some(&since), until)
+            .await?;
+        contents.extend(
+            review_comments
+                .iter()
+                .filter_map(|c| comment_content(repo, c, true)),
+        );
+
+        // Discussions need the GraphQL API and may be disabled; don't fail the repo
+        match self.client.list_discussions(repo, Some(&since)).await {
+            Ok(discussions) => {
+                contents.extend(discussions.iter().map(|d| discussion_content(repo, d)))
+            }
+            Err(e) => {
+                tracing::warn!("Skipping discussions for {}: {}", repo, e);
+            }
+        }
+
+        let mut items_synced = 0;
+        for item in &contents {
+            // Reviews and discussions are not bounded by their listings
+            let updated = parse_timestamp_ms(&item.updated_at).unwrap_or_default();
+            if until_ms.is_some_and(|until| updated < since_ms || updated >= until) {
+                continue;
+            }
+            match store_content(&self.db, &self.crypto, item).await {
+                Ok(()) => items_synced += 1,
+                Err(e) => errors.push(format!("{}: {}", item.source_id, e)),
+            }
+        }
+
+        tracing::info!("Synced {} GitHub items from {}", items_synced, repo);
+
+        Ok(SyncResult {
+            source: "github".to_string(),
+            items_synced,
+            errors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::github::types::GitHubUser;
+
+    fn pull_request() -> GitHubIssue {
+        GitHubIssue {
+            id: 1,
+            number: 812,
+            title: "Fix race in file watcher".into(),
+            body: Some("Closes #800".into()),
+            state: "open".into(),
+            html_url: "https://github.com/acme/api/pull/812".into(),
+            user: Some(GitHubUser {
+                login: "octocat".into(),
+                id: 1,
+            }),
+            labels: vec![],
+            created_at: "2024-01-15T10:00:00Z".into(),
+            updated_at: "2024-01-15T12:00:00Z".into(),
+            pull_request: Some(serde_json::json!({ "merged_at": null })),
+        }
+    }
+
+    #[test]
+    fn test_timestamp_roundtrip() {
+        let ms = parse_timestamp_ms("2024-01-15T10:00:00Z").unwrap();
+        assert_eq!(ms, 1705312800000);
+        assert_eq!(format_github_timestamp(ms), "2024-01-15T10:00:00Z");
+    }
+
+    #[test]
+    fn test_pull_request_content_mentions_number() {
+        let content = issue_content("acme/api", &pull_request());
+        assert_eq!(content.source_id, "acme/api#812");
+        assert_eq!(content.content_type, "pull_request");
+        assert_eq!(content.title, "PR #812: Fix race in file watcher");
+        assert!(content.text.contains("Closes #800"));
+        assert_eq!(content.url, "https://github.com/acme/api/pull/812");
+    }
+
+    #[test]
+    fn test_review_content() {
+        let approved = GitHubReview {
+            id: 5,
+            body: None,
+            state: "APPROVED".into(),
+            html_url: "https://github.com/acme/api/pull/812#pullrequestreview-5".into(),
+            user: None,
+            submitted_at: Some("2024-01-15T13:00:00Z".into()),
+        };
+        let content = review_content("acme/api", 812, &approved).unwrap();
+        assert_eq!(content.text, "Review (approved) on PR #812");
+        assert_eq!(content.parent_id.as_deref(), Some("acme/api#812"));
+
+        let empty_comment = GitHubReview {
+            state: "COMMENTED".into(),
+            ..approved
+        };
+        assert!(review_content("acme/api", 812, &empty_comment).is_none());
+    }
+
+    #[test]
+    fn test_comment_without_body_is_skipped() {
+        let comment = GitHubComment {
+            id: 3,
+            body: Some("  ".into()),
+            html_url: "https://github.com/acme/api/issues/9#issuecomment-3".into(),
+            user: None,
+            created_at: "2024-01-15T10:00:00Z".into(),
+            updated_at: "2024-01-15T10:00:00Z".into(),
+            issue_url: Some("https://api.github.com/repos/acme/api/issues/9".into()),
+            pull_request_url: None,
+            path: None,
+        };
+        assert!(comment_content("acme/api", &comment, false).is_none());
+    }
+}
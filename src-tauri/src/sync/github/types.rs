@@ -0,0 +1,215 @@
+//! GitHub data types and error definitions
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum GitHubError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Authentication failed: {0}")]
+    Auth(String),
+
+    #[error("Rate limited by GitHub, resets at {0}")]
+    RateLimited(i64),
+
+    #[error("API error: {0}")]
+    Api(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Crypto error: {0}")]
+    Crypto(String),
+}
+
+/// Stored GitHub credentials
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubTokens {
+    pub access_token: String,
+    pub token_type: String,
+    pub scope: String,
+    pub login: String,
+    /// REST base URL for GitHub Enterprise Server; `None` for github.com
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubUser {
+    pub login: String,
+    #[serde(default)]
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubRepo {
+    pub id: i64,
+    pub full_name: String,
+    pub private: bool,
+    pub html_url: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubLabel {
+    pub name: String,
+}
+
+/// An issue or pull request, as returned by the issues endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubIssue {
+    pub id: i64,
+    pub number: i64,
+    pub title: String,
+    pub body: Option<String>,
+    pub state: String,
+    pub html_url: String,
+    pub user: Option<GitHubUser>,
+    #[serde(default)]
+    pub labels: Vec<GitHubLabel>,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Present only when the issue is a pull request
+    pub pull_request: Option<serde_json::Value>,
+}
+
+impl GitHubIssue {
+    pub fn is_pull_request(&self) -> bool {
+        self.pull_request.is_some()
+    }
+
+    pub fn is_merged(&self) -> bool {
+        self.pull_request
+            .as_ref()
+            .and_then(|pr| pr.get("merged_at"))
+            .is_some_and(|merged| !merged.is_null())
+    }
+}
+
+/// An issue/PR conversation comment or a PR review comment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubComment {
+    pub id: i64,
+    pub body: Option<String>,
+    pub html_url: String,
+    pub user: Option<GitHubUser>,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Set on conversation comments, e.g. `.../issues/812`
+    pub issue_url: Option<String>,
+    /// Set on review comments, e.g. `.../pulls/812`
+    pub pull_request_url: Option<String>,
+    /// File a review comment is attached to
+    pub path: Option<String>,
+}
+
+impl GitHubComment {
+    /// Number of the issue or pull request this comment belongs to
+    pub fn parent_number(&self) -> Option<i64> {
+        self.issue_url
+            .as_deref()
+            .or(self.pull_request_url.as_deref())
+            .and_then(|url| url.rsplit('/').next())
+            .and_then(|n| n.parse().ok())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubReview {
+    pub id: i64,
+    pub body: Option<String>,
+    pub state: String,
+    pub html_url: String,
+    pub user: Option<GitHubUser>,
+    pub submitted_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHubDiscussion {
+    pub id: String,
+    pub number: i64,
+    pub title: String,
+    pub body: Option<String>,
+    pub url: String,
+    pub author: Option<GitHubUser>,
+    pub category: Option<GitHubDiscussionCategory>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubDiscussionCategory {
+    pub name: String,
+}
+
+/// Response to a device authorization request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+pub struct GitHubDeviceCode {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub expires_in: u64,
+    pub interval: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_distinguishes_pull_requests() {
+        let json = r#"{
+            "id": 1, "number": 812, "title": "Fix race", "body": null, "state": "closed",
+            "html_url": "https://github.com/acme/api/pull/812",
+            "user": {"login": "octocat", "id": 7}, "labels": [{"name": "bug"}],
+            "created_at": "2024-01-15T10:00:00Z", "updated_at": "2024-01-16T10:00:00Z",
+            "pull_request": {"merged_at": "2024-01-16T09:00:00Z"}
+        }"#;
+        let issue: GitHubIssue = serde_json::from_str(json).unwrap();
+        assert!(issue.is_pull_request());
+        assert!(issue.is_merged());
+        assert_eq!(issue.labels[0].name, "bug");
+
+        let json = r#"{
+            "id": 2, "number": 9, "title": "Docs", "body": "typo", "state": "open",
+            "html_url": "https://github.com/acme/api/issues/9", "user": null,
+            "created_at": "2024-01-15T10:00:00Z", "updated_at": "2024-01-15T10:00:00Z"
+        }"#;
+        let issue: GitHubIssue = serde_json::from_str(json).unwrap();
+        assert!(!issue.is_pull_request());
+        assert!(!issue.is_merged());
+    }
+
+    #[test]
+    fn test_comment_parent_number() {
+        let comment = GitHubComment {
+            id: 1,
+            body: Some("LGTM".into()),
+            html_url: "https://github.com/acme/api/pull/812#discussion_r1".into(),
+            user: None,
+            created_at: "2024-01-15T10:00:00Z".into(),
+            updated_at: "2024-01-15T10:00:00Z".into(),
+            issue_url: None,
+            pull_request_url: Some("https://api.github.com/repos/acme/api/pulls/812".into()),
+            path: Some("src/lib.rs".into()),
+        };
+        assert_eq!(comment.parent_number(), Some(812));
+    }
+
+    #[test]
+    fn test_tokens_omit_default_api_url() {
+        let tokens = GitHubTokens {
+            access_token: "ghp_x".into(),
+            token_type: "bearer".into(),
+            scope: "repo".into(),
+            login: "octocat".into(),
+            api_url: None,
+        };
+        let json = serde_json::to_string(&tokens).unwrap();
+        assert!(!json.contains("api_url"));
+    }
+}
@@ -0,0 +1,255 @@
+//! GitLab REST v4 client for self-hosted and gitlab.com instances
+
+use reqwest::{Client, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+
+use super::types::{
+    GitLabDiscussion, GitLabError, GitLabIssue, GitLabMergeRequest, GitLabPipeline, GitLabProject,
+    GitLabTokens, GitLabUser,
+};
+
+const GITLAB_BASE_URL: &str = "https://gitlab.com";
+const PER_PAGE: usize = 100;
+
+pub struct GitLabClient {
+    http: Client,
+    base_url: String,
+    access_token: Option<String>,
+}
+
+impl Default for GitLabClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Normalize a user-entered instance URL to its root, e.g. `https://gitlab.example.com`
+pub(super) fn normalize_base_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix("/api/v4").unwrap_or(url);
+    if url.starts_with("http://") || url.starts_with("https://") {
+        url.to_string()
+    } else {
+        format!("https://{}", url)
+    }
+}
+
+/// Projects are addressed by their URL-encoded full path, e.g. `group%2Fapi`
+fn project_path(project: &str) -> String {
+    urlencoding::encode(project).into_owned()
+}
+
+impl GitLabClient {
+    pub fn new() -> Self {
+        Self {
+            http: Client::new(),
+            base_url: GITLAB_BASE_URL.to_string(),
+            access_token: None,
+        }
+    }
+
+    pub fn with_token(mut self, access_token: String) -> Self {
+        self.access_token = Some(access_token);
+        self
+    }
+
+    pub fn with_base_url(mut self, base_url: Option<String>) -> Self {
+        if let Some(url) = base_url.filter(|u| !u.trim().is_empty()) {
+            self.base_url = normalize_base_url(&url);
+        }
+        self
+    }
+
+    /// Client for stored credentials
+    pub fn from_tokens(tokens: &GitLabTokens) -> Self {
+        Self::new()
+            .with_token(tokens.access_token.clone())
+            .with_base_url(Some(tokens.base_url.clone()))
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn authed(&self, request: RequestBuilder) -> Result<RequestBuilder, GitLabError> {
+        let token = self
+            .access_token
+            .as_ref()
+            .ok_or_else(|| GitLabError::Auth("Not authenticated".into()))?;
+
+        Ok(request.header("PRIVATE-TOKEN", token))
+    }
+
+    async fn check(response: Response) -> Result<Response, GitLabError> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        if status.as_u16() == 401 {
+            return Err(GitLabError::Auth("Token rejected by GitLab".into()));
+        }
+        if status.as_u16() == 429 {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60);
+            return Err(GitLabError::RateLimited(retry_after));
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        Err(GitLabError::Api(format!("HTTP {}: {}", status, body)))
+    }
+
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<T, GitLabError> {
+        let url = format!("{}/api/v4{}", self.base_url, path);
+        let request = self.authed(self.http.get(&url).query(query))?;
+        let response = Self::check(request.send().await?).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Fetch every page of a list endpoint. Incremental listings are bounded by
+    /// their `updated_after` filter, so nothing older than the cursor is paged through.
+    async fn get_paginated<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<Vec<T>, GitLabError> {
+        let mut all = Vec::new();
+
+        for page in 1.. {
+            let mut page_query = query.to_vec();
+            page_query.push(("per_page", PER_PAGE.to_string()));
+            page_query.push(("page", page.to_string()));
+
+            let items: Vec<T> = self.get_json(path, &page_query).await?;
+            let done = items.len() < PER_PAGE;
+            all.extend(items);
+            if done {
+                break;
+            }
+        }
+
+        Ok(all)
+    }
+
+    /// The authenticated user
+    pub async fn get_user(&self) -> Result<GitLabUser, GitLabError> {
+        self.get_json("/user", &[]).await
+    }
+
+    /// Projects the user is a member of, most recently active first
+    pub async fn list_projects(&self) -> Result<Vec<GitLabProject>, GitLabError> {
+        self.get_paginated(
+            "/projects",
+            &[
+                ("membership", "true".to_string()),
+                ("simple", "true".to_string()),
+                ("order_by", "last_activity_at".to_string()),
+            ],
+        )
+        .await
+    }
+
+    fn updated_after_query(updated_after: &str) -> Vec<(&'static str, String)> {
+        vec![
+            ("updated_after", updated_after.to_string()),
+            ("order_by", "updated_at".to_string()),
+            ("sort", "desc".to_string()),
+        ]
+    }
+
+    /// Merge requests updated after `updated_after` (ISO 8601)
+    pub async fn list_merge_requests(
+        &self,
+        project: &str,
+        updated_after: &str,
+    ) -> Result<Vec<GitLabMergeRequest>, GitLabError> {
+        let mut query = Self::updated_after_query(updated_after);
+        query.push(("scope", "all".to_string()));
+        self.get_paginated(
+            &format!("/projects/{}/merge_requests", project_path(project)),
+            &query,
+        )
+        .await
+    }
+
+    /// Discussion threads on a merge request
+    pub async fn list_merge_request_discussions(
+        &self,
+        project: &str,
+        iid: i64,
+    ) -> Result<Vec<GitLabDiscussion>, GitLabError> {
+        self.get_paginated(
+            &format!(
+                "/projects/{}/merge_requests/{}/discussions",
+                project_path(project),
+                iid
+            ),
+            &[],
+        )
+        .await
+    }
+
+    /// Issues updated after `updated_after` (ISO 8601)
+    pub async fn list_issues(
+        &self,
+        project: &str,
+        updated_after: &str,
+    ) -> Result<Vec<GitLabIssue>, GitLabError> {
+        let mut query = Self::updated_after_query(updated_after);
+        query.push(("scope", "all".to_string()));
+        self.get_paginated(
+            &format!("/projects/{}/issues", project_path(project)),
+            &query,
+        )
+        .await
+    }
+
+    /// Failed pipelines updated after `updated_after` (ISO 8601)
+    pub async fn list_failed_pipelines(
+        &self,
+        project: &str,
+        updated_after: &str,
+    ) -> Result<Vec<GitLabPipeline>, GitLabError> {
+        let mut query = Self::updated_after_query(updated_after);
+        query.push(("status", "failed".to_string()));
+        self.get_paginated(
+            &format!("/projects/{}/pipelines", project_path(project)),
+            &query,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_base_url() {
+        assert_eq!(
+            normalize_base_url("https://gitlab.example.com/"),
+            "https://gitlab.example.com"
+        );
+        assert_eq!(
+            normalize_base_url("https://gitlab.example.com/api/v4"),
+            "https://gitlab.example.com"
+        );
+        assert_eq!(
+            normalize_base_url("gitlab.example.com"),
+            "https://gitlab.example.com"
+        );
+    }
+
+    #[test]
+    fn test_project_path_is_encoded() {
+        assert_eq!(project_path("acme/platform/api"), "acme%2Fplatform%2Fapi");
+    }
+}
@@ -0,0 +1,309 @@
+//! GitLab source connector
+//!
+//! Connects to gitlab.com or a self-hosted instance with a personal access token
+//! and syncs the projects selected in settings, each with its own `sync_state` cursor.
+
+use async_trait::async_trait;
+use std::collections::HashSet;
+
+use super::client::{normalize_base_url, GitLabClient};
+use super::sync::{format_gitlab_timestamp, GitLabSyncService};
+use super::types::GitLabTokens;
+use crate::pipeline::PipelineTaskType;
+use crate::sync::connectivity::is_unreachable;
+use crate::sync::connector::{
+    clear_deselected_scopes, clear_sync_state, incremental_since, last_sync_at, load_credentials,
+    load_cursor, load_preference, save_cursor, save_preference, store_credentials,
+    ConnectorContext, ConnectorError, SourceConnector, SourceHealth, SourceScope, SyncRange,
+};
+use crate::sync::status::{mark_failed, mark_pending};
+use crate::sync::SyncResult;
+
+const SELECTED_PROJECTS_KEY: &str = "gitlab_selected_projects";
+
+pub(crate) async fn load_gitlab_tokens(
+    ctx: &ConnectorContext,
+) -> Result<Option<GitLabTokens>, ConnectorError> {
+    load_credentials(ctx, "gitlab").await
+}
+
+async fn load_selected_projects(ctx: &ConnectorContext) -> Result<Vec<String>, ConnectorError> {
+    load_preference(&ctx.db, SELECTED_PROJECTS_KEY).await
+}
+
+pub struct GitLabConnector;
+
+impl GitLabConnector {
+    async fn service(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<(GitLabSyncService, Vec<String>), ConnectorError> {
+        let tokens = load_gitlab_tokens(ctx)
+            .await?
+            .ok_or(ConnectorError::NotConnected("GitLab"))?;
+        let projects = load_selected_projects(ctx).await?;
+
+        let service = GitLabSyncService::new(
+            GitLabClient::from_tokens(&tokens),
+            ctx.db.clone(),
+            ctx.crypto.clone(),
+        );
+        Ok((service, projects))
+    }
+}
+
+#[async_trait]
+impl SourceConnector for GitLabConnector {
+    fn id(&self) -> &'static str {
+        "gitlab"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "GitLab"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sync merge requests, issues and failed pipelines from GitLab"
+    }
+
+    fn task_type(&self) -> PipelineTaskType {
+        PipelineTaskType::SyncGitlab
+    }
+
+    /// Accepts `{ "token", "baseUrl"? }`; `baseUrl` defaults to gitlab.com
+    async fn connect(
+        &self,
+        ctx: &ConnectorContext,
+        config: serde_json::Value,
+    ) -> Result<serde_json::Value, ConnectorError> {
+        let token = config["token"]
+            .as_str()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| ConnectorError::InvalidConfig("token is required".into()))?;
+        let base_url = config["baseUrl"]
+            .as_str()
+            .filter(|u| !u.trim().is_empty())
+            .map(String::from);
+
+        let client = GitLabClient::new()
+            .with_token(token.to_string())
+            .with_base_url(base_url);
+        let user = client
+            .get_user()
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+        let tokens = GitLabTokens {
+            access_token: token.to_string(),
+            base_url: normalize_base_url(client.base_url()),
+            username: user.username,
+        };
+        store_credentials(ctx, "gitlab", &tokens).await?;
+
+        tracing::info!(
+            "GitLab connected to {} as {}",
+            tokens.base_url,
+            tokens.username
+        );
+        Ok(serde_json::json!({ "username": tokens.username, "baseUrl": tokens.base_url }))
+    }
+
+    async fn is_connected(&self, ctx: &ConnectorContext) -> bool {
+        matches!(load_gitlab_tokens(ctx).await, Ok(Some(_)))
+    }
+
+    async fn list_scopes(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<Vec<SourceScope>, ConnectorError> {
+        let tokens = load_gitlab_tokens(ctx)
+            .await?
+            .ok_or(ConnectorError::NotConnected("GitLab"))?;
+        let selected: HashSet<String> = load_selected_projects(ctx).await?.into_iter().collect();
+
+        let projects = GitLabClient::from_tokens(&tokens)
+            .list_projects()
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+        Ok(projects
+            .into_iter()
+            .map(|project| SourceScope {
+                selected: selected.contains(&project.path_with_namespace),
+                id: project.path_with_namespace.clone(),
+                name: project.path_with_namespace,
+                kind: "project".to_string(),
+            })
+            .collect())
+    }
+
+    fn supports_scope_selection(&self) -> bool {
+        true
+    }
+
+    async fn select_scopes(
+        &self,
+        ctx: &ConnectorContext,
+        scope_ids: Vec<String>,
+    ) -> Result<(), ConnectorError> {
+        if let Some(invalid) = scope_ids.iter().find(|id| !id.contains('/')) {
+            return Err(ConnectorError::InvalidConfig(format!(
+                "Invalid project path: {}",
+                invalid
+            )));
+        }
+
+        save_preference(&ctx.db, SELECTED_PROJECTS_KEY, &scope_ids).await?;
+        clear_deselected_scopes(&ctx.db, self.id(), "project", &scope_ids).await?;
+
+        tracing::info!("Selected {} GitLab projects", scope_ids.len());
+        Ok(())
+    }
+
+    async fn sync_incremental(&self, ctx: &ConnectorContext) -> Result<SyncResult, ConnectorError> {
+        let (service, projects) = self.service(ctx).await?;
+        let mut total = SyncResult {
+            source: self.id().to_string(),
+            items_synced: 0,
+            errors: vec![],
+        };
+
+        mark_pending(&ctx.db, self.id(), "project", &projects).await;
+        for project in projects {
+            let now_ms = chrono::Utc::now().timestamp_millis();
+            let cursor = load_cursor(&ctx.db, self.id(), "project", &project).await?;
+            let since = incremental_since(cursor.as_deref(), now_ms);
+
+            let sync = service.sync_project(&project, since, None);
+            let result = ctx
+                .track_resource(self.id(), "project", &project, sync)
+                .await;
+            ctx.check_cancelled()?;
+            match result {
+                Ok(result) => {
+                    total.items_synced += result.items_synced;
+                    if result.errors.is_empty() {
+                        save_cursor(
+                            &ctx.db,
+                            self.id(),
+                            "project",
+                            &project,
+                            &format_gitlab_timestamp(now_ms),
+                        )
+                        .await?;
+                    } else {
+                        // Keep the cursor so what failed is fetched again next run
+                        let error = result.errors.join("; ");
+                        mark_failed(&ctx.db, self.id(), "project", &project, &error).await;
+                        total.errors.extend(result.errors);
+                    }
+                }
+                // Every project lives on the same GitLab instance
+                Err(e) if is_unreachable(&e) => {
+                    return Err(ConnectorError::Unreachable(e.to_string()))
+                }
+                Err(e) => {
+                    tracing::error!("GitLab sync error for {}: {}", project, e);
+                    total.errors.push(format!("{}: {}", project, e));
+                }
+            }
+        }
+
+        Ok(total)
+    }
+
+    async fn sync_range(
+        &self,
+        ctx: &ConnectorContext,
+        range: &SyncRange,
+    ) -> Result<SyncResult, ConnectorError> {
+        let (service, projects) = self.service(ctx).await?;
+        let (start, end) = range.utc_bounds();
+        let mut total = SyncResult {
+            source: self.id().to_string(),
+            items_synced: 0,
+            errors: vec![],
+        };
+
+        for project in projects {
+            let result = service
+                .sync_project(&project, start * 1000, Some(end * 1000))
+                .await;
+            ctx.check_cancelled()?;
+            match result {
+                Ok(result) => {
+                    total.items_synced += result.items_synced;
+                    total.errors.extend(result.errors);
+                }
+                // Every project lives on the same GitLab instance
+                Err(e) if is_unreachable(&e) => {
+                    return Err(ConnectorError::Unreachable(e.to_string()))
+                }
+                Err(e) => total.errors.push(format!("{}: {}", project, e)),
+            }
+        }
+
+        Ok(total)
+    }
+
+    async fn health(&self, ctx: &ConnectorContext) -> SourceHealth {
+        let last_sync_at = last_sync_at(&ctx.db, self.id()).await;
+        let mut health = SourceHealth {
+            source: self.id().to_string(),
+            connected: false,
+            healthy: false,
+            message: None,
+            last_sync_at,
+        };
+
+        let tokens = match load_gitlab_tokens(ctx).await {
+            Ok(Some(tokens)) => tokens,
+            Ok(None) => return health,
+            Err(e) => {
+                health.connected = true;
+                health.message = Some(e.to_string());
+                return health;
+            }
+        };
+        health.connected = true;
+
+        match GitLabClient::from_tokens(&tokens).get_user().await {
+            Ok(user) => {
+                let projects = load_selected_projects(ctx).await.unwrap_or_default();
+                health.healthy = true;
+                health.message = Some(if projects.is_empty() {
+                    format!(
+                        "Signed in to {} as {}; no projects selected",
+                        tokens.base_url, user.username
+                    )
+                } else {
+                    format!(
+                        "Signed in to {} as {}; {} projects",
+                        tokens.base_url,
+                        user.username,
+                        projects.len()
+                    )
+                });
+            }
+            Err(e) => health.message = Some(e.to_string()),
+        }
+
+        health
+    }
+
+    async fn disconnect(&self, ctx: &ConnectorContext) -> Result<(), ConnectorError> {
+        sqlx::query("DELETE FROM credentials WHERE id = 'gitlab'")
+            .execute(ctx.db.pool())
+            .await?;
+
+        sqlx::query("DELETE FROM preferences WHERE key = ?")
+            .bind(SELECTED_PROJECTS_KEY)
+            .execute(ctx.db.pool())
+            .await?;
+
+        clear_sync_state(&ctx.db, self.id()).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+//! GitLab integration module
+//!
+//! This module provides personal access token authentication against gitlab.com or
+//! a self-hosted instance and synchronization of merge requests, their discussions,
+//! issues and failed pipelines.
+
+mod client;
+mod connector;
+mod sync;
+mod types;
+
+pub use client::GitLabClient;
+pub use connector::GitLabConnector;
+pub use sync::GitLabSyncService;
+pub use types::{
+    GitLabDiscussion, GitLabError, GitLabIssue, GitLabMergeRequest, GitLabNote, GitLabPipeline,
+    GitLabProject, GitLabTokens, GitLabUser,
+};
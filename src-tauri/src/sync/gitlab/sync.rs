@@ -0,0 +1,375 @@
+//! GitLab data synchronization service
+
+use std::sync::Arc;
+
+use super::client::GitLabClient;
+use super::types::{GitLabError, GitLabIssue, GitLabMergeRequest, GitLabNote, GitLabPipeline};
+use crate::crypto::CryptoService;
+use crate::db::Database;
+use crate::sync::connector::parse_timestamp_ms;
+use crate::sync::SyncResult;
+
+/// A GitLab object normalized for `content_items`
+struct GitLabContent {
+    source_id: String,
+    url: String,
+    content_type: &'static str,
+    title: String,
+    text: String,
+    author: Option<String>,
+    project: String,
+    parent_id: Option<String>,
+    created_at: String,
+    updated_at: String,
+    metadata: serde_json::Value,
+}
+
+/// Format epoch milliseconds for GitLab's `updated_after` parameter
+pub(super) fn format_gitlab_timestamp(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .unwrap_or_default()
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string()
+}
+
+/// `group/project!42`, the key shared by a merge request and its notes
+fn merge_request_key(project: &str, iid: i64) -> String {
+    format!("{}!{}", project, iid)
+}
+
+fn with_body(title: &str, body: Option<&str>) -> String {
+    match body.map(str::trim).filter(|b| !b.is_empty()) {
+        Some(body) => format!("{}\n\n{}", title, body),
+        None => title.to_string(),
+    }
+}
+
+fn merge_request_content(project: &str, mr: &GitLabMergeRequest) -> GitLabContent {
+    let title = format!("MR !{}: {}", mr.iid, mr.title);
+    let branches = match (&mr.source_branch, &mr.target_branch) {
+        (Some(source), Some(target)) => format!(" ({} → {})", source, target),
+        _ => String::new(),
+    };
+
+    GitLabContent {
+        source_id: merge_request_key(project, mr.iid),
+        url: mr.web_url.clone(),
+        content_type: "pull_request",
+        text: with_body(
+            &format!("{} [{}]{}", title, mr.state, branches),
+            mr.description.as_deref(),
+        ),
+        title,
+        author: mr.author.as_ref().map(|u| u.username.clone()),
+        project: project.to_string(),
+        parent_id: None,
+        created_at: mr.created_at.clone(),
+        updated_at: mr.updated_at.clone(),
+        metadata: serde_json::json!({
+            "project": project,
+            "iid": mr.iid,
+            "state": mr.state,
+            "labels": mr.labels,
+        }),
+    }
+}
+
+fn note_content(
+    project: &str,
+    mr: &GitLabMergeRequest,
+    note: &GitLabNote,
+) -> Option<GitLabContent> {
+    let body = note.body.trim();
+    if note.system || body.is_empty() {
+        return None;
+    }
+
+    let key = merge_request_key(project, mr.iid);
+    Some(GitLabContent {
+        source_id: format!("{}/note/{}", key, note.id),
+        url: format!("{}#note_{}", mr.web_url, note.id),
+        content_type: "comment",
+        title: format!("Comment on MR !{}", mr.iid),
+        text: format!("Comment on MR !{} ({}): {}", mr.iid, mr.title, body),
+        author: note.author.as_ref().map(|u| u.username.clone()),
+        project: project.to_string(),
+        parent_id: Some(key),
+        created_at: note.created_at.clone(),
+        updated_at: note.updated_at.clone(),
+        metadata: serde_json::json!({
+            "project": project,
+            "iid": mr.iid,
+            "resolved": note.resolved,
+        }),
+    })
+}
+
+fn issue_content(project: &str, issue: &GitLabIssue) -> GitLabContent {
+    let title = format!("Issue #{}: {}", issue.iid, issue.title);
+
+    GitLabContent {
+        source_id: format!("{}#{}", project, issue.iid),
+        url: issue.web_url.clone(),
+        content_type: "ticket",
+        text: with_body(
+            &format!("{} [{}]", title, issue.state),
+            issue.description.as_deref(),
+        ),
+        title,
+        author: issue.author.as_ref().map(|u| u.username.clone()),
+        project: project.to_string(),
+        parent_id: None,
+        created_at: issue.created_at.clone(),
+        updated_at: issue.updated_at.clone(),
+        metadata: serde_json::json!({
+            "project": project,
+            "iid": issue.iid,
+            "state": issue.state,
+            "labels": issue.labels,
+        }),
+    }
+}
+
+fn pipeline_content(project: &str, pipeline: &GitLabPipeline) -> GitLabContent {
+    let git_ref = pipeline.git_ref.as_deref().unwrap_or("unknown ref");
+    let title = format!("Pipeline #{} failed on {}", pipeline.id, git_ref);
+    let commit = pipeline
+        .sha
+        .as_deref()
+        .map(|sha| format!(" at {}", &sha[..sha.len().min(8)]))
+        .unwrap_or_default();
+
+    GitLabContent {
+        source_id: format!("{}/pipelines/{}", project, pipeline.id),
+        url: pipeline.web_url.clone(),
+        content_type: "notification",
+        text: format!("{} in {}{}", title, project, commit),
+        title,
+        author: None,
+        project: project.to_string(),
+        parent_id: None,
+        // Report failures when they happened, not when the pipeline started
+        created_at: pipeline.updated_at.clone(),
+        updated_at: pipeline.updated_at.clone(),
+        metadata: serde_json::json!({
+            "project": project,
+            "pipeline_id": pipeline.id,
+            "ref": pipeline.git_ref,
+            "sha": pipeline.sha,
+            "trigger": pipeline.source,
+        }),
+    }
+}
+
+/// Encrypt and upsert a GitLab object into `content_items`
+async fn store_content(
+    db: &Database,
+    crypto: &CryptoService,
+    item: &GitLabContent,
+) -> Result<(), GitLabError> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let created_at = parse_timestamp_ms(&item.created_at).unwrap_or(now);
+    let updated_at = parse_timestamp_ms(&item.updated_at).unwrap_or(now);
+
+    let encrypted_body = crypto
+        .encrypt_string(&item.text)
+        .map_err(|e| GitLabError::Crypto(e.to_string()))?;
+    let metadata = item.metadata.to_string();
+
+    sqlx::query(
+        "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, parent_id, created_at, updated_at, synced_at, metadata)
+         VALUES (?, 'gitlab', ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(source, source_id) DO UPDATE SET title = ?, body = ?, updated_at = ?, synced_at = ?, metadata = ?"
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(&item.source_id)
+    .bind(&item.url)
+    .bind(item.content_type)
+    .bind(&item.title)
+    .bind(&encrypted_body)
+    .bind(&item.author)
+    .bind(&item.project)
+    .bind(&item.parent_id)
+    .bind(created_at)
+    .bind(updated_at)
+    .bind(now)
+    .bind(&metadata)
+    .bind(&item.title)
+    .bind(&encrypted_body)
+    .bind(updated_at)
+    .bind(now)
+    .bind(&metadata)
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+pub struct GitLabSyncService {
+    client: GitLabClient,
+    db: Arc<Database>,
+    crypto: Arc<CryptoService>,
+}
+
+impl GitLabSyncService {
+    pub fn new(client: GitLabClient, db: Arc<Database>, crypto: Arc<CryptoService>) -> Self {
+        Self { client, db, crypto }
+    }
+
+    /// Sync merge requests with their discussions, issues and failed pipelines of one
+    /// project updated at or after `since_ms`. With `until_ms`, objects created at or
+    /// after it are skipped, bounding historical syncs.
+    pub async fn sync_project(
+        &self,
+        project: &str,
+        since_ms: i64,
+        until_ms: Option<i64>,
+    ) -> Result<SyncResult, GitLabError> {
+        let since = format_gitlab_timestamp(since_ms);
+        let mut contents = Vec::new();
+        let mut errors = Vec::new();
+
+        let merge_requests = self.client.list_merge_requests(project, &since).await?;
+        for mr in &merge_requests {
+            contents.push(merge_request_content(project, mr));
+
+            match self
+                .client
+                .list_merge_request_discussions(project, mr.iid)
+                .await
+            {
+                Ok(discussions) => contents.extend(
+                    discussions
+                        .iter()
+                        .flat_map(|d| &d.notes)
+                        .filter(|n| parse_timestamp_ms(&n.updated_at) >= Some(since_ms))
+                        .filter_map(|n| note_content(project, mr, n)),
+                ),
+                Err(e) => errors.push(format!("{}!{} discussions: {}", project, mr.iid, e)),
+            }
+        }
+
+        let issues = self.client.list_issues(project, &since).await?;
+        contents.extend(issues.iter().map(|issue| issue_content(project, issue)));
+
+        // Pipelines may be disabled or hidden from the token; don't fail the project
+        match self.client.list_failed_pipelines(project, &since).await {
+            Ok(pipelines) => {
+                contents.extend(pipelines.iter().map(|p| pipeline_content(project, p)))
+            }
+            Err(e) => {
+                tracing::warn!("Skipping pipelines for {}: {}", project, e);
+            }
+        }
+
+        let mut items_synced = 0;
+        for item in &contents {
+            let created = parse_timestamp_ms(&item.created_at).unwrap_or_default();
+            if until_ms.is_some_and(|until| created >= until) {
+                continue;
+            }
+            match store_content(&self.db, &self.crypto, item).await {
+                Ok(()) => items_synced += 1,
+                Err(e) => errors.push(format!("{}: {}", item.source_id, e)),
+            }
+        }
+
+        tracing::info!("Synced {} GitLab items from {}", items_synced, project);
+
+        Ok(SyncResult {
+            source: "gitlab".to_string(),
+            items_synced,
+            errors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::gitlab::types::GitLabUser;
+
+    fn merge_request() -> GitLabMergeRequest {
+        GitLabMergeRequest {
+            id: 1,
+            iid: 42,
+            title: "Add retry to webhook delivery".into(),
+            description: Some("Closes #7".into()),
+            state: "opened".into(),
+            web_url: "https://gitlab.example.com/acme/api/-/merge_requests/42".into(),
+            author: Some(GitLabUser {
+                username: "jdoe".into(),
+                id: 1,
+            }),
+            labels: vec!["backend".into()],
+            source_branch: Some("retry".into()),
+            target_branch: Some("main".into()),
+            created_at: "2024-01-15T10:00:00.000Z".into(),
+            updated_at: "2024-01-15T12:00:00.000Z".into(),
+        }
+    }
+
+    #[test]
+    fn test_timestamp_with_fraction() {
+        let ms = parse_timestamp_ms("2024-01-15T10:00:00.250Z").unwrap();
+        assert_eq!(ms, 1705312800250);
+        assert_eq!(format_gitlab_timestamp(ms), "2024-01-15T10:00:00Z");
+    }
+
+    #[test]
+    fn test_merge_request_content() {
+        let content = merge_request_content("acme/api", &merge_request());
+        assert_eq!(content.source_id, "acme/api!42");
+        assert_eq!(content.content_type, "pull_request");
+        assert!(content
+            .text
+            .starts_with("MR !42: Add retry to webhook delivery [opened] (retry → main)"));
+        assert!(content.text.contains("Closes #7"));
+    }
+
+    #[test]
+    fn test_note_links_to_merge_request() {
+        let mr = merge_request();
+        let note = GitLabNote {
+            id: 1126,
+            body: "Can we add a backoff cap?".into(),
+            author: None,
+            created_at: "2024-01-15T13:00:00Z".into(),
+            updated_at: "2024-01-15T13:00:00Z".into(),
+            system: false,
+            resolved: Some(false),
+        };
+        let content = note_content("acme/api", &mr, &note).unwrap();
+        assert_eq!(content.parent_id.as_deref(), Some("acme/api!42"));
+        assert_eq!(
+            content.url,
+            "https://gitlab.example.com/acme/api/-/merge_requests/42#note_1126"
+        );
+
+        let system = GitLabNote {
+            system: true,
+            ..note
+        };
+        assert!(note_content("acme/api", &mr, &system).is_none());
+    }
+
+    #[test]
+    fn test_failed_pipeline_content() {
+        let pipeline = GitLabPipeline {
+            id: 46,
+            status: "failed".into(),
+            git_ref: Some("main".into()),
+            sha: Some("a91957a858320c0e17f3a0eca7cfacbff50ea29a".into()),
+            web_url: "https://gitlab.example.com/acme/api/-/pipelines/46".into(),
+            source: Some("push".into()),
+            created_at: "2024-01-15T10:00:00Z".into(),
+            updated_at: "2024-01-15T10:20:00Z".into(),
+        };
+        let content = pipeline_content("acme/api", &pipeline);
+        assert_eq!(
+            content.text,
+            "Pipeline #46 failed on main in acme/api at a91957a8"
+        );
+        assert_eq!(content.created_at, "2024-01-15T10:20:00Z");
+    }
+}
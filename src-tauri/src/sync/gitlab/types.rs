@@ -0,0 +1,151 @@
+//! GitLab data types and error definitions
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum GitLabError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Authentication failed: {0}")]
+    Auth(String),
+
+    #[error("Rate limited by GitLab, retry after {0}s")]
+    RateLimited(u64),
+
+    #[error("API error: {0}")]
+    Api(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Crypto error: {0}")]
+    Crypto(String),
+}
+
+/// Stored GitLab credentials
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabTokens {
+    pub access_token: String,
+    /// Instance root, e.g. `https://gitlab.example.com`
+    pub base_url: String,
+    pub username: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabUser {
+    pub username: String,
+    #[serde(default)]
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabProject {
+    pub id: i64,
+    pub path_with_namespace: String,
+    pub web_url: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabMergeRequest {
+    pub id: i64,
+    pub iid: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub state: String,
+    pub web_url: String,
+    pub author: Option<GitLabUser>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub source_branch: Option<String>,
+    pub target_branch: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabIssue {
+    pub id: i64,
+    pub iid: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub state: String,
+    pub web_url: String,
+    pub author: Option<GitLabUser>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A thread on a merge request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabDiscussion {
+    pub id: String,
+    #[serde(default)]
+    pub notes: Vec<GitLabNote>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabNote {
+    pub id: i64,
+    pub body: String,
+    pub author: Option<GitLabUser>,
+    pub created_at: String,
+    pub updated_at: String,
+    /// Notes generated by GitLab itself, e.g. "added 1 commit"
+    #[serde(default)]
+    pub system: bool,
+    #[serde(default)]
+    pub resolved: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabPipeline {
+    pub id: i64,
+    pub status: String,
+    #[serde(rename = "ref")]
+    pub git_ref: Option<String>,
+    pub sha: Option<String>,
+    pub web_url: String,
+    pub source: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipeline_ref_field() {
+        let json = r#"{
+            "id": 46, "status": "failed", "ref": "main", "sha": "a91957a8",
+            "web_url": "https://gitlab.example.com/acme/api/-/pipelines/46",
+            "source": "push",
+            "created_at": "2024-01-15T10:00:00.000Z", "updated_at": "2024-01-15T10:05:00.000Z"
+        }"#;
+        let pipeline: GitLabPipeline = serde_json::from_str(json).unwrap();
+        assert_eq!(pipeline.git_ref.as_deref(), Some("main"));
+        assert_eq!(pipeline.status, "failed");
+    }
+
+    #[test]
+    fn test_discussion_notes_default_to_user_notes() {
+        let json = r#"{
+            "id": "6a9c1750b37d513a43987b574953fceb50b03ce7",
+            "notes": [{
+                "id": 1126, "body": "Can we split this?", "author": {"username": "jdoe"},
+                "created_at": "2024-01-15T10:00:00.000Z", "updated_at": "2024-01-15T10:00:00.000Z"
+            }]
+        }"#;
+        let discussion: GitLabDiscussion = serde_json::from_str(json).unwrap();
+        assert!(!discussion.notes[0].system);
+        assert_eq!(
+            discussion.notes[0].author.as_ref().unwrap().username,
+            "jdoe"
+        );
+    }
+}
@@ -0,0 +1,359 @@
+//! Incident API client for PagerDuty's REST API v2 and compatible services
+
+use reqwest::{Client, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+
+use super::types::{Incident, IncidentError, IncidentLogEntry, IncidentNote, IncidentTokens};
+
+const PAGERDUTY_BASE_URL: &str = "https://api.pagerduty.com";
+const ACCEPT: &str = "application/vnd.pagerduty+json;version=2";
+const PAGE_LIMIT: usize = 100;
+
+pub struct IncidentClient {
+    http: Client,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl Default for IncidentClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Normalize a user-entered API URL, e.g. `api.eu.pagerduty.com/` -> `https://api.eu.pagerduty.com`
+pub(super) fn normalize_base_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    if url.starts_with("http://") || url.starts_with("https://") {
+        url.to_string()
+    } else {
+        format!("https://{}", url)
+    }
+}
+
+impl IncidentClient {
+    pub fn new() -> Self {
+        Self {
+            http: Client::new(),
+            base_url: PAGERDUTY_BASE_URL.to_string(),
+            api_key: None,
+        }
+    }
+
+    pub fn with_api_key(mut self, api_key: String) -> Self {
+        self.api_key = Some(api_key);
+        self
+    }
+
+    pub fn with_base_url(mut self, base_url: Option<String>) -> Self {
+        if let Some(url) = base_url.filter(|u| !u.trim().is_empty()) {
+            self.base_url = normalize_base_url(&url);
+        }
+        self
+    }
+
+    /// Client for stored credentials
+    pub fn from_tokens(tokens: &IncidentTokens) -> Self {
+        Self::new()
+            .with_api_key(tokens.api_key.clone())
+            .with_base_url(Some(tokens.base_url.clone()))
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn authed(&self, request: RequestBuilder) -> Result<RequestBuilder, IncidentError> {
+        let api_key = self
+            .api_key
+            .as_ref()
+            .ok_or_else(|| IncidentError::Auth("Not authenticated".into()))?;
+
+        Ok(request
+            .header("Authorization", format!("Token token={}", api_key))
+            .header("Accept", ACCEPT))
+    }
+
+    async fn check(response: Response) -> Result<Response, IncidentError> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        if status.as_u16() == 401 || status.as_u16() == 403 {
+            return Err(IncidentError::Auth("API key rejected".into()));
+        }
+        if status.as_u16() == 429 {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60);
+            return Err(IncidentError::RateLimited(retry_after));
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        Err(IncidentError::Api(format!("HTTP {}: {}", status, body)))
+    }
+
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<T, IncidentError> {
+        let url = format!("{}{}", self.base_url, path);
+        let request = self.authed(self.http.get(&url).query(query))?;
+        let response = Self::check(request.send().await?).await?;
+        Ok(response.json().await?)
+    }
+
+    /// Fetch every page of a list endpoint whose items are under `key`. Activity
+    /// listings are bounded by their `since`/`until` window.
+    async fn get_paginated<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        key: &str,
+        query: &[(&str, String)],
+    ) -> Result<Vec<T>, IncidentError> {
+        let mut all = Vec::new();
+
+        for page in 0.. {
+            let mut page_query = query.to_vec();
+            page_query.push(("limit", PAGE_LIMIT.to_string()));
+            page_query.push(("offset", (page * PAGE_LIMIT).to_string()));
+
+            let mut body: serde_json::Value = self.get_json(path, &page_query).await?;
+            let items: Vec<T> = serde_json::from_value(body[key].take())
+                .map_err(|e| IncidentError::Api(format!("Unexpected {} response: {}", key, e)))?;
+            let done = items.is_empty() || !body["more"].as_bool().unwrap_or(false);
+            all.extend(items);
+            if done {
+                break;
+            }
+        }
+
+        Ok(all)
+    }
+
+    /// Check the key by listing a single incident
+    pub async fn verify(&self) -> Result<(), IncidentError> {
+        let _: serde_json::Value = self
+            .get_json("/incidents", &[("limit", "1".to_string())])
+            .await?;
+        Ok(())
+    }
+
+    /// Incidents created within `[since, until)` (ISO 8601)
+    pub async fn list_incidents(
+        &self,
+        since: &str,
+        until: &str,
+    ) -> Result<Vec<Incident>, IncidentError> {
+        self.get_paginated(
+            "/incidents",
+            "incidents",
+            &[
+                ("since", since.to_string()),
+                ("until", until.to_string()),
+                ("sort_by", "created_at:asc".to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// Timeline entries across all incidents within `[since, until)` (ISO 8601)
+    pub async fn list_log_entries(
+        &self,
+        since: &str,
+        until: &str,
+    ) -> Result<Vec<IncidentLogEntry>, IncidentError> {
+        self.get_paginated(
+            "/log_entries",
+            "log_entries",
+            &[
+                ("since", since.to_string()),
+                ("until", until.to_string()),
+                ("is_overview", "true".to_string()),
+            ],
+        )
+        .await
+    }
+
+    pub async fn get_incident(&self, id: &str) -> Result<Incident, IncidentError> {
+        let mut body: serde_json::Value = self
+            .get_json(&format!("/incidents/{}", urlencoding::encode(id)), &[])
+            .await?;
+        serde_json::from_value(body["incident"].take())
+            .map_err(|e| IncidentError::Api(format!("Unexpected incident response: {}", e)))
+    }
+
+    /// Overview timeline of one incident
+    pub async fn list_incident_log_entries(
+        &self,
+        id: &str,
+    ) -> Result<Vec<IncidentLogEntry>, IncidentError> {
+        self.get_paginated(
+            &format!("/incidents/{}/log_entries", urlencoding::encode(id)),
+            "log_entries",
+            &[("is_overview", "true".to_string())],
+        )
+        .await
+    }
+
+    pub async fn list_notes(&self, id: &str) -> Result<Vec<IncidentNote>, IncidentError> {
+        let mut body: serde_json::Value = self
+            .get_json(
+                &format!("/incidents/{}/notes", urlencoding::encode(id)),
+                &[],
+            )
+            .await?;
+        serde_json::from_value(body["notes"].take())
+            .map_err(|e| IncidentError::Api(format!("Unexpected notes response: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const API_KEY: &str = "u+test-key";
+
+    fn incident_json(status: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": "PT4KHLK", "incident_number": 1234, "title": "Checkout 5xx",
+            "status": status, "urgency": "high",
+            "service": {"id": "PIJ90N7", "summary": "Checkout"},
+            "assignments": [{"assignee": {"id": "PXPGF42", "summary": "Earline Greenholt"}}],
+            "html_url": "https://acme.pagerduty.com/incidents/PT4KHLK",
+            "created_at": "2024-03-05T10:00:00Z"
+        })
+    }
+
+    /// Response body of the fake API for a request path and query
+    fn respond(path: &str, query: &str) -> serde_json::Value {
+        let offset = query
+            .split('&')
+            .find_map(|p| p.strip_prefix("offset="))
+            .unwrap_or("0");
+        match path {
+            "/incidents" => serde_json::json!({
+                "incidents": [incident_json("acknowledged")], "more": false
+            }),
+            "/incidents/PT4KHLK" => {
+                serde_json::json!({ "incident": incident_json("acknowledged") })
+            }
+            // Two pages, to exercise pagination
+            "/log_entries" | "/incidents/PT4KHLK/log_entries" if offset == "0" => {
+                serde_json::json!({
+                    "log_entries": [{
+                        "id": "R1", "type": "trigger_log_entry",
+                        "summary": "Triggered through the API",
+                        "created_at": "2024-03-05T10:00:00Z",
+                        "incident": {"id": "PT4KHLK"}
+                    }],
+                    "more": true
+                })
+            }
+            "/log_entries" | "/incidents/PT4KHLK/log_entries" => serde_json::json!({
+                "log_entries": [{
+                    "id": "R2", "type": "acknowledge_log_entry",
+                    "summary": "Acknowledged by Earline Greenholt",
+                    "created_at": "2024-03-05T10:04:00Z",
+                    "agent": {"id": "PXPGF42", "summary": "Earline Greenholt"},
+                    "incident": {"id": "PT4KHLK"}
+                }],
+                "more": false
+            }),
+            "/incidents/PT4KHLK/notes" => serde_json::json!({
+                "notes": [{
+                    "id": "PWL7QXS", "content": "Rolled back 2024.03.05-2",
+                    "created_at": "2024-03-05T10:20:00Z",
+                    "user": {"id": "PXPGF42", "summary": "Earline Greenholt"}
+                }]
+            }),
+            _ => serde_json::Value::Null,
+        }
+    }
+
+    /// Fake incident API on localhost answering from canned responses
+    async fn spawn_fake_api() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 8192];
+                    let n = socket.read(&mut buf).await.unwrap();
+                    let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                    let target = request.split_whitespace().nth(1).unwrap_or("/");
+                    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+                    let authorized = request.lines().any(|l| {
+                        l.eq_ignore_ascii_case(&format!("authorization: Token token={}", API_KEY))
+                    });
+                    let (status, body) = match respond(path, query) {
+                        _ if !authorized => ("401 Unauthorized", "{}".to_string()),
+                        serde_json::Value::Null => ("404 Not Found", "{}".to_string()),
+                        body => ("200 OK", body.to_string()),
+                    };
+                    let response = format!(
+                        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    socket.write_all(response.as_bytes()).await.unwrap();
+                });
+            }
+        });
+
+        format!("http://127.0.0.1:{}", port)
+    }
+
+    #[test]
+    fn test_normalize_base_url() {
+        assert_eq!(
+            normalize_base_url("api.eu.pagerduty.com/"),
+            "https://api.eu.pagerduty.com"
+        );
+        assert_eq!(
+            normalize_base_url("http://127.0.0.1:8080"),
+            "http://127.0.0.1:8080"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client_against_fake_api() {
+        let base_url = spawn_fake_api().await;
+
+        let unauthorized = IncidentClient::new()
+            .with_api_key("wrong".into())
+            .with_base_url(Some(base_url.clone()));
+        assert!(matches!(
+            unauthorized.verify().await,
+            Err(IncidentError::Auth(_))
+        ));
+
+        let client = IncidentClient::new()
+            .with_api_key(API_KEY.into())
+            .with_base_url(Some(base_url));
+        client.verify().await.unwrap();
+
+        let entries = client
+            .list_log_entries("2024-03-05T00:00:00Z", "2024-03-06T00:00:00Z")
+            .await
+            .unwrap();
+        let types: Vec<_> = entries.iter().map(|e| e.entry_type.as_str()).collect();
+        assert_eq!(types, vec!["trigger_log_entry", "acknowledge_log_entry"]);
+
+        let incident = client.get_incident("PT4KHLK").await.unwrap();
+        assert_eq!(incident.incident_number, 1234);
+        let notes = client.list_notes("PT4KHLK").await.unwrap();
+        assert_eq!(notes[0].content, "Rolled back 2024.03.05-2");
+    }
+}
@@ -0,0 +1,205 @@
+//! Incident source connector
+//!
+//! Connects to PagerDuty, or a service exposing the same REST API, with an API
+//! key and syncs incidents that saw activity since the last sync.
+
+use async_trait::async_trait;
+
+use super::client::{normalize_base_url, IncidentClient};
+use super::sync::{format_incident_timestamp, IncidentSyncService};
+use super::types::IncidentTokens;
+use crate::pipeline::PipelineTaskType;
+use crate::sync::connector::{
+    clear_sync_state, incremental_since, last_sync_at, load_credentials, load_cursor, save_cursor,
+    store_credentials, ConnectorContext, ConnectorError, SourceConnector, SourceHealth,
+    SourceScope, SyncRange,
+};
+use crate::sync::status::mark_failed;
+use crate::sync::SyncResult;
+
+/// `sync_state` resource for the account-wide activity cursor
+const ACTIVITY_RESOURCE: &str = "activity";
+
+pub(crate) async fn load_incident_tokens(
+    ctx: &ConnectorContext,
+) -> Result<Option<IncidentTokens>, ConnectorError> {
+    load_credentials(ctx, "incident").await
+}
+
+pub struct IncidentConnector;
+
+impl IncidentConnector {
+    async fn service(&self, ctx: &ConnectorContext) -> Result<IncidentSyncService, ConnectorError> {
+        let tokens = load_incident_tokens(ctx)
+            .await?
+            .ok_or(ConnectorError::NotConnected("Incidents"))?;
+
+        Ok(IncidentSyncService::new(
+            IncidentClient::from_tokens(&tokens),
+            ctx.db.clone(),
+            ctx.crypto.clone(),
+        ))
+    }
+}
+
+#[async_trait]
+impl SourceConnector for IncidentConnector {
+    fn id(&self) -> &'static str {
+        "incident"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Incidents"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sync incidents, status changes, responders and notes from PagerDuty"
+    }
+
+    fn task_type(&self) -> PipelineTaskType {
+        PipelineTaskType::SyncIncidents
+    }
+
+    fn item_label(&self) -> &'static str {
+        "incident updates"
+    }
+
+    /// Accepts `{ "token", "baseUrl"? }`; `baseUrl` defaults to api.pagerduty.com
+    async fn connect(
+        &self,
+        ctx: &ConnectorContext,
+        config: serde_json::Value,
+    ) -> Result<serde_json::Value, ConnectorError> {
+        let token = config["token"]
+            .as_str()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| ConnectorError::InvalidConfig("token is required".into()))?;
+        let base_url = config["baseUrl"]
+            .as_str()
+            .filter(|u| !u.trim().is_empty())
+            .map(String::from);
+
+        let client = IncidentClient::new()
+            .with_api_key(token.to_string())
+            .with_base_url(base_url);
+        client
+            .verify()
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+        let tokens = IncidentTokens {
+            api_key: token.to_string(),
+            base_url: normalize_base_url(client.base_url()),
+        };
+        store_credentials(ctx, "incident", &tokens).await?;
+
+        tracing::info!("Incident API connected at {}", tokens.base_url);
+        Ok(serde_json::json!({ "baseUrl": tokens.base_url }))
+    }
+
+    async fn is_connected(&self, ctx: &ConnectorContext) -> bool {
+        matches!(load_incident_tokens(ctx).await, Ok(Some(_)))
+    }
+
+    async fn list_scopes(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<Vec<SourceScope>, ConnectorError> {
+        let tokens = load_incident_tokens(ctx)
+            .await?
+            .ok_or(ConnectorError::NotConnected("Incidents"))?;
+        Ok(vec![SourceScope {
+            id: "all".to_string(),
+            name: tokens.base_url,
+            kind: "account".to_string(),
+            selected: true,
+        }])
+    }
+
+    async fn sync_incremental(&self, ctx: &ConnectorContext) -> Result<SyncResult, ConnectorError> {
+        let service = self.service(ctx).await?;
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let cursor = load_cursor(&ctx.db, self.id(), ACTIVITY_RESOURCE, "all").await?;
+        let since = incremental_since(cursor.as_deref(), now_ms);
+
+        let sync = service.sync_activity(since, now_ms);
+        let result = ctx
+            .track_resource(self.id(), ACTIVITY_RESOURCE, "all", sync)
+            .await
+            .map_err(ConnectorError::classify)?;
+        ctx.check_cancelled()?;
+        if result.errors.is_empty() {
+            save_cursor(
+                &ctx.db,
+                self.id(),
+                ACTIVITY_RESOURCE,
+                "all",
+                &format_incident_timestamp(now_ms),
+            )
+            .await?;
+        } else {
+            // Keep the cursor so what failed is fetched again next run
+            let error = result.errors.join("; ");
+            mark_failed(&ctx.db, self.id(), ACTIVITY_RESOURCE, "all", &error).await;
+        }
+
+        Ok(result)
+    }
+
+    async fn sync_range(
+        &self,
+        ctx: &ConnectorContext,
+        range: &SyncRange,
+    ) -> Result<SyncResult, ConnectorError> {
+        let service = self.service(ctx).await?;
+        let (start, end) = range.utc_bounds();
+
+        service
+            .sync_created(start * 1000, end * 1000)
+            .await
+            .map_err(ConnectorError::classify)
+    }
+
+    async fn health(&self, ctx: &ConnectorContext) -> SourceHealth {
+        let last_sync_at = last_sync_at(&ctx.db, self.id()).await;
+        let mut health = SourceHealth {
+            source: self.id().to_string(),
+            connected: false,
+            healthy: false,
+            message: None,
+            last_sync_at,
+        };
+
+        let tokens = match load_incident_tokens(ctx).await {
+            Ok(Some(tokens)) => tokens,
+            Ok(None) => return health,
+            Err(e) => {
+                health.connected = true;
+                health.message = Some(e.to_string());
+                return health;
+            }
+        };
+        health.connected = true;
+
+        match IncidentClient::from_tokens(&tokens).verify().await {
+            Ok(()) => {
+                health.healthy = true;
+                health.message = Some(format!("Connected to {}", tokens.base_url));
+            }
+            Err(e) => health.message = Some(e.to_string()),
+        }
+
+        health
+    }
+
+    async fn disconnect(&self, ctx: &ConnectorContext) -> Result<(), ConnectorError> {
+        sqlx::query("DELETE FROM credentials WHERE id = 'incident'")
+            .execute(ctx.db.pool())
+            .await?;
+
+        clear_sync_state(&ctx.db, self.id()).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+//! Incident integration module
+//!
+//! This module provides API key authentication against PagerDuty (or a service
+//! exposing the same REST API) and synchronization of incidents, their status
+//! changes, responders and notes.
+
+mod client;
+mod connector;
+mod sync;
+mod types;
+
+pub use client::IncidentClient;
+pub use connector::IncidentConnector;
+pub use sync::IncidentSyncService;
+pub use types::{
+    Incident, IncidentError, IncidentLogEntry, IncidentNote, IncidentReference, IncidentTokens,
+};
@@ -0,0 +1,399 @@
+//! Incident data synchronization service
+
+use std::sync::Arc;
+
+use super::client::IncidentClient;
+use super::types::{Incident, IncidentError, IncidentLogEntry, IncidentNote};
+use crate::crypto::CryptoService;
+use crate::db::Database;
+use crate::sync::connector::parse_timestamp_ms;
+use crate::sync::SyncResult;
+
+/// An incident object normalized for `content_items`
+struct IncidentContent {
+    source_id: String,
+    url: String,
+    content_type: &'static str,
+    title: String,
+    text: String,
+    author: Option<String>,
+    service: String,
+    parent_id: Option<String>,
+    created_at: String,
+    metadata: serde_json::Value,
+}
+
+/// Format epoch milliseconds for the `since`/`until` parameters
+pub(super) fn format_incident_timestamp(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .unwrap_or_default()
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string()
+}
+
+/// Past-tense label for the timeline entries kept as status changes; other
+/// entry types (notifications, annotations) are skipped
+fn status_change_label(entry_type: &str) -> Option<&'static str> {
+    match entry_type {
+        "trigger_log_entry" => Some("triggered"),
+        "acknowledge_log_entry" => Some("acknowledged"),
+        "unacknowledge_log_entry" => Some("unacknowledged"),
+        "assign_log_entry" => Some("reassigned"),
+        "escalate_log_entry" => Some("escalated"),
+        "resolve_log_entry" => Some("resolved"),
+        _ => None,
+    }
+}
+
+fn service_name(incident: &Incident) -> String {
+    incident
+        .service
+        .as_ref()
+        .and_then(|s| s.summary.clone())
+        .unwrap_or_else(|| "Incidents".to_string())
+}
+
+/// Metadata shared by an incident and its timeline, read by the AI pipeline
+/// to prioritize active and high-severity incidents
+fn incident_metadata(incident: &Incident) -> serde_json::Value {
+    serde_json::json!({
+        "incident_id": incident.id,
+        "incident_number": incident.incident_number,
+        "status": incident.status,
+        "urgency": incident.urgency,
+        "priority": incident.priority.as_ref().map(|p| &p.summary),
+        "responders": incident.responders(),
+        "active": incident.is_active(),
+        "high_severity": incident.is_high_severity(),
+    })
+}
+
+fn incident_content(incident: &Incident) -> IncidentContent {
+    let title = format!("Incident #{}: {}", incident.incident_number, incident.title);
+    let mut labels = vec![incident.status.clone()];
+    if let Some(urgency) = &incident.urgency {
+        labels.push(format!("{} urgency", urgency));
+    }
+    if let Some(priority) = &incident.priority {
+        labels.push(priority.summary.clone());
+    }
+
+    let mut text = format!("{} [{}]", title, labels.join(", "));
+    text.push_str(&format!("\nService: {}", service_name(incident)));
+    let responders = incident.responders();
+    if !responders.is_empty() {
+        text.push_str(&format!("\nResponders: {}", responders.join(", ")));
+    }
+    if let Some(description) = incident
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty() && *d != incident.title)
+    {
+        text.push_str(&format!("\n\n{}", description));
+    }
+
+    IncidentContent {
+        source_id: incident.id.clone(),
+        url: incident.html_url.clone(),
+        content_type: "incident",
+        title,
+        text,
+        author: None,
+        service: service_name(incident),
+        parent_id: None,
+        created_at: incident.created_at.clone(),
+        metadata: incident_metadata(incident),
+    }
+}
+
+fn log_entry_content(incident: &Incident, entry: &IncidentLogEntry) -> Option<IncidentContent> {
+    let label = status_change_label(&entry.entry_type)?;
+    let summary = entry
+        .summary
+        .clone()
+        .unwrap_or_else(|| format!("Incident {}", label));
+
+    Some(IncidentContent {
+        source_id: format!("{}/log/{}", incident.id, entry.id),
+        url: incident.html_url.clone(),
+        content_type: "status_change",
+        title: format!("Incident #{} {}", incident.incident_number, label),
+        text: format!(
+            "Incident #{} ({}) {}: {}",
+            incident.incident_number, incident.title, label, summary
+        ),
+        author: entry.agent.as_ref().and_then(|a| a.summary.clone()),
+        service: service_name(incident),
+        parent_id: Some(incident.id.clone()),
+        created_at: entry.created_at.clone(),
+        metadata: incident_metadata(incident),
+    })
+}
+
+fn note_content(incident: &Incident, note: &IncidentNote) -> Option<IncidentContent> {
+    let body = note.content.trim();
+    if body.is_empty() {
+        return None;
+    }
+
+    Some(IncidentContent {
+        source_id: format!("{}/note/{}", incident.id, note.id),
+        url: incident.html_url.clone(),
+        content_type: "comment",
+        title: format!("Note on incident #{}", incident.incident_number),
+        text: format!(
+            "Note on incident #{} ({}): {}",
+            incident.incident_number, incident.title, body
+        ),
+        author: note.user.as_ref().and_then(|u| u.summary.clone()),
+        service: service_name(incident),
+        parent_id: Some(incident.id.clone()),
+        created_at: note.created_at.clone(),
+        metadata: incident_metadata(incident),
+    })
+}
+
+/// Encrypt and upsert an incident object into `content_items`
+async fn store_content(
+    db: &Database,
+    crypto: &CryptoService,
+    item: &IncidentContent,
+) -> Result<(), IncidentError> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let created_at = parse_timestamp_ms(&item.created_at).unwrap_or(now);
+
+    let encrypted_body = crypto
+        .encrypt_string(&item.text)
+        .map_err(|e| IncidentError::Crypto(e.to_string()))?;
+    let metadata = item.metadata.to_string();
+
+    sqlx::query(
+        "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, parent_id, created_at, updated_at, synced_at, metadata)
+         VALUES (?, 'incident', ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(source, source_id) DO UPDATE SET title = ?, body = ?, updated_at = ?, synced_at = ?, metadata = ?"
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(&item.source_id)
+    .bind(&item.url)
+    .bind(item.content_type)
+    .bind(&item.title)
+    .bind(&encrypted_body)
+    .bind(&item.author)
+    .bind(&item.service)
+    .bind(&item.parent_id)
+    .bind(created_at)
+    .bind(now)
+    .bind(now)
+    .bind(&metadata)
+    .bind(&item.title)
+    .bind(&encrypted_body)
+    .bind(now)
+    .bind(now)
+    .bind(&metadata)
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+pub struct IncidentSyncService {
+    client: IncidentClient,
+    db: Arc<Database>,
+    crypto: Arc<CryptoService>,
+}
+
+impl IncidentSyncService {
+    pub fn new(client: IncidentClient, db: Arc<Database>, crypto: Arc<CryptoService>) -> Self {
+        Self { client, db, crypto }
+    }
+
+    /// Sync incidents with timeline activity in `[since_ms, until_ms)`: the
+    /// incident itself with its current status and responders, plus the
+    /// status changes and notes made in the window
+    pub async fn sync_activity(
+        &self,
+        since_ms: i64,
+        until_ms: i64,
+    ) -> Result<SyncResult, IncidentError> {
+        let entries = self
+            .client
+            .list_log_entries(
+                &format_incident_timestamp(since_ms),
+                &format_incident_timestamp(until_ms),
+            )
+            .await?;
+
+        let mut incident_ids: Vec<String> = Vec::new();
+        for id in entries.iter().filter_map(|e| e.incident.as_ref()) {
+            if !incident_ids.contains(&id.id) {
+                incident_ids.push(id.id.clone());
+            }
+        }
+
+        let mut result = Self::empty_result();
+        for id in &incident_ids {
+            match self.client.get_incident(id).await {
+                Ok(incident) => {
+                    self.sync_incident(&incident, since_ms, until_ms, &mut result)
+                        .await
+                }
+                Err(e) => result.errors.push(format!("{}: {}", id, e)),
+            }
+        }
+
+        tracing::info!(
+            "Synced {} incident items from {} incidents",
+            result.items_synced,
+            incident_ids.len()
+        );
+        Ok(result)
+    }
+
+    /// Sync incidents created in `[since_ms, until_ms)` with their timeline in
+    /// the same window, for historical syncs
+    pub async fn sync_created(
+        &self,
+        since_ms: i64,
+        until_ms: i64,
+    ) -> Result<SyncResult, IncidentError> {
+        let incidents = self
+            .client
+            .list_incidents(
+                &format_incident_timestamp(since_ms),
+                &format_incident_timestamp(until_ms),
+            )
+            .await?;
+
+        let mut result = Self::empty_result();
+        for incident in &incidents {
+            self.sync_incident(incident, since_ms, until_ms, &mut result)
+                .await;
+        }
+
+        tracing::info!(
+            "Synced {} incident items from {} incidents",
+            result.items_synced,
+            incidents.len()
+        );
+        Ok(result)
+    }
+
+    fn empty_result() -> SyncResult {
+        SyncResult {
+            source: "incident".to_string(),
+            items_synced: 0,
+            errors: vec![],
+        }
+    }
+
+    async fn sync_incident(
+        &self,
+        incident: &Incident,
+        since_ms: i64,
+        until_ms: i64,
+        result: &mut SyncResult,
+    ) {
+        let in_window =
+            |ts: &str| parse_timestamp_ms(ts).is_some_and(|ms| ms >= since_ms && ms < until_ms);
+        let mut contents = vec![incident_content(incident)];
+
+        match self.client.list_incident_log_entries(&incident.id).await {
+            Ok(entries) => contents.extend(
+                entries
+                    .iter()
+                    .filter(|e| in_window(&e.created_at))
+                    .filter_map(|e| log_entry_content(incident, e)),
+            ),
+            Err(e) => result
+                .errors
+                .push(format!("#{} timeline: {}", incident.incident_number, e)),
+        }
+
+        match self.client.list_notes(&incident.id).await {
+            Ok(notes) => contents.extend(
+                notes
+                    .iter()
+                    .filter(|n| in_window(&n.created_at))
+                    .filter_map(|n| note_content(incident, n)),
+            ),
+            Err(e) => result
+                .errors
+                .push(format!("#{} notes: {}", incident.incident_number, e)),
+        }
+
+        for item in &contents {
+            match store_content(&self.db, &self.crypto, item).await {
+                Ok(()) => result.items_synced += 1,
+                Err(e) => result.errors.push(format!("{}: {}", item.source_id, e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::incident::types::{IncidentPriority, IncidentReference};
+
+    fn incident() -> Incident {
+        Incident {
+            id: "PT4KHLK".into(),
+            incident_number: 1234,
+            title: "Checkout 5xx".into(),
+            description: Some("Checkout 5xx".into()),
+            status: "triggered".into(),
+            urgency: Some("high".into()),
+            priority: Some(IncidentPriority {
+                summary: "P1".into(),
+            }),
+            service: Some(IncidentReference {
+                id: "PIJ90N7".into(),
+                summary: Some("Checkout".into()),
+            }),
+            assignments: vec![],
+            html_url: "https://acme.pagerduty.com/incidents/PT4KHLK".into(),
+            created_at: "2024-03-05T10:00:00Z".into(),
+            last_status_change_at: None,
+        }
+    }
+
+    #[test]
+    fn test_incident_content() {
+        let content = incident_content(&incident());
+        assert_eq!(content.content_type, "incident");
+        assert_eq!(
+            content.text,
+            "Incident #1234: Checkout 5xx [triggered, high urgency, P1]\nService: Checkout"
+        );
+        assert_eq!(content.metadata["active"], true);
+        assert_eq!(content.metadata["high_severity"], true);
+    }
+
+    #[test]
+    fn test_log_entry_content() {
+        let incident = incident();
+        let entry = IncidentLogEntry {
+            id: "R2".into(),
+            entry_type: "acknowledge_log_entry".into(),
+            summary: Some("Acknowledged by Earline Greenholt".into()),
+            created_at: "2024-03-05T10:04:00Z".into(),
+            agent: Some(IncidentReference {
+                id: "PXPGF42".into(),
+                summary: Some("Earline Greenholt".into()),
+            }),
+            incident: None,
+        };
+        let content = log_entry_content(&incident, &entry).unwrap();
+        assert_eq!(content.source_id, "PT4KHLK/log/R2");
+        assert_eq!(content.parent_id.as_deref(), Some("PT4KHLK"));
+        assert_eq!(content.author.as_deref(), Some("Earline Greenholt"));
+        assert_eq!(content.title, "Incident #1234 acknowledged");
+
+        let notify = IncidentLogEntry {
+            entry_type: "notify_log_entry".into(),
+            ..entry
+        };
+        assert!(log_entry_content(&incident, &notify).is_none());
+    }
+}
@@ -0,0 +1,159 @@
+//! Incident data types and error definitions
+//!
+//! The wire types follow the PagerDuty REST API v2, which other incident tools
+//! and self-hosted bridges commonly mirror.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum IncidentError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Authentication failed: {0}")]
+    Auth(String),
+
+    #[error("Rate limited by the incident API, retry after {0}s")]
+    RateLimited(u64),
+
+    #[error("API error: {0}")]
+    Api(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Crypto error: {0}")]
+    Crypto(String),
+}
+
+/// Stored incident API credentials
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentTokens {
+    pub api_key: String,
+    /// API root, e.g. `https://api.pagerduty.com`
+    pub base_url: String,
+}
+
+/// A `{ id, summary }` reference to a user, service or escalation policy
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IncidentReference {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentAssignment {
+    pub assignee: IncidentReference,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentPriority {
+    /// e.g. `P1`
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Incident {
+    pub id: String,
+    pub incident_number: i64,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// `triggered`, `acknowledged` or `resolved`
+    pub status: String,
+    /// `high` or `low`
+    #[serde(default)]
+    pub urgency: Option<String>,
+    #[serde(default)]
+    pub priority: Option<IncidentPriority>,
+    #[serde(default)]
+    pub service: Option<IncidentReference>,
+    #[serde(default)]
+    pub assignments: Vec<IncidentAssignment>,
+    pub html_url: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub last_status_change_at: Option<String>,
+}
+
+impl Incident {
+    /// Triggered or acknowledged, i.e. not yet resolved
+    pub fn is_active(&self) -> bool {
+        self.status != "resolved"
+    }
+
+    /// High urgency, or priority P1/P2
+    pub fn is_high_severity(&self) -> bool {
+        self.urgency.as_deref() == Some("high")
+            || self
+                .priority
+                .as_ref()
+                .is_some_and(|p| matches!(p.summary.as_str(), "P1" | "P2" | "SEV-1" | "SEV-2"))
+    }
+
+    /// Names of the current assignees
+    pub fn responders(&self) -> Vec<String> {
+        self.assignments
+            .iter()
+            .filter_map(|a| a.assignee.summary.clone())
+            .collect()
+    }
+}
+
+/// An entry in an incident's timeline: triggers, acknowledgements,
+/// reassignments, escalations and resolutions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentLogEntry {
+    pub id: String,
+    /// e.g. `acknowledge_log_entry`
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    #[serde(default)]
+    pub summary: Option<String>,
+    pub created_at: String,
+    #[serde(default)]
+    pub agent: Option<IncidentReference>,
+    #[serde(default)]
+    pub incident: Option<IncidentReference>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentNote {
+    pub id: String,
+    pub content: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub user: Option<IncidentReference>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incident_severity() {
+        let json = r#"{
+            "id": "PT4KHLK", "incident_number": 1234, "title": "Checkout 5xx",
+            "status": "acknowledged", "urgency": "low", "priority": {"summary": "P1"},
+            "service": {"id": "PIJ90N7", "summary": "Checkout"},
+            "assignments": [{"assignee": {"id": "PXPGF42", "summary": "Earline Greenholt"}}],
+            "html_url": "https://acme.pagerduty.com/incidents/PT4KHLK",
+            "created_at": "2024-03-05T10:00:00Z"
+        }"#;
+        let incident: Incident = serde_json::from_str(json).unwrap();
+        assert!(incident.is_active());
+        assert!(incident.is_high_severity());
+        assert_eq!(incident.responders(), vec!["Earline Greenholt"]);
+
+        let resolved = Incident {
+            status: "resolved".into(),
+            priority: None,
+            ..incident
+        };
+        assert!(!resolved.is_active());
+        assert!(!resolved.is_high_severity());
+    }
+}
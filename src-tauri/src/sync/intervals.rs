@@ -0,0 +1,294 @@
+//! Per-source and per-scope sync frequency
+//!
+//! Background runs fire on the global schedule, but each source, and each of its
+//! channels, projects or spaces, can have its own interval. A scope is due once
+//! its interval has elapsed since its `sync_state.last_sync_at`; scopes without
+//! an interval follow their source, and sources without one sync on every run.
+//! Manual and historical syncs ignore intervals.
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::Database;
+
+/// A configured sync frequency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncInterval {
+    pub source: String,
+    /// `sync_state.resource_type` of the scope; `None` for the source default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_id: Option<String>,
+    pub interval_minutes: i64,
+}
+
+/// Whether a resource last synced at `last_sync_at` is due under `interval_minutes`
+pub fn is_due(last_sync_at: Option<i64>, interval_minutes: Option<i64>, now: i64) -> bool {
+    match (last_sync_at, interval_minutes) {
+        (Some(last), Some(minutes)) => now - last >= minutes * 60 * 1000,
+        _ => true,
+    }
+}
+
+pub async fn list_intervals(db: &Database) -> Result<Vec<SyncInterval>, sqlx::Error> {
+    let rows: Vec<(String, String, String, i64)> = sqlx::query_as(
+        "SELECT source, resource_type, resource_id, interval_minutes FROM sync_intervals
+         ORDER BY source, resource_type, resource_id",
+    )
+    .fetch_all(db.pool())
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(source, resource_type, resource_id, interval_minutes)| SyncInterval {
+                source,
+                resource_type: Some(resource_type).filter(|t| !t.is_empty()),
+                resource_id: Some(resource_id).filter(|id| !id.is_empty()),
+                interval_minutes,
+            },
+        )
+        .collect())
+}
+
+/// Set or clear (`None`) the interval of a source, or of one of its scopes
+pub async fn set_interval(
+    db: &Database,
+    source: &str,
+    scope: Option<(&str, &str)>,
+    interval_minutes: Option<i64>,
+) -> Result<(), sqlx::Error> {
+    let (resource_type, resource_id) = scope.unwrap_or(("", ""));
+
+    match interval_minutes {
+        Some(minutes) => {
+            sqlx::query(
+                "INSERT INTO sync_intervals (source, resource_type, resource_id, interval_minutes)
+                 VALUES (?, ?, ?, ?)
+                 ON CONFLICT(source, resource_type, resource_id)
+                 DO UPDATE SET interval_minutes = excluded.interval_minutes",
+            )
+            .bind(source)
+            .bind(resource_type)
+            .bind(resource_id)
+            .bind(minutes.max(1))
+            .execute(db.pool())
+            .await?;
+        }
+        None => {
+            sqlx::query(
+                "DELETE FROM sync_intervals
+                 WHERE source = ? AND resource_type = ? AND resource_id = ?",
+            )
+            .bind(source)
+            .bind(resource_type)
+            .bind(resource_id)
+            .execute(db.pool())
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Ids of the scopes of one kind that have their own interval
+pub async fn scope_overrides(
+    db: &Database,
+    source: &str,
+    resource_type: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    let rows: Vec<(String,)> = sqlx::query_as(
+        "SELECT resource_id FROM sync_intervals WHERE source = ? AND resource_type = ?",
+    )
+    .bind(source)
+    .bind(resource_type)
+    .fetch_all(db.pool())
+    .await?;
+
+    Ok(rows.into_iter().map(|r| r.0).collect())
+}
+
+async fn scope_due(
+    db: &Database,
+    source: &str,
+    resource_type: &str,
+    resource_id: &str,
+    now: i64,
+) -> Result<bool, sqlx::Error> {
+    let interval: Option<(i64,)> = sqlx::query_as(
+        "SELECT interval_minutes FROM sync_intervals
+         WHERE source = ? AND ((resource_type = ? AND resource_id = ?) OR resource_type = '')
+         ORDER BY resource_type DESC LIMIT 1",
+    )
+    .bind(source)
+    .bind(resource_type)
+    .bind(resource_id)
+    .fetch_optional(db.pool())
+    .await?;
+
+    let Some((interval,)) = interval else {
+        return Ok(true);
+    };
+
+    let last: Option<(Option<i64>,)> = sqlx::query_as(
+        "SELECT last_sync_at FROM sync_state
+         WHERE source = ? AND resource_type = ? AND resource_id = ?",
+    )
+    .bind(source)
+    .bind(resource_type)
+    .bind(resource_id)
+    .fetch_optional(db.pool())
+    .await?;
+
+    Ok(is_due(last.and_then(|r| r.0), Some(interval), now))
+}
+
+async fn source_due(db: &Database, source: &str, now: i64) -> Result<bool, sqlx::Error> {
+    let default: Option<(i64,)> = sqlx::query_as(
+        "SELECT interval_minutes FROM sync_intervals
+         WHERE source = ? AND resource_type = '' AND resource_id = ''",
+    )
+    .bind(source)
+    .fetch_optional(db.pool())
+    .await?;
+
+    let Some((default,)) = default else {
+        return Ok(true);
+    };
+
+    // Resources following the source interval are due when the least recently synced one is
+    let (oldest,): (Option<i64>,) = sqlx::query_as(
+        "SELECT MIN(s.last_sync_at) FROM sync_state s
+         WHERE s.source = ? AND NOT EXISTS (
+             SELECT 1 FROM sync_intervals i
+             WHERE i.source = s.source AND i.resource_type = s.resource_type
+               AND i.resource_id = s.resource_id
+         )",
+    )
+    .bind(source)
+    .fetch_one(db.pool())
+    .await?;
+
+    if is_due(oldest, Some(default), now) {
+        return Ok(true);
+    }
+
+    let overrides: Vec<(i64, Option<i64>)> = sqlx::query_as(
+        "SELECT i.interval_minutes, s.last_sync_at FROM sync_intervals i
+         LEFT JOIN sync_state s ON s.source = i.source
+             AND s.resource_type = i.resource_type AND s.resource_id = i.resource_id
+         WHERE i.source = ? AND i.resource_type != ''",
+    )
+    .bind(source)
+    .fetch_all(db.pool())
+    .await?;
+
+    Ok(overrides
+        .into_iter()
+        .any(|(interval, last)| is_due(last, Some(interval), now)))
+}
+
+/// Whether a scope's interval has elapsed. Errors count as due, so a broken
+/// interval table never stops syncing.
+pub async fn is_scope_due(
+    db: &Database,
+    source: &str,
+    resource_type: &str,
+    resource_id: &str,
+) -> bool {
+    let now = chrono::Utc::now().timestamp_millis();
+    scope_due(db, source, resource_type, resource_id, now)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to check sync interval for {}: {}", source, e);
+            true
+        })
+}
+
+/// Whether a source, or any of its scopes with their own interval, is due
+pub async fn is_source_due(db: &Database, source: &str) -> bool {
+    let now = chrono::Utc::now().timestamp_millis();
+    source_due(db, source, now).await.unwrap_or_else(|e| {
+        tracing::warn!("Failed to check sync interval for {}: {}", source, e);
+        true
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_due() {
+        let now = 10_000_000;
+        assert!(is_due(None, Some(60), now));
+        assert!(is_due(Some(now), None, now));
+        assert!(!is_due(Some(now - 59 * 60 * 1000), Some(60), now));
+        assert!(is_due(Some(now - 60 * 60 * 1000), Some(60), now));
+    }
+
+    #[test]
+    fn test_interval_serialization() {
+        let source = SyncInterval {
+            source: "jira".to_string(),
+            resource_type: None,
+            resource_id: None,
+            interval_minutes: 60,
+        };
+        assert_eq!(
+            serde_json::to_value(&source).unwrap(),
+            serde_json::json!({ "source": "jira", "intervalMinutes": 60 })
+        );
+
+        let scope: SyncInterval = serde_json::from_value(serde_json::json!({
+            "source": "slack",
+            "resourceType": "channel",
+            "resourceId": "C123",
+            "intervalMinutes": 5,
+        }))
+        .unwrap();
+        assert_eq!(scope.resource_id.as_deref(), Some("C123"));
+    }
+
+    #[tokio::test]
+    async fn test_deselected_scope_no_longer_keeps_source_due() {
+        let db = Database::in_memory().await.unwrap();
+        let now = 10_000_000_000;
+        set_interval(&db, "github", None, Some(60)).await.unwrap();
+        set_interval(&db, "github", Some(("repo", "acme/old")), Some(5))
+            .await
+            .unwrap();
+        for (repo, last_sync_at) in [
+            ("acme/app", now - 60_000),
+            ("acme/gone", 0),
+            ("acme/old", 0),
+        ] {
+            sqlx::query(
+                "INSERT INTO sync_state (id, source, resource_type, resource_id, last_sync_at)
+                 VALUES (?, 'github', 'repo', ?, ?)",
+            )
+            .bind(repo)
+            .bind(repo)
+            .bind(last_sync_at)
+            .execute(db.pool())
+            .await
+            .unwrap();
+        }
+        assert!(source_due(&db, "github", now).await.unwrap());
+
+        crate::sync::connector::clear_deselected_scopes(
+            &db,
+            "github",
+            "repo",
+            &["acme/app".to_string()],
+        )
+        .await
+        .unwrap();
+        assert!(!source_due(&db, "github", now).await.unwrap());
+        assert!(scope_overrides(&db, "github", "repo")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
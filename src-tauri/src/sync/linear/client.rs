@@ -0,0 +1,280 @@
+//! Linear GraphQL API client
+
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+
+use super::types::{
+    Connection, LinearCycle, LinearError, LinearIssue, LinearOrganization, LinearTeam, LinearUser,
+};
+
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+
+const VIEWER_QUERY: &str = "query { viewer { name } organization { name urlKey } }";
+
+const TEAMS_QUERY: &str = "query Teams($after: String) {
+  teams(first: 100, after: $after) {
+    nodes { id key name }
+    pageInfo { hasNextPage endCursor }
+  }
+}";
+
+const ISSUES_QUERY: &str = "query Issues($filter: IssueFilter, $after: String) {
+  issues(first: 50, after: $after, filter: $filter, orderBy: updatedAt) {
+    nodes {
+      id identifier title description url priorityLabel createdAt updatedAt
+      state { name type }
+      assignee { name }
+      creator { name }
+      cycle { number name }
+      labels(first: 20) { nodes { name } }
+      comments(first: 50) { nodes { id body url createdAt updatedAt user { name } } }
+      history(first: 50) {
+        nodes { id createdAt actor { name } fromState { name type } toState { name type } }
+      }
+    }
+    pageInfo { hasNextPage endCursor }
+  }
+}";
+
+const CYCLES_QUERY: &str = "query Cycles($filter: CycleFilter, $after: String) {
+  cycles(first: 50, after: $after, filter: $filter) {
+    nodes {
+      id number name description startsAt endsAt completedAt progress createdAt updatedAt
+    }
+    pageInfo { hasNextPage endCursor }
+  }
+}";
+
+/// Viewer and workspace of an API key
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct LinearViewer {
+    pub viewer: LinearUser,
+    pub organization: LinearOrganization,
+}
+
+pub struct LinearClient {
+    http: Client,
+    api_url: String,
+    api_key: Option<String>,
+}
+
+impl Default for LinearClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Error codes Linear reports in `errors[].extensions.code`
+fn graphql_error(errors: &serde_json::Value) -> LinearError {
+    let first = &errors[0];
+    let message = first["message"]
+        .as_str()
+        .unwrap_or("unknown error")
+        .to_string();
+    match first["extensions"]["code"].as_str() {
+        Some("AUTHENTICATION_ERROR") | Some("FORBIDDEN") => LinearError::Auth(message),
+        Some("RATELIMITED") => LinearError::RateLimited(60),
+        _ => LinearError::Api(message),
+    }
+}
+
+impl LinearClient {
+    pub fn new() -> Self {
+        Self {
+            http: Client::new(),
+            api_url: LINEAR_API_URL.to_string(),
+            api_key: None,
+        }
+    }
+
+    pub fn with_api_key(mut self, api_key: String) -> Self {
+        self.api_key = Some(api_key);
+        self
+    }
+
+    /// Run a query and deserialize its `data`
+    async fn query<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<T, LinearError> {
+        let api_key = self
+            .api_key
+            .as_ref()
+            .ok_or_else(|| LinearError::Auth("Not authenticated".into()))?;
+
+        let response = self
+            .http
+            .post(&self.api_url)
+            // Personal API keys are sent without a `Bearer` prefix
+            .header("Authorization", api_key)
+            .json(&serde_json::json!({ "query": query, "variables": variables }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.as_u16() == 401 {
+            return Err(LinearError::Auth("API key rejected by Linear".into()));
+        }
+        if status.as_u16() == 429 {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60);
+            return Err(LinearError::RateLimited(retry_after));
+        }
+
+        // GraphQL errors arrive with 200 or 400 and an `errors` array
+        let mut body: serde_json::Value = response.json().await?;
+        if body["errors"].as_array().is_some_and(|e| !e.is_empty()) {
+            return Err(graphql_error(&body["errors"]));
+        }
+        if !status.is_success() {
+            return Err(LinearError::Api(format!("HTTP {}", status)));
+        }
+
+        serde_json::from_value(body["data"].take())
+            .map_err(|e| LinearError::Api(format!("Unexpected response: {}", e)))
+    }
+
+    /// Fetch every page of the connection at `data[field]`. Incremental listings
+    /// filter on `updatedAt`, so paging stops at the cursor.
+    async fn query_paginated<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        field: &str,
+        variables: serde_json::Value,
+    ) -> Result<Vec<T>, LinearError> {
+        let mut all = Vec::new();
+        let mut after: Option<String> = None;
+
+        loop {
+            let mut page_variables = variables.clone();
+            page_variables["after"] = serde_json::json!(after);
+
+            let mut data: serde_json::Value = self.query(query, page_variables).await?;
+            let page: Connection<T> = serde_json::from_value(data[field].take())
+                .map_err(|e| LinearError::Api(format!("Unexpected {} response: {}", field, e)))?;
+            all.extend(page.nodes);
+
+            match page.page_info.end_cursor {
+                Some(cursor) if page.page_info.has_next_page => after = Some(cursor),
+                _ => break,
+            }
+        }
+
+        Ok(all)
+    }
+
+    /// The key's user and workspace
+    pub async fn get_viewer(&self) -> Result<LinearViewer, LinearError> {
+        self.query(VIEWER_QUERY, serde_json::json!({})).await
+    }
+
+    pub async fn list_teams(&self) -> Result<Vec<LinearTeam>, LinearError> {
+        self.query_paginated(TEAMS_QUERY, "teams", serde_json::json!({}))
+            .await
+    }
+
+    /// Issues of a team updated after `updated_after` (ISO 8601), with their
+    /// latest comments and history
+    pub async fn list_issues_updated(
+        &self,
+        team_id: &str,
+        updated_after: &str,
+    ) -> Result<Vec<LinearIssue>, LinearError> {
+        let filter = serde_json::json!({
+            "team": { "id": { "eq": team_id } },
+            "updatedAt": { "gt": updated_after },
+        });
+        self.query_paginated(
+            ISSUES_QUERY,
+            "issues",
+            serde_json::json!({ "filter": filter }),
+        )
+        .await
+    }
+
+    /// Issues of a team created within `[start, end)` (ISO 8601)
+    pub async fn list_issues_created(
+        &self,
+        team_id: &str,
+        start: &str,
+        end: &str,
+    ) -> Result<Vec<LinearIssue>, LinearError> {
+        let filter = serde_json::json!({
+            "team": { "id": { "eq": team_id } },
+            "createdAt": { "gte": start, "lt": end },
+        });
+        self.query_paginated(
+            ISSUES_QUERY,
+            "issues",
+            serde_json::json!({ "filter": filter }),
+        )
+        .await
+    }
+
+    /// Cycles of a team updated after `updated_after` (ISO 8601)
+    pub async fn list_cycles_updated(
+        &self,
+        team_id: &str,
+        updated_after: &str,
+    ) -> Result<Vec<LinearCycle>, LinearError> {
+        let filter = serde_json::json!({
+            "team": { "id": { "eq": team_id } },
+            "updatedAt": { "gt": updated_after },
+        });
+        self.query_paginated(
+            CYCLES_QUERY,
+            "cycles",
+            serde_json::json!({ "filter": filter }),
+        )
+        .await
+    }
+
+    /// Cycles of a team starting within `[start, end)` (ISO 8601)
+    pub async fn list_cycles_starting(
+        &self,
+        team_id: &str,
+        start: &str,
+        end: &str,
+    ) -> Result<Vec<LinearCycle>, LinearError> {
+        let filter = serde_json::json!({
+            "team": { "id": { "eq": team_id } },
+            "startsAt": { "gte": start, "lt": end },
+        });
+        self.query_paginated(
+            CYCLES_QUERY,
+            "cycles",
+            serde_json::json!({ "filter": filter }),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graphql_error_codes() {
+        let errors = serde_json::json!([{
+            "message": "Authentication required, not authenticated",
+            "extensions": { "code": "AUTHENTICATION_ERROR" }
+        }]);
+        assert!(matches!(graphql_error(&errors), LinearError::Auth(_)));
+
+        let errors = serde_json::json!([{ "message": "Rate limit exceeded", "extensions": { "code": "RATELIMITED" } }]);
+        assert!(matches!(
+            graphql_error(&errors),
+            LinearError::RateLimited(60)
+        ));
+
+        let errors = serde_json::json!([{ "message": "Argument Validation Error" }]);
+        assert!(
+            matches!(graphql_error(&errors), LinearError::Api(m) if m == "Argument Validation Error")
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! Linear source connector
+//!
+//! Connects with a personal API key and syncs the teams selected in settings,
+//! each with its own `sync_state` cursor holding the latest `updatedAt` seen.
+
+use async_trait::async_trait;
+use std::collections::HashSet;
+
+use super::client::LinearClient;
+use super::sync::{format_linear_timestamp, LinearSyncService};
+use super::types::{LinearTeam, LinearTokens};
+use crate::pipeline::PipelineTaskType;
+use crate::sync::connectivity::is_unreachable;
+use crate::sync::connector::{
+    clear_deselected_scopes, clear_sync_state, incremental_since, last_sync_at, load_credentials,
+    load_cursor, load_preference, save_cursor, save_preference, store_credentials,
+    ConnectorContext, ConnectorError, SourceConnector, SourceHealth, SourceScope, SyncRange,
+};
+use crate::sync::status::{mark_complete, mark_failed, mark_pending};
+use crate::sync::SyncResult;
+
+const SELECTED_TEAMS_KEY: &str = "linear_selected_teams";
+
+pub(crate) async fn load_linear_tokens(
+    ctx: &ConnectorContext,
+) -> Result<Option<LinearTokens>, ConnectorError> {
+    load_credentials(ctx, "linear").await
+}
+
+async fn load_selected_teams(ctx: &ConnectorContext) -> Result<Vec<String>, ConnectorError> {
+    load_preference(&ctx.db, SELECTED_TEAMS_KEY).await
+}
+
+pub struct LinearConnector;
+
+impl LinearConnector {
+    /// Sync service plus the selected teams, resolved to their keys and names
+    async fn service(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<(LinearSyncService, Vec<LinearTeam>), ConnectorError> {
+        let tokens = load_linear_tokens(ctx)
+            .await?
+            .ok_or(ConnectorError::NotConnected("Linear"))?;
+        let selected: HashSet<String> = load_selected_teams(ctx).await?.into_iter().collect();
+
+        let client = LinearClient::new().with_api_key(tokens.api_key.clone());
+        let teams = if selected.is_empty() {
+            Vec::new()
+        } else {
+            client
+                .list_teams()
+                .await
+                .map_err(ConnectorError::classify)?
+                .into_iter()
+                .filter(|team| selected.contains(&team.id))
+                .collect()
+        };
+
+        let service =
+            LinearSyncService::new(client, ctx.db.clone(), ctx.crypto.clone(), tokens.url_key);
+        Ok((service, teams))
+    }
+}
+
+#[async_trait]
+impl SourceConnector for LinearConnector {
+    fn id(&self) -> &'static str {
+        "linear"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Linear"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sync issues, comments, state changes and cycles from Linear"
+    }
+
+    fn task_type(&self) -> PipelineTaskType {
+        PipelineTaskType::SyncLinear
+    }
+
+    /// Accepts `{ "token" }`, a Linear personal API key
+    async fn connect(
+        &self,
+        ctx: &ConnectorContext,
+        config: serde_json::Value,
+    ) -> Result<serde_json::Value, ConnectorError> {
+        let token = config["token"]
+            .as_str()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .ok_or_else(|| ConnectorError::InvalidConfig("token is required".into()))?;
+
+        let viewer = LinearClient::new()
+            .with_api_key(token.to_string())
+            .get_viewer()
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+        let tokens = LinearTokens {
+            api_key: token.to_string(),
+            user_name: viewer.viewer.name,
+            url_key: viewer.organization.url_key,
+        };
+        store_credentials(ctx, "linear", &tokens).await?;
+
+        tracing::info!(
+            "Linear connected to {} as {}",
+            viewer.organization.name,
+            tokens.user_name
+        );
+        Ok(serde_json::json!({
+            "userName": tokens.user_name,
+            "workspace": viewer.organization.name,
+        }))
+    }
+
+    async fn is_connected(&self, ctx: &ConnectorContext) -> bool {
+        matches!(load_linear_tokens(ctx).await, Ok(Some(_)))
+    }
+
+    async fn list_scopes(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<Vec<SourceScope>, ConnectorError> {
+        let tokens = load_linear_tokens(ctx)
+            .await?
+            .ok_or(ConnectorError::NotConnected("Linear"))?;
+        let selected: HashSet<String> = load_selected_teams(ctx).await?.into_iter().collect();
+
+        let teams = LinearClient::new()
+            .with_api_key(tokens.api_key)
+            .list_teams()
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+        Ok(teams
+            .into_iter()
+            .map(|team| SourceScope {
+                selected: selected.contains(&team.id),
+                name: format!("{} ({})", team.name, team.key),
+                id: team.id,
+                kind: "team".to_string(),
+            })
+            .collect())
+    }
+
+    fn supports_scope_selection(&self) -> bool {
+        true
+    }
+
+    async fn select_scopes(
+        &self,
+        ctx: &ConnectorContext,
+        scope_ids: Vec<String>,
+    ) -> Result<(), ConnectorError> {
+        save_preference(&ctx.db, SELECTED_TEAMS_KEY, &scope_ids).await?;
+        clear_deselected_scopes(&ctx.db, self.id(), "team", &scope_ids).await?;
+
+        tracing::info!("Selected {} Linear teams", scope_ids.len());
+        Ok(())
+    }
+
+    async fn sync_incremental(&self, ctx: &ConnectorContext) -> Result<SyncResult, ConnectorError> {
+        let (service, teams) = self.service(ctx).await?;
+        let mut total = SyncResult {
+            source: self.id().to_string(),
+            items_synced: 0,
+            errors: vec![],
+        };
+
+        let team_ids: Vec<String> = teams.iter().map(|t| t.id.clone()).collect();
+        mark_pending(&ctx.db, self.id(), "team", &team_ids).await;
+        for team in teams {
+            let now_ms = chrono::Utc::now().timestamp_millis();
+            let cursor = load_cursor(&ctx.db, self.id(), "team", &team.id).await?;
+            let since = incremental_since(cursor.as_deref(), now_ms);
+
+            let sync = service.sync_team_updated(&team, since);
+            let result = ctx.track_resource(self.id(), "team", &team.id, sync).await;
+            ctx.check_cancelled()?;
+            match result {
+                Ok(synced) if !synced.result.errors.is_empty() => {
+                    // Keep the cursor so what failed is fetched again next run
+                    total.items_synced += synced.result.items_synced;
+                    let error = synced.result.errors.join("; ");
+                    mark_failed(&ctx.db, self.id(), "team", &team.id, &error).await;
+                    total.errors.extend(synced.result.errors);
+                }
+                Ok(synced) => {
+                    total.items_synced += synced.result.items_synced;
+                    // Keep the previous cursor when nothing changed
+                    if let Some(latest) = synced.latest_updated_at {
+                        save_cursor(
+                            &ctx.db,
+                            self.id(),
+                            "team",
+                            &team.id,
+                            &format_linear_timestamp(latest),
+                        )
+                        .await?;
+                    } else {
+                        mark_complete(&ctx.db, self.id(), "team", &team.id).await;
+                    }
+                }
+                // The other teams are behind the same API
+                Err(e) if is_unreachable(&e) => {
+                    return Err(ConnectorError::Unreachable(e.to_string()))
+                }
+                Err(e) => {
+                    tracing::error!("Linear sync error for {}: {}", team.key, e);
+                    total.errors.push(format!("{}: {}", team.key, e));
+                }
+            }
+        }
+
+        Ok(total)
+    }
+
+    async fn sync_range(
+        &self,
+        ctx: &ConnectorContext,
+        range: &SyncRange,
+    ) -> Result<SyncResult, ConnectorError> {
+        let (service, teams) = self.service(ctx).await?;
+        let (start, end) = range.utc_bounds();
+        let mut total = SyncResult {
+            source: self.id().to_string(),
+            items_synced: 0,
+            errors: vec![],
+        };
+
+        for team in teams {
+            let result = service
+                .sync_team_range(&team, start * 1000, end * 1000)
+                .await;
+            ctx.check_cancelled()?;
+            match result {
+                Ok(result) => {
+                    total.items_synced += result.items_synced;
+                    total.errors.extend(result.errors);
+                }
+                // The other teams are behind the same API
+                Err(e) if is_unreachable(&e) => {
+                    return Err(ConnectorError::Unreachable(e.to_string()))
+                }
+                Err(e) => total.errors.push(format!("{}: {}", team.key, e)),
+            }
+        }
+
+        Ok(total)
+    }
+
+    async fn health(&self, ctx: &ConnectorContext) -> SourceHealth {
+        let last_sync_at = last_sync_at(&ctx.db, self.id()).await;
+        let mut health = SourceHealth {
+            source: self.id().to_string(),
+            connected: false,
+            healthy: false,
+            message: None,
+            last_sync_at,
+        };
+
+        let tokens = match load_linear_tokens(ctx).await {
+            Ok(Some(tokens)) => tokens,
+            Ok(None) => return health,
+            Err(e) => {
+                health.connected = true;
+                health.message = Some(e.to_string());
+                return health;
+            }
+        };
+        health.connected = true;
+
+        match LinearClient::new()
+            .with_api_key(tokens.api_key)
+            .get_viewer()
+            .await
+        {
+            Ok(viewer) => {
+                let teams = load_selected_teams(ctx).await.unwrap_or_default();
+                health.healthy = true;
+                health.message = Some(if teams.is_empty() {
+                    format!(
+                        "Signed in to {} as {}; no teams selected",
+                        viewer.organization.name, viewer.viewer.name
+                    )
+                } else {
+                    format!(
+                        "Signed in to {} as {}; {} teams",
+                        viewer.organization.name,
+                        viewer.viewer.name,
+                        teams.len()
+                    )
+                });
+            }
+            Err(e) => health.message = Some(e.to_string()),
+        }
+
+        health
+    }
+
+    async fn disconnect(&self, ctx: &ConnectorContext) -> Result<(), ConnectorError> {
+        sqlx::query("DELETE FROM credentials WHERE id = 'linear'")
+            .execute(ctx.db.pool())
+            .await?;
+
+        sqlx::query("DELETE FROM preferences WHERE key = ?")
+            .bind(SELECTED_TEAMS_KEY)
+            .execute(ctx.db.pool())
+            .await?;
+
+        clear_sync_state(&ctx.db, self.id()).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+//! Linear integration module
+//!
+//! This module provides personal API key authentication against Linear's GraphQL
+//! API and synchronization of issues, their comments and state changes, and cycles
+//! for selected teams.
+
+mod client;
+mod connector;
+mod sync;
+mod types;
+
+pub use client::{LinearClient, LinearViewer};
+pub use connector::LinearConnector;
+pub use sync::LinearSyncService;
+pub use types::{
+    LinearComment, LinearCycle, LinearError, LinearHistory, LinearIssue, LinearTeam, LinearTokens,
+};
@@ -0,0 +1,520 @@
+//! Linear data synchronization service
+
+use std::sync::Arc;
+
+use super::client::LinearClient;
+use super::types::{
+    LinearComment, LinearCycle, LinearError, LinearHistory, LinearIssue, LinearTeam,
+};
+use crate::crypto::CryptoService;
+use crate::db::Database;
+use crate::sync::connector::parse_timestamp_ms;
+use crate::sync::SyncResult;
+
+/// A Linear object normalized for `content_items`
+struct LinearContent {
+    source_id: String,
+    url: String,
+    content_type: &'static str,
+    title: String,
+    text: String,
+    author: Option<String>,
+    team: String,
+    parent_id: Option<String>,
+    created_at: String,
+    updated_at: String,
+    metadata: serde_json::Value,
+}
+
+/// Format epoch milliseconds for Linear date filters
+pub(super) fn format_linear_timestamp(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .unwrap_or_default()
+        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+        .to_string()
+}
+
+/// Deep link to a cycle, e.g. `https://linear.app/acme/team/ENG/cycle/12`
+fn cycle_url(url_key: &str, team: &LinearTeam, number: i64) -> String {
+    format!(
+        "https://linear.app/{}/team/{}/cycle/{}",
+        url_key, team.key, number
+    )
+}
+
+fn issue_content(team: &LinearTeam, issue: &LinearIssue) -> LinearContent {
+    let title = format!("{}: {}", issue.identifier, issue.title);
+    let mut labels = Vec::new();
+    if let Some(state) = &issue.state {
+        labels.push(state.name.clone());
+    }
+    if let Some(priority) = issue
+        .priority_label
+        .as_deref()
+        .filter(|p| *p != "No priority")
+    {
+        labels.push(format!("{} priority", priority));
+    }
+    if let Some(cycle) = &issue.cycle {
+        labels.push(format!("Cycle {}", cycle.number));
+    }
+    let label_names: Vec<String> = issue
+        .labels
+        .as_ref()
+        .map(|l| l.nodes.iter().map(|l| l.name.clone()).collect())
+        .unwrap_or_default();
+
+    let mut text = if labels.is_empty() {
+        title.clone()
+    } else {
+        format!("{} [{}]", title, labels.join(", "))
+    };
+    if let Some(assignee) = &issue.assignee {
+        text.push_str(&format!("\nAssignee: {}", assignee.name));
+    }
+    if !label_names.is_empty() {
+        text.push_str(&format!("\nLabels: {}", label_names.join(", ")));
+    }
+    if let Some(description) = issue
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+    {
+        text.push_str(&format!("\n\n{}", description));
+    }
+
+    LinearContent {
+        source_id: issue.identifier.clone(),
+        url: issue.url.clone(),
+        content_type: "ticket",
+        title,
+        text,
+        author: issue.creator.as_ref().map(|u| u.name.clone()),
+        team: team.name.clone(),
+        parent_id: None,
+        created_at: issue.created_at.clone(),
+        updated_at: issue.updated_at.clone(),
+        metadata: serde_json::json!({
+            "team": team.key,
+            "identifier": issue.identifier,
+            "state": issue.state.as_ref().map(|s| &s.name),
+            "state_type": issue.state.as_ref().map(|s| &s.state_type),
+            "priority": issue.priority_label,
+            "assignee": issue.assignee.as_ref().map(|u| &u.name),
+            "cycle": issue.cycle.as_ref().map(|c| c.number),
+            "labels": label_names,
+        }),
+    }
+}
+
+fn comment_content(
+    team: &LinearTeam,
+    issue: &LinearIssue,
+    comment: &LinearComment,
+) -> Option<LinearContent> {
+    let body = comment.body.trim();
+    if body.is_empty() {
+        return None;
+    }
+
+    Some(LinearContent {
+        source_id: format!("{}/comment/{}", issue.identifier, comment.id),
+        url: comment.url.clone(),
+        content_type: "comment",
+        title: format!("Comment on {}", issue.identifier),
+        text: format!(
+            "Comment on {} ({}): {}",
+            issue.identifier, issue.title, body
+        ),
+        author: comment.user.as_ref().map(|u| u.name.clone()),
+        team: team.name.clone(),
+        parent_id: Some(issue.identifier.clone()),
+        created_at: comment.created_at.clone(),
+        updated_at: comment.updated_at.clone(),
+        metadata: serde_json::json!({
+            "team": team.key,
+            "identifier": issue.identifier,
+        }),
+    })
+}
+
+/// A workflow state change; other history entries (edits, relabels) are skipped
+fn state_change_content(
+    team: &LinearTeam,
+    issue: &LinearIssue,
+    entry: &LinearHistory,
+) -> Option<LinearContent> {
+    let to = entry.to_state.as_ref()?;
+    let change = match &entry.from_state {
+        Some(from) => format!("moved from {} to {}", from.name, to.name),
+        None => format!("moved to {}", to.name),
+    };
+
+    Some(LinearContent {
+        source_id: format!("{}/history/{}", issue.identifier, entry.id),
+        url: issue.url.clone(),
+        content_type: "status_change",
+        title: format!("{} moved to {}", issue.identifier, to.name),
+        text: format!("{} ({}) {}", issue.identifier, issue.title, change),
+        author: entry.actor.as_ref().map(|u| u.name.clone()),
+        team: team.name.clone(),
+        parent_id: Some(issue.identifier.clone()),
+        created_at: entry.created_at.clone(),
+        updated_at: entry.created_at.clone(),
+        metadata: serde_json::json!({
+            "team": team.key,
+            "identifier": issue.identifier,
+            "from_state": entry.from_state.as_ref().map(|s| &s.name),
+            "to_state": to.name,
+            "to_state_type": to.state_type,
+        }),
+    })
+}
+
+fn cycle_content(url_key: &str, team: &LinearTeam, cycle: &LinearCycle) -> LinearContent {
+    let name = match cycle.name.as_deref().filter(|n| !n.is_empty()) {
+        Some(name) => format!("Cycle {} ({})", cycle.number, name),
+        None => format!("Cycle {}", cycle.number),
+    };
+    let date = |ts: &str| {
+        chrono::DateTime::parse_from_rfc3339(ts)
+            .map(|dt| dt.format("%b %-d").to_string())
+            .unwrap_or_default()
+    };
+    let status = if cycle.completed_at.is_some() {
+        "completed".to_string()
+    } else {
+        format!("{:.0}% complete", cycle.progress * 100.0)
+    };
+
+    let mut text = format!(
+        "{} {} for {}: {} – {}, {}",
+        team.key,
+        name,
+        team.name,
+        date(&cycle.starts_at),
+        date(&cycle.ends_at),
+        status
+    );
+    if let Some(description) = cycle
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+    {
+        text.push_str(&format!("\n\n{}", description));
+    }
+
+    LinearContent {
+        source_id: format!("cycle:{}", cycle.id),
+        url: cycle_url(url_key, team, cycle.number),
+        content_type: "cycle",
+        title: format!("{} {}", team.key, name),
+        text,
+        author: None,
+        team: team.name.clone(),
+        parent_id: None,
+        // Cycles are reported from when they start
+        created_at: cycle.starts_at.clone(),
+        updated_at: cycle.updated_at.clone(),
+        metadata: serde_json::json!({
+            "team": team.key,
+            "number": cycle.number,
+            "starts_at": cycle.starts_at,
+            "ends_at": cycle.ends_at,
+            "progress": cycle.progress,
+            "completed": cycle.completed_at.is_some(),
+        }),
+    }
+}
+
+/// Encrypt and upsert a Linear object into `content_items`
+async fn store_content(
+    db: &Database,
+    crypto: &CryptoService,
+    item: &LinearContent,
+) -> Result<(), LinearError> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let created_at = parse_timestamp_ms(&item.created_at).unwrap_or(now);
+    let updated_at = parse_timestamp_ms(&item.updated_at).unwrap_or(now);
+
+    let encrypted_body = crypto
+        .encrypt_string(&item.text)
+        .map_err(|e| LinearError::Crypto(e.to_string()))?;
+    let metadata = item.metadata.to_string();
+
+    sqlx::query(
+        "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, parent_id, created_at, updated_at, synced_at, metadata)
+         VALUES (?, 'linear', ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(source, source_id) DO UPDATE SET source_url = ?, title = ?, body = ?, updated_at = ?, synced_at = ?, metadata = ?"
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(&item.source_id)
+    .bind(&item.url)
+    .bind(item.content_type)
+    .bind(&item.title)
+    .bind(&encrypted_body)
+    .bind(&item.author)
+    .bind(&item.team)
+    .bind(&item.parent_id)
+    .bind(created_at)
+    .bind(updated_at)
+    .bind(now)
+    .bind(&metadata)
+    .bind(&item.url)
+    .bind(&item.title)
+    .bind(&encrypted_body)
+    .bind(updated_at)
+    .bind(now)
+    .bind(&metadata)
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Result of syncing one team
+pub struct TeamSyncResult {
+    pub result: SyncResult,
+    /// Latest `updatedAt` seen, the team's next incremental cursor
+    pub latest_updated_at: Option<i64>,
+}
+
+pub struct LinearSyncService {
+    client: LinearClient,
+    db: Arc<Database>,
+    crypto: Arc<CryptoService>,
+    url_key: String,
+}
+
+impl LinearSyncService {
+    pub fn new(
+        client: LinearClient,
+        db: Arc<Database>,
+        crypto: Arc<CryptoService>,
+        url_key: String,
+    ) -> Self {
+        Self {
+            client,
+            db,
+            crypto,
+            url_key,
+        }
+    }
+
+    /// Sync issues and cycles of a team updated after `since_ms`, with the
+    /// comments and state changes made after it
+    pub async fn sync_team_updated(
+        &self,
+        team: &LinearTeam,
+        since_ms: i64,
+    ) -> Result<TeamSyncResult, LinearError> {
+        let since = format_linear_timestamp(since_ms);
+        let issues = self.client.list_issues_updated(&team.id, &since).await?;
+        let mut errors = Vec::new();
+        let cycles = match self.client.list_cycles_updated(&team.id, &since).await {
+            Ok(cycles) => cycles,
+            Err(e) => {
+                errors.push(format!("{} cycles: {}", team.key, e));
+                Vec::new()
+            }
+        };
+
+        let latest_updated_at = issues
+            .iter()
+            .map(|i| &i.updated_at)
+            .chain(cycles.iter().map(|c| &c.updated_at))
+            .filter_map(|ts| parse_timestamp_ms(ts))
+            .max();
+        let mut result = self
+            .store_team(team, &issues, &cycles, |ts| {
+                parse_timestamp_ms(ts).is_some_and(|ms| ms > since_ms)
+            })
+            .await;
+        result.errors.splice(0..0, errors);
+
+        Ok(TeamSyncResult {
+            result,
+            latest_updated_at,
+        })
+    }
+
+    /// Sync issues created and cycles starting in `[start_ms, end_ms)`, with
+    /// their comments and state changes in the same window
+    pub async fn sync_team_range(
+        &self,
+        team: &LinearTeam,
+        start_ms: i64,
+        end_ms: i64,
+    ) -> Result<SyncResult, LinearError> {
+        let (start, end) = (
+            format_linear_timestamp(start_ms),
+            format_linear_timestamp(end_ms),
+        );
+        let issues = self
+            .client
+            .list_issues_created(&team.id, &start, &end)
+            .await?;
+        let cycles = self
+            .client
+            .list_cycles_starting(&team.id, &start, &end)
+            .await?;
+
+        Ok(self
+            .store_team(team, &issues, &cycles, |ts| {
+                parse_timestamp_ms(ts).is_some_and(|ms| ms >= start_ms && ms < end_ms)
+            })
+            .await)
+    }
+
+    /// Store issues and cycles; comments and state changes only when `in_window`
+    async fn store_team(
+        &self,
+        team: &LinearTeam,
+        issues: &[LinearIssue],
+        cycles: &[LinearCycle],
+        in_window: impl Fn(&str) -> bool,
+    ) -> SyncResult {
+        let mut contents = Vec::new();
+        for issue in issues {
+            contents.push(issue_content(team, issue));
+            if let Some(comments) = &issue.comments {
+                contents.extend(
+                    comments
+                        .nodes
+                        .iter()
+                        .filter(|c| in_window(&c.updated_at))
+                        .filter_map(|c| comment_content(team, issue, c)),
+                );
+            }
+            if let Some(history) = &issue.history {
+                contents.extend(
+                    history
+                        .nodes
+                        .iter()
+                        .filter(|h| in_window(&h.created_at))
+                        .filter_map(|h| state_change_content(team, issue, h)),
+                );
+            }
+        }
+        contents.extend(cycles.iter().map(|c| cycle_content(&self.url_key, team, c)));
+
+        let mut result = SyncResult {
+            source: "linear".to_string(),
+            items_synced: 0,
+            errors: vec![],
+        };
+        for item in &contents {
+            match store_content(&self.db, &self.crypto, item).await {
+                Ok(()) => result.items_synced += 1,
+                Err(e) => result.errors.push(format!("{}: {}", item.source_id, e)),
+            }
+        }
+
+        tracing::info!(
+            "Synced {} Linear items from team {}",
+            result.items_synced,
+            team.key
+        );
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::linear::types::{LinearState, LinearUser};
+
+    fn team() -> LinearTeam {
+        LinearTeam {
+            id: "team-1".into(),
+            key: "ENG".into(),
+            name: "Engineering".into(),
+        }
+    }
+
+    fn issue() -> LinearIssue {
+        serde_json::from_value(serde_json::json!({
+            "id": "9cfb482a", "identifier": "ENG-123", "title": "Flaky login test",
+            "description": "Fails on CI about 1 in 20 runs",
+            "url": "https://linear.app/acme/issue/ENG-123/flaky-login-test",
+            "priorityLabel": "High",
+            "state": {"name": "In Progress", "type": "started"},
+            "assignee": {"name": "Riley"}, "creator": {"name": "Sam"},
+            "cycle": {"number": 12, "name": null},
+            "labels": {"nodes": [{"name": "bug"}]},
+            "createdAt": "2024-03-01T09:00:00.000Z", "updatedAt": "2024-03-05T10:00:00.000Z"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_timestamp_roundtrip() {
+        let ms = parse_timestamp_ms("2024-03-05T10:00:00.250Z").unwrap();
+        assert_eq!(format_linear_timestamp(ms), "2024-03-05T10:00:00.250Z");
+    }
+
+    #[test]
+    fn test_issue_content() {
+        let content = issue_content(&team(), &issue());
+        assert_eq!(content.source_id, "ENG-123");
+        assert_eq!(content.content_type, "ticket");
+        assert_eq!(
+            content.text,
+            "ENG-123: Flaky login test [In Progress, High priority, Cycle 12]\nAssignee: Riley\nLabels: bug\n\nFails on CI about 1 in 20 runs"
+        );
+        assert_eq!(content.author.as_deref(), Some("Sam"));
+    }
+
+    #[test]
+    fn test_state_change_content() {
+        let issue = issue();
+        let entry = LinearHistory {
+            id: "h1".into(),
+            created_at: "2024-03-05T10:00:00.000Z".into(),
+            actor: Some(LinearUser {
+                name: "Riley".into(),
+            }),
+            from_state: Some(LinearState {
+                name: "Todo".into(),
+                state_type: "unstarted".into(),
+            }),
+            to_state: Some(LinearState {
+                name: "In Progress".into(),
+                state_type: "started".into(),
+            }),
+        };
+        let content = state_change_content(&team(), &issue, &entry).unwrap();
+        assert_eq!(
+            content.text,
+            "ENG-123 (Flaky login test) moved from Todo to In Progress"
+        );
+        assert_eq!(content.parent_id.as_deref(), Some("ENG-123"));
+        assert_eq!(content.url, issue.url);
+
+        let edit = LinearHistory {
+            to_state: None,
+            ..entry
+        };
+        assert!(state_change_content(&team(), &issue, &edit).is_none());
+    }
+
+    #[test]
+    fn test_cycle_content() {
+        let cycle: LinearCycle = serde_json::from_value(serde_json::json!({
+            "id": "c-1", "number": 12, "name": "Auth hardening", "description": null,
+            "startsAt": "2024-03-04T00:00:00.000Z", "endsAt": "2024-03-18T00:00:00.000Z",
+            "completedAt": null, "progress": 0.45,
+            "createdAt": "2024-02-20T00:00:00.000Z", "updatedAt": "2024-03-05T10:00:00.000Z"
+        }))
+        .unwrap();
+        let content = cycle_content("acme", &team(), &cycle);
+        assert_eq!(content.url, "https://linear.app/acme/team/ENG/cycle/12");
+        assert_eq!(
+            content.text,
+            "ENG Cycle 12 (Auth hardening) for Engineering: Mar 4 – Mar 18, 45% complete"
+        );
+        assert_eq!(content.created_at, cycle.starts_at);
+    }
+}
@@ -0,0 +1,189 @@
+//! Linear data types and error definitions
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LinearError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Authentication failed: {0}")]
+    Auth(String),
+
+    #[error("Rate limited by Linear, retry after {0}s")]
+    RateLimited(u64),
+
+    #[error("API error: {0}")]
+    Api(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Crypto error: {0}")]
+    Crypto(String),
+}
+
+/// Stored Linear credentials
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearTokens {
+    pub api_key: String,
+    pub user_name: String,
+    /// Workspace slug used in deep links, e.g. `acme` in `linear.app/acme/...`
+    pub url_key: String,
+}
+
+/// A GraphQL connection page: `{ nodes, pageInfo }`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Connection<T> {
+    #[serde(default = "Vec::new")]
+    pub nodes: Vec<T>,
+    #[serde(default)]
+    pub page_info: PageInfo,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    #[serde(default)]
+    pub has_next_page: bool,
+    pub end_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearUser {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinearOrganization {
+    pub name: String,
+    pub url_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearTeam {
+    pub id: String,
+    /// Issue identifier prefix, e.g. `ENG`
+    pub key: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearState {
+    pub name: String,
+    /// `triage`, `backlog`, `unstarted`, `started`, `completed` or `canceled`
+    #[serde(rename = "type")]
+    pub state_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearLabel {
+    pub name: String,
+}
+
+/// Cycle reference on an issue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearCycleRef {
+    pub number: i64,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinearComment {
+    pub id: String,
+    pub body: String,
+    pub url: String,
+    pub user: Option<LinearUser>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// An issue history entry; only entries with a `toState` are state changes
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinearHistory {
+    pub id: String,
+    pub created_at: String,
+    pub actor: Option<LinearUser>,
+    pub from_state: Option<LinearState>,
+    pub to_state: Option<LinearState>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinearIssue {
+    pub id: String,
+    /// e.g. `ENG-123`
+    pub identifier: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub url: String,
+    #[serde(default)]
+    pub priority_label: Option<String>,
+    pub state: Option<LinearState>,
+    pub assignee: Option<LinearUser>,
+    pub creator: Option<LinearUser>,
+    pub cycle: Option<LinearCycleRef>,
+    pub labels: Option<Connection<LinearLabel>>,
+    pub comments: Option<Connection<LinearComment>>,
+    pub history: Option<Connection<LinearHistory>>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinearCycle {
+    pub id: String,
+    pub number: i64,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub starts_at: String,
+    pub ends_at: String,
+    pub completed_at: Option<String>,
+    /// Fraction of scope completed, 0.0-1.0
+    #[serde(default)]
+    pub progress: f64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_connection_parsing() {
+        let json = r#"{
+            "nodes": [{
+                "id": "9cfb482a", "identifier": "ENG-123", "title": "Flaky login test",
+                "description": null, "url": "https://linear.app/acme/issue/ENG-123/flaky-login-test",
+                "priorityLabel": "High",
+                "state": {"name": "In Progress", "type": "started"},
+                "assignee": {"name": "Riley"}, "creator": null,
+                "cycle": {"number": 12, "name": null},
+                "labels": {"nodes": [{"name": "bug"}]},
+                "history": {"nodes": [{
+                    "id": "h1", "createdAt": "2024-03-05T10:00:00.000Z", "actor": {"name": "Riley"},
+                    "fromState": {"name": "Todo", "type": "unstarted"},
+                    "toState": {"name": "In Progress", "type": "started"}
+                }]},
+                "createdAt": "2024-03-01T09:00:00.000Z", "updatedAt": "2024-03-05T10:00:00.000Z"
+            }],
+            "pageInfo": {"hasNextPage": true, "endCursor": "c1"}
+        }"#;
+        let page: Connection<LinearIssue> = serde_json::from_str(json).unwrap();
+        assert!(page.page_info.has_next_page);
+        assert_eq!(page.page_info.end_cursor.as_deref(), Some("c1"));
+
+        let issue = &page.nodes[0];
+        assert_eq!(issue.state.as_ref().unwrap().state_type, "started");
+        assert!(issue.comments.is_none());
+        let history = &issue.history.as_ref().unwrap().nodes[0];
+        assert_eq!(history.to_state.as_ref().unwrap().name, "In Progress");
+    }
+}
@@ -1,18 +1,55 @@
 //! Data synchronization module
 
 pub mod atlassian;
+pub mod backfill;
 pub mod background;
+pub mod calendar;
+pub mod circuit;
+pub mod connectivity;
+pub mod connector;
+pub mod email;
+pub mod estimate;
+pub mod feeds;
+pub mod github;
+pub mod gitlab;
+pub mod incident;
+pub mod intervals;
+pub mod linear;
+pub mod notes;
 pub mod oauth;
 pub mod queue;
+pub mod schedule;
+pub mod scheduler;
 pub mod slack;
+pub mod status;
+pub mod transcript;
+pub mod webhook;
 
 // Re-export commonly used types
-pub use atlassian::{AtlassianClient, AtlassianSyncService, AtlassianTokens, CloudResource};
+pub use atlassian::{
+    AtlassianClient, AtlassianExportImporter, AtlassianExportKind, AtlassianImportResult,
+    AtlassianSyncService, AtlassianTokens, CloudResource,
+};
+pub use backfill::{BackfillJob, BackfillStatus};
 pub use background::{
     get_last_sync_at, sync_slack_historical_day, sync_slack_now, BackgroundSyncService,
 };
-pub use queue::{SyncQueue, SyncRequest};
+pub use calendar::{CalendarFeed, CalendarSyncService};
+pub use circuit::{CircuitBreakers, CircuitState, SourceCircuit};
+pub use connector::{
+    ConnectorContext, ConnectorError, SourceConnector, SourceHealth, SourceInfo, SourceRegistry,
+    SourceScope, SyncRange,
+};
+pub use estimate::{DryRun, SourceEstimate, SyncEstimate};
+pub use github::{GitHubClient, GitHubSyncService, GitHubTokens};
+pub use gitlab::{GitLabClient, GitLabSyncService, GitLabTokens};
+pub use intervals::SyncInterval;
+pub use queue::{SyncJob, SyncJobPayload, SyncJobStatus, SyncQueue};
+pub use schedule::{QuietPeriod, ScheduledRun, SyncScheduleConfig, SyncTimetable};
+pub use scheduler::{SyncClaim, SyncPermit, SyncPriority, SyncSchedule, SyncScheduler};
 pub use slack::{
-    SlackChannel, SlackChannelSelection, SlackClient, SlackConnectionStatus, SlackSyncService,
-    SlackTokens, SlackUser, SyncResult,
+    SlackChannel, SlackChannelSelection, SlackClient, SlackConnectionStatus, SlackExportImporter,
+    SlackImportResult, SlackSyncService, SlackTokens, SlackUser, SyncResult,
 };
+pub use status::{ResourceHealth, ResourceStatus};
+pub use transcript::{TranscriptImportResult, TranscriptImporter};
@@ -0,0 +1,186 @@
+//! Markdown notes source connector
+//!
+//! Syncs a local folder of Markdown files, such as an Obsidian vault. Each sync
+//! rescans the folder; [`super::watch_notes`] triggers one when files change.
+
+use async_trait::async_trait;
+use std::path::Path;
+
+use super::sync::NotesSyncService;
+use super::types::NotesVault;
+use crate::pipeline::PipelineTaskType;
+use crate::sync::connector::{
+    clear_sync_state, last_sync_at, load_preference, save_cursor, save_preference,
+    ConnectorContext, ConnectorError, SourceConnector, SourceHealth, SourceScope, SyncRange,
+};
+use crate::sync::SyncResult;
+
+const VAULT_KEY: &str = "notes_vault";
+
+pub async fn load_notes_vault(
+    ctx: &ConnectorContext,
+) -> Result<Option<NotesVault>, ConnectorError> {
+    load_preference(&ctx.db, VAULT_KEY).await
+}
+
+pub struct NotesConnector;
+
+impl NotesConnector {
+    async fn vault(&self, ctx: &ConnectorContext) -> Result<NotesVault, ConnectorError> {
+        load_notes_vault(ctx)
+            .await?
+            .ok_or(ConnectorError::NotConnected("Notes"))
+    }
+}
+
+#[async_trait]
+impl SourceConnector for NotesConnector {
+    fn id(&self) -> &'static str {
+        "notes"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Notes"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sync a local Markdown folder or Obsidian vault"
+    }
+
+    fn task_type(&self) -> PipelineTaskType {
+        PipelineTaskType::SyncNotes
+    }
+
+    fn item_label(&self) -> &'static str {
+        "notes"
+    }
+
+    /// Accepts `{ "path", "name"? }`; `name` defaults to the folder name
+    async fn connect(
+        &self,
+        ctx: &ConnectorContext,
+        config: serde_json::Value,
+    ) -> Result<serde_json::Value, ConnectorError> {
+        let path = config["path"]
+            .as_str()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .ok_or_else(|| ConnectorError::InvalidConfig("path is required".into()))?;
+        let folder = Path::new(path);
+        if !folder.is_dir() {
+            return Err(ConnectorError::InvalidConfig(format!(
+                "{} is not a folder",
+                path
+            )));
+        }
+
+        let name = config["name"]
+            .as_str()
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(String::from)
+            .or_else(|| {
+                folder
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .map(String::from)
+            })
+            .unwrap_or_else(|| "Notes".to_string());
+        let vault = NotesVault {
+            path: path.to_string(),
+            name,
+        };
+        save_preference(&ctx.db, VAULT_KEY, &vault).await?;
+
+        let obsidian = folder.join(".obsidian").is_dir();
+        tracing::info!(
+            "Notes connected to {} ({})",
+            vault.path,
+            if obsidian { "Obsidian vault" } else { "folder" }
+        );
+        Ok(serde_json::json!({ "name": vault.name, "path": vault.path, "obsidian": obsidian }))
+    }
+
+    async fn is_connected(&self, ctx: &ConnectorContext) -> bool {
+        matches!(load_notes_vault(ctx).await, Ok(Some(_)))
+    }
+
+    async fn list_scopes(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<Vec<SourceScope>, ConnectorError> {
+        let vault = self.vault(ctx).await?;
+        Ok(vec![SourceScope {
+            id: vault.path,
+            name: vault.name,
+            kind: "vault".to_string(),
+            selected: true,
+        }])
+    }
+
+    async fn sync_incremental(&self, ctx: &ConnectorContext) -> Result<SyncResult, ConnectorError> {
+        let vault = self.vault(ctx).await?;
+        let service = NotesSyncService::new(ctx.db.clone(), ctx.crypto.clone());
+        let sync = service.sync_vault(&vault, None);
+        let result = ctx
+            .track_resource(self.id(), "vault", &vault.path, sync)
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+        ctx.check_cancelled()?;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        save_cursor(&ctx.db, self.id(), "vault", &vault.path, &now).await?;
+
+        Ok(result)
+    }
+
+    async fn sync_range(
+        &self,
+        ctx: &ConnectorContext,
+        range: &SyncRange,
+    ) -> Result<SyncResult, ConnectorError> {
+        let vault = self.vault(ctx).await?;
+        let (start, end) = range.utc_bounds();
+
+        NotesSyncService::new(ctx.db.clone(), ctx.crypto.clone())
+            .sync_vault(&vault, Some((start * 1000, end * 1000)))
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))
+    }
+
+    async fn health(&self, ctx: &ConnectorContext) -> SourceHealth {
+        let last_sync_at = last_sync_at(&ctx.db, self.id()).await;
+        let mut health = SourceHealth {
+            source: self.id().to_string(),
+            connected: false,
+            healthy: false,
+            message: None,
+            last_sync_at,
+        };
+
+        let Ok(Some(vault)) = load_notes_vault(ctx).await else {
+            return health;
+        };
+        health.connected = true;
+
+        if Path::new(&vault.path).is_dir() {
+            health.healthy = true;
+            health.message = Some(format!("Watching {}", vault.path));
+        } else {
+            health.message = Some(format!("Folder not found: {}", vault.path));
+        }
+
+        health
+    }
+
+    async fn disconnect(&self, ctx: &ConnectorContext) -> Result<(), ConnectorError> {
+        sqlx::query("DELETE FROM preferences WHERE key = ?")
+            .bind(VAULT_KEY)
+            .execute(ctx.db.pool())
+            .await?;
+
+        clear_sync_state(&ctx.db, self.id()).await?;
+
+        Ok(())
+    }
+}
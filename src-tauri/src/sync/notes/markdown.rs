@@ -0,0 +1,283 @@
+//! Markdown note parsing: front matter, wiki-links and titles
+//!
+//! Front matter support covers the flat `key: value` and list forms Obsidian
+//! writes; nested mappings are ignored.
+
+use serde_json::{Map, Value};
+
+use super::types::Note;
+
+/// Split `---` delimited front matter from the body
+fn split_front_matter(text: &str) -> (Option<&str>, &str) {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let Some(rest) = text
+        .strip_prefix("---")
+        .filter(|r| r.starts_with('\n') || r.starts_with("\r\n"))
+    else {
+        return (None, text);
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if offset > 0 && (trimmed == "---" || trimmed == "...") {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+
+    (None, text)
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return value[1..value.len() - 1].to_string();
+        }
+    }
+    value.to_string()
+}
+
+fn parse_front_matter(yaml: &str) -> Map<String, Value> {
+    let mut map = Map::new();
+    let mut list_key: Option<String> = None;
+
+    for line in yaml.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            let Some(key) = &list_key else {
+                continue;
+            };
+            let entry = map.entry(key.clone()).or_insert(Value::Null);
+            if entry.is_null() {
+                *entry = Value::Array(Vec::new());
+            }
+            if let Value::Array(items) = entry {
+                items.push(Value::String(unquote(item)));
+            }
+            continue;
+        }
+
+        if line.starts_with([' ', '\t']) {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().to_string();
+        let value = value.trim();
+
+        if value.is_empty() {
+            map.insert(key.clone(), Value::Null);
+            list_key = Some(key);
+            continue;
+        }
+        list_key = None;
+
+        let value = match value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            Some(inner) => Value::Array(
+                inner
+                    .split(',')
+                    .map(unquote)
+                    .filter(|v| !v.is_empty())
+                    .map(Value::String)
+                    .collect(),
+            ),
+            None => Value::String(unquote(value)),
+        };
+        map.insert(key, value);
+    }
+
+    map
+}
+
+/// String values of a front matter field, splitting comma/space separated strings
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    let values = match value {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect(),
+        Some(Value::String(s)) => s.split([',', ' ']).map(String::from).collect::<Vec<_>>(),
+        _ => Vec::new(),
+    };
+    values
+        .into_iter()
+        .map(|v| v.trim().trim_start_matches('#').to_string())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+/// Parse `YYYY-MM-DD` with an optional time; date-only values map to noon UTC
+/// so the note lands on its day in any timezone within ±12h
+fn parse_date(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(dt.timestamp_millis());
+    }
+    for format in [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ] {
+        if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(value, format) {
+            return Some(dt.and_utc().timestamp_millis());
+        }
+    }
+    let date = chrono::NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d").ok()?;
+    Some(date.and_hms_opt(12, 0, 0)?.and_utc().timestamp_millis())
+}
+
+struct WikiLink<'a> {
+    start: usize,
+    end: usize,
+    target: &'a str,
+    label: &'a str,
+}
+
+/// `[[Target]]`, `[[Target|Alias]]`, `[[Target#Heading]]` and `![[Embed]]` links
+fn find_wiki_links(text: &str) -> Vec<WikiLink<'_>> {
+    let mut links = Vec::new();
+    let mut pos = 0;
+
+    while let Some(found) = text[pos..].find("[[") {
+        let open = pos + found;
+        let Some(length) = text[open + 2..].find("]]") else {
+            break;
+        };
+        let close = open + 2 + length;
+        let inner = &text[open + 2..close];
+        if inner.contains('\n') || inner.contains("[[") {
+            pos = open + 2;
+            continue;
+        }
+
+        let (target, alias) = match inner.split_once('|') {
+            Some((target, alias)) => (target, Some(alias.trim())),
+            None => (inner, None),
+        };
+        let embed = text[..open].ends_with('!');
+        links.push(WikiLink {
+            start: if embed { open - 1 } else { open },
+            end: close + 2,
+            target: target.split('#').next().unwrap_or_default().trim(),
+            label: alias.unwrap_or(target.trim()),
+        });
+        pos = close + 2;
+    }
+
+    links
+}
+
+fn render_wiki_links(text: &str, links: &[WikiLink<'_>]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut pos = 0;
+    for link in links {
+        out.push_str(&text[pos..link.start]);
+        out.push_str(link.label);
+        pos = link.end;
+    }
+    out.push_str(&text[pos..]);
+    out
+}
+
+fn first_heading(body: &str) -> Option<String> {
+    body.lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .map(|heading| heading.trim().to_string())
+        .filter(|heading| !heading.is_empty())
+}
+
+/// Parse a note; `file_stem` is the file name without `.md`
+pub fn parse_note(file_stem: &str, text: &str) -> Note {
+    let (front_matter, body) = split_front_matter(text);
+    let front_matter = front_matter.map(parse_front_matter).unwrap_or_default();
+    let field = |key: &str| front_matter.get(key).and_then(|v| v.as_str());
+
+    let links = find_wiki_links(body);
+    let mut targets: Vec<String> = Vec::new();
+    for link in &links {
+        if !link.target.is_empty() && !targets.iter().any(|t| t == link.target) {
+            targets.push(link.target.to_string());
+        }
+    }
+
+    let title = field("title")
+        .map(String::from)
+        .or_else(|| first_heading(body))
+        .unwrap_or_else(|| file_stem.to_string());
+    let date = field("date")
+        .or_else(|| field("created"))
+        .and_then(parse_date)
+        .or_else(|| parse_date(file_stem));
+
+    Note {
+        title,
+        text: render_wiki_links(body, &links).trim().to_string(),
+        tags: string_list(front_matter.get("tags")),
+        links: targets,
+        author: field("author").map(String::from),
+        date,
+        front_matter,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTE: &str = "---
+title: Pricing sync
+tags: [pricing, \"customers\"]
+aliases:
+  - Price review
+created: 2024-03-04
+---
+# Agenda
+
+Discussed [[Acme Corp|Acme]] renewal and [[Pricing#Tiers]].
+See ![[whiteboard.png]] and [[Acme Corp]] again.
+";
+
+    #[test]
+    fn test_parses_front_matter_and_links() {
+        let note = parse_note("pricing-sync", NOTE);
+
+        assert_eq!(note.title, "Pricing sync");
+        assert_eq!(note.tags, vec!["pricing", "customers"]);
+        assert_eq!(
+            note.front_matter["aliases"],
+            serde_json::json!(["Price review"])
+        );
+        assert_eq!(note.links, vec!["Acme Corp", "Pricing", "whiteboard.png"]);
+        assert_eq!(note.date, Some(parse_date("2024-03-04T12:00:00Z").unwrap()));
+        assert_eq!(
+            note.text,
+            "# Agenda\n\nDiscussed Acme renewal and Pricing#Tiers.\nSee whiteboard.png and Acme Corp again."
+        );
+    }
+
+    #[test]
+    fn test_daily_note_without_front_matter() {
+        let note = parse_note("2024-03-05", "# Tuesday\n\n- Call with #legal\n");
+
+        assert_eq!(note.title, "Tuesday");
+        assert!(note.front_matter.is_empty());
+        assert!(note.tags.is_empty());
+        assert_eq!(note.date, Some(parse_date("2024-03-05T12:00:00Z").unwrap()));
+    }
+
+    #[test]
+    fn test_unterminated_front_matter_is_body() {
+        let note = parse_note("draft", "---\ntitle: nope\n\nText");
+        assert_eq!(note.title, "draft");
+        assert!(note.text.starts_with("---"));
+        assert_eq!(note.date, None);
+    }
+}
@@ -0,0 +1,16 @@
+//! Markdown notes integration module
+//!
+//! This module syncs a local Markdown folder or Obsidian vault, parsing front
+//! matter and wiki-links, and watches the folder for changes.
+
+mod connector;
+mod markdown;
+mod sync;
+mod types;
+mod watcher;
+
+pub use connector::NotesConnector;
+pub use markdown::parse_note;
+pub use sync::NotesSyncService;
+pub use types::{Note, NotesError, NotesVault};
+pub use watcher::watch_notes;
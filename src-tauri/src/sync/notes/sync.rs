@@ -0,0 +1,297 @@
+//! Notes folder synchronization service
+
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::markdown::parse_note;
+use super::types::{Note, NotesError, NotesVault};
+use crate::crypto::CryptoService;
+use crate::db::Database;
+use crate::sync::SyncResult;
+
+/// Change-detection state kept in a stored note's metadata
+struct StoredNote {
+    mtime: i64,
+    hash: String,
+}
+
+/// Whether a path component is hidden, e.g. `.obsidian`, `.trash` or `.git`
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with('.'))
+}
+
+pub(super) fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("md"))
+}
+
+/// Markdown files under `root`, skipping hidden folders
+fn collect_notes(root: &Path) -> Result<Vec<PathBuf>, NotesError> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if is_hidden(&path) {
+                continue;
+            }
+            if path.is_dir() {
+                pending.push(path);
+            } else if is_markdown(&path) {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// `/`-separated path relative to the vault, used as `source_id`
+fn relative_id(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// `vault` for top-level notes, `vault/folder` for the rest
+fn note_channel(vault: &NotesVault, id: &str) -> String {
+    match id.rsplit_once('/') {
+        Some((folder, _)) => format!("{}/{}", vault.name, folder),
+        None => vault.name.clone(),
+    }
+}
+
+fn content_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn modified_ms(path: &Path) -> Result<i64, NotesError> {
+    let modified = std::fs::metadata(path)?.modified()?;
+    Ok(chrono::DateTime::<chrono::Utc>::from(modified).timestamp_millis())
+}
+
+pub struct NotesSyncService {
+    db: Arc<Database>,
+    crypto: Arc<CryptoService>,
+}
+
+impl NotesSyncService {
+    pub fn new(db: Arc<Database>, crypto: Arc<CryptoService>) -> Self {
+        Self { db, crypto }
+    }
+
+    async fn stored_notes(&self) -> Result<HashMap<String, StoredNote>, NotesError> {
+        let rows: Vec<(String, Option<String>)> =
+            sqlx::query_as("SELECT source_id, metadata FROM content_items WHERE source = 'notes'")
+                .fetch_all(self.db.pool())
+                .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(id, metadata)| {
+                let metadata: serde_json::Value = metadata
+                    .and_then(|m| serde_json::from_str(&m).ok())
+                    .unwrap_or_default();
+                let stored = StoredNote {
+                    mtime: metadata["mtime"].as_i64().unwrap_or(0),
+                    hash: metadata["hash"].as_str().unwrap_or_default().to_string(),
+                };
+                (id, stored)
+            })
+            .collect())
+    }
+
+    async fn store_note(
+        &self,
+        vault: &NotesVault,
+        id: &str,
+        note: &Note,
+        created_at: i64,
+        mtime: i64,
+        hash: &str,
+    ) -> Result<(), NotesError> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let encrypted_body = self
+            .crypto
+            .encrypt_string(&format!("{}\n\n{}", note.title, note.text))
+            .map_err(|e| NotesError::Crypto(e.to_string()))?;
+        let metadata = serde_json::json!({
+            "mtime": mtime,
+            "hash": hash,
+            "tags": note.tags,
+            "links": note.links,
+            "front_matter": note.front_matter,
+        })
+        .to_string();
+        let channel = note_channel(vault, id);
+
+        sqlx::query(
+            "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, parent_id, created_at, updated_at, synced_at, metadata)
+             VALUES (?, 'notes', ?, NULL, 'note', ?, ?, ?, ?, NULL, ?, ?, ?, ?)
+             ON CONFLICT(source, source_id) DO UPDATE SET title = ?, body = ?, author_id = ?, channel_or_project = ?, created_at = ?, updated_at = ?, synced_at = ?, metadata = ?"
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(id)
+        .bind(&note.title)
+        .bind(&encrypted_body)
+        .bind(&note.author)
+        .bind(&channel)
+        .bind(created_at)
+        .bind(mtime)
+        .bind(now)
+        .bind(&metadata)
+        .bind(&note.title)
+        .bind(&encrypted_body)
+        .bind(&note.author)
+        .bind(&channel)
+        .bind(created_at)
+        .bind(mtime)
+        .bind(now)
+        .bind(&metadata)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Store new and changed notes and remove deleted ones.
+    ///
+    /// Files whose mtime is unchanged are skipped without being read; touched
+    /// files with an unchanged hash only have their mtime updated. With a
+    /// `window` of `[start_ms, end_ms)`, notes dated in it are re-stored and
+    /// nothing is deleted.
+    pub async fn sync_vault(
+        &self,
+        vault: &NotesVault,
+        window: Option<(i64, i64)>,
+    ) -> Result<SyncResult, NotesError> {
+        let root = Path::new(&vault.path);
+        if !root.is_dir() {
+            return Err(NotesError::NotFound(vault.path.clone()));
+        }
+
+        let stored = self.stored_notes().await?;
+        let mut seen = HashSet::new();
+        let mut items_synced = 0;
+        let mut errors = Vec::new();
+
+        for path in collect_notes(root)? {
+            let id = relative_id(root, &path);
+            seen.insert(id.clone());
+
+            let mtime = match modified_ms(&path) {
+                Ok(mtime) => mtime,
+                Err(e) => {
+                    errors.push(format!("{}: {}", id, e));
+                    continue;
+                }
+            };
+            let previous = stored.get(&id).filter(|_| window.is_none());
+            if previous.is_some_and(|p| p.mtime == mtime) {
+                continue;
+            }
+
+            let bytes = match std::fs::read(&path) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    errors.push(format!("{}: {}", id, e));
+                    continue;
+                }
+            };
+            let hash = content_hash(&bytes);
+            if previous.is_some_and(|p| p.hash == hash) {
+                sqlx::query(
+                    "UPDATE content_items SET metadata = json_set(metadata, '$.mtime', ?)
+                     WHERE source = 'notes' AND source_id = ?",
+                )
+                .bind(mtime)
+                .bind(&id)
+                .execute(self.db.pool())
+                .await?;
+                continue;
+            }
+
+            let stem = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            let note = parse_note(stem, &String::from_utf8_lossy(&bytes));
+            let created_at = note.date.unwrap_or(mtime);
+            if let Some((start, end)) = window {
+                if created_at < start || created_at >= end {
+                    continue;
+                }
+            }
+
+            match self
+                .store_note(vault, &id, &note, created_at, mtime, &hash)
+                .await
+            {
+                Ok(()) => items_synced += 1,
+                Err(e) => errors.push(format!("{}: {}", id, e)),
+            }
+        }
+
+        if window.is_none() {
+            for id in stored.keys().filter(|id| !seen.contains(*id)) {
+                sqlx::query("DELETE FROM content_items WHERE source = 'notes' AND source_id = ?")
+                    .bind(id)
+                    .execute(self.db.pool())
+                    .await?;
+                tracing::debug!("Removed deleted note {}", id);
+            }
+        }
+
+        tracing::info!("Synced {} notes from {}", items_synced, vault.name);
+
+        Ok(SyncResult {
+            source: "notes".to_string(),
+            items_synced,
+            errors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_notes_skips_hidden_folders() {
+        let dir = std::env::temp_dir().join(format!("companion-notes-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("Daily")).unwrap();
+        std::fs::create_dir_all(dir.join(".obsidian")).unwrap();
+        std::fs::write(dir.join("Inbox.md"), "# Inbox").unwrap();
+        std::fs::write(dir.join("Daily").join("2024-03-05.md"), "notes").unwrap();
+        std::fs::write(dir.join(".obsidian").join("workspace.md"), "{}").unwrap();
+        std::fs::write(dir.join("image.png"), "png").unwrap();
+
+        let files = collect_notes(&dir).unwrap();
+        let ids: Vec<String> = files.iter().map(|f| relative_id(&dir, f)).collect();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(ids, vec!["Daily/2024-03-05.md", "Inbox.md"]);
+    }
+
+    #[test]
+    fn test_note_channel_uses_folder() {
+        let vault = NotesVault {
+            path: "/notes".to_string(),
+            name: "Brain".to_string(),
+        };
+        assert_eq!(note_channel(&vault, "Inbox.md"), "Brain");
+        assert_eq!(
+            note_channel(&vault, "Work/Acme/Kickoff.md"),
+            "Brain/Work/Acme"
+        );
+    }
+}
@@ -0,0 +1,42 @@
+//! Markdown notes data types and error definitions
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum NotesError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Notes folder not found: {0}")]
+    NotFound(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Crypto error: {0}")]
+    Crypto(String),
+}
+
+/// The configured notes folder or Obsidian vault
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotesVault {
+    pub path: String,
+    /// Used as the `channel_or_project` prefix for stored notes
+    pub name: String,
+}
+
+/// A parsed Markdown note
+#[derive(Debug, Clone, PartialEq)]
+pub struct Note {
+    pub title: String,
+    /// Body without front matter, wiki-links rendered as their display text
+    pub text: String,
+    pub front_matter: serde_json::Map<String, serde_json::Value>,
+    pub tags: Vec<String>,
+    /// Wiki-link targets, without headings or aliases
+    pub links: Vec<String>,
+    pub author: Option<String>,
+    /// From front matter `date`/`created` or a daily-note file name, in epoch ms
+    pub date: Option<i64>,
+}
@@ -0,0 +1,146 @@
+//! Filesystem watcher for the notes folder
+//!
+//! Syncs the notes source shortly after Markdown files change, instead of
+//! waiting for the next background sync cycle.
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+
+use super::connector::{load_notes_vault, NotesConnector};
+use super::sync::is_markdown;
+use crate::pipeline::PipelineManager;
+use crate::sync::connector::{run_sync_task, ConnectorContext, SourceConnector};
+use crate::sync::scheduler::{SyncClaim, SyncPriority, SyncScheduler};
+
+/// How often to check whether the configured folder changed
+const CONFIG_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Quiet period after the last change before syncing; editors write in bursts
+const DEBOUNCE: Duration = Duration::from_secs(3);
+
+fn is_note_change(vault: &Path, event: &Event) -> bool {
+    !event.kind.is_access()
+        && event.paths.iter().any(|path| {
+            let relative = path.strip_prefix(vault).unwrap_or(path);
+            is_markdown(path)
+                && !relative.components().any(|c| {
+                    c.as_os_str()
+                        .to_str()
+                        .is_some_and(|name| name.starts_with('.'))
+                })
+        })
+}
+
+async fn vault_path(ctx: &ConnectorContext) -> Option<PathBuf> {
+    load_notes_vault(ctx)
+        .await
+        .ok()
+        .flatten()
+        .map(|vault| PathBuf::from(vault.path))
+}
+
+/// Watch the configured notes folder for the lifetime of the app, following
+/// connects, disconnects and folder changes
+pub async fn watch_notes(
+    ctx: ConnectorContext,
+    pipeline: Arc<Mutex<PipelineManager>>,
+    scheduler: Arc<SyncScheduler>,
+) {
+    loop {
+        let Some(vault) = vault_path(&ctx).await else {
+            tokio::time::sleep(CONFIG_CHECK_INTERVAL).await;
+            continue;
+        };
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let _ = tx.send(event);
+        });
+        let mut watcher: RecommendedWatcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                tracing::error!("Failed to create notes watcher: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = watcher.watch(&vault, RecursiveMode::Recursive) {
+            tracing::warn!("Cannot watch notes folder {}: {}", vault.display(), e);
+            tokio::time::sleep(CONFIG_CHECK_INTERVAL).await;
+            continue;
+        }
+        tracing::info!("Watching notes folder {}", vault.display());
+
+        let mut config_check = tokio::time::interval(CONFIG_CHECK_INTERVAL);
+        loop {
+            let changed = tokio::select! {
+                event = rx.recv() => match event {
+                    Some(Ok(event)) => is_note_change(&vault, &event),
+                    Some(Err(e)) => {
+                        tracing::debug!("Notes watcher error: {}", e);
+                        false
+                    }
+                    None => break,
+                },
+                _ = config_check.tick() => {
+                    if vault_path(&ctx).await.as_ref() != Some(&vault) {
+                        break;
+                    }
+                    false
+                }
+            };
+            if !changed {
+                continue;
+            }
+
+            while let Ok(Some(_)) = tokio::time::timeout(DEBOUNCE, rx.recv()).await {}
+
+            // Notes sync skips the AI pass, so it only waits on other notes syncs
+            let _permit = scheduler
+                .acquire(
+                    "Notes folder sync",
+                    SyncPriority::Incremental,
+                    SyncClaim::Resources(vec![NotesConnector.id().to_string()]),
+                )
+                .await;
+            if let Err(e) = run_sync_task(&NotesConnector, &ctx, &pipeline, None).await {
+                tracing::warn!("Notes sync after file change failed: {}", e);
+            }
+        }
+
+        tracing::info!("Stopped watching notes folder {}", vault.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, EventKind};
+
+    #[test]
+    fn test_is_note_change() {
+        let vault = Path::new("/home/me/.notes/vault");
+        let event = |kind, path: &str| Event::new(kind).add_path(vault.join(path));
+
+        assert!(is_note_change(
+            vault,
+            &event(EventKind::Create(CreateKind::File), "Daily/2024-03-05.md")
+        ));
+        assert!(!is_note_change(
+            vault,
+            &event(
+                EventKind::Create(CreateKind::File),
+                ".obsidian/workspace.md"
+            )
+        ));
+        assert!(!is_note_change(
+            vault,
+            &event(EventKind::Create(CreateKind::File), "image.png")
+        ));
+        assert!(!is_note_change(
+            vault,
+            &event(EventKind::Access(AccessKind::Any), "Inbox.md")
+        ));
+    }
+}
@@ -1,142 +1,526 @@
+//! Persistent sync job queue
+//!
+//! Work that cannot run right away, such as a historical resync requested while
+//! another sync holds the lock or a multi-day backfill, is stored in `sync_jobs`
+//! with its attempts, next-run time and last error so it survives restarts and
+//! can be listed, cancelled and retried from the UI.
+
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
-use tokio::sync::Mutex;
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SyncRequest {
-    pub id: String,
-    pub source: String,
-    pub created_at: i64,
-    pub retry_count: u32,
-    /// For historical resyncs: the date string (e.g. "2026-02-17")
-    pub date: Option<String>,
-    /// For historical resyncs: the timezone offset in minutes
-    pub timezone_offset: Option<i32>,
+use crate::db::Database;
+
+/// First retry delay; doubles with each further attempt
+const RETRY_BASE_DELAY_MS: i64 = 60 * 1000;
+const RETRY_MAX_DELAY_MS: i64 = 60 * 60 * 1000;
+
+type SyncJobRow = (
+    String,
+    String,
+    String,
+    i32,
+    i32,
+    i64,
+    Option<String>,
+    i64,
+    i64,
+);
+
+/// What a queued job does
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SyncJobPayload {
+    /// Resync every connected source for one local day, then run AI processing for it
+    HistoricalDay {
+        /// 'YYYY-MM-DD'
+        date: String,
+        /// Minutes west of UTC (JS getTimezoneOffset convention)
+        #[serde(rename = "timezoneOffset")]
+        timezone_offset: i32,
+    },
+    /// Run a multi-day backfill; its per-day progress lives in `backfill_jobs`
+    Backfill {
+        #[serde(rename = "backfillId")]
+        backfill_id: String,
+        #[serde(rename = "startDate")]
+        start_date: String,
+        #[serde(rename = "endDate")]
+        end_date: String,
+    },
 }
 
-impl SyncRequest {
-    pub fn new(source: String) -> Self {
-        Self {
-            id: uuid::Uuid::new_v4().to_string(),
-            source,
-            created_at: chrono::Utc::now().timestamp(),
-            retry_count: 0,
-            date: None,
-            timezone_offset: None,
+impl SyncJobPayload {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::HistoricalDay { .. } => "historical_day",
+            Self::Backfill { .. } => "backfill",
         }
     }
 
-    /// Create a request for a historical day resync.
-    /// Uses `source = "historical:YYYY-MM-DD"` for deduplication.
-    pub fn historical(date: String, timezone_offset: i32) -> Self {
-        Self {
-            id: uuid::Uuid::new_v4().to_string(),
-            source: format!("historical:{}", date),
-            created_at: chrono::Utc::now().timestamp(),
-            retry_count: 0,
-            date: Some(date),
-            timezone_offset: Some(timezone_offset),
+    /// Jobs with the same key are duplicates while one of them is pending or running
+    pub fn dedupe_key(&self) -> String {
+        match self {
+            Self::HistoricalDay { date, .. } => format!("historical_day:{}", date),
+            Self::Backfill { backfill_id, .. } => format!("backfill:{}", backfill_id),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::HistoricalDay { date, .. } => format!("Resync {}", date),
+            Self::Backfill {
+                start_date,
+                end_date,
+                ..
+            } => format!("Backfill {} to {}", start_date, end_date),
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncJobStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl SyncJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "running" => Self::Running,
+            "completed" => Self::Completed,
+            "failed" => Self::Failed,
+            "cancelled" => Self::Cancelled,
+            _ => Self::Pending,
+        }
+    }
+}
+
+/// A persisted sync job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncJob {
+    pub id: String,
+    pub payload: SyncJobPayload,
+    pub status: SyncJobStatus,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    /// Earliest time the job may run, in epoch ms
+    pub next_run_at: i64,
+    pub last_error: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl SyncJob {
+    fn from_row(row: SyncJobRow) -> Result<Self, String> {
+        let payload = serde_json::from_str(&row.1)
+            .map_err(|e| format!("Invalid payload for sync job {}: {}", row.0, e))?;
+
+        Ok(Self {
+            id: row.0,
+            payload,
+            status: SyncJobStatus::parse(&row.2),
+            attempts: row.3,
+            max_attempts: row.4,
+            next_run_at: row.5,
+            last_error: row.6,
+            created_at: row.7,
+            updated_at: row.8,
+        })
+    }
+}
+
+/// Delay before retrying a job that has failed `attempts` times
+fn retry_delay_ms(attempts: i32) -> i64 {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    RETRY_BASE_DELAY_MS
+        .saturating_mul(2_i64.pow(exponent))
+        .min(RETRY_MAX_DELAY_MS)
+}
+
+/// Whether an insert or update hit the one-active-job-per-key index
+fn is_unique_violation(error: &sqlx::Error) -> bool {
+    matches!(error, sqlx::Error::Database(e) if e.is_unique_violation())
+}
+
+const SELECT_COLUMNS: &str = "SELECT id, payload, status, attempts, max_attempts, next_run_at, last_error, created_at, updated_at FROM sync_jobs";
+
 pub struct SyncQueue {
-    queue: Mutex<VecDeque<SyncRequest>>,
-    max_retries: u32,
+    db: Arc<Database>,
+    max_attempts: i32,
 }
 
 impl SyncQueue {
-    pub fn new() -> Self {
+    pub fn new(db: Arc<Database>) -> Self {
         Self {
-            queue: Mutex::new(VecDeque::new()),
-            max_retries: 3,
+            db,
+            max_attempts: 3,
         }
     }
 
-    pub fn with_max_retries(max_retries: u32) -> Self {
-        Self {
-            queue: Mutex::new(VecDeque::new()),
-            max_retries,
-        }
+    pub fn with_max_attempts(mut self, max_attempts: i32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
     }
 
-    pub async fn enqueue(&self, request: SyncRequest) {
-        let mut queue = self.queue.lock().await;
-        if !queue.iter().any(|r| r.source == request.source) {
-            queue.push_back(request);
-            tracing::info!("Sync request queued, total pending: {}", queue.len());
+    /// Queue a job, or return the pending or running job it duplicates
+    pub async fn enqueue(&self, payload: SyncJobPayload) -> Result<SyncJob, String> {
+        if let Some(existing) = self.find_active(&payload.dedupe_key()).await? {
+            tracing::debug!(
+                "Sync job for {} already queued as {}",
+                existing.payload.dedupe_key(),
+                existing.id
+            );
+            return Ok(existing);
         }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let job = SyncJob {
+            id: uuid::Uuid::new_v4().to_string(),
+            payload,
+            status: SyncJobStatus::Pending,
+            attempts: 0,
+            max_attempts: self.max_attempts,
+            next_run_at: now,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let payload_json = serde_json::to_string(&job.payload).map_err(|e| e.to_string())?;
+
+        let inserted = sqlx::query(
+            "INSERT INTO sync_jobs (id, kind, dedupe_key, payload, status, attempts, max_attempts, next_run_at, last_error, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&job.id)
+        .bind(job.payload.kind())
+        .bind(job.payload.dedupe_key())
+        .bind(&payload_json)
+        .bind(job.status.as_str())
+        .bind(job.attempts)
+        .bind(job.max_attempts)
+        .bind(job.next_run_at)
+        .bind(&job.last_error)
+        .bind(job.created_at)
+        .bind(job.updated_at)
+        .execute(self.db.pool())
+        .await;
+
+        match inserted {
+            Ok(_) => {}
+            // A concurrent enqueue inserted the same job after the check above
+            Err(e) if is_unique_violation(&e) => {
+                return self
+                    .find_active(&job.payload.dedupe_key())
+                    .await?
+                    .ok_or_else(|| e.to_string());
+            }
+            Err(e) => return Err(e.to_string()),
+        }
+
+        tracing::info!(
+            "Sync job queued: {} ({} pending)",
+            job.payload.describe(),
+            self.len().await
+        );
+        Ok(job)
     }
 
-    pub async fn dequeue(&self) -> Option<SyncRequest> {
-        self.queue.lock().await.pop_front()
+    async fn find_active(&self, dedupe_key: &str) -> Result<Option<SyncJob>, String> {
+        let row: Option<SyncJobRow> = sqlx::query_as(&format!(
+            "{} WHERE dedupe_key = ? AND status IN ('pending', 'running') LIMIT 1",
+            SELECT_COLUMNS
+        ))
+        .bind(dedupe_key)
+        .fetch_optional(self.db.pool())
+        .await
+        .map_err(|e| e.to_string())?;
+
+        row.map(SyncJob::from_row).transpose()
     }
 
-    pub async fn peek(&self) -> Option<SyncRequest> {
-        self.queue.lock().await.front().cloned()
+    pub async fn get(&self, id: &str) -> Result<Option<SyncJob>, String> {
+        let row: Option<SyncJobRow> = sqlx::query_as(&format!("{} WHERE id = ?", SELECT_COLUMNS))
+            .bind(id)
+            .fetch_optional(self.db.pool())
+            .await
+            .map_err(|e| e.to_string())?;
+
+        row.map(SyncJob::from_row).transpose()
     }
 
-    pub async fn len(&self) -> usize {
-        self.queue.lock().await.len()
+    /// Claim the oldest due pending job, marking it running and counting the attempt
+    pub async fn dequeue(&self) -> Result<Option<SyncJob>, String> {
+        let now = chrono::Utc::now().timestamp_millis();
+
+        loop {
+            let row: Option<SyncJobRow> = sqlx::query_as(&format!(
+                "{} WHERE status = 'pending' AND next_run_at <= ? ORDER BY next_run_at ASC, created_at ASC, rowid ASC LIMIT 1",
+                SELECT_COLUMNS
+            ))
+            .bind(now)
+            .fetch_optional(self.db.pool())
+            .await
+            .map_err(|e| e.to_string())?;
+
+            let Some(row) = row else {
+                return Ok(None);
+            };
+            let id = row.0.clone();
+
+            let mut job = match SyncJob::from_row(row) {
+                Ok(job) => job,
+                Err(e) => {
+                    // Unreadable payloads (e.g. from a newer version) would block the queue
+                    tracing::warn!("{}", e);
+                    self.finish(&id, SyncJobStatus::Failed, Some(e), None)
+                        .await?;
+                    continue;
+                }
+            };
+
+            // Another drainer may have claimed it between the select and the update
+            let claimed = sqlx::query(
+                "UPDATE sync_jobs SET status = 'running', attempts = attempts + 1, updated_at = ?
+                 WHERE id = ? AND status = 'pending'",
+            )
+            .bind(now)
+            .bind(&job.id)
+            .execute(self.db.pool())
+            .await
+            .map_err(|e| e.to_string())?
+            .rows_affected()
+                > 0;
+
+            if claimed {
+                job.status = SyncJobStatus::Running;
+                job.attempts += 1;
+                job.updated_at = now;
+                return Ok(Some(job));
+            }
+        }
     }
 
-    pub async fn is_empty(&self) -> bool {
-        self.queue.lock().await.is_empty()
+    /// Record the outcome of a running job. Failures are retried with exponential
+    /// backoff until `max_attempts` is reached. Returns whether a retry was scheduled.
+    ///
+    /// Jobs cancelled while running stay cancelled.
+    pub async fn finish_job(&self, job: &SyncJob, result: Result<(), String>) -> bool {
+        let (status, error, next_run_at) = match result {
+            Ok(()) => (SyncJobStatus::Completed, None, None),
+            Err(e) if job.attempts < job.max_attempts => {
+                tracing::warn!(
+                    "Sync job {} failed (attempt {} of {}), retrying: {}",
+                    job.payload.describe(),
+                    job.attempts,
+                    job.max_attempts,
+                    e
+                );
+                let next_run_at =
+                    chrono::Utc::now().timestamp_millis() + retry_delay_ms(job.attempts);
+                (SyncJobStatus::Pending, Some(e), Some(next_run_at))
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Sync job {} exceeded {} attempts, giving up: {}",
+                    job.payload.describe(),
+                    job.max_attempts,
+                    e
+                );
+                (SyncJobStatus::Failed, Some(e), None)
+            }
+        };
+        let retrying = status == SyncJobStatus::Pending;
+
+        match self.finish(&job.id, status, error, next_run_at).await {
+            Ok(updated) => updated && retrying,
+            Err(e) => {
+                tracing::error!("Failed to record sync job {} outcome: {}", job.id, e);
+                false
+            }
+        }
     }
 
-    pub async fn get_pending(&self) -> Vec<SyncRequest> {
-        self.queue.lock().await.iter().cloned().collect()
+    /// Move a running job to `status`; returns whether the job was still running
+    async fn finish(
+        &self,
+        id: &str,
+        status: SyncJobStatus,
+        error: Option<String>,
+        next_run_at: Option<i64>,
+    ) -> Result<bool, String> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let result = sqlx::query(
+            "UPDATE sync_jobs
+             SET status = ?, last_error = COALESCE(?, last_error), next_run_at = COALESCE(?, next_run_at), updated_at = ?
+             WHERE id = ? AND status IN ('pending', 'running')",
+        )
+        .bind(status.as_str())
+        .bind(&error)
+        .bind(next_run_at)
+        .bind(now)
+        .bind(id)
+        .execute(self.db.pool())
+        .await
+        .map_err(|e| e.to_string())?;
+
+        Ok(result.rows_affected() > 0)
     }
 
-    pub async fn requeue_failed(&self, mut request: SyncRequest) -> bool {
-        request.retry_count += 1;
+    /// Cancel a pending or running job. A running job finishes its current step
+    /// but is not retried.
+    pub async fn cancel(&self, id: &str) -> Result<SyncJob, String> {
+        let cancelled = self
+            .finish(id, SyncJobStatus::Cancelled, None, None)
+            .await?;
+        let job = self
+            .get(id)
+            .await?
+            .ok_or_else(|| format!("Sync job not found: {}", id))?;
 
-        if request.retry_count <= self.max_retries {
-            let mut queue = self.queue.lock().await;
-            queue.push_back(request);
-            true
-        } else {
-            tracing::warn!(
-                "Sync request for {} exceeded max retries, dropping",
-                request.source
-            );
-            false
+        if !cancelled {
+            return Err(format!(
+                "Sync job {} is {} and cannot be cancelled",
+                id,
+                job.status.as_str()
+            ));
         }
+
+        tracing::info!("Sync job cancelled: {}", job.payload.describe());
+        Ok(job)
     }
 
-    pub async fn process_all<F, Fut>(&self, mut processor: F) -> (usize, usize)
-    where
-        F: FnMut(SyncRequest) -> Fut,
-        Fut: std::future::Future<Output = Result<(), String>>,
-    {
-        let mut successes = 0;
-        let mut failures = 0;
+    /// Queue a failed or cancelled job again with a fresh attempt budget
+    pub async fn retry(&self, id: &str) -> Result<SyncJob, String> {
+        let job = self
+            .get(id)
+            .await?
+            .ok_or_else(|| format!("Sync job not found: {}", id))?;
 
-        while let Some(request) = self.dequeue().await {
-            match processor(request.clone()).await {
-                Ok(()) => {
-                    successes += 1;
-                    tracing::info!("Successfully processed sync request for {}", request.source);
-                }
-                Err(e) => {
-                    failures += 1;
-                    tracing::error!("Failed to process sync request: {}", e);
-                    self.requeue_failed(request).await;
-                }
+        if !matches!(job.status, SyncJobStatus::Failed | SyncJobStatus::Cancelled) {
+            return Err(format!(
+                "Sync job {} is {} and cannot be retried",
+                id,
+                job.status.as_str()
+            ));
+        }
+        if let Some(active) = self.find_active(&job.payload.dedupe_key()).await? {
+            return Err(format!(
+                "{} is already queued as job {}",
+                job.payload.describe(),
+                active.id
+            ));
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let requeued = sqlx::query(
+            "UPDATE sync_jobs SET status = 'pending', attempts = 0, next_run_at = ?, updated_at = ?
+             WHERE id = ?",
+        )
+        .bind(now)
+        .bind(now)
+        .bind(id)
+        .execute(self.db.pool())
+        .await;
+
+        match requeued {
+            Ok(_) => {}
+            Err(e) if is_unique_violation(&e) => {
+                return Err(format!(
+                    "{} is already queued as another job",
+                    job.payload.describe()
+                ));
             }
+            Err(e) => return Err(e.to_string()),
         }
 
-        (successes, failures)
+        tracing::info!("Sync job requeued: {}", job.payload.describe());
+        self.get(id)
+            .await?
+            .ok_or_else(|| format!("Sync job not found: {}", id))
     }
 
-    pub async fn clear(&self) {
-        self.queue.lock().await.clear();
+    /// Whether the job has been cancelled, so a long-running job can stop early
+    pub async fn is_cancelled(&self, id: &str) -> bool {
+        matches!(
+            self.get(id).await,
+            Ok(Some(SyncJob {
+                status: SyncJobStatus::Cancelled,
+                ..
+            }))
+        )
+    }
+
+    /// Return jobs left running by a previous session to the queue. The
+    /// interrupted attempt is not counted against the job.
+    pub async fn recover_interrupted(&self) -> Result<u64, String> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let result = sqlx::query(
+            "UPDATE sync_jobs SET status = 'pending', attempts = MAX(attempts - 1, 0), updated_at = ?
+             WHERE status = 'running'",
+        )
+        .bind(now)
+        .execute(self.db.pool())
+        .await
+        .map_err(|e| e.to_string())?;
+
+        Ok(result.rows_affected())
     }
-}
 
-impl Default for SyncQueue {
-    fn default() -> Self {
-        Self::new()
+    /// Recent jobs, newest first
+    pub async fn list(&self, limit: i64) -> Result<Vec<SyncJob>, String> {
+        let rows: Vec<SyncJobRow> = sqlx::query_as(&format!(
+            "{} ORDER BY created_at DESC LIMIT ?",
+            SELECT_COLUMNS
+        ))
+        .bind(limit)
+        .fetch_all(self.db.pool())
+        .await
+        .map_err(|e| e.to_string())?;
+
+        rows.into_iter().map(SyncJob::from_row).collect()
+    }
+
+    /// Number of pending jobs, due or not
+    pub async fn len(&self) -> usize {
+        let count: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM sync_jobs WHERE status = 'pending'")
+                .fetch_one(self.db.pool())
+                .await
+                .unwrap_or((0,));
+        count.0 as usize
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+
+    /// Whether any pending job is ready to run now
+    pub async fn has_due(&self) -> Result<bool, String> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let due: Option<(String,)> = sqlx::query_as(
+            "SELECT id FROM sync_jobs WHERE status = 'pending' AND next_run_at <= ? LIMIT 1",
+        )
+        .bind(now)
+        .fetch_optional(self.db.pool())
+        .await
+        .map_err(|e| e.to_string())?;
+
+        Ok(due.is_some())
     }
 }
 
@@ -144,32 +528,217 @@ impl Default for SyncQueue {
 mod tests {
     use super::*;
 
+    async fn test_queue() -> SyncQueue {
+        SyncQueue::new(Arc::new(Database::in_memory().await.unwrap()))
+    }
+
+    fn day(date: &str) -> SyncJobPayload {
+        SyncJobPayload::HistoricalDay {
+            date: date.to_string(),
+            timezone_offset: 0,
+        }
+    }
+
+    /// Make a job scheduled for a later retry due now
+    async fn make_due(queue: &SyncQueue, id: &str) {
+        sqlx::query("UPDATE sync_jobs SET next_run_at = 0 WHERE id = ?")
+            .bind(id)
+            .execute(queue.db.pool())
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     async fn test_queue_operations() {
-        let queue = SyncQueue::new();
-        queue.enqueue(SyncRequest::new("slack".to_string())).await;
-        queue.enqueue(SyncRequest::new("jira".to_string())).await;
+        let queue = test_queue().await;
+        let first = queue.enqueue(day("2026-02-16")).await.unwrap();
+        queue.enqueue(day("2026-02-17")).await.unwrap();
         assert_eq!(queue.len().await, 2);
 
-        let req = queue.dequeue().await.unwrap();
-        assert_eq!(req.source, "slack");
+        let job = queue.dequeue().await.unwrap().unwrap();
+        assert_eq!(job.id, first.id);
+        assert_eq!(job.status, SyncJobStatus::Running);
+        assert_eq!(job.attempts, 1);
         assert_eq!(queue.len().await, 1);
+
+        assert!(!queue.finish_job(&job, Ok(())).await);
+        let job = queue.get(&job.id).await.unwrap().unwrap();
+        assert_eq!(job.status, SyncJobStatus::Completed);
     }
 
     #[tokio::test]
     async fn test_no_duplicates() {
-        let queue = SyncQueue::new();
-        queue.enqueue(SyncRequest::new("slack".to_string())).await;
-        queue.enqueue(SyncRequest::new("slack".to_string())).await;
+        let queue = test_queue().await;
+        let first = queue.enqueue(day("2026-02-17")).await.unwrap();
+        let second = queue.enqueue(day("2026-02-17")).await.unwrap();
+        assert_eq!(first.id, second.id);
         assert_eq!(queue.len().await, 1);
+
+        // A running job still counts as a duplicate
+        let job = queue.dequeue().await.unwrap().unwrap();
+        assert_eq!(queue.enqueue(day("2026-02-17")).await.unwrap().id, job.id);
+
+        // Once it finishes the day can be queued again
+        queue.finish_job(&job, Ok(())).await;
+        let again = queue.enqueue(day("2026-02-17")).await.unwrap();
+        assert_ne!(again.id, job.id);
+    }
+
+    #[tokio::test]
+    async fn test_second_active_job_rejected_by_index() {
+        let queue = test_queue().await;
+        let first = queue.enqueue(day("2026-02-17")).await.unwrap();
+        queue.cancel(&first.id).await.unwrap();
+        queue.enqueue(day("2026-02-17")).await.unwrap();
+
+        let error = sqlx::query("UPDATE sync_jobs SET status = 'pending' WHERE id = ?")
+            .bind(&first.id)
+            .execute(queue.db.pool())
+            .await
+            .unwrap_err();
+        assert!(is_unique_violation(&error));
     }
 
     #[tokio::test]
     async fn test_retry_limit() {
-        let queue = SyncQueue::with_max_retries(2);
-        let mut request = SyncRequest::new("slack".to_string());
-        request.retry_count = 2;
-        assert!(!queue.requeue_failed(request).await);
+        let queue = test_queue().await.with_max_attempts(2);
+        queue.enqueue(day("2026-02-17")).await.unwrap();
+
+        let job = queue.dequeue().await.unwrap().unwrap();
+        assert!(queue.finish_job(&job, Err("boom".to_string())).await);
+        let retried = queue.get(&job.id).await.unwrap().unwrap();
+        assert_eq!(retried.status, SyncJobStatus::Pending);
+        assert_eq!(retried.last_error.as_deref(), Some("boom"));
+
+        // The retry is backed off, so it is not due yet
+        assert!(!queue.has_due().await.unwrap());
+        assert!(queue.dequeue().await.unwrap().is_none());
+
+        make_due(&queue, &job.id).await;
+        let job = queue.dequeue().await.unwrap().unwrap();
+        assert_eq!(job.attempts, 2);
+        assert!(!queue.finish_job(&job, Err("boom again".to_string())).await);
+
+        let failed = queue.get(&job.id).await.unwrap().unwrap();
+        assert_eq!(failed.status, SyncJobStatus::Failed);
+        assert_eq!(failed.last_error.as_deref(), Some("boom again"));
         assert!(queue.is_empty().await);
     }
+
+    #[tokio::test]
+    async fn test_cancel() {
+        let queue = test_queue().await;
+        let pending = queue.enqueue(day("2026-02-16")).await.unwrap();
+        let cancelled = queue.cancel(&pending.id).await.unwrap();
+        assert_eq!(cancelled.status, SyncJobStatus::Cancelled);
+        assert!(queue.is_cancelled(&pending.id).await);
+        assert!(queue.cancel(&pending.id).await.is_err());
+        assert!(queue.dequeue().await.unwrap().is_none());
+
+        // A job cancelled while running is not retried when it finishes
+        queue.enqueue(day("2026-02-17")).await.unwrap();
+        let running = queue.dequeue().await.unwrap().unwrap();
+        queue.cancel(&running.id).await.unwrap();
+        assert!(!queue.finish_job(&running, Err("boom".to_string())).await);
+        let job = queue.get(&running.id).await.unwrap().unwrap();
+        assert_eq!(job.status, SyncJobStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let queue = test_queue().await.with_max_attempts(1);
+        queue.enqueue(day("2026-02-17")).await.unwrap();
+        let job = queue.dequeue().await.unwrap().unwrap();
+        queue.finish_job(&job, Err("boom".to_string())).await;
+
+        let retried = queue.retry(&job.id).await.unwrap();
+        assert_eq!(retried.status, SyncJobStatus::Pending);
+        assert_eq!(retried.attempts, 0);
+        assert!(queue.retry(&job.id).await.is_err());
+
+        // Retrying is refused while another job covers the same day
+        queue.cancel(&job.id).await.unwrap();
+        let replacement = queue.enqueue(day("2026-02-17")).await.unwrap();
+        let error = queue.retry(&job.id).await.unwrap_err();
+        assert!(error.contains(&replacement.id));
+    }
+
+    #[tokio::test]
+    async fn test_recover_interrupted() {
+        let queue = test_queue().await;
+        queue.enqueue(day("2026-02-17")).await.unwrap();
+        let job = queue.dequeue().await.unwrap().unwrap();
+
+        assert_eq!(queue.recover_interrupted().await.unwrap(), 1);
+        let recovered = queue.get(&job.id).await.unwrap().unwrap();
+        assert_eq!(recovered.status, SyncJobStatus::Pending);
+        assert_eq!(recovered.attempts, 0);
+        assert_eq!(queue.dequeue().await.unwrap().unwrap().id, job.id);
+    }
+
+    #[test]
+    fn test_payload_serialization() {
+        let payload = SyncJobPayload::HistoricalDay {
+            date: "2026-02-17".to_string(),
+            timezone_offset: 300,
+        };
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "kind": "historical_day", "date": "2026-02-17", "timezoneOffset": 300 })
+        );
+        assert_eq!(
+            serde_json::from_value::<SyncJobPayload>(json).unwrap(),
+            payload
+        );
+    }
+
+    #[test]
+    fn test_dedupe_key_ignores_timezone() {
+        let a = SyncJobPayload::HistoricalDay {
+            date: "2026-02-17".to_string(),
+            timezone_offset: 0,
+        };
+        let b = SyncJobPayload::HistoricalDay {
+            date: "2026-02-17".to_string(),
+            timezone_offset: 300,
+        };
+        assert_eq!(a.dedupe_key(), b.dedupe_key());
+        assert_eq!(a.dedupe_key(), "historical_day:2026-02-17");
+    }
+
+    #[test]
+    fn test_backfill_payload() {
+        let payload = SyncJobPayload::Backfill {
+            backfill_id: "b1".to_string(),
+            start_date: "2026-01-01".to_string(),
+            end_date: "2026-01-31".to_string(),
+        };
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json["kind"], "backfill");
+        assert_eq!(json["backfillId"], "b1");
+        assert_eq!(payload.dedupe_key(), "backfill:b1");
+        assert_eq!(payload.describe(), "Backfill 2026-01-01 to 2026-01-31");
+    }
+
+    #[test]
+    fn test_retry_delay_backs_off() {
+        assert_eq!(retry_delay_ms(1), 60_000);
+        assert_eq!(retry_delay_ms(2), 120_000);
+        assert_eq!(retry_delay_ms(3), 240_000);
+        assert_eq!(retry_delay_ms(20), RETRY_MAX_DELAY_MS);
+    }
+
+    #[test]
+    fn test_sync_job_status_roundtrip() {
+        for status in [
+            SyncJobStatus::Pending,
+            SyncJobStatus::Running,
+            SyncJobStatus::Completed,
+            SyncJobStatus::Failed,
+            SyncJobStatus::Cancelled,
+        ] {
+            assert_eq!(SyncJobStatus::parse(status.as_str()), status);
+        }
+    }
 }
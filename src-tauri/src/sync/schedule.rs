@@ -0,0 +1,539 @@
+//! Time-based schedule for background sync
+//!
+//! A [`SyncScheduleConfig`] is stored in user preferences as a set of cron
+//! expressions, quiet periods and an optional end-of-day digest time, all in
+//! local time. The background loop compiles it into a [`SyncTimetable`] and
+//! sleeps until the next run. Expressions fire as a union, so "every 10 minutes
+//! during working hours, hourly otherwise" is two entries, and quiet periods
+//! suppress syncs (and the AI pass that follows them) even when an entry matches.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+
+/// How far ahead to look for the next match before treating an expression as never firing
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+/// Upper bound on skipping matches that fall in quiet periods
+const MAX_QUIET_SKIPS: usize = 60 * 24 * 8;
+
+/// A window in which background sync does not run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuietPeriod {
+    /// Days the period starts on, 0 = Sunday through 6 = Saturday; empty means every day
+    #[serde(default)]
+    pub days: Vec<u8>,
+    /// Local `HH:MM`. A period whose end is before its start runs past midnight;
+    /// equal start and end cover the whole day.
+    pub start: String,
+    pub end: String,
+}
+
+/// User-facing background sync schedule, stored in preferences
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncScheduleConfig {
+    /// Five-field cron expressions (`minute hour day-of-month month day-of-week`)
+    /// in local time; a sync is due whenever any of them matches
+    pub entries: Vec<String>,
+    #[serde(default)]
+    pub quiet_periods: Vec<QuietPeriod>,
+    /// Local `HH:MM` of the end-of-day digest run; `None` disables it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest_time: Option<String>,
+}
+
+impl Default for SyncScheduleConfig {
+    fn default() -> Self {
+        Self::from_interval_minutes(15)
+    }
+}
+
+impl SyncScheduleConfig {
+    /// Closest schedule to the old fixed `syncIntervalMinutes` preference
+    pub fn from_interval_minutes(minutes: i64) -> Self {
+        let entry = match minutes.max(1) {
+            m if m < 60 => format!("*/{} * * * *", m),
+            m if m < 24 * 60 => format!("0 */{} * * *", (m + 30) / 60),
+            _ => "0 0 * * *".to_string(),
+        };
+        Self {
+            entries: vec![entry],
+            quiet_periods: vec![],
+            digest_time: None,
+        }
+    }
+}
+
+/// What a scheduled run does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduledRun {
+    Sync,
+    /// A final sync and AI pass for the day, followed by the daily digest
+    Digest,
+}
+
+/// A parsed five-field cron expression. Days of week accept 0-7 (0 and 7 are
+/// Sunday) and fields support `*`, lists, ranges and `/` steps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Day-of-month and day-of-week fields restricted; such days match if either does
+    restricted_days: bool,
+}
+
+impl CronExpr {
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let &[minute, hour, dom, month, dow] = fields.as_slice() else {
+            return Err(format!(
+                "Invalid cron expression '{}': expected 5 fields",
+                expr
+            ));
+        };
+
+        let field = |spec: &str, min: u32, max: u32, name: &str| {
+            parse_field(spec, min, max)
+                .map_err(|e| format!("Invalid cron expression '{}': {} {}", expr, name, e))
+        };
+
+        let mut days_of_week = field(dow, 0, 7, "day of week")?;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            minutes: field(minute, 0, 59, "minute")?,
+            hours: field(hour, 0, 23, "hour")?,
+            days_of_month: field(dom, 1, 31, "day of month")?,
+            months: field(month, 1, 12, "month")?,
+            days_of_week,
+            restricted_days: !dom.starts_with('*') && !dow.starts_with('*'),
+        })
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+        let dom = self.days_of_month & (1 << date.day()) != 0;
+        let dow = self.days_of_week & (1 << date.weekday().num_days_from_sunday()) != 0;
+        if self.restricted_days {
+            dom || dow
+        } else {
+            dom && dow
+        }
+    }
+
+    pub fn matches(&self, t: NaiveDateTime) -> bool {
+        self.matches_day(t.date())
+            && self.hours & (1 << t.hour()) != 0
+            && self.minutes & (1 << t.minute()) != 0
+    }
+
+    /// First matching minute strictly after `after`
+    pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let limit = after + Duration::days(MAX_LOOKAHEAD_DAYS);
+        let mut t =
+            after.date().and_hms_opt(after.hour(), after.minute(), 0)? + Duration::minutes(1);
+
+        while t <= limit {
+            if !self.matches_day(t.date()) {
+                t = t.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+            } else if self.hours & (1 << t.hour()) == 0 {
+                t = t.date().and_hms_opt(t.hour(), 0, 0)? + Duration::hours(1);
+            } else if self.minutes & (1 << t.minute()) == 0 {
+                t += Duration::minutes(1);
+            } else {
+                return Some(t);
+            }
+        }
+        None
+    }
+}
+
+/// Parse one cron field into a bitmask of allowed values
+fn parse_field(spec: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in spec.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("has an invalid step '{}'", step))?;
+                if step == 0 {
+                    return Err("has a zero step".to_string());
+                }
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+
+        let value = |s: &str| {
+            s.parse::<u32>()
+                .map_err(|_| format!("has an invalid value '{}'", s))
+        };
+        let (lo, hi) = if range == "*" {
+            (min, max)
+        } else if let Some((lo, hi)) = range.split_once('-') {
+            (value(lo)?, value(hi)?)
+        } else {
+            let v = value(range)?;
+            // `5/15` means "from 5, every 15"
+            (v, if step.is_some() { max } else { v })
+        };
+
+        if lo < min || hi > max || lo > hi {
+            return Err(format!("'{}' is outside {}-{}", part, min, max));
+        }
+        for v in (lo..=hi).step_by(step.unwrap_or(1) as usize) {
+            mask |= 1 << v;
+        }
+    }
+    Ok(mask)
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value, "%H:%M").map_err(|_| format!("Invalid time '{}'", value))
+}
+
+#[derive(Debug, Clone)]
+struct CompiledQuietPeriod {
+    /// Bit per weekday, Sunday = bit 0
+    days: u8,
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl CompiledQuietPeriod {
+    fn starts_on(&self, date: NaiveDate) -> bool {
+        self.days & (1 << date.weekday().num_days_from_sunday()) != 0
+    }
+
+    fn contains(&self, t: NaiveDateTime) -> bool {
+        let time = t.time();
+        if self.start == self.end {
+            self.starts_on(t.date())
+        } else if self.start < self.end {
+            self.starts_on(t.date()) && time >= self.start && time < self.end
+        } else {
+            (self.starts_on(t.date()) && time >= self.start)
+                || (t.date().pred_opt().is_some_and(|d| self.starts_on(d)) && time < self.end)
+        }
+    }
+}
+
+/// A validated [`SyncScheduleConfig`], ready to compute run times
+#[derive(Debug, Clone)]
+pub struct SyncTimetable {
+    entries: Vec<CronExpr>,
+    quiet_periods: Vec<CompiledQuietPeriod>,
+    digest_time: Option<NaiveTime>,
+}
+
+impl Default for SyncTimetable {
+    fn default() -> Self {
+        Self::compile(&SyncScheduleConfig::default()).expect("default schedule is valid")
+    }
+}
+
+impl SyncTimetable {
+    pub fn compile(config: &SyncScheduleConfig) -> Result<Self, String> {
+        let entries = config
+            .entries
+            .iter()
+            .map(|e| CronExpr::parse(e))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let quiet_periods = config
+            .quiet_periods
+            .iter()
+            .map(|p| {
+                let days = if p.days.is_empty() {
+                    0x7f
+                } else if let Some(day) = p.days.iter().find(|d| **d > 6) {
+                    return Err(format!("Invalid quiet period day {}", day));
+                } else {
+                    p.days.iter().fold(0u8, |mask, d| mask | (1 << d))
+                };
+                Ok(CompiledQuietPeriod {
+                    days,
+                    start: parse_time(&p.start)?,
+                    end: parse_time(&p.end)?,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let digest_time = config.digest_time.as_deref().map(parse_time).transpose()?;
+
+        Ok(Self {
+            entries,
+            quiet_periods,
+            digest_time,
+        })
+    }
+
+    pub fn is_quiet(&self, t: NaiveDateTime) -> bool {
+        self.quiet_periods.iter().any(|p| p.contains(t))
+    }
+
+    /// Next background sync strictly after `after`, skipping quiet periods
+    pub fn next_sync_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let mut t = entry.next_after(after)?;
+                for _ in 0..MAX_QUIET_SKIPS {
+                    if !self.is_quiet(t) {
+                        return Some(t);
+                    }
+                    t = entry.next_after(t)?;
+                }
+                None
+            })
+            .min()
+    }
+
+    /// Next end-of-day digest run strictly after `after`. Digests run even in quiet periods.
+    pub fn next_digest_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let time = self.digest_time?;
+        let today = after.date().and_time(time);
+        if today > after {
+            Some(today)
+        } else {
+            Some(after.date().succ_opt()?.and_time(time))
+        }
+    }
+
+    /// The next run of either kind; a digest wins a tie since it includes a sync
+    pub fn next_run_after(&self, after: NaiveDateTime) -> Option<(NaiveDateTime, ScheduledRun)> {
+        let sync = self.next_sync_after(after).map(|t| (t, ScheduledRun::Sync));
+        let digest = self
+            .next_digest_after(after)
+            .map(|t| (t, ScheduledRun::Digest));
+        match (sync, digest) {
+            (Some(s), Some(d)) => Some(if d.0 <= s.0 { d } else { s }),
+            (s, d) => s.or(d),
+        }
+    }
+
+    /// Whether a scheduled sync fell between the last sync and now
+    pub fn missed_sync(&self, last_sync: NaiveDateTime, now: NaiveDateTime) -> bool {
+        self.next_sync_after(last_sync).is_some_and(|t| t <= now)
+    }
+}
+
+/// Epoch milliseconds of a local wall-clock time. Times skipped by a DST jump
+/// resolve to the first valid time after the gap.
+pub fn local_timestamp_millis(t: NaiveDateTime) -> i64 {
+    (0..=2)
+        .find_map(|hours| {
+            chrono::Local
+                .from_local_datetime(&(t + Duration::hours(hours)))
+                .earliest()
+        })
+        .map(|dt| dt.timestamp_millis())
+        .unwrap_or_else(|| t.and_utc().timestamp_millis())
+}
+
+/// Local wall-clock time of an epoch-millisecond timestamp
+pub fn local_time_from_millis(ms: i64) -> Option<NaiveDateTime> {
+    chrono::Local
+        .timestamp_millis_opt(ms)
+        .single()
+        .map(|dt| dt.naive_local())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn working_hours() -> SyncTimetable {
+        SyncTimetable::compile(&SyncScheduleConfig {
+            entries: vec!["*/10 8-17 * * 1-5".into(), "0 * * * *".into()],
+            quiet_periods: vec![QuietPeriod {
+                days: vec![0, 6],
+                start: "00:00".into(),
+                end: "00:00".into(),
+            }],
+            digest_time: Some("18:30".into()),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cron_parse_fields() {
+        let expr = CronExpr::parse("*/15 9-17 * * 1-5").unwrap();
+        // 2024-01-15 is a Monday
+        assert!(expr.matches(at("2024-01-15", "09:45")));
+        assert!(!expr.matches(at("2024-01-15", "09:50")));
+        assert!(!expr.matches(at("2024-01-15", "18:00")));
+        assert!(!expr.matches(at("2024-01-14", "10:00")));
+
+        let sunday = CronExpr::parse("0 12 * * 7").unwrap();
+        assert!(sunday.matches(at("2024-01-14", "12:00")));
+
+        let list = CronExpr::parse("5,35 */6 1 1,7 *").unwrap();
+        assert!(list.matches(at("2024-07-01", "18:35")));
+        assert!(!list.matches(at("2024-07-02", "18:35")));
+    }
+
+    #[test]
+    fn test_cron_parse_rejects_invalid() {
+        assert!(CronExpr::parse("* * * *").is_err());
+        assert!(CronExpr::parse("60 * * * *").is_err());
+        assert!(CronExpr::parse("*/0 * * * *").is_err());
+        assert!(CronExpr::parse("5-1 * * * *").is_err());
+        assert!(CronExpr::parse("a * * * *").is_err());
+    }
+
+    #[test]
+    fn test_cron_restricted_days_match_either() {
+        // The 1st of the month or any Monday
+        let expr = CronExpr::parse("0 0 1 * 1").unwrap();
+        assert!(expr.matches(at("2024-02-01", "00:00")));
+        assert!(expr.matches(at("2024-02-05", "00:00")));
+        assert!(!expr.matches(at("2024-02-06", "00:00")));
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        let expr = CronExpr::parse("*/10 8-17 * * 1-5").unwrap();
+        assert_eq!(
+            expr.next_after(at("2024-01-15", "09:03")),
+            Some(at("2024-01-15", "09:10"))
+        );
+        assert_eq!(
+            expr.next_after(at("2024-01-15", "09:10")),
+            Some(at("2024-01-15", "09:20"))
+        );
+        // Friday evening rolls over to Monday morning
+        assert_eq!(
+            expr.next_after(at("2024-01-19", "17:55")),
+            Some(at("2024-01-22", "08:00"))
+        );
+        assert_eq!(
+            CronExpr::parse("0 0 31 2 *")
+                .unwrap()
+                .next_after(at("2024-01-01", "00:00")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_working_hours_schedule() {
+        let timetable = working_hours();
+        assert_eq!(
+            timetable.next_sync_after(at("2024-01-15", "10:05")),
+            Some(at("2024-01-15", "10:10"))
+        );
+        // Hourly outside working hours
+        assert_eq!(
+            timetable.next_sync_after(at("2024-01-15", "18:05")),
+            Some(at("2024-01-15", "19:00"))
+        );
+        // Never on weekends
+        assert_eq!(
+            timetable.next_sync_after(at("2024-01-19", "23:30")),
+            Some(at("2024-01-22", "00:00"))
+        );
+    }
+
+    #[test]
+    fn test_quiet_period_past_midnight() {
+        let timetable = SyncTimetable::compile(&SyncScheduleConfig {
+            entries: vec!["*/30 * * * *".into()],
+            quiet_periods: vec![QuietPeriod {
+                days: vec![],
+                start: "22:00".into(),
+                end: "07:00".into(),
+            }],
+            digest_time: None,
+        })
+        .unwrap();
+
+        assert!(timetable.is_quiet(at("2024-01-15", "23:00")));
+        assert!(timetable.is_quiet(at("2024-01-16", "06:59")));
+        assert!(!timetable.is_quiet(at("2024-01-16", "07:00")));
+        assert_eq!(
+            timetable.next_sync_after(at("2024-01-15", "21:45")),
+            Some(at("2024-01-16", "07:00"))
+        );
+    }
+
+    #[test]
+    fn test_digest_runs_in_quiet_periods() {
+        let timetable = working_hours();
+        // Saturday is quiet, but the digest still runs
+        assert_eq!(
+            timetable.next_digest_after(at("2024-01-20", "12:00")),
+            Some(at("2024-01-20", "18:30"))
+        );
+        assert_eq!(
+            timetable.next_run_after(at("2024-01-15", "18:25")),
+            Some((at("2024-01-15", "18:30"), ScheduledRun::Digest))
+        );
+        assert_eq!(
+            timetable.next_run_after(at("2024-01-15", "18:30")),
+            Some((at("2024-01-15", "19:00"), ScheduledRun::Sync))
+        );
+    }
+
+    #[test]
+    fn test_missed_sync() {
+        let timetable = working_hours();
+        assert!(timetable.missed_sync(at("2024-01-15", "09:00"), at("2024-01-15", "09:30")));
+        assert!(!timetable.missed_sync(at("2024-01-15", "09:00"), at("2024-01-15", "09:05")));
+        // Nothing was due over the weekend
+        assert!(!timetable.missed_sync(at("2024-01-20", "09:00"), at("2024-01-21", "20:00")));
+    }
+
+    #[test]
+    fn test_from_interval_minutes() {
+        assert_eq!(
+            SyncScheduleConfig::from_interval_minutes(15).entries,
+            vec!["*/15 * * * *"]
+        );
+        assert_eq!(
+            SyncScheduleConfig::from_interval_minutes(60).entries,
+            vec!["0 */1 * * *"]
+        );
+        assert_eq!(
+            SyncScheduleConfig::from_interval_minutes(0).entries,
+            vec!["*/1 * * * *"]
+        );
+        assert!(SyncTimetable::compile(&SyncScheduleConfig::from_interval_minutes(180)).is_ok());
+    }
+
+    #[test]
+    fn test_compile_rejects_invalid_config() {
+        let mut config = SyncScheduleConfig::default();
+        config.digest_time = Some("25:00".into());
+        assert!(SyncTimetable::compile(&config).is_err());
+
+        let mut config = SyncScheduleConfig::default();
+        config.quiet_periods.push(QuietPeriod {
+            days: vec![7],
+            start: "00:00".into(),
+            end: "00:00".into(),
+        });
+        assert!(SyncTimetable::compile(&config).is_err());
+    }
+
+    #[test]
+    fn test_schedule_config_serialization() {
+        let json = r#"{"entries":["0 * * * *"],"quietPeriods":[{"days":[0,6],"start":"00:00","end":"00:00"}],"digestTime":"18:00"}"#;
+        let config: SyncScheduleConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.quiet_periods[0].days, vec![0, 6]);
+        assert_eq!(config.digest_time.as_deref(), Some("18:00"));
+        assert_eq!(serde_json::to_string(&config).unwrap(), json);
+    }
+}
@@ -0,0 +1,426 @@
+//! Priority-aware scheduling of sync work
+//!
+//! Every sync path (user-initiated syncs, the background cycle, queued jobs,
+//! backfills and the notes watcher) acquires a [`SyncPermit`] for the resources
+//! it touches instead of `try_lock`ing one global mutex. Waiters are granted in
+//! priority order, jobs with disjoint claims run side by side, and long jobs call
+//! [`SyncPermit::checkpoint`] between steps so higher-priority work can run first.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::Notify;
+
+/// Resource claimed by every job that runs the AI batch pass, which rewrites
+/// shared topics and must never run twice at once
+pub const AI_RESOURCE: &str = "ai";
+
+/// Lowest to highest; higher-priority waiters are granted first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPriority {
+    Backfill,
+    Incremental,
+    UserInitiated,
+}
+
+/// Resources a job needs exclusive use of
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncClaim {
+    /// Every source and the AI pass
+    All,
+    /// Source ids and/or [`AI_RESOURCE`]
+    Resources(Vec<String>),
+}
+
+impl SyncClaim {
+    /// The given sources plus the AI pass that follows them; `None` claims everything
+    pub fn sources_with_ai(only: Option<&[String]>) -> Self {
+        match only {
+            None => Self::All,
+            Some(sources) => {
+                let mut resources = sources.to_vec();
+                resources.push(AI_RESOURCE.to_string());
+                Self::Resources(resources)
+            }
+        }
+    }
+
+    pub fn conflicts_with(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::All, _) | (_, Self::All) => true,
+            (Self::Resources(a), Self::Resources(b)) => a.iter().any(|r| b.contains(r)),
+        }
+    }
+}
+
+/// A running or waiting job, as shown to the UI
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledJob {
+    pub id: u64,
+    pub label: String,
+    pub priority: SyncPriority,
+    /// Claimed resources; `None` when the job claims everything
+    pub resources: Option<Vec<String>>,
+    /// Epoch ms when the job first asked to run
+    pub queued_at: i64,
+    /// Epoch ms when the job last started or resumed; `None` while waiting
+    pub started_at: Option<i64>,
+}
+
+/// Jobs holding permits and jobs waiting for one, in the order they will start
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncSchedule {
+    pub running: Vec<ScheduledJob>,
+    pub waiting: Vec<ScheduledJob>,
+}
+
+#[derive(Debug, Clone)]
+struct Slot {
+    /// Assigned in arrival order; breaks ties between equal priorities
+    id: u64,
+    label: String,
+    priority: SyncPriority,
+    claim: SyncClaim,
+    queued_at: i64,
+    started_at: Option<i64>,
+}
+
+impl Slot {
+    fn to_job(&self) -> ScheduledJob {
+        ScheduledJob {
+            id: self.id,
+            label: self.label.clone(),
+            priority: self.priority,
+            resources: match &self.claim {
+                SyncClaim::All => None,
+                SyncClaim::Resources(resources) => Some(resources.clone()),
+            },
+            queued_at: self.queued_at,
+            started_at: self.started_at,
+        }
+    }
+}
+
+#[derive(Default)]
+struct SchedulerState {
+    next_id: u64,
+    running: Vec<Slot>,
+    waiting: Vec<Slot>,
+}
+
+/// Start every waiter whose claim is free.
+///
+/// Waiters are considered by priority, then arrival. A waiter only starts when no
+/// running job and no blocked waiter ahead of it wants an overlapping claim, so
+/// lower-priority work never overtakes higher-priority work on the same resources.
+fn schedule(state: &mut SchedulerState, now: i64) {
+    let mut waiting = std::mem::take(&mut state.waiting);
+    waiting.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.id.cmp(&b.id)));
+
+    for mut slot in waiting {
+        let blocked = state
+            .running
+            .iter()
+            .chain(state.waiting.iter())
+            .any(|other| other.claim.conflicts_with(&slot.claim));
+
+        if blocked {
+            state.waiting.push(slot);
+        } else {
+            slot.started_at = Some(now);
+            state.running.push(slot);
+        }
+    }
+}
+
+pub struct SyncScheduler {
+    state: Mutex<SchedulerState>,
+    changed: Notify,
+    /// True while any job holds a permit
+    is_syncing: Arc<AtomicBool>,
+}
+
+impl Default for SyncScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SyncScheduler {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(SchedulerState::default()),
+            changed: Notify::new(),
+            is_syncing: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn is_syncing_flag(&self) -> Arc<AtomicBool> {
+        self.is_syncing.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SchedulerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Apply a change, start whatever can now run and wake waiters
+    fn update(&self, change: impl FnOnce(&mut SchedulerState)) {
+        {
+            let mut state = self.lock();
+            change(&mut state);
+            schedule(&mut state, chrono::Utc::now().timestamp_millis());
+            self.is_syncing
+                .store(!state.running.is_empty(), Ordering::SeqCst);
+        }
+        self.changed.notify_waiters();
+    }
+
+    fn enqueue(&self, label: String, priority: SyncPriority, claim: SyncClaim) -> u64 {
+        let mut id = 0;
+        self.update(|state| {
+            id = state.next_id;
+            state.next_id += 1;
+            state.waiting.push(Slot {
+                id,
+                label,
+                priority,
+                claim,
+                queued_at: chrono::Utc::now().timestamp_millis(),
+                started_at: None,
+            });
+        });
+        id
+    }
+
+    fn is_running(&self, id: u64) -> bool {
+        self.lock().running.iter().any(|slot| slot.id == id)
+    }
+
+    async fn wait_until_running(&self, id: u64) {
+        loop {
+            let notified = self.changed.notified();
+            tokio::pin!(notified);
+            // Register before checking so a grant in between is not missed
+            notified.as_mut().enable();
+            if self.is_running(id) {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Wait for a permit covering `claim`. Dropping the future before it
+    /// resolves withdraws the request.
+    pub async fn acquire(
+        self: &Arc<Self>,
+        label: impl Into<String>,
+        priority: SyncPriority,
+        claim: SyncClaim,
+    ) -> SyncPermit {
+        let label = label.into();
+        let id = self.enqueue(label.clone(), priority, claim);
+        let permit = SyncPermit {
+            scheduler: self.clone(),
+            id,
+        };
+
+        if !self.is_running(id) {
+            tracing::info!("{} waiting for other sync work to finish", label);
+            self.wait_until_running(id).await;
+        }
+        permit
+    }
+
+    /// A permit covering `claim` if one can start right away
+    pub fn try_acquire(
+        self: &Arc<Self>,
+        label: impl Into<String>,
+        priority: SyncPriority,
+        claim: SyncClaim,
+    ) -> Option<SyncPermit> {
+        let id = self.enqueue(label.into(), priority, claim);
+        let permit = SyncPermit {
+            scheduler: self.clone(),
+            id,
+        };
+        // Dropping the permit withdraws the request
+        self.is_running(id).then_some(permit)
+    }
+
+    pub fn snapshot(&self) -> SyncSchedule {
+        let state = self.lock();
+        SyncSchedule {
+            running: state.running.iter().map(Slot::to_job).collect(),
+            waiting: state.waiting.iter().map(Slot::to_job).collect(),
+        }
+    }
+}
+
+/// Permission to run sync work on a claim; released on drop
+pub struct SyncPermit {
+    scheduler: Arc<SyncScheduler>,
+    id: u64,
+}
+
+impl SyncPermit {
+    /// Whether higher-priority work is waiting on resources this job holds
+    pub fn should_yield(&self) -> bool {
+        let state = self.scheduler.lock();
+        let Some(own) = state.running.iter().find(|slot| slot.id == self.id) else {
+            return false;
+        };
+        state
+            .waiting
+            .iter()
+            .any(|slot| slot.priority > own.priority && slot.claim.conflicts_with(&own.claim))
+    }
+
+    /// Step aside for waiting higher-priority work, resuming once it is done.
+    /// Call only between self-contained steps whose progress has been saved.
+    pub async fn checkpoint(&mut self) {
+        if !self.should_yield() {
+            return;
+        }
+
+        let id = self.id;
+        self.scheduler.update(|state| {
+            if let Some(pos) = state.running.iter().position(|slot| slot.id == id) {
+                let mut slot = state.running.remove(pos);
+                tracing::info!("{} yielding to higher-priority sync work", slot.label);
+                slot.started_at = None;
+                state.waiting.push(slot);
+            }
+        });
+        self.scheduler.wait_until_running(id).await;
+    }
+}
+
+impl Drop for SyncPermit {
+    fn drop(&mut self) {
+        let id = self.id;
+        self.scheduler.update(|state| {
+            state.running.retain(|slot| slot.id != id);
+            state.waiting.retain(|slot| slot.id != id);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resources(ids: &[&str]) -> SyncClaim {
+        SyncClaim::Resources(ids.iter().map(|id| id.to_string()).collect())
+    }
+
+    fn labels(jobs: &[ScheduledJob]) -> Vec<&str> {
+        jobs.iter().map(|job| job.label.as_str()).collect()
+    }
+
+    #[test]
+    fn test_claim_conflicts() {
+        assert!(SyncClaim::All.conflicts_with(&resources(&["notes"])));
+        assert!(resources(&["slack", "ai"]).conflicts_with(&resources(&["jira", "ai"])));
+        assert!(!resources(&["slack", "ai"]).conflicts_with(&resources(&["notes"])));
+        assert_eq!(
+            SyncClaim::sources_with_ai(Some(&["slack".to_string()])),
+            resources(&["slack", "ai"])
+        );
+        assert_eq!(SyncClaim::sources_with_ai(None), SyncClaim::All);
+    }
+
+    #[test]
+    fn test_disjoint_claims_run_together() {
+        let scheduler = Arc::new(SyncScheduler::new());
+        let _slack = scheduler
+            .try_acquire("slack", SyncPriority::Incremental, resources(&["slack"]))
+            .unwrap();
+        let _notes = scheduler
+            .try_acquire("notes", SyncPriority::Incremental, resources(&["notes"]))
+            .unwrap();
+        assert!(scheduler
+            .try_acquire("all", SyncPriority::UserInitiated, SyncClaim::All)
+            .is_none());
+
+        assert_eq!(
+            labels(&scheduler.snapshot().running),
+            vec!["slack", "notes"]
+        );
+        // The failed attempt does not linger as a waiter
+        assert!(scheduler.snapshot().waiting.is_empty());
+        assert!(scheduler.is_syncing_flag().load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_waiters_start_by_priority() {
+        let mut state = SchedulerState::default();
+        let slot = |id, label: &str, priority, claim| Slot {
+            id,
+            label: label.to_string(),
+            priority,
+            claim,
+            queued_at: 0,
+            started_at: None,
+        };
+        state.running.push(slot(
+            0,
+            "backfill",
+            SyncPriority::Backfill,
+            resources(&["jira", "ai"]),
+        ));
+        state.waiting = vec![
+            slot(1, "background", SyncPriority::Incremental, SyncClaim::All),
+            slot(2, "notes", SyncPriority::Backfill, resources(&["notes"])),
+            slot(
+                3,
+                "user",
+                SyncPriority::UserInitiated,
+                resources(&["slack", "ai"]),
+            ),
+            slot(
+                4,
+                "slack only",
+                SyncPriority::Backfill,
+                resources(&["slack"]),
+            ),
+        ];
+
+        schedule(&mut state, 1);
+
+        // Notes is free; slack-only must not overtake the blocked user sync on slack
+        let running: Vec<&str> = state.running.iter().map(|s| s.label.as_str()).collect();
+        let waiting: Vec<&str> = state.waiting.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(running, vec!["backfill", "notes"]);
+        assert_eq!(waiting, vec!["user", "background", "slack only"]);
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_yields_to_higher_priority() {
+        let scheduler = Arc::new(SyncScheduler::new());
+        let mut backfill = scheduler
+            .acquire("backfill", SyncPriority::Backfill, SyncClaim::All)
+            .await;
+        assert!(!backfill.should_yield());
+
+        let user_scheduler = scheduler.clone();
+        let user = tokio::spawn(async move {
+            let _permit = user_scheduler
+                .acquire("user", SyncPriority::UserInitiated, SyncClaim::All)
+                .await;
+            labels(&user_scheduler.snapshot().running).join(",")
+        });
+        while scheduler.snapshot().waiting.is_empty() {
+            tokio::task::yield_now().await;
+        }
+        assert!(backfill.should_yield());
+
+        backfill.checkpoint().await;
+
+        assert_eq!(user.await.unwrap(), "user");
+        assert_eq!(labels(&scheduler.snapshot().running), vec!["backfill"]);
+        drop(backfill);
+        assert!(!scheduler.is_syncing_flag().load(Ordering::SeqCst));
+    }
+}
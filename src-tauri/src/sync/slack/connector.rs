@@ -0,0 +1,338 @@
+//! Slack source connector
+
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+
+use super::client::SlackClient;
+use super::sync::SlackSyncService;
+use super::types::SlackTokens;
+use crate::pipeline::PipelineTaskType;
+use crate::sync::background::{sync_slack_historical_day, sync_slack_now};
+use crate::sync::connector::{
+    clear_sync_state, last_sync_at, load_credentials, store_credentials, ConnectorContext,
+    ConnectorError, SourceConnector, SourceHealth, SourceScope, SyncRange,
+};
+use crate::sync::estimate::{DryRun, SourceEstimate};
+use crate::sync::SyncResult;
+
+/// Load the stored Slack tokens, if Slack is connected
+pub(crate) async fn load_slack_tokens(
+    ctx: &ConnectorContext,
+) -> Result<Option<SlackTokens>, ConnectorError> {
+    load_credentials(ctx, "slack").await
+}
+
+fn client_for(tokens: &SlackTokens) -> SlackClient {
+    SlackClient::new(String::new(), String::new())
+        .with_token(tokens.access_token.clone())
+        .with_team_id(tokens.team_id.clone())
+}
+
+pub struct SlackConnector;
+
+impl SlackConnector {
+    /// Verify a user token, detect its scopes and store it
+    pub async fn connect_token(
+        &self,
+        ctx: &ConnectorContext,
+        token: String,
+    ) -> Result<SlackTokens, ConnectorError> {
+        let client = SlackClient::new(String::new(), String::new()).with_token(token.clone());
+
+        let auth_info = client
+            .test_auth()
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+        let mut detected_scopes = Vec::new();
+
+        let scope_tests = [
+            ("public_channel", "channels:read"),
+            ("private_channel", "groups:read"),
+            ("im", "im:read"),
+            ("mpim", "mpim:read"),
+        ];
+
+        for (channel_type, scope_name) in scope_tests {
+            let resp = client
+                .http_client()
+                .get("https://slack.com/api/conversations.list")
+                .bearer_auth(&token)
+                .query(&[("types", channel_type), ("limit", "1")])
+                .send()
+                .await;
+            if let Ok(resp) = resp {
+                if let Ok(json) = resp.json::<serde_json::Value>().await {
+                    if json["ok"].as_bool().unwrap_or(false) {
+                        detected_scopes.push(scope_name);
+                    }
+                }
+            }
+        }
+
+        let resp = client
+            .http_client()
+            .get("https://slack.com/api/users.list")
+            .bearer_auth(&token)
+            .query(&[("limit", "1")])
+            .send()
+            .await;
+        if let Ok(resp) = resp {
+            if let Ok(json) = resp.json::<serde_json::Value>().await {
+                if json["ok"].as_bool().unwrap_or(false) {
+                    detected_scopes.push("users:read");
+                }
+            }
+        }
+
+        let tokens = SlackTokens {
+            access_token: token,
+            token_type: "bearer".to_string(),
+            scope: detected_scopes.join(","),
+            team_id: auth_info.team_id,
+            team_name: auth_info.team_name,
+            team_domain: auth_info.team_domain,
+            user_id: auth_info.user_id,
+        };
+
+        store_credentials(ctx, "slack", &tokens).await?;
+
+        tracing::info!("Slack connected for team: {}", tokens.team_name);
+        Ok(tokens)
+    }
+}
+
+#[async_trait]
+impl SourceConnector for SlackConnector {
+    fn id(&self) -> &'static str {
+        "slack"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Slack"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sync messages and channels from your Slack workspace"
+    }
+
+    fn task_type(&self) -> PipelineTaskType {
+        PipelineTaskType::SyncSlack
+    }
+
+    fn item_label(&self) -> &'static str {
+        "messages"
+    }
+
+    /// Expects `{ "token": "xoxp-..." }`
+    async fn connect(
+        &self,
+        ctx: &ConnectorContext,
+        config: serde_json::Value,
+    ) -> Result<serde_json::Value, ConnectorError> {
+        let token = config["token"]
+            .as_str()
+            .filter(|t| !t.trim().is_empty())
+            .ok_or_else(|| ConnectorError::InvalidConfig("token is required".into()))?;
+
+        let tokens = self.connect_token(ctx, token.trim().to_string()).await?;
+        serde_json::to_value(tokens).map_err(|e| ConnectorError::Failed(e.to_string()))
+    }
+
+    async fn is_connected(&self, ctx: &ConnectorContext) -> bool {
+        matches!(load_slack_tokens(ctx).await, Ok(Some(_)))
+    }
+
+    fn supports_scope_intervals(&self) -> bool {
+        true
+    }
+
+    async fn list_scopes(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<Vec<SourceScope>, ConnectorError> {
+        let tokens = load_slack_tokens(ctx)
+            .await?
+            .ok_or(ConnectorError::NotConnected("Slack"))?;
+
+        let selected: HashSet<String> = sqlx::query_as::<_, (String,)>(
+            "SELECT channel_id FROM slack_selected_channels WHERE enabled = 1",
+        )
+        .fetch_all(ctx.db.pool())
+        .await?
+        .into_iter()
+        .map(|r| r.0)
+        .collect();
+
+        let channels = client_for(&tokens)
+            .list_channels()
+            .await
+            .map_err(|e| ConnectorError::Failed(e.to_string()))?;
+
+        Ok(channels
+            .into_iter()
+            .map(|c| SourceScope {
+                selected: selected.contains(&c.id),
+                id: c.id,
+                name: c.name,
+                kind: "channel".to_string(),
+            })
+            .collect())
+    }
+
+    async fn sync_incremental(&self, ctx: &ConnectorContext) -> Result<SyncResult, ConnectorError> {
+        if !self.is_connected(ctx).await {
+            return Err(ConnectorError::NotConnected("Slack"));
+        }
+
+        let result = sync_slack_now(
+            ctx.db.clone(),
+            ctx.crypto.clone(),
+            &ctx.cancel,
+            &ctx.progress,
+            ctx.due_only,
+        )
+        .await;
+        ctx.check_cancelled()?;
+        let items_synced = result?;
+        Ok(SyncResult {
+            source: self.id().to_string(),
+            items_synced,
+            errors: vec![],
+        })
+    }
+
+    async fn sync_range(
+        &self,
+        ctx: &ConnectorContext,
+        range: &SyncRange,
+    ) -> Result<SyncResult, ConnectorError> {
+        if !self.is_connected(ctx).await {
+            return Err(ConnectorError::NotConnected("Slack"));
+        }
+
+        let mut total = SyncResult {
+            source: self.id().to_string(),
+            items_synced: 0,
+            errors: vec![],
+        };
+        let dates = range.dates();
+        for (index, date) in dates.iter().enumerate() {
+            ctx.progress
+                .report(
+                    index as f32 / dates.len() as f32,
+                    format!("Syncing Slack messages for {}...", date),
+                )
+                .await;
+            let result = sync_slack_historical_day(
+                ctx.db.clone(),
+                ctx.crypto.clone(),
+                date,
+                range.timezone_offset,
+                &ctx.cancel,
+            )
+            .await;
+            ctx.check_cancelled()?;
+            let result = result?;
+            total.items_synced += result.items_synced;
+            total.errors.extend(result.errors);
+        }
+
+        Ok(total)
+    }
+
+    /// Walks the history of the enabled channels, or of the channels given in
+    /// the dry run whether or not they are enabled
+    async fn estimate(
+        &self,
+        ctx: &ConnectorContext,
+        dry_run: &DryRun,
+    ) -> Result<Option<SourceEstimate>, ConnectorError> {
+        let tokens = load_slack_tokens(ctx)
+            .await?
+            .ok_or(ConnectorError::NotConnected("Slack"))?;
+        let client = client_for(&tokens);
+
+        let channels: Vec<(String, String)> = match dry_run.scopes_for(self.id()) {
+            Some(ids) => {
+                let names: HashMap<String, String> = client
+                    .list_channels()
+                    .await
+                    .map_err(|e| ConnectorError::Failed(e.to_string()))?
+                    .into_iter()
+                    .map(|c| (c.id, c.name))
+                    .collect();
+                ids.iter()
+                    .map(|id| (id.clone(), names.get(id).unwrap_or(id).clone()))
+                    .collect()
+            }
+            None => sqlx::query_as(
+                "SELECT channel_id, channel_name FROM slack_selected_channels WHERE enabled = 1",
+            )
+            .fetch_all(ctx.db.pool())
+            .await?,
+        };
+
+        let estimate = SlackSyncService::new(client, ctx.db.clone(), ctx.crypto.clone())
+            .with_cancellation(ctx.cancel.clone())
+            .with_progress(ctx.progress.clone())
+            .estimate_channels(&channels, dry_run.range.as_ref(), dry_run.timezone_offset)
+            .await;
+        ctx.check_cancelled()?;
+        estimate
+            .map(Some)
+            .map_err(|e| ConnectorError::Failed(e.to_string()))
+    }
+
+    async fn health(&self, ctx: &ConnectorContext) -> SourceHealth {
+        let last_sync_at = last_sync_at(&ctx.db, self.id()).await;
+        let tokens = match load_slack_tokens(ctx).await {
+            Ok(Some(tokens)) => tokens,
+            Ok(None) => {
+                return SourceHealth {
+                    source: self.id().to_string(),
+                    connected: false,
+                    healthy: false,
+                    message: None,
+                    last_sync_at,
+                }
+            }
+            Err(e) => {
+                return SourceHealth {
+                    source: self.id().to_string(),
+                    connected: true,
+                    healthy: false,
+                    message: Some(e.to_string()),
+                    last_sync_at,
+                }
+            }
+        };
+
+        let (healthy, message) = match client_for(&tokens).test_auth().await {
+            Ok(info) => (true, Some(format!("Connected to {}", info.team_name))),
+            Err(e) => (false, Some(e.to_string())),
+        };
+
+        SourceHealth {
+            source: self.id().to_string(),
+            connected: true,
+            healthy,
+            message,
+            last_sync_at,
+        }
+    }
+
+    async fn disconnect(&self, ctx: &ConnectorContext) -> Result<(), ConnectorError> {
+        sqlx::query("DELETE FROM credentials WHERE id = 'slack'")
+            .execute(ctx.db.pool())
+            .await?;
+
+        sqlx::query("DELETE FROM slack_selected_channels")
+            .execute(ctx.db.pool())
+            .await?;
+
+        clear_sync_state(&ctx.db, self.id()).await?;
+
+        Ok(())
+    }
+}
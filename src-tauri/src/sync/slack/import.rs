@@ -0,0 +1,468 @@
+//! Slack workspace export import
+//!
+//! Reads the ZIP produced by Slack's "Export data" admin feature and writes its
+//! users and messages through the same storage path as the live sync, so
+//! imported history is indistinguishable from synced history.
+
+use super::sync::{store_message, store_users};
+use super::types::{SlackChannel, SlackError, SlackMessage, SlackUser};
+use crate::crypto::CryptoService;
+use crate::db::Database;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::io::{Read, Seek};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Team id recorded for users when the export does not carry one
+const EXPORT_TEAM_ID: &str = "export";
+
+/// Channel metadata files written at the root of a Slack export
+const CHANNEL_FILES: [&str; 4] = ["channels.json", "groups.json", "dms.json", "mpims.json"];
+
+/// Parsed contents of a Slack export archive
+#[derive(Debug, Default)]
+pub struct SlackExport {
+    pub team_id: Option<String>,
+    pub users: Vec<SlackUser>,
+    pub channels: Vec<SlackExportChannel>,
+}
+
+/// A single conversation folder from the export with its messages grouped by day
+#[derive(Debug, Clone)]
+pub struct SlackExportChannel {
+    pub channel: SlackChannel,
+    /// (`YYYY-MM-DD`, messages) pairs in date order
+    pub days: Vec<(String, Vec<SlackMessage>)>,
+}
+
+/// Summary of a completed export import
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlackImportResult {
+    pub channels_imported: i32,
+    pub messages_imported: i32,
+    pub users_imported: i32,
+    /// Distinct days that received messages, sorted ascending
+    pub days: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+fn import_error(e: impl std::fmt::Display) -> SlackError {
+    SlackError::Import(e.to_string())
+}
+
+/// Split an archive path into (folder, file name), ignoring any single
+/// top-level directory the export was wrapped in.
+fn split_entry_path(path: &str) -> Option<(Option<&str>, &str)> {
+    let parts: Vec<&str> = path.split('/').filter(|p| !p.is_empty()).collect();
+    match parts.as_slice() {
+        [file] => Some((None, file)),
+        [folder, file] => Some((Some(folder), file)),
+        [_, folder, file] => Some((Some(folder), file)),
+        _ => None,
+    }
+}
+
+/// Returns the date for day files named `YYYY-MM-DD.json`
+fn day_from_file_name(file: &str) -> Option<&str> {
+    let date = file.strip_suffix(".json")?;
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    Some(date)
+}
+
+fn parse_user(user: &serde_json::Value) -> Option<SlackUser> {
+    // Skip bots and deleted users, matching users.list handling
+    if user["is_bot"].as_bool().unwrap_or(false) || user["deleted"].as_bool().unwrap_or(false) {
+        return None;
+    }
+
+    let id = user["id"].as_str().filter(|s| !s.is_empty())?;
+    Some(SlackUser {
+        id: id.to_string(),
+        name: user["name"].as_str().unwrap_or_default().to_string(),
+        real_name: user["real_name"]
+            .as_str()
+            .or_else(|| user["profile"]["real_name"].as_str())
+            .filter(|s| !s.is_empty())
+            .map(String::from),
+        display_name: user["profile"]["display_name"]
+            .as_str()
+            .filter(|s| !s.is_empty())
+            .map(String::from),
+    })
+}
+
+fn parse_channel(file: &str, value: &serde_json::Value) -> Option<(String, SlackChannel)> {
+    let id = value["id"].as_str().filter(|s| !s.is_empty())?.to_string();
+    let is_im = file == "dms.json";
+    let is_mpim = file == "mpims.json";
+    let is_private = file == "groups.json" || value["is_private"].as_bool().unwrap_or(false);
+
+    // DMs are exported into folders named by conversation id
+    let folder = if is_im {
+        id.clone()
+    } else {
+        value["name"].as_str().unwrap_or(&id).to_string()
+    };
+
+    let user = if is_im {
+        value["members"]
+            .as_array()
+            .and_then(|members| members.first())
+            .and_then(|m| m.as_str())
+            .map(String::from)
+    } else {
+        None
+    };
+
+    let channel = SlackChannel {
+        id,
+        name: folder.clone(),
+        is_private: is_private || is_im || is_mpim,
+        is_im,
+        is_mpim,
+        user,
+        member_count: value["members"].as_array().map(|m| m.len() as i32),
+        purpose: value["purpose"]["value"]
+            .as_str()
+            .filter(|s| !s.is_empty())
+            .map(String::from),
+        topic: value["topic"]["value"]
+            .as_str()
+            .filter(|s| !s.is_empty())
+            .map(String::from),
+    };
+
+    Some((folder, channel))
+}
+
+fn parse_message(value: &serde_json::Value) -> Option<SlackMessage> {
+    let ts = value["ts"].as_str().filter(|s| !s.is_empty())?;
+    if value["type"].as_str().is_some_and(|t| t != "message") {
+        return None;
+    }
+    // Join/leave notices carry no content worth summarizing
+    if matches!(
+        value["subtype"].as_str(),
+        Some("channel_join" | "channel_leave" | "group_join" | "group_leave")
+    ) {
+        return None;
+    }
+
+    Some(SlackMessage {
+        ts: ts.to_string(),
+        user: value["user"].as_str().map(String::from),
+        text: value["text"].as_str().unwrap_or_default().to_string(),
+        thread_ts: value["thread_ts"].as_str().map(String::from),
+        reply_count: value["reply_count"].as_i64().map(|n| n as i32),
+    })
+}
+
+/// Parse a Slack export archive from any seekable reader.
+pub fn parse_slack_export<R: Read + Seek>(reader: R) -> Result<SlackExport, SlackError> {
+    let mut archive = zip::ZipArchive::new(reader).map_err(import_error)?;
+
+    let mut export = SlackExport::default();
+    let mut channels_by_folder: HashMap<String, SlackChannel> = HashMap::new();
+    let mut days_by_folder: HashMap<String, Vec<(String, Vec<SlackMessage>)>> = HashMap::new();
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(import_error)?;
+        if !entry.is_file() {
+            continue;
+        }
+
+        let name = entry.name().to_string();
+        let Some((folder, file)) = split_entry_path(&name) else {
+            continue;
+        };
+
+        let is_users = file == "users.json";
+        let is_channels = CHANNEL_FILES.contains(&file);
+        let day = folder.and_then(|f| day_from_file_name(file).map(|d| (f, d)));
+
+        // Metadata lives at the export root, possibly inside one wrapper folder
+        let at_root = folder.is_none() || name.split('/').filter(|p| !p.is_empty()).count() == 2;
+        if !(day.is_some() || (at_root && (is_users || is_channels))) {
+            continue;
+        }
+
+        let mut contents = String::new();
+        entry.read_to_string(&mut contents)?;
+        let json: serde_json::Value = serde_json::from_str(&contents)
+            .map_err(|e| SlackError::Import(format!("{}: {}", name, e)))?;
+        let items = json.as_array().cloned().unwrap_or_default();
+
+        if let Some((folder, date)) = day {
+            let messages: Vec<SlackMessage> = items.iter().filter_map(parse_message).collect();
+            days_by_folder
+                .entry(folder.to_string())
+                .or_default()
+                .push((date.to_string(), messages));
+        } else if is_users {
+            if export.team_id.is_none() {
+                export.team_id = items
+                    .iter()
+                    .find_map(|u| u["team_id"].as_str())
+                    .map(String::from);
+            }
+            export.users.extend(items.iter().filter_map(parse_user));
+        } else {
+            for (folder, channel) in items.iter().filter_map(|c| parse_channel(file, c)) {
+                channels_by_folder.insert(folder, channel);
+            }
+        }
+    }
+
+    let mut folders: Vec<String> = days_by_folder.keys().cloned().collect();
+    folders.sort();
+
+    for folder in folders {
+        let mut days = days_by_folder.remove(&folder).unwrap_or_default();
+        days.sort_by(|a, b| a.0.cmp(&b.0));
+
+        // Folders without metadata still import, keyed by their folder name
+        let channel = channels_by_folder
+            .remove(&folder)
+            .unwrap_or_else(|| SlackChannel {
+                id: folder.clone(),
+                name: folder.clone(),
+                is_private: false,
+                is_im: false,
+                is_mpim: false,
+                user: None,
+                member_count: None,
+                purpose: None,
+                topic: None,
+            });
+
+        export.channels.push(SlackExportChannel { channel, days });
+    }
+
+    Ok(export)
+}
+
+/// Imports Slack export archives into local storage
+pub struct SlackExportImporter {
+    db: Arc<Database>,
+    crypto: Arc<CryptoService>,
+    team_domain: Option<String>,
+}
+
+impl SlackExportImporter {
+    pub fn new(db: Arc<Database>, crypto: Arc<CryptoService>) -> Self {
+        Self {
+            db,
+            crypto,
+            team_domain: None,
+        }
+    }
+
+    pub fn with_team_domain(mut self, domain: Option<String>) -> Self {
+        self.team_domain = domain;
+        self
+    }
+
+    /// Import an export ZIP from disk. When `channels` is given, only conversations
+    /// whose id or name appears in it are imported.
+    pub async fn import_file(
+        &self,
+        path: impl AsRef<Path>,
+        channels: Option<&[String]>,
+    ) -> Result<SlackImportResult, SlackError> {
+        let path: PathBuf = path.as_ref().to_path_buf();
+        let export = tokio::task::spawn_blocking(move || {
+            let file = std::fs::File::open(&path)?;
+            parse_slack_export(std::io::BufReader::new(file))
+        })
+        .await
+        .map_err(import_error)??;
+
+        self.import(export, channels).await
+    }
+
+    /// Store an already parsed export
+    pub async fn import(
+        &self,
+        export: SlackExport,
+        channels: Option<&[String]>,
+    ) -> Result<SlackImportResult, SlackError> {
+        let mut result = SlackImportResult::default();
+        let team_id = export.team_id.as_deref().unwrap_or(EXPORT_TEAM_ID);
+
+        if !export.users.is_empty() {
+            store_users(&self.db, &export.users, team_id).await?;
+            result.users_imported = export.users.len() as i32;
+        }
+
+        let mut days = BTreeSet::new();
+
+        for export_channel in &export.channels {
+            let channel = &export_channel.channel;
+            if let Some(filter) = channels {
+                if !filter
+                    .iter()
+                    .any(|c| c == &channel.id || c == &channel.name)
+                {
+                    continue;
+                }
+            }
+
+            let mut channel_messages = 0;
+            for (date, messages) in &export_channel.days {
+                for msg in messages {
+                    match store_message(
+                        &self.db,
+                        &self.crypto,
+                        self.team_domain.as_deref(),
+                        channel,
+                        msg,
+                    )
+                    .await
+                    {
+                        Ok(()) => channel_messages += 1,
+                        Err(e) => result
+                            .errors
+                            .push(format!("{} {} {}: {}", channel.name, date, msg.ts, e)),
+                    }
+                }
+                if !messages.is_empty() {
+                    days.insert(date.clone());
+                }
+            }
+
+            tracing::info!(
+                "Imported {} messages from Slack export channel {}",
+                channel_messages,
+                channel.name
+            );
+            result.channels_imported += 1;
+            result.messages_imported += channel_messages;
+        }
+
+        result.days = days.into_iter().collect();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+
+    fn build_zip(files: &[(&str, &str)]) -> Cursor<Vec<u8>> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in files {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        let mut cursor = writer.finish().unwrap();
+        cursor.set_position(0);
+        cursor
+    }
+
+    #[test]
+    fn test_day_from_file_name() {
+        assert_eq!(day_from_file_name("2024-03-15.json"), Some("2024-03-15"));
+        assert_eq!(day_from_file_name("users.json"), None);
+        assert_eq!(day_from_file_name("2024-13-01.json"), None);
+    }
+
+    #[test]
+    fn test_split_entry_path() {
+        assert_eq!(split_entry_path("users.json"), Some((None, "users.json")));
+        assert_eq!(
+            split_entry_path("general/2024-03-15.json"),
+            Some((Some("general"), "2024-03-15.json"))
+        );
+        assert_eq!(
+            split_entry_path("Acme Export/general/2024-03-15.json"),
+            Some((Some("general"), "2024-03-15.json"))
+        );
+        assert_eq!(split_entry_path("a/b/c/d.json"), None);
+    }
+
+    #[test]
+    fn test_parse_slack_export() {
+        let zip = build_zip(&[
+            (
+                "users.json",
+                r#"[
+                    {"id": "U1", "team_id": "T1", "name": "alice", "real_name": "Alice", "profile": {"display_name": "ali"}},
+                    {"id": "B1", "name": "bot", "is_bot": true},
+                    {"id": "U2", "name": "gone", "deleted": true}
+                ]"#,
+            ),
+            (
+                "channels.json",
+                r#"[{"id": "C1", "name": "general", "members": ["U1"], "purpose": {"value": "Chat"}}]"#,
+            ),
+            ("dms.json", r#"[{"id": "D1", "members": ["U1", "U3"]}]"#),
+            (
+                "general/2024-03-16.json",
+                r#"[
+                    {"type": "message", "ts": "1710590400.000200", "user": "U1", "text": "reply", "thread_ts": "1710504000.000100"}
+                ]"#,
+            ),
+            (
+                "general/2024-03-15.json",
+                r#"[
+                    {"type": "message", "ts": "1710504000.000100", "user": "U1", "text": "hello", "thread_ts": "1710504000.000100", "reply_count": 1},
+                    {"type": "message", "subtype": "channel_join", "ts": "1710504001.000100", "user": "U1", "text": "joined"}
+                ]"#,
+            ),
+            (
+                "D1/2024-03-15.json",
+                r#"[{"type": "message", "ts": "1710504100.000100", "user": "U3", "text": "hi"}]"#,
+            ),
+        ]);
+
+        let export = parse_slack_export(zip).unwrap();
+
+        assert_eq!(export.team_id.as_deref(), Some("T1"));
+        assert_eq!(export.users.len(), 1);
+        assert_eq!(export.users[0].display_name.as_deref(), Some("ali"));
+
+        assert_eq!(export.channels.len(), 2);
+        let dm = &export.channels[0];
+        assert_eq!(dm.channel.id, "D1");
+        assert!(dm.channel.is_im);
+        assert_eq!(dm.channel.user.as_deref(), Some("U1"));
+
+        let general = &export.channels[1];
+        assert_eq!(general.channel.id, "C1");
+        assert_eq!(general.channel.purpose.as_deref(), Some("Chat"));
+        assert_eq!(general.days.len(), 2);
+        assert_eq!(general.days[0].0, "2024-03-15");
+        assert_eq!(general.days[0].1.len(), 1);
+        assert_eq!(general.days[0].1[0].reply_count, Some(1));
+        assert_eq!(
+            general.days[1].1[0].thread_ts.as_deref(),
+            Some("1710504000.000100")
+        );
+    }
+
+    #[test]
+    fn test_parse_slack_export_wrapped_folder_without_metadata() {
+        let zip = build_zip(&[(
+            "Acme Slack export/random/2024-01-02.json",
+            r#"[{"ts": "1704200000.000100", "text": "no metadata"}]"#,
+        )]);
+
+        let export = parse_slack_export(zip).unwrap();
+
+        assert_eq!(export.channels.len(), 1);
+        assert_eq!(export.channels[0].channel.id, "random");
+        assert_eq!(export.channels[0].days[0].1[0].text, "no metadata");
+    }
+
+    #[test]
+    fn test_parse_slack_export_rejects_non_zip() {
+        let result = parse_slack_export(Cursor::new(b"not a zip".to_vec()));
+        assert!(matches!(result, Err(SlackError::Import(_))));
+    }
+}
@@ -4,10 +4,14 @@
 //! for Slack workspaces.
 
 mod client;
+mod connector;
+mod import;
 mod sync;
 mod types;
 
 pub use client::SlackClient;
+pub use connector::SlackConnector;
+pub use import::{parse_slack_export, SlackExport, SlackExportImporter, SlackImportResult};
 pub use sync::SlackSyncService;
 pub use types::{
     SlackAuthInfo, SlackChannel, SlackChannelSelection, SlackConnectionStatus, SlackError,
@@ -6,6 +6,14 @@ use super::types::{
 };
 use crate::crypto::CryptoService;
 use crate::db::Database;
+use crate::pipeline::{CancellationToken, ProgressReporter};
+use crate::sync::connectivity::is_unreachable;
+use crate::sync::connector::{save_cursor, SyncRange};
+use crate::sync::estimate::{ceil_div, local_date, text_tokens, SourceEstimate};
+use crate::sync::intervals::is_scope_due;
+use crate::sync::status::{mark_failed, mark_pending, mark_syncing};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Semaphore;
@@ -30,6 +38,8 @@ const MAX_RETRIES: u32 = 3;
 const RETRY_BASE_DELAY_MS: u64 = 2000;
 const HISTORY_PAGE_SIZE: usize = 100;
 const THREAD_REPLIES_PAGE_SIZE: usize = 200;
+/// Largest history page Slack serves, so dry runs need as few requests as possible
+const DRY_RUN_PAGE_SIZE: usize = 1000;
 
 fn get_today_start_ts() -> String {
     let now = chrono::Utc::now();
@@ -38,12 +48,42 @@ fn get_today_start_ts() -> String {
     format!("{}.000000", ts)
 }
 
+/// Channels finished so far in a sync, shared by the concurrent channel tasks
+struct ChannelProgress {
+    total: usize,
+    done: AtomicUsize,
+}
+
+impl ChannelProgress {
+    fn new(total: usize) -> Self {
+        Self {
+            total,
+            done: AtomicUsize::new(0),
+        }
+    }
+
+    fn fraction(&self) -> f32 {
+        self.done.load(Ordering::SeqCst) as f32 / self.total.max(1) as f32
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "{}/{} channels",
+            self.done.load(Ordering::SeqCst),
+            self.total
+        )
+    }
+}
+
 #[derive(Clone)]
 pub struct SlackSyncService {
     client: SlackClient,
     db: Arc<Database>,
     crypto: Arc<CryptoService>,
     team_domain: Option<String>,
+    cancel: CancellationToken,
+    progress: ProgressReporter,
+    due_only: bool,
 }
 
 impl SlackSyncService {
@@ -53,16 +93,44 @@ impl SlackSyncService {
             db,
             crypto,
             team_domain: None,
+            cancel: CancellationToken::new(),
+            progress: ProgressReporter::default(),
+            due_only: false,
         }
     }
 
+    /// Skip channels whose sync interval has not elapsed yet
+    pub fn with_due_only(mut self, due_only: bool) -> Self {
+        self.due_only = due_only;
+        self
+    }
+
     pub fn with_team_domain(mut self, domain: Option<String>) -> Self {
         self.team_domain = domain;
         self
     }
 
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Report per-channel and per-page progress, and rate-limit waits
+    pub fn with_progress(mut self, progress: ProgressReporter) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    fn check_cancelled(&self) -> Result<(), SlackError> {
+        if self.cancel.is_cancelled() {
+            Err(SlackError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
     async fn get_sync_cursor(&self, channel_id: &str) -> Result<Option<String>, SlackError> {
-        let row: Option<(String,)> = sqlx::query_as(
+        let row: Option<(Option<String>,)> = sqlx::query_as(
             "SELECT cursor FROM sync_state 
              WHERE source = 'slack' AND resource_type = 'channel' AND resource_id = ?",
         )
@@ -70,7 +138,7 @@ impl SlackSyncService {
         .fetch_optional(self.db.pool())
         .await?;
 
-        Ok(row.map(|r| r.0))
+        Ok(row.and_then(|r| r.0))
     }
 
     async fn should_refresh_user_cache(&self) -> Result<bool, SlackError> {
@@ -86,29 +154,7 @@ impl SlackSyncService {
     }
 
     async fn store_users(&self, users: &[SlackUser], team_id: &str) -> Result<(), SlackError> {
-        let now = chrono::Utc::now().timestamp_millis();
-
-        for user in users {
-            sqlx::query(
-                "INSERT INTO slack_users (user_id, team_id, username, real_name, display_name, updated_at)
-                 VALUES (?, ?, ?, ?, ?, ?)
-                 ON CONFLICT(user_id) DO UPDATE SET 
-                    username = excluded.username,
-                    real_name = excluded.real_name,
-                    display_name = excluded.display_name,
-                    updated_at = excluded.updated_at"
-            )
-            .bind(&user.id)
-            .bind(team_id)
-            .bind(&user.name)
-            .bind(&user.real_name)
-            .bind(&user.display_name)
-            .bind(now)
-            .execute(self.db.pool())
-            .await?;
-        }
-
-        Ok(())
+        store_users(&self.db, users, team_id).await
     }
 
     async fn get_team_id(&self) -> Result<Option<String>, SlackError> {
@@ -161,6 +207,26 @@ impl SlackSyncService {
             });
         }
 
+        let selected_channels = if self.due_only {
+            let mut due = Vec::new();
+            for channel in selected_channels {
+                if is_scope_due(&self.db, "slack", "channel", &channel.channel_id).await {
+                    due.push(channel);
+                }
+            }
+            if due.is_empty() {
+                tracing::debug!("No Slack channels due for sync");
+                return Ok(SyncResult {
+                    source: "slack".to_string(),
+                    items_synced: 0,
+                    errors: vec![],
+                });
+            }
+            due
+        } else {
+            selected_channels
+        };
+
         if self.should_refresh_user_cache().await? {
             if let Some(team_id) = self.get_team_id().await? {
                 tracing::info!("Refreshing Slack user cache");
@@ -180,26 +246,76 @@ impl SlackSyncService {
             }
         }
 
+        let channel_ids: Vec<String> = selected_channels
+            .iter()
+            .map(|c| c.channel_id.clone())
+            .collect();
+        mark_pending(&self.db, "slack", "channel", &channel_ids).await;
+
+        let channels = Arc::new(ChannelProgress::new(selected_channels.len()));
+        self.progress
+            .report(0.0, format!("Syncing {} Slack channels...", channels.total))
+            .await;
+
         let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_SYNCS));
         let mut handles = Vec::new();
 
         for channel in selected_channels {
             let sem = semaphore.clone();
             let service = self.clone();
+            let channels = channels.clone();
 
             handles.push(tokio::spawn(async move {
                 let _permit = sem.acquire().await.expect("Semaphore closed unexpectedly");
-                let result = service.sync_channel(&channel).await;
+                mark_syncing(&service.db, "slack", "channel", &channel.channel_id).await;
+                let result = service.sync_channel(&channel, &channels).await;
+                match &result {
+                    Ok(_) => {}
+                    Err(SlackError::Cancelled) => {
+                        mark_pending(&service.db, "slack", "channel", &[&channel.channel_id]).await
+                    }
+                    Err(e) => {
+                        mark_failed(
+                            &service.db,
+                            "slack",
+                            "channel",
+                            &channel.channel_id,
+                            &e.to_string(),
+                        )
+                        .await
+                    }
+                }
+                channels.done.fetch_add(1, Ordering::SeqCst);
+                if let Ok(count) = &result {
+                    service
+                        .progress
+                        .report(
+                            channels.fraction(),
+                            format!(
+                                "Synced #{} ({} messages) · {}",
+                                channel.channel_name,
+                                count,
+                                channels.summary()
+                            ),
+                        )
+                        .await;
+                }
                 (channel.channel_name.clone(), result)
             }));
         }
 
         let mut total_items = 0;
+        let mut unreachable = None;
         for handle in handles {
             match handle.await {
                 Ok((_channel_name, Ok(count))) => {
                     total_items += count;
                 }
+                Ok((_channel_name, Err(SlackError::Cancelled))) => {}
+                // Every channel is on the same host, so the source is unreachable
+                Ok((_channel_name, Err(e))) if is_unreachable(&e) => {
+                    unreachable = Some(e);
+                }
                 Ok((channel_name, Err(e))) => {
                     tracing::error!("Error syncing channel {}: {}", channel_name, e);
                     errors.push(format!("{}: {}", channel_name, e));
@@ -211,6 +327,12 @@ impl SlackSyncService {
             }
         }
 
+        // Channels that finished kept their new cursors; the rest resume next sync
+        self.check_cancelled()?;
+        if let Some(e) = unreachable {
+            return Err(e);
+        }
+
         Ok(SyncResult {
             source: "slack".to_string(),
             items_synced: total_items,
@@ -218,7 +340,13 @@ impl SlackSyncService {
         })
     }
 
-    async fn sync_channel(&self, channel: &SlackChannelSelection) -> Result<i32, SlackError> {
+    /// Sync new messages in a channel. The cursor only advances once every page
+    /// has been stored, so a cancelled or failed sync re-fetches the channel next time.
+    async fn sync_channel(
+        &self,
+        channel: &SlackChannelSelection,
+        channels: &ChannelProgress,
+    ) -> Result<i32, SlackError> {
         tracing::debug!(
             "Syncing channel: {} ({})",
             channel.channel_name,
@@ -233,6 +361,7 @@ impl SlackSyncService {
             .or_else(|| Some(get_today_start_ts()));
         let mut newest_ts: Option<String> = None;
         let mut api_cursor: Option<String> = None;
+        let mut page = 0;
 
         let slack_channel = SlackChannel {
             id: channel.channel_id.clone(),
@@ -247,6 +376,7 @@ impl SlackSyncService {
         };
 
         loop {
+            self.check_cancelled()?;
             let response = self
                 .fetch_with_retry(|| async {
                     self.client
@@ -284,6 +414,20 @@ impl SlackSyncService {
                 }
             }
 
+            page += 1;
+            self.progress
+                .report(
+                    channels.fraction(),
+                    format!(
+                        "Syncing #{}: page {}, {} messages · {}",
+                        channel.channel_name,
+                        page,
+                        items_synced,
+                        channels.summary()
+                    ),
+                )
+                .await;
+
             if !response.has_more {
                 break;
             }
@@ -296,7 +440,8 @@ impl SlackSyncService {
             sleep(Duration::from_millis(API_CALL_DELAY_MS)).await;
         }
 
-        if let Some(ts) = newest_ts {
+        // Record the sync even without new messages so per-channel intervals see it
+        if let Some(ts) = newest_ts.or(oldest) {
             self.update_sync_cursor(&channel.channel_id, &ts).await?;
         }
 
@@ -357,6 +502,7 @@ impl SlackSyncService {
         let mut errors = Vec::new();
 
         for channel in selected_channels {
+            self.check_cancelled()?;
             match self
                 .sync_channel_range(&channel, &oldest_ts, &latest_ts)
                 .await
@@ -364,6 +510,8 @@ impl SlackSyncService {
                 Ok(count) => {
                     total_items += count;
                 }
+                Err(SlackError::Cancelled) => return Err(SlackError::Cancelled),
+                Err(e) if is_unreachable(&e) => return Err(e),
                 Err(e) => {
                     tracing::error!(
                         "Error syncing channel {} for {}: {}",
@@ -422,6 +570,7 @@ impl SlackSyncService {
         };
 
         loop {
+            self.check_cancelled()?;
             let response = self
                 .fetch_with_retry(|| async {
                     self.client
@@ -474,6 +623,101 @@ impl SlackSyncService {
         Ok(items_synced)
     }
 
+    /// Count what syncing `channels` (id, name) would fetch, without storing
+    /// anything. With a `range` each channel is walked over those days, which
+    /// the historical sync does one day at a time; otherwise from its
+    /// incremental cursor. Thread replies are counted from `reply_count` rather
+    /// than fetched, and assumed to be about as long as the message they follow.
+    pub async fn estimate_channels(
+        &self,
+        channels: &[(String, String)],
+        range: Option<&SyncRange>,
+        timezone_offset: i32,
+    ) -> Result<SourceEstimate, SlackError> {
+        let mut estimate = SourceEstimate::new("slack");
+        // Slack API uses exclusive bounds
+        let bounds = range.map(|range| {
+            let (start, end) = range.utc_bounds();
+            (format!("{}.999999", start - 1), format!("{}.000000", end))
+        });
+        let days = range.map(|range| range.dates().len() as i64).unwrap_or(1);
+
+        for (channel_id, channel_name) in channels {
+            self.check_cancelled()?;
+            let oldest = match &bounds {
+                Some((oldest, _)) => oldest.clone(),
+                None => self
+                    .get_sync_cursor(channel_id)
+                    .await?
+                    .unwrap_or_else(get_today_start_ts),
+            };
+            let latest = bounds.as_ref().map(|(_, latest)| latest.as_str());
+            let mut messages_by_day: HashMap<String, i64> = HashMap::new();
+            let mut api_cursor: Option<String> = None;
+
+            loop {
+                self.check_cancelled()?;
+                let response = self
+                    .fetch_with_retry(|| async {
+                        self.client
+                            .get_channel_history(
+                                channel_id,
+                                Some(oldest.as_str()),
+                                latest,
+                                api_cursor.as_deref(),
+                                DRY_RUN_PAGE_SIZE,
+                            )
+                            .await
+                    })
+                    .await?;
+
+                for msg in &response.messages {
+                    let ts_float: f64 = msg.ts.parse().unwrap_or(0.0);
+                    let date = local_date((ts_float * 1000.0) as i64, timezone_offset);
+                    let replies = i64::from(msg.reply_count.unwrap_or(0).max(0));
+                    estimate.add(
+                        channel_name,
+                        &date,
+                        1 + replies,
+                        text_tokens(&msg.text) * (1 + replies),
+                    );
+                    if replies > 0 {
+                        // Reply pages include the parent message
+                        estimate.api_requests +=
+                            ceil_div(replies + 1, THREAD_REPLIES_PAGE_SIZE as i64);
+                    }
+                    *messages_by_day.entry(date).or_default() += 1;
+                }
+
+                if !response.has_more {
+                    break;
+                }
+
+                api_cursor = response.next_cursor;
+                if api_cursor.is_none() {
+                    break;
+                }
+
+                sleep(Duration::from_millis(API_CALL_DELAY_MS)).await;
+            }
+
+            // History pages of the real sync: one walk from the cursor, or a
+            // walk per day, even an empty one, for a historical range
+            let page_size = HISTORY_PAGE_SIZE as i64;
+            estimate.api_requests += if range.is_some() {
+                let pages: i64 = messages_by_day
+                    .values()
+                    .map(|&count| ceil_div(count, page_size))
+                    .sum();
+                pages + (days - messages_by_day.len() as i64).max(0)
+            } else {
+                ceil_div(messages_by_day.values().sum(), page_size).max(1)
+            };
+        }
+
+        Ok(estimate)
+    }
+
     async fn sync_thread_replies(
         &self,
         channel_id: &str,
@@ -487,6 +731,7 @@ impl SlackSyncService {
         let mut cursor: Option<String> = None;
 
         loop {
+            self.check_cancelled()?;
             let response = self
                 .fetch_with_retry(|| async {
                     self.client
@@ -542,6 +787,14 @@ impl SlackSyncService {
                         MAX_RETRIES,
                         delay
                     );
+                    self.progress
+                        .message(format!(
+                            "Slack rate limit reached, retrying in {}s ({}/{})",
+                            delay / 1000,
+                            retries,
+                            MAX_RETRIES
+                        ))
+                        .await;
                     sleep(Duration::from_millis(delay)).await;
                 }
                 Err(e) => return Err(e),
@@ -550,23 +803,7 @@ impl SlackSyncService {
     }
 
     async fn update_sync_cursor(&self, channel_id: &str, cursor: &str) -> Result<(), SlackError> {
-        let now = chrono::Utc::now().timestamp_millis();
-
-        sqlx::query(
-            "INSERT INTO sync_state (id, source, resource_type, resource_id, last_sync_at, cursor, status)
-             VALUES (?, 'slack', 'channel', ?, ?, ?, 'complete')
-             ON CONFLICT(source, resource_type, resource_id) 
-             DO UPDATE SET last_sync_at = ?, cursor = ?, status = 'complete'"
-        )
-        .bind(uuid::Uuid::new_v4().to_string())
-        .bind(channel_id)
-        .bind(now)
-        .bind(cursor)
-        .bind(now)
-        .bind(cursor)
-        .execute(self.db.pool())
-        .await?;
-
+        save_cursor(&self.db, "slack", "channel", channel_id, cursor).await?;
         Ok(())
     }
 
@@ -575,50 +812,98 @@ impl SlackSyncService {
         channel: &SlackChannel,
         msg: &SlackMessage,
     ) -> Result<(), SlackError> {
-        let now = chrono::Utc::now().timestamp_millis();
-        let ts_float: f64 = msg.ts.parse().unwrap_or(0.0);
-        let created_at = (ts_float * 1000.0) as i64;
-        let encrypted_body = self
-            .crypto
-            .encrypt_string(&msg.text)
-            .map_err(|e| SlackError::Crypto(e.to_string()))?;
-
-        let source_url = if let Some(ref domain) = self.team_domain {
-            // e.g., https://acme-corp.slack.com/archives/C04KQBBPPLN/p1769203754053419
-            let permalink_ts = format!("p{}", msg.ts.replace('.', ""));
-            format!(
-                "https://{}.slack.com/archives/{}/{}",
-                domain, channel.id, permalink_ts
-            )
-        } else {
-            format!(
-                "https://slack.com/app_redirect?channel={}&message_ts={}",
-                channel.id, msg.ts
-            )
-        };
+        store_message(
+            &self.db,
+            &self.crypto,
+            self.team_domain.as_deref(),
+            channel,
+            msg,
+        )
+        .await
+    }
+}
+
+/// Upsert Slack users into the `slack_users` cache.
+pub(super) async fn store_users(
+    db: &Database,
+    users: &[SlackUser],
+    team_id: &str,
+) -> Result<(), SlackError> {
+    let now = chrono::Utc::now().timestamp_millis();
 
+    for user in users {
         sqlx::query(
-            "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, parent_id, created_at, updated_at, synced_at)
-             VALUES (?, 'slack', ?, ?, 'message', NULL, ?, ?, ?, ?, ?, ?, ?)
-             ON CONFLICT(source, source_id) DO UPDATE SET body = ?, synced_at = ?"
+            "INSERT INTO slack_users (user_id, team_id, username, real_name, display_name, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?)
+             ON CONFLICT(user_id) DO UPDATE SET 
+                username = excluded.username,
+                real_name = excluded.real_name,
+                display_name = excluded.display_name,
+                updated_at = excluded.updated_at"
         )
-        .bind(uuid::Uuid::new_v4().to_string())
-        .bind(&msg.ts)
-        .bind(&source_url)
-        .bind(&encrypted_body)
-        .bind(&msg.user)
-        .bind(&channel.name)
-        .bind(&msg.thread_ts)
-        .bind(created_at)
+        .bind(&user.id)
+        .bind(team_id)
+        .bind(&user.name)
+        .bind(&user.real_name)
+        .bind(&user.display_name)
         .bind(now)
-        .bind(now)
-        .bind(&encrypted_body)
-        .bind(now)
-        .execute(self.db.pool())
+        .execute(db.pool())
         .await?;
-
-        Ok(())
     }
+
+    Ok(())
+}
+
+/// Encrypt and upsert a Slack message into `content_items`, keyed on its `ts`.
+pub(super) async fn store_message(
+    db: &Database,
+    crypto: &CryptoService,
+    team_domain: Option<&str>,
+    channel: &SlackChannel,
+    msg: &SlackMessage,
+) -> Result<(), SlackError> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let ts_float: f64 = msg.ts.parse().unwrap_or(0.0);
+    let created_at = (ts_float * 1000.0) as i64;
+    let encrypted_body = crypto
+        .encrypt_string(&msg.text)
+        .map_err(|e| SlackError::Crypto(e.to_string()))?;
+
+    let source_url = if let Some(domain) = team_domain {
+        // e.g., https://acme-corp.slack.com/archives/C04KQBBPPLN/p1769203754053419
+        let permalink_ts = format!("p{}", msg.ts.replace('.', ""));
+        format!(
+            "https://{}.slack.com/archives/{}/{}",
+            domain, channel.id, permalink_ts
+        )
+    } else {
+        format!(
+            "https://slack.com/app_redirect?channel={}&message_ts={}",
+            channel.id, msg.ts
+        )
+    };
+
+    sqlx::query(
+        "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, parent_id, created_at, updated_at, synced_at)
+         VALUES (?, 'slack', ?, ?, 'message', NULL, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(source, source_id) DO UPDATE SET body = ?, synced_at = ?"
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(&msg.ts)
+    .bind(&source_url)
+    .bind(&encrypted_body)
+    .bind(&msg.user)
+    .bind(&channel.name)
+    .bind(&msg.thread_ts)
+    .bind(created_at)
+    .bind(now)
+    .bind(now)
+    .bind(&encrypted_body)
+    .bind(now)
+    .execute(db.pool())
+    .await?;
+
+    Ok(())
 }
 
 #[cfg(test)]
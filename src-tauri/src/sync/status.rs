@@ -0,0 +1,326 @@
+//! Per-resource sync lifecycle
+//!
+//! Every channel, project, space, folder or feed a connector syncs has a
+//! `sync_state` row. A run marks the resources it is about to sync `pending`,
+//! each moves to `syncing` when its own sync starts and ends `complete` (see
+//! `save_cursor`) or `error`. The last error is kept until the resource next
+//! completes, so one that keeps failing shows why even while it is retried.
+//! Status writes are best effort: a failure is logged and never fails a sync.
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::Database;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceStatus {
+    Pending,
+    Syncing,
+    Complete,
+    Error,
+}
+
+impl ResourceStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Syncing => "syncing",
+            Self::Complete => "complete",
+            Self::Error => "error",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "syncing" => Self::Syncing,
+            "complete" => Self::Complete,
+            "error" => Self::Error,
+            _ => Self::Pending,
+        }
+    }
+}
+
+/// Sync health of one resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceHealth {
+    pub source: String,
+    pub resource_type: String,
+    pub resource_id: String,
+    /// Display name where the source keeps one (Slack channels), else the id
+    pub name: String,
+    pub status: ResourceStatus,
+    /// False while the resource has an unresolved error
+    pub healthy: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_sync_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<i64>,
+}
+
+type ResourceHealthRow = (
+    String,
+    String,
+    String,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<i64>,
+    Option<i64>,
+    Option<i64>,
+    Option<i64>,
+);
+
+impl ResourceHealth {
+    fn from_row(row: ResourceHealthRow) -> Self {
+        let (
+            source,
+            resource_type,
+            resource_id,
+            name,
+            status,
+            error_message,
+            error_at,
+            last_sync_at,
+            started_at,
+            updated_at,
+        ) = row;
+        Self {
+            name: name.unwrap_or_else(|| resource_id.clone()),
+            status: ResourceStatus::parse(status.as_deref().unwrap_or_default()),
+            healthy: error_message.is_none(),
+            source,
+            resource_type,
+            resource_id,
+            error_message,
+            error_at,
+            last_sync_at,
+            started_at,
+            updated_at,
+        }
+    }
+}
+
+/// Move a resource to `status`, recording `error` when it failed. Columns the
+/// new status says nothing about keep their values.
+async fn set_status(
+    db: &Database,
+    source: &str,
+    resource_type: &str,
+    resource_id: &str,
+    status: ResourceStatus,
+    error: Option<&str>,
+) {
+    let now = chrono::Utc::now().timestamp_millis();
+
+    let result = sqlx::query(
+        "INSERT INTO sync_state (id, source, resource_type, resource_id, status, error_message, error_at, started_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(source, resource_type, resource_id)
+         DO UPDATE SET status = excluded.status,
+             error_message = COALESCE(excluded.error_message, sync_state.error_message),
+             error_at = COALESCE(excluded.error_at, sync_state.error_at),
+             started_at = COALESCE(excluded.started_at, sync_state.started_at),
+             updated_at = excluded.updated_at",
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(source)
+    .bind(resource_type)
+    .bind(resource_id)
+    .bind(status.as_str())
+    .bind(error)
+    .bind(error.map(|_| now))
+    .bind((status == ResourceStatus::Syncing).then_some(now))
+    .bind(now)
+    .execute(db.pool())
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(
+            "Failed to mark {} {} {} as {}: {}",
+            source,
+            resource_type,
+            resource_id,
+            status.as_str(),
+            e
+        );
+    }
+}
+
+/// Mark the resources a run is about to sync as queued
+pub async fn mark_pending(
+    db: &Database,
+    source: &str,
+    resource_type: &str,
+    resource_ids: &[impl AsRef<str>],
+) {
+    for resource_id in resource_ids {
+        set_status(
+            db,
+            source,
+            resource_type,
+            resource_id.as_ref(),
+            ResourceStatus::Pending,
+            None,
+        )
+        .await;
+    }
+}
+
+pub async fn mark_syncing(db: &Database, source: &str, resource_type: &str, resource_id: &str) {
+    set_status(
+        db,
+        source,
+        resource_type,
+        resource_id,
+        ResourceStatus::Syncing,
+        None,
+    )
+    .await;
+}
+
+pub async fn mark_failed(
+    db: &Database,
+    source: &str,
+    resource_type: &str,
+    resource_id: &str,
+    error: &str,
+) {
+    set_status(
+        db,
+        source,
+        resource_type,
+        resource_id,
+        ResourceStatus::Error,
+        Some(error),
+    )
+    .await;
+}
+
+/// Record a successful sync that leaves the cursor as it was
+pub async fn mark_complete(db: &Database, source: &str, resource_type: &str, resource_id: &str) {
+    let now = chrono::Utc::now().timestamp_millis();
+
+    let result = sqlx::query(
+        "UPDATE sync_state
+         SET status = 'complete', last_sync_at = ?, updated_at = ?, error_message = NULL, error_at = NULL
+         WHERE source = ? AND resource_type = ? AND resource_id = ?",
+    )
+    .bind(now)
+    .bind(now)
+    .bind(source)
+    .bind(resource_type)
+    .bind(resource_id)
+    .execute(db.pool())
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(
+            "Failed to mark {} {} {} as complete: {}",
+            source,
+            resource_type,
+            resource_id,
+            e
+        );
+    }
+}
+
+/// Return resources left `syncing` by a previous session to `pending`
+pub async fn recover_interrupted(db: &Database) -> Result<u64, sqlx::Error> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let result = sqlx::query(
+        "UPDATE sync_state SET status = 'pending', updated_at = ? WHERE status = 'syncing'",
+    )
+    .bind(now)
+    .execute(db.pool())
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Every tracked resource, optionally of one source. Failing resources come
+/// first within each source.
+pub async fn list_resource_health(
+    db: &Database,
+    source: Option<&str>,
+) -> Result<Vec<ResourceHealth>, sqlx::Error> {
+    let rows: Vec<ResourceHealthRow> = sqlx::query_as(
+        "SELECT s.source, s.resource_type, s.resource_id, c.channel_name, s.status,
+                s.error_message, s.error_at, s.last_sync_at, s.started_at, s.updated_at
+         FROM sync_state s
+         LEFT JOIN slack_selected_channels c
+             ON s.source = 'slack' AND s.resource_type = 'channel' AND c.channel_id = s.resource_id
+         WHERE ? IS NULL OR s.source = ?
+         ORDER BY s.source, s.error_message IS NULL, s.resource_type,
+                  COALESCE(c.channel_name, s.resource_id)",
+    )
+    .bind(source)
+    .bind(source)
+    .fetch_all(db.pool())
+    .await?;
+
+    Ok(rows.into_iter().map(ResourceHealth::from_row).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            ResourceStatus::Pending,
+            ResourceStatus::Syncing,
+            ResourceStatus::Complete,
+            ResourceStatus::Error,
+        ] {
+            assert_eq!(ResourceStatus::parse(status.as_str()), status);
+        }
+        assert_eq!(ResourceStatus::parse(""), ResourceStatus::Pending);
+    }
+
+    #[test]
+    fn test_health_from_row() {
+        let health = ResourceHealth::from_row((
+            "slack".to_string(),
+            "channel".to_string(),
+            "C123".to_string(),
+            Some("eng-secret".to_string()),
+            Some("error".to_string()),
+            Some("API error: not_in_channel".to_string()),
+            Some(2_000),
+            None,
+            Some(1_000),
+            Some(2_000),
+        ));
+        assert_eq!(health.name, "eng-secret");
+        assert_eq!(health.status, ResourceStatus::Error);
+        assert!(!health.healthy);
+
+        let health = ResourceHealth::from_row((
+            "github".to_string(),
+            "repo".to_string(),
+            "acme/app".to_string(),
+            None,
+            Some("complete".to_string()),
+            None,
+            None,
+            Some(3_000),
+            Some(1_000),
+            Some(3_000),
+        ));
+        assert_eq!(health.name, "acme/app");
+        assert!(health.healthy);
+
+        let json = serde_json::to_value(&health).unwrap();
+        assert_eq!(json["status"], "complete");
+        assert_eq!(json["resourceType"], "repo");
+        assert!(json.get("errorMessage").is_none());
+    }
+}
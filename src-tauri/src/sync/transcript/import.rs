@@ -0,0 +1,300 @@
+//! Meeting transcript import
+//!
+//! A transcript is stored as one `meeting` item plus a `transcript` item per
+//! speaker turn, linked to the meeting through `parent_id`. The meeting id is
+//! derived from the file contents, so importing the same file again replaces
+//! its segments instead of duplicating them.
+
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::parse::parse_transcript;
+use super::types::{TranscriptError, TranscriptFormat, TranscriptImportResult, TranscriptSegment};
+use crate::crypto::CryptoService;
+use crate::db::Database;
+
+/// Leading `YYYY-MM-DD` of a file name, at noon UTC like date-only notes
+fn date_from_file_name(stem: &str) -> Option<i64> {
+    let date = chrono::NaiveDate::parse_from_str(stem.get(..10)?, "%Y-%m-%d").ok()?;
+    Some(date.and_hms_opt(12, 0, 0)?.and_utc().timestamp_millis())
+}
+
+/// File stem without a leading date, e.g. `2024-03-05 Weekly sync` -> `Weekly sync`
+fn title_from_file_name(stem: &str) -> String {
+    let title = match date_from_file_name(stem) {
+        Some(_) => stem[10..].trim_start_matches([' ', '-', '_', '.']),
+        None => stem,
+    };
+    if title.is_empty() {
+        stem.to_string()
+    } else {
+        title.to_string()
+    }
+}
+
+/// Speakers in order of first appearance
+fn speakers(segments: &[TranscriptSegment]) -> Vec<String> {
+    let mut speakers: Vec<String> = Vec::new();
+    for speaker in segments.iter().filter_map(|s| s.speaker.as_ref()) {
+        if !speakers.contains(speaker) {
+            speakers.push(speaker.clone());
+        }
+    }
+    speakers
+}
+
+fn format_duration(duration_ms: i64) -> String {
+    let minutes = (duration_ms + 59_999) / 60_000;
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{} min", minutes)
+    }
+}
+
+pub struct TranscriptImporter {
+    db: Arc<Database>,
+    crypto: Arc<CryptoService>,
+}
+
+impl TranscriptImporter {
+    pub fn new(db: Arc<Database>, crypto: Arc<CryptoService>) -> Self {
+        Self { db, crypto }
+    }
+
+    fn encrypt(&self, text: &str) -> Result<String, TranscriptError> {
+        self.crypto
+            .encrypt_string(text)
+            .map_err(|e| TranscriptError::Crypto(e.to_string()))
+    }
+
+    /// Import a `.vtt`, `.srt` or `.txt` transcript.
+    ///
+    /// `title` defaults to the file name and `started_at` (epoch ms) to a date
+    /// in the file name, then to the file's modification time minus the
+    /// meeting's duration.
+    pub async fn import_file(
+        &self,
+        path: &str,
+        title: Option<String>,
+        started_at: Option<i64>,
+    ) -> Result<TranscriptImportResult, TranscriptError> {
+        let path = Path::new(path);
+        let data = std::fs::read(path)?;
+        let text = String::from_utf8_lossy(&data);
+
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Meeting");
+        let format = TranscriptFormat::detect(file_name, &text);
+        let segments = parse_transcript(&text, format);
+        if segments.is_empty() {
+            return Err(TranscriptError::Parse(format!(
+                "no speech found in {}",
+                path.display()
+            )));
+        }
+
+        let duration_ms = segments.iter().map(|s| s.end_ms).max().unwrap_or(0);
+        let started_at = match started_at.or_else(|| date_from_file_name(stem)) {
+            Some(started_at) => started_at,
+            None => {
+                let modified = std::fs::metadata(path)?.modified()?;
+                let modified = chrono::DateTime::<chrono::Utc>::from(modified);
+                modified.timestamp_millis() - duration_ms
+            }
+        };
+        let title = title
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| title_from_file_name(stem));
+
+        let meeting_id = format!("meeting:{}", &format!("{:x}", Sha256::digest(&data))[..16]);
+        let speakers = speakers(&segments);
+
+        self.store_meeting(
+            &meeting_id,
+            &title,
+            started_at,
+            duration_ms,
+            &speakers,
+            format,
+            file_name,
+        )
+        .await?;
+
+        let mut stored = HashSet::new();
+        for (index, segment) in segments.iter().enumerate() {
+            let segment_id = format!("{}/{:04}", meeting_id, index);
+            self.store_segment(&meeting_id, &segment_id, &title, started_at, segment)
+                .await?;
+            stored.insert(segment_id);
+        }
+
+        // Drop segments left over from an earlier import that parsed differently
+        let existing: Vec<(String,)> = sqlx::query_as(
+            "SELECT source_id FROM content_items WHERE source = 'transcript' AND parent_id = ?",
+        )
+        .bind(&meeting_id)
+        .fetch_all(self.db.pool())
+        .await?;
+        for (source_id,) in existing {
+            if !stored.contains(&source_id) {
+                sqlx::query(
+                    "DELETE FROM content_items WHERE source = 'transcript' AND source_id = ?",
+                )
+                .bind(&source_id)
+                .execute(self.db.pool())
+                .await?;
+            }
+        }
+
+        tracing::info!(
+            "Imported transcript '{}': {} segments from {} speakers",
+            title,
+            segments.len(),
+            speakers.len()
+        );
+
+        Ok(TranscriptImportResult {
+            meeting_id,
+            title,
+            segments_imported: segments.len() as i32,
+            speakers,
+            items_summarized: 0,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn store_meeting(
+        &self,
+        meeting_id: &str,
+        title: &str,
+        started_at: i64,
+        duration_ms: i64,
+        speakers: &[String],
+        format: TranscriptFormat,
+        file_name: &str,
+    ) -> Result<(), TranscriptError> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut body = format!(
+            "Meeting: {}\nDuration: {}",
+            title,
+            format_duration(duration_ms)
+        );
+        if !speakers.is_empty() {
+            body.push_str(&format!("\nParticipants: {}", speakers.join(", ")));
+        }
+        let encrypted_body = self.encrypt(&body)?;
+        let metadata = serde_json::json!({
+            "file": file_name,
+            "format": format,
+            "duration_ms": duration_ms,
+            "speakers": speakers,
+        })
+        .to_string();
+
+        sqlx::query(
+            "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, parent_id, created_at, updated_at, synced_at, metadata)
+             VALUES (?, 'transcript', ?, NULL, 'meeting', ?, ?, NULL, ?, NULL, ?, ?, ?, ?)
+             ON CONFLICT(source, source_id) DO UPDATE SET title = ?, body = ?, channel_or_project = ?, created_at = ?, updated_at = ?, synced_at = ?, metadata = ?"
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(meeting_id)
+        .bind(title)
+        .bind(&encrypted_body)
+        .bind(title)
+        .bind(started_at)
+        .bind(now)
+        .bind(now)
+        .bind(&metadata)
+        .bind(title)
+        .bind(&encrypted_body)
+        .bind(title)
+        .bind(started_at)
+        .bind(now)
+        .bind(now)
+        .bind(&metadata)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+
+    async fn store_segment(
+        &self,
+        meeting_id: &str,
+        segment_id: &str,
+        title: &str,
+        started_at: i64,
+        segment: &TranscriptSegment,
+    ) -> Result<(), TranscriptError> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let encrypted_body = self.encrypt(&segment.text)?;
+        let created_at = started_at + segment.start_ms;
+        let metadata = serde_json::json!({
+            "offset_ms": segment.start_ms,
+            "end_ms": segment.end_ms,
+        })
+        .to_string();
+
+        sqlx::query(
+            "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, parent_id, created_at, updated_at, synced_at, metadata)
+             VALUES (?, 'transcript', ?, NULL, 'transcript', NULL, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(source, source_id) DO UPDATE SET body = ?, author_id = ?, channel_or_project = ?, created_at = ?, updated_at = ?, synced_at = ?, metadata = ?"
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(segment_id)
+        .bind(&encrypted_body)
+        .bind(&segment.speaker)
+        .bind(title)
+        .bind(meeting_id)
+        .bind(created_at)
+        .bind(now)
+        .bind(now)
+        .bind(&metadata)
+        .bind(&encrypted_body)
+        .bind(&segment.speaker)
+        .bind(title)
+        .bind(created_at)
+        .bind(now)
+        .bind(now)
+        .bind(&metadata)
+        .execute(self.db.pool())
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_and_date_from_file_name() {
+        assert_eq!(
+            title_from_file_name("2024-03-05 Weekly sync"),
+            "Weekly sync"
+        );
+        assert_eq!(title_from_file_name("2024-03-05"), "2024-03-05");
+        assert_eq!(title_from_file_name("Design review"), "Design review");
+        assert_eq!(
+            date_from_file_name("2024-03-05_standup"),
+            Some(
+                chrono::DateTime::parse_from_rfc3339("2024-03-05T12:00:00Z")
+                    .unwrap()
+                    .timestamp_millis()
+            )
+        );
+        assert_eq!(format_duration(61 * 60_000), "1h 01m");
+        assert_eq!(format_duration(90_000), "2 min");
+    }
+}
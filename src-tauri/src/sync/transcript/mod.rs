@@ -0,0 +1,12 @@
+//! Meeting transcript import module
+//!
+//! This module parses WebVTT, SRT and plain-text transcripts into
+//! speaker-attributed segments and stores them under a parent meeting item.
+
+mod import;
+mod parse;
+mod types;
+
+pub use import::TranscriptImporter;
+pub use parse::parse_transcript;
+pub use types::{TranscriptError, TranscriptFormat, TranscriptImportResult, TranscriptSegment};
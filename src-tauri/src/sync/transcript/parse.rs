@@ -0,0 +1,315 @@
+//! WebVTT, SRT and plain-text transcript parsing
+//!
+//! Speakers come from VTT voice tags (`<v Name>`), a `Name:` prefix, or in plain
+//! text a `Name  00:03` header line as written by Otter and Teams exports.
+
+use super::types::{TranscriptFormat, TranscriptSegment};
+
+/// Merged turns are split once they grow past this many characters
+const MAX_TURN_CHARS: usize = 2000;
+
+/// `HH:MM:SS.mmm`, `MM:SS.mmm` or SRT's `HH:MM:SS,mmm`, in milliseconds
+fn parse_timestamp(value: &str) -> Option<i64> {
+    let value = value.trim().replace(',', ".");
+    let parts: Vec<&str> = value.split(':').collect();
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [h, m, s] => (h.parse::<i64>().ok()?, m.parse::<i64>().ok()?, *s),
+        [m, s] => (0, m.parse::<i64>().ok()?, *s),
+        _ => return None,
+    };
+    let seconds: f64 = seconds.parse().ok()?;
+    if !(0.0..60.0).contains(&seconds) || !(0..60).contains(&minutes) {
+        return None;
+    }
+    Some((hours * 3600 + minutes * 60) * 1000 + (seconds * 1000.0).round() as i64)
+}
+
+/// Remove markup tags such as `<i>`, `<c.yellow>` or timestamps `<00:01.000>`
+fn strip_tags(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+}
+
+/// `Name: text` where `Name` looks like a person or `Speaker 2`, not a sentence
+fn speaker_prefix(line: &str) -> Option<(String, &str)> {
+    let line = line.trim_start_matches(['-', '>', ' ']);
+    let (name, rest) = line.split_once(':')?;
+    let name = name.trim().trim_matches(['[', ']']);
+    let words = name.split_whitespace().count();
+    let plausible = !name.is_empty()
+        && name.len() <= 40
+        && words <= 4
+        && !name.contains("http")
+        && name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() && !c.is_lowercase());
+    // `10:30` style times are not speakers
+    (plausible && !rest.starts_with(|c: char| c.is_ascii_digit()))
+        .then(|| (name.to_string(), rest.trim_start()))
+}
+
+/// Speaker of a VTT cue from its `<v Name>` voice tag
+fn voice_tag(text: &str) -> Option<String> {
+    let start = text.find("<v")?;
+    let rest = &text[start + 2..];
+    let end = rest.find('>')?;
+    let tag = &rest[..end];
+    // `<v.loud Name>` carries classes before the name
+    let name = match tag.strip_prefix('.') {
+        Some(classes) => classes.split_once(' ').map(|(_, name)| name)?,
+        None => tag,
+    };
+    Some(name.trim().to_string()).filter(|n| !n.is_empty())
+}
+
+/// Cues of a VTT or SRT file: blocks separated by blank lines, each with a
+/// `start --> end` timing line followed by text
+fn parse_cues(text: &str) -> Vec<TranscriptSegment> {
+    let normalized = text.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let mut cues = Vec::new();
+
+    for block in normalized.split("\n\n") {
+        let lines: Vec<&str> = block.lines().collect();
+        let Some(timing) = lines.iter().position(|l| l.contains("-->")) else {
+            continue;
+        };
+        let (start, end) = lines[timing].split_once("-->").unwrap();
+        let end = end.split_whitespace().next().unwrap_or_default();
+        let (Some(start_ms), Some(end_ms)) = (parse_timestamp(start), parse_timestamp(end)) else {
+            continue;
+        };
+
+        let raw = lines[timing + 1..].join("\n");
+        let mut speaker = voice_tag(&raw);
+        let mut text = strip_tags(&raw).trim().to_string();
+        if speaker.is_none() {
+            if let Some((name, rest)) = speaker_prefix(&text) {
+                speaker = Some(name);
+                text = rest.trim().to_string();
+            }
+        }
+        if text.is_empty() {
+            continue;
+        }
+
+        cues.push(TranscriptSegment {
+            speaker,
+            start_ms,
+            end_ms,
+            text: text.split_whitespace().collect::<Vec<_>>().join(" "),
+        });
+    }
+
+    cues
+}
+
+/// Leading `[00:01:02]`, `(01:02)` or `00:01:02` timestamp of a text line
+fn leading_timestamp(line: &str) -> Option<(i64, &str)> {
+    let trimmed = line.trim_start();
+    let (stamp, rest) = if let Some(inner) = trimmed.strip_prefix(['[', '(']) {
+        inner.split_once([']', ')'])?
+    } else {
+        trimmed.split_once(char::is_whitespace)?
+    };
+    Some((parse_timestamp(stamp)?, rest.trim_start()))
+}
+
+/// Otter/Teams style `Speaker Name  00:03` header line
+fn speaker_header(line: &str) -> Option<(String, i64)> {
+    let (name, stamp) = line.trim().rsplit_once(char::is_whitespace)?;
+    let offset = parse_timestamp(stamp)?;
+    let name = name.trim();
+    (!name.is_empty() && name.split_whitespace().count() <= 4 && !name.contains(':'))
+        .then(|| (name.to_string(), offset))
+}
+
+/// Plain text: one segment per `Name:` line, optionally timestamped;
+/// unlabelled lines continue the current speaker
+fn parse_text(text: &str) -> Vec<TranscriptSegment> {
+    let mut segments: Vec<TranscriptSegment> = Vec::new();
+    let mut offset = 0;
+
+    for line in text.trim_start_matches('\u{feff}').lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some((speaker, start)) = speaker_header(line) {
+            offset = start;
+            segments.push(TranscriptSegment {
+                speaker: Some(speaker),
+                start_ms: start,
+                end_ms: start,
+                text: String::new(),
+            });
+            continue;
+        }
+
+        let (line, stamp) = match leading_timestamp(line) {
+            Some((start, rest)) => (rest, Some(start)),
+            None => (line, None),
+        };
+        offset = stamp.unwrap_or(offset);
+
+        match speaker_prefix(line) {
+            Some((speaker, rest)) => segments.push(TranscriptSegment {
+                speaker: Some(speaker),
+                start_ms: offset,
+                end_ms: offset,
+                text: rest.to_string(),
+            }),
+            None => match segments.last_mut() {
+                Some(current) => {
+                    if !current.text.is_empty() {
+                        current.text.push(' ');
+                    }
+                    current.text.push_str(line);
+                    current.end_ms = offset;
+                }
+                None => segments.push(TranscriptSegment {
+                    speaker: None,
+                    start_ms: offset,
+                    end_ms: offset,
+                    text: line.to_string(),
+                }),
+            },
+        }
+    }
+
+    segments.retain(|s| !s.text.trim().is_empty());
+    segments
+}
+
+/// Merge consecutive cues into speaker turns. Cues without a speaker continue
+/// the previous turn, as in captions that only label speaker changes.
+fn merge_turns(cues: Vec<TranscriptSegment>) -> Vec<TranscriptSegment> {
+    let mut turns: Vec<TranscriptSegment> = Vec::new();
+
+    for mut cue in cues {
+        if let Some(turn) = turns.last_mut() {
+            if cue.speaker.is_none() {
+                cue.speaker = turn.speaker.clone();
+            }
+            if cue.speaker == turn.speaker && turn.text.len() + cue.text.len() < MAX_TURN_CHARS {
+                turn.text.push(' ');
+                turn.text.push_str(&cue.text);
+                turn.end_ms = turn.end_ms.max(cue.end_ms);
+                continue;
+            }
+        }
+        turns.push(cue);
+    }
+
+    turns
+}
+
+/// Parse a transcript into speaker turns ordered by start offset
+pub fn parse_transcript(text: &str, format: TranscriptFormat) -> Vec<TranscriptSegment> {
+    let segments = match format {
+        TranscriptFormat::Vtt | TranscriptFormat::Srt => parse_cues(text),
+        TranscriptFormat::Text => parse_text(text),
+    };
+    merge_turns(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_vtt_voice_tags_and_turns() {
+        let vtt = "WEBVTT
+
+NOTE recorded by the meeting bot
+
+1
+00:00:01.000 --> 00:00:04.500 align:start
+<v Dana Scully>We should ship the <i>pricing</i> change</v>
+
+2
+00:00:04.500 --> 00:00:06.000
+<v Dana Scully>on Monday.</v>
+
+3
+00:01:02.250 --> 00:01:05.000
+<v.loud Fox Mulder>Agreed, I'll update the docs.</v>
+";
+        let turns = parse_transcript(vtt, TranscriptFormat::Vtt);
+
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].speaker.as_deref(), Some("Dana Scully"));
+        assert_eq!(
+            turns[0].text,
+            "We should ship the pricing change on Monday."
+        );
+        assert_eq!((turns[0].start_ms, turns[0].end_ms), (1000, 6000));
+        assert_eq!(turns[1].speaker.as_deref(), Some("Fox Mulder"));
+        assert_eq!(turns[1].start_ms, 62_250);
+    }
+
+    #[test]
+    fn test_parse_srt_with_name_prefixes() {
+        let srt = "1\r\n00:00:01,000 --> 00:00:03,000\r\nAlex: Budget is approved.\r\n\r\n\
+2\r\n00:00:03,000 --> 00:00:05,000\r\nThe vendor starts at 10:30 tomorrow.\r\n\r\n\
+3\r\n01:00:00,000 --> 01:00:02,000\r\nSam: Thanks all.\r\n";
+        let turns = parse_transcript(srt, TranscriptFormat::Srt);
+
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns[0].speaker.as_deref(), Some("Alex"));
+        assert_eq!(
+            turns[0].text,
+            "Budget is approved. The vendor starts at 10:30 tomorrow."
+        );
+        assert_eq!(turns[1].speaker.as_deref(), Some("Sam"));
+        assert_eq!(turns[1].start_ms, 3_600_000);
+    }
+
+    #[test]
+    fn test_parse_text_formats() {
+        let text = "[00:00:05] Priya: Kicking off.\nLet's review the roadmap.\n\
+Jordan Lee  01:10\nThe migration slipped a week.\n\n(02:00) Priya: Action item for Jordan: send the new date.";
+        let turns = parse_transcript(text, TranscriptFormat::Text);
+
+        assert_eq!(turns.len(), 3);
+        assert_eq!(turns[0].text, "Kicking off. Let's review the roadmap.");
+        assert_eq!(turns[0].start_ms, 5000);
+        assert_eq!(turns[1].speaker.as_deref(), Some("Jordan Lee"));
+        assert_eq!(turns[1].start_ms, 70_000);
+        assert_eq!(turns[2].speaker.as_deref(), Some("Priya"));
+        assert_eq!(turns[2].start_ms, 120_000);
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(
+            TranscriptFormat::detect("call.VTT", ""),
+            TranscriptFormat::Vtt
+        );
+        assert_eq!(
+            TranscriptFormat::detect("call", "WEBVTT\n\n00:01.000 --> 00:02.000\nhi"),
+            TranscriptFormat::Vtt
+        );
+        assert_eq!(
+            TranscriptFormat::detect("call.srt", ""),
+            TranscriptFormat::Srt
+        );
+        assert_eq!(
+            TranscriptFormat::detect("call.txt", "Sam: hi"),
+            TranscriptFormat::Text
+        );
+    }
+}
@@ -0,0 +1,65 @@
+//! Meeting transcript data types and error definitions
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TranscriptError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid transcript: {0}")]
+    Parse(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Crypto error: {0}")]
+    Crypto(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    Vtt,
+    Srt,
+    Text,
+}
+
+impl TranscriptFormat {
+    /// Format from the file extension, falling back to sniffing the content
+    pub fn detect(file_name: &str, text: &str) -> Self {
+        let extension = file_name
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("vtt") => Self::Vtt,
+            Some("srt") => Self::Srt,
+            _ if text.trim_start_matches('\u{feff}').starts_with("WEBVTT") => Self::Vtt,
+            _ if text.lines().any(|l| l.contains(" --> ")) => Self::Srt,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// A stretch of speech by one speaker; offsets are from the meeting start
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptSegment {
+    pub speaker: Option<String>,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+}
+
+/// Summary of a completed transcript import
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptImportResult {
+    /// `source_id` of the parent meeting item
+    pub meeting_id: String,
+    pub title: String,
+    pub segments_imported: i32,
+    pub speakers: Vec<String>,
+    /// Topics and items stored by AI summarization, when it ran
+    pub items_summarized: i32,
+}
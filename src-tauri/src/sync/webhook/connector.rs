@@ -0,0 +1,286 @@
+//! Webhook source connector
+//!
+//! Each connect adds (or updates) an endpoint that CI systems, alerting tools
+//! and scripts push JSON to; [`super::serve_webhooks`] receives the events.
+//! Syncing only counts what arrived since the last sync so new events trigger
+//! AI processing like any other source.
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use rand::RngCore;
+
+use super::sync::ENDPOINT_RESOURCE;
+use super::types::{WebhookConfig, WebhookEndpoint, WebhookMapping};
+use crate::pipeline::PipelineTaskType;
+use crate::sync::connector::{
+    clear_sync_state, last_sync_at, load_credentials, load_cursor, save_cursor, store_credentials,
+    ConnectorContext, ConnectorError, SourceConnector, SourceHealth, SourceScope, SyncRange,
+};
+use crate::sync::SyncResult;
+
+/// `sync_state` resource for the received-events cursor
+const RECEIVED_RESOURCE: &str = "received";
+const MIN_SECRET_LEN: usize = 16;
+
+pub async fn load_webhook_config(ctx: &ConnectorContext) -> Result<WebhookConfig, ConnectorError> {
+    Ok(load_credentials(ctx, "webhook").await?.unwrap_or_default())
+}
+
+/// URL path segment for an endpoint name, e.g. `Build Alerts` -> `build-alerts`
+fn endpoint_id(name: &str) -> String {
+    let mut id = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c.to_ascii_lowercase());
+        } else if !id.is_empty() && !id.ends_with('-') {
+            id.push('-');
+        }
+    }
+    id.trim_end_matches('-').to_string()
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 24];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn config_str<'a>(config: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    config[key]
+        .as_str()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+pub struct WebhookConnector;
+
+#[async_trait]
+impl SourceConnector for WebhookConnector {
+    fn id(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Webhooks"
+    }
+
+    fn description(&self) -> &'static str {
+        "Receive events pushed by CI, alerting tools and scripts"
+    }
+
+    fn task_type(&self) -> PipelineTaskType {
+        PipelineTaskType::SyncWebhook
+    }
+
+    fn item_label(&self) -> &'static str {
+        "events"
+    }
+
+    /// Accepts `{ "name", "secret"?, "port"? }` plus [`WebhookMapping`] fields
+    /// (`items`, `title`, `body`, `author`, `channel`, `url`, `createdAt`,
+    /// `contentType`). Connecting an existing name updates that endpoint;
+    /// a secret is generated when none is given.
+    async fn connect(
+        &self,
+        ctx: &ConnectorContext,
+        config: serde_json::Value,
+    ) -> Result<serde_json::Value, ConnectorError> {
+        let name = config_str(&config, "name")
+            .ok_or_else(|| ConnectorError::InvalidConfig("name is required".into()))?;
+        let id = endpoint_id(name);
+        if id.is_empty() {
+            return Err(ConnectorError::InvalidConfig(
+                "name must contain letters or digits".into(),
+            ));
+        }
+
+        let mut settings = load_webhook_config(ctx).await?;
+        let secret = match config_str(&config, "secret") {
+            Some(secret) if secret.len() < MIN_SECRET_LEN => {
+                return Err(ConnectorError::InvalidConfig(format!(
+                    "secret must be at least {} characters",
+                    MIN_SECRET_LEN
+                )))
+            }
+            Some(secret) => secret.to_string(),
+            None => settings
+                .endpoint(&id)
+                .map(|e| e.secret.clone())
+                .unwrap_or_else(generate_secret),
+        };
+
+        // Empty form fields mean "use the default"
+        let mut mapping_json = config.clone();
+        if let Some(fields) = mapping_json.as_object_mut() {
+            fields.retain(|_, v| v.as_str().is_none_or(|s| !s.trim().is_empty()));
+        }
+        let mapping: WebhookMapping = serde_json::from_value(mapping_json)
+            .map_err(|e| ConnectorError::InvalidConfig(e.to_string()))?;
+
+        // The settings form sends the port as text, API callers as a number
+        let port = config["port"]
+            .as_u64()
+            .map(|p| p.to_string())
+            .or_else(|| config_str(&config, "port").map(String::from));
+        if let Some(port) = port {
+            settings.port = port
+                .parse::<u16>()
+                .ok()
+                .filter(|p| *p >= 1024)
+                .ok_or_else(|| ConnectorError::InvalidConfig(format!("invalid port {}", port)))?;
+        }
+
+        let endpoint = WebhookEndpoint {
+            id: id.clone(),
+            name: name.to_string(),
+            secret: secret.clone(),
+            mapping,
+        };
+        settings.endpoints.retain(|e| e.id != id);
+        settings.endpoints.push(endpoint);
+        store_credentials(ctx, "webhook", &settings).await?;
+
+        let url = settings.endpoint_url(&id);
+        tracing::info!("Webhook endpoint {} configured at {}", id, url);
+        Ok(serde_json::json!({ "id": id, "name": name, "url": url, "secret": secret }))
+    }
+
+    async fn is_connected(&self, ctx: &ConnectorContext) -> bool {
+        load_webhook_config(ctx)
+            .await
+            .is_ok_and(|config| !config.endpoints.is_empty())
+    }
+
+    async fn list_scopes(
+        &self,
+        ctx: &ConnectorContext,
+    ) -> Result<Vec<SourceScope>, ConnectorError> {
+        let config = load_webhook_config(ctx).await?;
+        Ok(config
+            .endpoints
+            .iter()
+            .map(|endpoint| SourceScope {
+                id: endpoint.id.clone(),
+                name: format!("{} ({})", endpoint.name, config.endpoint_url(&endpoint.id)),
+                kind: "endpoint".to_string(),
+                selected: true,
+            })
+            .collect())
+    }
+
+    /// Events are pushed as they happen; this reports how many arrived since
+    /// the previous sync
+    async fn sync_incremental(&self, ctx: &ConnectorContext) -> Result<SyncResult, ConnectorError> {
+        if !self.is_connected(ctx).await {
+            return Err(ConnectorError::NotConnected("Webhooks"));
+        }
+
+        let since = load_cursor(&ctx.db, self.id(), RECEIVED_RESOURCE, "all")
+            .await?
+            .and_then(|c| c.parse::<i64>().ok())
+            .unwrap_or(0);
+        let now = chrono::Utc::now().timestamp_millis();
+        let (received,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM content_items WHERE source = 'webhook' AND synced_at > ? AND synced_at <= ?",
+        )
+        .bind(since)
+        .bind(now)
+        .fetch_one(ctx.db.pool())
+        .await?;
+        ctx.check_cancelled()?;
+        save_cursor(
+            &ctx.db,
+            self.id(),
+            RECEIVED_RESOURCE,
+            "all",
+            &now.to_string(),
+        )
+        .await?;
+
+        Ok(SyncResult {
+            source: self.id().to_string(),
+            items_synced: received as i32,
+            errors: vec![],
+        })
+    }
+
+    /// Past events can't be fetched from push-only tools
+    async fn sync_range(
+        &self,
+        _ctx: &ConnectorContext,
+        _range: &SyncRange,
+    ) -> Result<SyncResult, ConnectorError> {
+        Ok(SyncResult {
+            source: self.id().to_string(),
+            items_synced: 0,
+            errors: vec![],
+        })
+    }
+
+    async fn health(&self, ctx: &ConnectorContext) -> SourceHealth {
+        let mut health = SourceHealth {
+            source: self.id().to_string(),
+            connected: false,
+            healthy: false,
+            message: None,
+            last_sync_at: None,
+        };
+
+        let Ok(config) = load_webhook_config(ctx).await else {
+            return health;
+        };
+        if config.endpoints.is_empty() {
+            return health;
+        }
+        health.connected = true;
+
+        // Only events received count as activity, not the periodic counting sync
+        let last_received: Option<(Option<i64>,)> = sqlx::query_as(
+            "SELECT MAX(last_sync_at) FROM sync_state WHERE source = ? AND resource_type = ?",
+        )
+        .bind(self.id())
+        .bind(ENDPOINT_RESOURCE)
+        .fetch_optional(ctx.db.pool())
+        .await
+        .ok()
+        .flatten();
+        health.last_sync_at = last_received
+            .and_then(|r| r.0)
+            .or(last_sync_at(&ctx.db, self.id()).await);
+
+        let listening = tokio::net::TcpStream::connect(("127.0.0.1", config.port))
+            .await
+            .is_ok();
+        health.healthy = listening;
+        health.message = Some(if listening {
+            format!("Listening on 127.0.0.1:{}", config.port)
+        } else {
+            format!("Listener not running on port {}", config.port)
+        });
+
+        health
+    }
+
+    async fn disconnect(&self, ctx: &ConnectorContext) -> Result<(), ConnectorError> {
+        sqlx::query("DELETE FROM credentials WHERE id = 'webhook'")
+            .execute(ctx.db.pool())
+            .await?;
+
+        clear_sync_state(&ctx.db, self.id()).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_id() {
+        assert_eq!(endpoint_id("Build Alerts"), "build-alerts");
+        assert_eq!(endpoint_id("  CI / Deploys!! "), "ci-deploys");
+        assert_eq!(endpoint_id("***"), "");
+        assert!(generate_secret().len() >= MIN_SECRET_LEN);
+    }
+}
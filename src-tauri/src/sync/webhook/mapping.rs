@@ -0,0 +1,198 @@
+//! Field mapping from JSON payloads to content items
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::types::{WebhookItem, WebhookMapping};
+
+const TITLE_KEYS: &[&str] = &["title", "summary", "subject", "name"];
+const BODY_KEYS: &[&str] = &["text", "message", "description", "body", "content"];
+const AUTHOR_KEYS: &[&str] = &["author", "user", "sender", "username"];
+const URL_KEYS: &[&str] = &["url", "link", "html_url", "web_url"];
+const TIME_KEYS: &[&str] = &["timestamp", "created_at", "createdAt", "time", "date"];
+const ID_KEYS: &[&str] = &["id", "uuid", "event_id", "fingerprint"];
+
+/// Value at a dotted path; numeric segments index arrays
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |current, segment| match current {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        })
+}
+
+/// Scalars as text and objects or arrays as compact JSON; null is absent
+fn as_text(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::Null => return None,
+        Value::String(s) => s.trim().to_string(),
+        _ => value.to_string(),
+    };
+    Some(text).filter(|t| !t.is_empty())
+}
+
+/// Resolve a mapping field: a template when it contains `{`, otherwise a path
+fn render(value: &Value, spec: &str) -> Option<String> {
+    if !spec.contains('{') {
+        return lookup(value, spec.trim()).and_then(as_text);
+    }
+
+    let mut out = String::new();
+    let mut rest = spec;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('}') else {
+            out.push_str(&rest[open..]);
+            rest = "";
+            break;
+        };
+        let path = &rest[open + 1..open + close];
+        if let Some(text) = lookup(value, path.trim()).and_then(as_text) {
+            out.push_str(&text);
+        }
+        rest = &rest[open + close + 1..];
+    }
+    out.push_str(rest);
+
+    let out = out.trim().to_string();
+    Some(out).filter(|o| !o.is_empty())
+}
+
+fn field(value: &Value, spec: Option<&str>, fallbacks: &[&str]) -> Option<String> {
+    match spec {
+        Some(spec) => render(value, spec),
+        None => fallbacks
+            .iter()
+            .find_map(|key| value.get(*key).and_then(as_text)),
+    }
+}
+
+/// Epoch seconds or milliseconds, numeric strings, or RFC 3339
+fn parse_time(text: &str) -> Option<i64> {
+    if let Ok(number) = text.parse::<f64>() {
+        let number = number as i64;
+        return Some(if number < 100_000_000_000 {
+            number * 1000
+        } else {
+            number
+        });
+    }
+    chrono::DateTime::parse_from_rfc3339(text)
+        .ok()
+        .map(|dt| dt.timestamp_millis())
+}
+
+fn map_item(value: &Value, mapping: &WebhookMapping) -> WebhookItem {
+    let body = field(value, mapping.body.as_deref(), BODY_KEYS)
+        // Unknown shapes still reach the digest as their raw JSON
+        .unwrap_or_else(|| serde_json::to_string_pretty(value).unwrap_or_default());
+    let id = field(value, mapping.id.as_deref(), ID_KEYS)
+        .unwrap_or_else(|| format!("{:x}", Sha256::digest(value.to_string()))[..16].to_string());
+
+    WebhookItem {
+        id,
+        title: field(value, mapping.title.as_deref(), TITLE_KEYS),
+        body,
+        author: field(value, mapping.author.as_deref(), AUTHOR_KEYS),
+        channel: mapping
+            .channel
+            .as_deref()
+            .and_then(|spec| render(value, spec)),
+        url: field(value, mapping.url.as_deref(), URL_KEYS),
+        created_at: field(value, mapping.created_at.as_deref(), TIME_KEYS)
+            .and_then(|t| parse_time(&t)),
+        content_type: mapping
+            .content_type
+            .clone()
+            .unwrap_or_else(|| "event".to_string()),
+    }
+}
+
+/// Map a payload to items: one per element of `mapping.items` (or of a
+/// top-level array), otherwise one for the whole payload
+pub fn map_payload(payload: &Value, mapping: &WebhookMapping) -> Vec<WebhookItem> {
+    let elements = match mapping.items.as_deref() {
+        Some(path) => lookup(payload, path),
+        None => Some(payload),
+    };
+
+    match elements {
+        Some(Value::Array(items)) => items.iter().map(|item| map_item(item, mapping)).collect(),
+        Some(item) => vec![map_item(item, mapping)],
+        None => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_map_payload_with_templates_and_items() {
+        let payload = json!({
+            "receiver": "companion",
+            "alerts": [
+                {
+                    "fingerprint": "a1",
+                    "labels": { "alertname": "HighLatency", "service": "api" },
+                    "annotations": { "description": "p99 above 2s" },
+                    "startsAt": "2024-03-05T10:00:00Z",
+                    "generatorURL": "https://prometheus.example.com/graph"
+                },
+                { "fingerprint": "b2", "labels": { "alertname": "DiskFull" } }
+            ]
+        });
+        let mapping = WebhookMapping {
+            items: Some("alerts".into()),
+            title: Some("{labels.alertname} on {labels.service}".into()),
+            body: Some("annotations.description".into()),
+            channel: Some("Alerts: {labels.service}".into()),
+            url: Some("generatorURL".into()),
+            created_at: Some("startsAt".into()),
+            ..Default::default()
+        };
+
+        let items = map_payload(&payload, &mapping);
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].id, "a1");
+        assert_eq!(items[0].title.as_deref(), Some("HighLatency on api"));
+        assert_eq!(items[0].body, "p99 above 2s");
+        assert_eq!(items[0].channel.as_deref(), Some("Alerts: api"));
+        assert_eq!(items[0].created_at, Some(1_709_632_800_000));
+        assert_eq!(items[1].title.as_deref(), Some("DiskFull on"));
+        assert!(items[1].body.contains("\"DiskFull\""));
+    }
+
+    #[test]
+    fn test_map_payload_defaults() {
+        let payload = json!({
+            "title": "Deploy finished",
+            "text": "v1.4.2 is live",
+            "user": "ci-bot",
+            "timestamp": 1709632800,
+            "commits": [{ "message": "Fix login" }]
+        });
+        let items = map_payload(&payload, &WebhookMapping::default());
+
+        assert_eq!(items.len(), 1);
+        let item = &items[0];
+        assert_eq!(item.title.as_deref(), Some("Deploy finished"));
+        assert_eq!(item.body, "v1.4.2 is live");
+        assert_eq!(item.author.as_deref(), Some("ci-bot"));
+        assert_eq!(item.created_at, Some(1_709_632_800_000));
+        assert_eq!(item.content_type, "event");
+        assert_eq!(item.id.len(), 16);
+        assert_eq!(
+            map_payload(&payload, &WebhookMapping::default())[0].id,
+            item.id
+        );
+        assert_eq!(
+            render(&payload, "commits.0.message").as_deref(),
+            Some("Fix login")
+        );
+    }
+}
@@ -0,0 +1,16 @@
+//! Inbound webhook integration module
+//!
+//! This module runs a loopback HTTP listener that CI systems, alerting tools and
+//! scripts push JSON events to, mapping each payload into content items through
+//! a per-endpoint field mapping.
+
+mod connector;
+mod mapping;
+mod server;
+mod sync;
+mod types;
+
+pub use connector::WebhookConnector;
+pub use mapping::map_payload;
+pub use server::serve_webhooks;
+pub use types::{WebhookConfig, WebhookEndpoint, WebhookError, WebhookItem, WebhookMapping};
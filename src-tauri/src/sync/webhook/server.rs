@@ -0,0 +1,289 @@
+//! Loopback HTTP listener for inbound webhooks
+//!
+//! Like the OAuth callback server in `sync::oauth`, but long-lived: it runs
+//! while at least one webhook endpoint is configured and accepts
+//! `POST /hooks/<id>` with a JSON body and the endpoint's shared secret in an
+//! `Authorization: Bearer` or `X-Webhook-Secret` header.
+
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::connector::load_webhook_config;
+use super::mapping::map_payload;
+use super::sync::store_webhook_items;
+use crate::sync::connector::ConnectorContext;
+
+/// How often to check whether endpoints or the port changed
+const CONFIG_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Slow or stalled clients are dropped after this long
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+struct Response {
+    status: u16,
+    body: serde_json::Value,
+}
+
+impl Response {
+    fn new(status: u16, body: serde_json::Value) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::new(status, serde_json::json!({ "error": message }))
+    }
+
+    fn to_http(&self) -> String {
+        let reason = match self.status {
+            200 => "OK",
+            202 => "Accepted",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            _ => "Internal Server Error",
+        };
+        let body = self.body.to_string();
+        format!(
+            "HTTP/1.1 {} {}\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: {}\r\n\
+             Connection: close\r\n\r\n{}",
+            self.status,
+            reason,
+            body.len(),
+            body
+        )
+    }
+}
+
+/// Parse the request line and headers; returns the request without its body
+/// and the body length announced by `Content-Length`
+fn parse_head(head: &str) -> Option<(Request, usize)> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?;
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+    let length = match headers.get("content-length") {
+        Some(length) => length.parse().ok()?,
+        None => 0,
+    };
+
+    Some((
+        Request {
+            method,
+            path,
+            headers,
+            body: Vec::new(),
+        },
+        length,
+    ))
+}
+
+async fn read_request(socket: &mut TcpStream) -> Result<Request, Response> {
+    let mut buffer = Vec::with_capacity(4096);
+    let mut chunk = [0u8; 4096];
+
+    let head_end = loop {
+        if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end;
+        }
+        if buffer.len() > MAX_HEADER_BYTES {
+            return Err(Response::error(400, "headers too large"));
+        }
+        match socket.read(&mut chunk).await {
+            Ok(0) | Err(_) => return Err(Response::error(400, "incomplete request")),
+            Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_end]).to_string();
+    let (mut request, length) =
+        parse_head(&head).ok_or_else(|| Response::error(400, "malformed request"))?;
+    if length > MAX_BODY_BYTES {
+        return Err(Response::error(413, "payload too large"));
+    }
+
+    let mut body = buffer.split_off(head_end + 4);
+    while body.len() < length {
+        match socket.read(&mut chunk).await {
+            Ok(0) | Err(_) => return Err(Response::error(400, "incomplete body")),
+            Ok(n) => body.extend_from_slice(&chunk[..n]),
+        }
+    }
+    body.truncate(length);
+    request.body = body;
+
+    Ok(request)
+}
+
+/// Secret from `Authorization: Bearer <secret>` or `X-Webhook-Secret`
+fn presented_secret(request: &Request) -> Option<&str> {
+    request
+        .headers
+        .get("authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or_else(|| request.headers.get("x-webhook-secret").map(String::as_str))
+        .map(str::trim)
+}
+
+/// Compare without short-circuiting so timing doesn't leak the secret
+fn secrets_match(expected: &str, presented: &str) -> bool {
+    expected.len() == presented.len()
+        && expected
+            .bytes()
+            .zip(presented.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+async fn handle_request(ctx: &ConnectorContext, request: Request) -> Response {
+    if request.path == "/health" {
+        return Response::new(200, serde_json::json!({ "status": "ok" }));
+    }
+    let Some(endpoint_id) = request.path.strip_prefix("/hooks/") else {
+        return Response::error(404, "unknown path");
+    };
+    if request.method != "POST" {
+        return Response::error(405, "use POST");
+    }
+
+    let config = match load_webhook_config(ctx).await {
+        Ok(config) => config,
+        Err(e) => return Response::error(500, &e.to_string()),
+    };
+    let Some(endpoint) = config.endpoint(endpoint_id.trim_end_matches('/')) else {
+        return Response::error(404, "unknown webhook");
+    };
+    if !presented_secret(&request).is_some_and(|s| secrets_match(&endpoint.secret, s)) {
+        tracing::warn!("Rejected webhook for {}: bad secret", endpoint.id);
+        return Response::error(401, "invalid secret");
+    }
+
+    let payload: serde_json::Value = match serde_json::from_slice(&request.body) {
+        Ok(payload) => payload,
+        Err(e) => return Response::error(400, &format!("invalid JSON: {}", e)),
+    };
+    let items = map_payload(&payload, &endpoint.mapping);
+
+    match store_webhook_items(ctx, endpoint, &items).await {
+        Ok(stored) => {
+            tracing::info!("Webhook {} stored {} events", endpoint.id, stored);
+            Response::new(202, serde_json::json!({ "stored": stored }))
+        }
+        Err(e) => {
+            tracing::error!("Webhook {} failed: {}", endpoint.id, e);
+            Response::error(500, &e.to_string())
+        }
+    }
+}
+
+async fn handle_connection(ctx: ConnectorContext, mut socket: TcpStream) {
+    let response = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut socket)).await {
+        Ok(Ok(request)) => handle_request(&ctx, request).await,
+        Ok(Err(response)) => response,
+        Err(_) => Response::error(400, "request timed out"),
+    };
+    let _ = socket.write_all(response.to_http().as_bytes()).await;
+    let _ = socket.shutdown().await;
+}
+
+/// Port to listen on, or `None` while no endpoint is configured
+async fn listener_port(ctx: &ConnectorContext) -> Option<u16> {
+    load_webhook_config(ctx)
+        .await
+        .ok()
+        .filter(|config| !config.endpoints.is_empty())
+        .map(|config| config.port)
+}
+
+/// Serve webhooks for the lifetime of the app, starting, stopping and
+/// rebinding as endpoints are added or removed and the port changes
+pub async fn serve_webhooks(ctx: ConnectorContext) {
+    loop {
+        let Some(port) = listener_port(&ctx).await else {
+            tokio::time::sleep(CONFIG_CHECK_INTERVAL).await;
+            continue;
+        };
+
+        let listener = match TcpListener::bind(("127.0.0.1", port)).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                tracing::warn!("Cannot listen for webhooks on port {}: {}", port, e);
+                tokio::time::sleep(CONFIG_CHECK_INTERVAL).await;
+                continue;
+            }
+        };
+        tracing::info!("Listening for webhooks on 127.0.0.1:{}", port);
+
+        let mut config_check = tokio::time::interval(CONFIG_CHECK_INTERVAL);
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((socket, _)) => {
+                        tokio::spawn(handle_connection(ctx.clone(), socket));
+                    }
+                    Err(e) => tracing::debug!("Webhook accept error: {}", e),
+                },
+                _ = config_check.tick() => {
+                    if listener_port(&ctx).await != Some(port) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        tracing::info!("Stopped listening for webhooks on port {}", port);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_head() {
+        let head = "POST /hooks/ci?x=1 HTTP/1.1\r\nHost: 127.0.0.1\r\nContent-Length: 42\r\n\
+Authorization: Bearer s3cret";
+        let (request, length) = parse_head(head).unwrap();
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/hooks/ci");
+        assert_eq!(length, 42);
+        assert_eq!(presented_secret(&request), Some("s3cret"));
+        assert!(parse_head("POST /hooks/ci HTTP/1.1\r\nContent-Length: nope").is_none());
+    }
+
+    #[test]
+    fn test_secrets_match() {
+        assert!(secrets_match("abc123", "abc123"));
+        assert!(!secrets_match("abc123", "abc124"));
+        assert!(!secrets_match("abc123", "abc1234"));
+    }
+
+    #[test]
+    fn test_response_to_http() {
+        let http = Response::new(202, serde_json::json!({ "stored": 2 })).to_http();
+        assert!(http.starts_with("HTTP/1.1 202 Accepted\r\n"));
+        assert!(http.contains("Content-Length: 12\r\n"));
+        assert!(http.ends_with("{\"stored\":2}"));
+    }
+}
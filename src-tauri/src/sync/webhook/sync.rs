@@ -0,0 +1,84 @@
+//! Storage for received webhook events
+
+use super::types::{WebhookEndpoint, WebhookError, WebhookItem};
+use crate::sync::connector::{save_cursor, ConnectorContext};
+
+/// `sync_state` resource recording when each endpoint last received events
+pub(super) const ENDPOINT_RESOURCE: &str = "endpoint";
+
+async fn store_item(
+    ctx: &ConnectorContext,
+    endpoint: &WebhookEndpoint,
+    item: &WebhookItem,
+    now: i64,
+) -> Result<(), WebhookError> {
+    let encrypted_body = ctx
+        .crypto
+        .encrypt_string(&item.body)
+        .map_err(|e| WebhookError::Crypto(e.to_string()))?;
+    let source_id = format!("{}:{}", endpoint.id, item.id);
+    let channel = item.channel.as_deref().unwrap_or(&endpoint.name);
+    let created_at = item.created_at.unwrap_or(now);
+    let metadata = serde_json::json!({ "endpoint": endpoint.id }).to_string();
+
+    sqlx::query(
+        "INSERT INTO content_items (id, source, source_id, source_url, content_type, title, body, author_id, channel_or_project, parent_id, created_at, updated_at, synced_at, metadata)
+         VALUES (?, 'webhook', ?, ?, ?, ?, ?, ?, ?, NULL, ?, ?, ?, ?)
+         ON CONFLICT(source, source_id) DO UPDATE SET source_url = ?, content_type = ?, title = ?, body = ?, author_id = ?, channel_or_project = ?, updated_at = ?, synced_at = ?, metadata = ?"
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(&source_id)
+    .bind(&item.url)
+    .bind(&item.content_type)
+    .bind(&item.title)
+    .bind(&encrypted_body)
+    .bind(&item.author)
+    .bind(channel)
+    .bind(created_at)
+    .bind(now)
+    .bind(now)
+    .bind(&metadata)
+    .bind(&item.url)
+    .bind(&item.content_type)
+    .bind(&item.title)
+    .bind(&encrypted_body)
+    .bind(&item.author)
+    .bind(channel)
+    .bind(now)
+    .bind(now)
+    .bind(&metadata)
+    .execute(ctx.db.pool())
+    .await?;
+
+    Ok(())
+}
+
+/// Store events received on an endpoint. Redelivered events with the same id
+/// update the stored item instead of duplicating it.
+pub async fn store_webhook_items(
+    ctx: &ConnectorContext,
+    endpoint: &WebhookEndpoint,
+    items: &[WebhookItem],
+) -> Result<i32, WebhookError> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let store = async {
+        for item in items {
+            store_item(ctx, endpoint, item, now).await?;
+        }
+        Ok::<_, WebhookError>(())
+    };
+    ctx.track_resource("webhook", ENDPOINT_RESOURCE, &endpoint.id, store)
+        .await?;
+
+    let received_at = chrono::Utc::now().to_rfc3339();
+    save_cursor(
+        &ctx.db,
+        "webhook",
+        ENDPOINT_RESOURCE,
+        &endpoint.id,
+        &received_at,
+    )
+    .await?;
+
+    Ok(items.len() as i32)
+}
@@ -0,0 +1,99 @@
+//! Webhook data types and error definitions
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Loopback port the listener binds when none is configured
+pub const DEFAULT_WEBHOOK_PORT: u16 = 8380;
+
+#[derive(Error, Debug)]
+pub enum WebhookError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Crypto error: {0}")]
+    Crypto(String),
+}
+
+/// How a JSON payload becomes content items.
+///
+/// Each field is either a dotted path into the payload (`alert.labels.severity`,
+/// `commits.0.message`) or a template with `{path}` placeholders
+/// (`{pipeline.name} failed on {ref}`). Unset fields fall back to common keys
+/// such as `title`, `text` or `timestamp`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookMapping {
+    /// Path to an array in the payload; each element becomes one item
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    /// Literal `content_type`, `event` by default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+}
+
+/// One tool pushing to `/hooks/<id>`, authenticated by its own secret
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEndpoint {
+    pub id: String,
+    pub name: String,
+    pub secret: String,
+    #[serde(default)]
+    pub mapping: WebhookMapping,
+}
+
+/// Listener settings and endpoints; stored encrypted since it holds secrets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookConfig {
+    pub port: u16,
+    pub endpoints: Vec<WebhookEndpoint>,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            port: DEFAULT_WEBHOOK_PORT,
+            endpoints: Vec::new(),
+        }
+    }
+}
+
+impl WebhookConfig {
+    pub fn endpoint(&self, id: &str) -> Option<&WebhookEndpoint> {
+        self.endpoints.iter().find(|e| e.id == id)
+    }
+
+    pub fn endpoint_url(&self, id: &str) -> String {
+        format!("http://127.0.0.1:{}/hooks/{}", self.port, id)
+    }
+}
+
+/// A payload mapped to `content_items` columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookItem {
+    pub id: String,
+    pub title: Option<String>,
+    pub body: String,
+    pub author: Option<String>,
+    pub channel: Option<String>,
+    pub url: Option<String>,
+    /// Epoch milliseconds
+    pub created_at: Option<i64>,
+    pub content_type: String,
+}
@@ -1,4 +1,5 @@
 use crate::pipeline::{PipelineManager, PipelineState, TaskStatus};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 use std::sync::Arc;
 use tauri::{
     image::Image,
@@ -40,7 +41,7 @@ const TRAY_ICON: &[u8] = include_bytes!("../../icons/tray-icon.png");
 
 pub fn init_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
     let icon = Image::from_bytes(TRAY_ICON)?;
-    let menu = build_tray_menu(app, None)?;
+    let menu = build_tray_menu(app, None, true)?;
 
     let _tray = TrayIconBuilder::with_id("main")
         .icon(icon)
@@ -59,6 +60,7 @@ pub fn init_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
 fn build_tray_menu(
     app: &AppHandle,
     pipeline_state: Option<&PipelineState>,
+    is_online: bool,
 ) -> Result<Menu<tauri::Wry>, Box<dyn std::error::Error>> {
     let show_window = MenuItem::with_id(app, "show", "Show Companion", true, None::<&str>)?;
     let open_settings = MenuItem::with_id(app, "settings", "Open Settings", true, None::<&str>)?;
@@ -66,6 +68,8 @@ fn build_tray_menu(
     let is_syncing = pipeline_state.map(|s| s.is_busy).unwrap_or(false);
     let sync_label = if is_syncing {
         "⟳ Syncing..."
+    } else if !is_online {
+        "Sync Now (Offline)"
     } else {
         "Sync Now"
     };
@@ -128,6 +132,7 @@ fn build_activity_submenu(
                     TaskStatus::Failed => "✗",
                     TaskStatus::Running => "◎",
                     TaskStatus::Pending => "○",
+                    TaskStatus::Cancelled => "⊘",
                 };
                 let label = format!("{} {}", status_icon, task.task_type.display_name());
                 MenuItem::with_id(
@@ -176,7 +181,28 @@ fn handle_menu_event(app: &AppHandle, event_id: &str) {
     }
 }
 
-pub fn spawn_tray_updater(app_handle: AppHandle, pipeline: Arc<Mutex<PipelineManager>>) {
+/// Tooltip while idle, with the next scheduled sync when there is one, or the
+/// offline state while sources cannot be reached
+fn idle_tooltip(message: String, next_sync_at: i64, is_online: bool) -> String {
+    if !is_online {
+        return format!("{} - Offline", message);
+    }
+    match chrono::DateTime::from_timestamp_millis(next_sync_at).filter(|_| next_sync_at > 0) {
+        Some(next) => format!(
+            "{} - Next sync at {}",
+            message,
+            next.with_timezone(&chrono::Local).format("%H:%M")
+        ),
+        None => message,
+    }
+}
+
+pub fn spawn_tray_updater(
+    app_handle: AppHandle,
+    pipeline: Arc<Mutex<PipelineManager>>,
+    next_sync_at: Arc<AtomicI64>,
+    is_online: Arc<AtomicBool>,
+) {
     tauri::async_runtime::spawn(async move {
         let mut cache = TrayCache::new();
         let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(2));
@@ -189,6 +215,13 @@ pub fn spawn_tray_updater(app_handle: AppHandle, pipeline: Arc<Mutex<PipelineMan
             let state = pipeline.get_state().await;
             drop(pipeline);
 
+            let online = is_online.load(Ordering::SeqCst);
+            let message = if state.is_busy {
+                message
+            } else {
+                idle_tooltip(message, next_sync_at.load(Ordering::SeqCst), online)
+            };
+
             if let Some(tray) = app_handle.tray_by_id("main") {
                 if cache.needs_update(&state, &message) {
                     tracing::debug!(
@@ -199,7 +232,7 @@ pub fn spawn_tray_updater(app_handle: AppHandle, pipeline: Arc<Mutex<PipelineMan
                     );
                     cache.update(&state, &message);
                     let _ = tray.set_tooltip(Some(&message));
-                    if let Ok(menu) = build_tray_menu(&app_handle, Some(&state)) {
+                    if let Ok(menu) = build_tray_menu(&app_handle, Some(&state), online) {
                         let _ = tray.set_menu(Some(menu));
                     }
                 }
@@ -295,6 +328,22 @@ mod tests {
         assert!(cache.needs_update(&idle_state, "Companion"));
     }
 
+    #[test]
+    fn test_idle_tooltip_shows_next_sync() {
+        assert_eq!(idle_tooltip("Companion".to_string(), 0, true), "Companion");
+
+        let tooltip = idle_tooltip("Companion".to_string(), 1_700_000_000_000, true);
+        assert!(tooltip.starts_with("Companion - Next sync at "));
+    }
+
+    #[test]
+    fn test_idle_tooltip_shows_offline() {
+        assert_eq!(
+            idle_tooltip("Companion".to_string(), 1_700_000_000_000, false),
+            "Companion - Offline"
+        );
+    }
+
     #[test]
     fn test_tray_cache_detects_active_task_change() {
         let mut cache = TrayCache::new();
@@ -7,8 +7,15 @@ vi.mock('../hooks/usePipeline', () => ({
   getTaskDisplayName: (type: string) => `Display: ${type}`,
 }))
 
+vi.mock('../lib/api', () => ({
+  api: {
+    cancelTask: vi.fn().mockResolvedValue(true),
+  },
+}))
+
 import { PipelineStatus } from './PipelineStatus'
 import { usePipeline } from '../hooks/usePipeline'
+import { api } from '../lib/api'
 
 const mockUsePipeline = usePipeline as ReturnType<typeof vi.fn>
 
@@ -95,6 +102,32 @@ describe('PipelineStatus', () => {
     expect(screen.getByText('50%')).toBeInTheDocument()
   })
 
+  it('cancels a running task', () => {
+    mockUsePipeline.mockReturnValue({
+      activeTasks: [
+        {
+          id: 'task-1',
+          task_type: 'sync_slack',
+          status: 'running',
+          message: 'Syncing messages',
+          progress: 0.5,
+          started_at: Date.now() / 1000,
+          completed_at: null,
+          error: null,
+        },
+      ],
+      recentHistory: [],
+      isBusy: true,
+      taskCount: 1,
+    })
+
+    render(<PipelineStatus />)
+    fireEvent.click(screen.getByTitle('1 task(s) running'))
+    fireEvent.click(screen.getByTitle('Cancel task'))
+
+    expect(api.cancelTask).toHaveBeenCalledWith('task-1')
+  })
+
   it('shows recent history in dropdown', () => {
     mockUsePipeline.mockReturnValue({
       activeTasks: [],
@@ -1,7 +1,8 @@
 import { useState, useRef, useEffect } from 'react'
 import { clsx } from 'clsx'
-import { Activity, CheckCircle, XCircle, Loader2, ChevronDown } from 'lucide-react'
+import { Activity, Ban, CheckCircle, XCircle, Loader2, ChevronDown, X } from 'lucide-react'
 import { usePipeline, getTaskDisplayName, type PipelineTask } from '../hooks/usePipeline'
+import { api } from '../lib/api'
 
 function formatTime(timestamp: number): string {
   const date = new Date(timestamp * 1000)
@@ -12,6 +13,7 @@ function TaskItem({ task }: { task: PipelineTask }) {
   const isRunning = task.status === 'running'
   const isCompleted = task.status === 'completed'
   const isFailed = task.status === 'failed'
+  const isCancelled = task.status === 'cancelled'
 
   return (
     <div className="flex items-start gap-3 px-3 py-2">
@@ -19,6 +21,7 @@ function TaskItem({ task }: { task: PipelineTask }) {
         {isRunning && <Loader2 className="h-4 w-4 animate-spin text-primary-500" />}
         {isCompleted && <CheckCircle className="h-4 w-4 text-green-500" />}
         {isFailed && <XCircle className="h-4 w-4 text-red-500" />}
+        {isCancelled && <Ban className="h-4 w-4 text-muted-foreground" />}
       </div>
       <div className="flex-1 min-w-0">
         <p className="text-sm font-medium text-foreground truncate">{task.message}</p>
@@ -30,6 +33,15 @@ function TaskItem({ task }: { task: PipelineTask }) {
         </p>
       </div>
       <span className="text-xs text-muted-foreground">{formatTime(task.started_at)}</span>
+      {isRunning && (
+        <button
+          onClick={() => api.cancelTask(task.id).catch(console.error)}
+          className="rounded p-0.5 text-muted-foreground hover:bg-muted hover:text-foreground"
+          title="Cancel task"
+        >
+          <X className="h-3.5 w-3.5" />
+        </button>
+      )}
     </div>
   )
 }
@@ -19,6 +19,8 @@ import { Input } from './ui/Input'
 import { useAppStore } from '../store'
 import { usePreferences } from '../hooks/usePreferences'
 import { useSlackChannels } from '../hooks/useSlackChannels'
+import { useSyncIntervals } from '../hooks/useSyncIntervals'
+import { SyncIntervalSelect } from './SyncIntervalSelect'
 import {
   type ChannelType,
   getChannelType,
@@ -56,6 +58,7 @@ export function SlackChannelSelector({
   
   const setSlackState = useAppStore((s) => s.setSlackState)
   const { preferences, save: savePreferences } = usePreferences()
+  const { intervalFor, setInterval: setSyncInterval } = useSyncIntervals()
 
   const {
     userMap,
@@ -242,39 +245,57 @@ export function SlackChannelSelector({
                             const displayName = getChannelDisplayName(channel, channelType, userMap)
                             
                             return (
-                              <button
+                              <div
                                 key={channel.id}
-                                onClick={() => toggleChannel(channel.id)}
                                 className={clsx(
-                                  'w-full flex items-center gap-3 px-4 py-2.5 transition-colors text-left',
+                                  'flex items-center gap-3 pr-4 transition-colors',
                                   isSelected
                                     ? 'bg-primary-50 dark:bg-primary-900/20'
                                     : 'hover:bg-muted/50'
                                 )}
                               >
-                                {isSelected ? (
-                                  <CheckSquare className="h-4 w-4 text-primary-500 flex-shrink-0" />
-                                ) : (
-                                  <Square className="h-4 w-4 text-muted-foreground flex-shrink-0" />
-                                )}
-                                <div className="flex-1 min-w-0">
-                                  <div className="flex items-center gap-2">
-                                    <span className="text-sm font-medium text-foreground truncate">
-                                      {displayName}
-                                    </span>
-                                    {channel.memberCount != null && channel.memberCount > 0 && (
-                                      <span className="text-xs text-muted-foreground flex-shrink-0">
-                                        {channel.memberCount} members
+                                <button
+                                  onClick={() => toggleChannel(channel.id)}
+                                  className="flex-1 min-w-0 flex items-center gap-3 px-4 py-2.5 text-left"
+                                >
+                                  {isSelected ? (
+                                    <CheckSquare className="h-4 w-4 text-primary-500 flex-shrink-0" />
+                                  ) : (
+                                    <Square className="h-4 w-4 text-muted-foreground flex-shrink-0" />
+                                  )}
+                                  <div className="flex-1 min-w-0">
+                                    <div className="flex items-center gap-2">
+                                      <span className="text-sm font-medium text-foreground truncate">
+                                        {displayName}
                                       </span>
+                                      {channel.memberCount != null && channel.memberCount > 0 && (
+                                        <span className="text-xs text-muted-foreground flex-shrink-0">
+                                          {channel.memberCount} members
+                                        </span>
+                                      )}
+                                    </div>
+                                    {channel.purpose && (
+                                      <p className="text-xs text-muted-foreground truncate mt-0.5">
+                                        {channel.purpose}
+                                      </p>
                                     )}
                                   </div>
-                                  {channel.purpose && (
-                                    <p className="text-xs text-muted-foreground truncate mt-0.5">
-                                      {channel.purpose}
-                                    </p>
-                                  )}
-                                </div>
-                              </button>
+                                </button>
+                                {isSelected && (
+                                  <SyncIntervalSelect
+                                    aria-label={`${displayName} sync frequency`}
+                                    value={intervalFor('slack', { resourceType: 'channel', resourceId: channel.id })}
+                                    onChange={(intervalMinutes) =>
+                                      setSyncInterval({
+                                        source: 'slack',
+                                        intervalMinutes,
+                                        scope: { resourceType: 'channel', resourceId: channel.id },
+                                      })
+                                    }
+                                    inheritLabel="Same as Slack"
+                                  />
+                                )}
+                              </div>
                             )
                           })}
                         </div>
@@ -0,0 +1,221 @@
+import { useState, useEffect, useCallback, useMemo } from 'react'
+import { X, Search, CheckSquare, Square, RefreshCw, Save } from 'lucide-react'
+import { Button } from './ui/Button'
+import { Input } from './ui/Input'
+import { SyncIntervalSelect } from './SyncIntervalSelect'
+import { useSyncIntervals } from '../hooks/useSyncIntervals'
+import { api, type SourceInfo, type SourceScope } from '../lib/api'
+
+interface SourceScopeSelectorProps {
+  source: SourceInfo | null
+  onClose: () => void
+  onSave?: () => void
+}
+
+/**
+ * Picks which scopes (repositories, projects, ...) a connected source syncs and,
+ * where supported, how often each one syncs. Sources without scope selection
+ * only show the frequency of each scope.
+ */
+export function SourceScopeSelector({ source, onClose, onSave }: SourceScopeSelectorProps) {
+  const [scopes, setScopes] = useState<SourceScope[]>([])
+  const [selectedIds, setSelectedIds] = useState<Set<string>>(new Set())
+  const [searchQuery, setSearchQuery] = useState('')
+  const [isLoading, setIsLoading] = useState(false)
+  const [isSaving, setIsSaving] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+  const { intervalFor, setInterval: setSyncInterval } = useSyncIntervals()
+
+  const loadScopes = useCallback(async () => {
+    if (!source) return
+    setIsLoading(true)
+    setError(null)
+    try {
+      const result = await api.listSourceScopes(source.id)
+      setScopes(result)
+      setSelectedIds(new Set(result.filter((s) => s.selected).map((s) => s.id)))
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e))
+    } finally {
+      setIsLoading(false)
+    }
+  }, [source])
+
+  useEffect(() => {
+    loadScopes()
+  }, [loadScopes])
+
+  const filteredScopes = useMemo(() => {
+    const query = searchQuery.trim().toLowerCase()
+    if (!query) return scopes
+    return scopes.filter((s) => s.name.toLowerCase().includes(query))
+  }, [scopes, searchQuery])
+
+  const toggleScope = (id: string) => {
+    setSelectedIds((prev) => {
+      const next = new Set(prev)
+      if (next.has(id)) {
+        next.delete(id)
+      } else {
+        next.add(id)
+      }
+      return next
+    })
+  }
+
+  const handleSave = async () => {
+    if (!source) return
+    setIsSaving(true)
+    setError(null)
+    try {
+      await api.selectSourceScopes(source.id, Array.from(selectedIds))
+      onSave?.()
+      onClose()
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e))
+    } finally {
+      setIsSaving(false)
+    }
+  }
+
+  if (!source) return null
+
+  const canSelect = source.scopeSelection
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/50">
+      <div className="bg-background border border-border rounded-xl shadow-xl w-full max-w-2xl max-h-[80vh] flex flex-col">
+        {/* Header */}
+        <div className="flex items-center justify-between px-6 py-4 border-b border-border">
+          <div>
+            <h2 className="text-lg font-semibold text-foreground">
+              Configure {source.displayName}
+            </h2>
+            <p className="text-sm text-muted-foreground mt-0.5">
+              {canSelect
+                ? 'Choose what to include in your daily digest'
+                : 'Choose how often each one syncs'}
+            </p>
+          </div>
+          <button
+            onClick={onClose}
+            className="p-2 hover:bg-muted rounded-lg transition-colors"
+          >
+            <X className="h-5 w-5 text-muted-foreground" />
+          </button>
+        </div>
+
+        {/* Search and Actions */}
+        <div className="px-6 py-3 border-b border-border flex items-center gap-3">
+          <div className="relative flex-1">
+            <Search className="absolute left-3 top-1/2 -translate-y-1/2 h-4 w-4 text-muted-foreground" />
+            <Input
+              type="text"
+              placeholder="Search..."
+              value={searchQuery}
+              onChange={(e) => setSearchQuery(e.target.value)}
+              className="pl-9"
+            />
+          </div>
+          {canSelect && (
+            <>
+              <button
+                onClick={() => setSelectedIds(new Set(scopes.map((s) => s.id)))}
+                className="text-sm text-primary-500 hover:text-primary-600"
+              >
+                Select All
+              </button>
+              <span className="text-muted-foreground">|</span>
+              <button
+                onClick={() => setSelectedIds(new Set())}
+                className="text-sm text-muted-foreground hover:text-foreground"
+              >
+                Deselect All
+              </button>
+            </>
+          )}
+        </div>
+
+        {/* Scope List */}
+        <div className="flex-1 overflow-y-auto px-6 py-4">
+          {isLoading ? (
+            <div className="flex items-center justify-center py-12">
+              <RefreshCw className="h-6 w-6 animate-spin text-muted-foreground" />
+              <span className="ml-2 text-muted-foreground">Loading...</span>
+            </div>
+          ) : error ? (
+            <div className="text-center py-12">
+              <p className="text-red-500 max-w-md mx-auto">{error}</p>
+              <Button onClick={loadScopes} variant="outline" className="mt-4">
+                Retry
+              </Button>
+            </div>
+          ) : filteredScopes.length === 0 ? (
+            <p className="text-center py-12 text-muted-foreground">Nothing to select</p>
+          ) : (
+            <div className="space-y-1">
+              {filteredScopes.map((scope) => {
+                const selected = selectedIds.has(scope.id)
+                const resource = { resourceType: scope.kind, resourceId: scope.id }
+                return (
+                  <div
+                    key={scope.id}
+                    className="flex items-center gap-3 px-3 py-2 rounded-lg hover:bg-muted transition-colors"
+                  >
+                    {canSelect ? (
+                      <button
+                        onClick={() => toggleScope(scope.id)}
+                        className="flex-1 flex items-center gap-3 text-left"
+                      >
+                        {selected ? (
+                          <CheckSquare className="h-4 w-4 text-primary-500" />
+                        ) : (
+                          <Square className="h-4 w-4 text-muted-foreground" />
+                        )}
+                        <span className="text-sm text-foreground">{scope.name}</span>
+                      </button>
+                    ) : (
+                      <span className="flex-1 text-sm text-foreground">{scope.name}</span>
+                    )}
+                    {source.scopeIntervals && selected && (
+                      <SyncIntervalSelect
+                        aria-label={`${scope.name} sync frequency`}
+                        value={intervalFor(source.id, resource)}
+                        onChange={(intervalMinutes) =>
+                          setSyncInterval({ source: source.id, intervalMinutes, scope: resource })
+                        }
+                        inheritLabel={`Same as ${source.displayName}`}
+                      />
+                    )}
+                  </div>
+                )
+              })}
+            </div>
+          )}
+        </div>
+
+        {/* Footer */}
+        <div className="flex items-center justify-between px-6 py-4 border-t border-border">
+          <span className="text-sm text-muted-foreground">
+            {canSelect ? `${selectedIds.size} selected` : 'Frequency changes apply immediately'}
+          </span>
+          <div className="flex gap-3">
+            <Button variant="outline" onClick={onClose}>
+              {canSelect ? 'Cancel' : 'Done'}
+            </Button>
+            {canSelect && (
+              <Button onClick={handleSave} disabled={isSaving || isLoading}>
+                {isSaving ? (
+                  <RefreshCw className="h-4 w-4 animate-spin" />
+                ) : (
+                  <Save className="h-4 w-4" />
+                )}
+                Save
+              </Button>
+            )}
+          </div>
+        </div>
+      </div>
+    </div>
+  )
+}
@@ -0,0 +1,51 @@
+import { clsx } from 'clsx'
+
+const INTERVAL_OPTIONS: { value: number; label: string }[] = [
+  { value: 5, label: 'Every 5 minutes' },
+  { value: 15, label: 'Every 15 minutes' },
+  { value: 30, label: 'Every 30 minutes' },
+  { value: 60, label: 'Every hour' },
+  { value: 240, label: 'Every 4 hours' },
+  { value: 1440, label: 'Once a day' },
+]
+
+interface SyncIntervalSelectProps {
+  value: number | null
+  onChange: (intervalMinutes: number | null) => void
+  /** Label of the empty option, i.e. what applies without an interval */
+  inheritLabel: string
+  disabled?: boolean
+  className?: string
+  'aria-label'?: string
+}
+
+/** Chooses how often a source or scope syncs in the background */
+export function SyncIntervalSelect({
+  value,
+  onChange,
+  inheritLabel,
+  disabled,
+  className,
+  'aria-label': ariaLabel = 'Sync frequency',
+}: SyncIntervalSelectProps) {
+  return (
+    <select
+      aria-label={ariaLabel}
+      value={value ?? ''}
+      onChange={(e) => onChange(e.target.value === '' ? null : Number(e.target.value))}
+      onClick={(e) => e.stopPropagation()}
+      disabled={disabled}
+      className={clsx(
+        'rounded-lg border border-border bg-background px-2 py-1 text-xs text-foreground focus:outline-none focus:ring-2 focus:ring-primary-500',
+        className
+      )}
+    >
+      <option value="">{inheritLabel}</option>
+      {INTERVAL_OPTIONS.map((option) => (
+        <option key={option.value} value={option.value}>
+          {option.label}
+        </option>
+      ))}
+    </select>
+  )
+}
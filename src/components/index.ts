@@ -18,6 +18,8 @@ export { UpdateNotification } from './UpdateNotification'
 
 // Integration components
 export { SlackChannelSelector } from './SlackChannelSelector'
+export { SourceScopeSelector } from './SourceScopeSelector'
+export { SyncIntervalSelect } from './SyncIntervalSelect'
 
 // Export components
 export { ExportMenu } from './ExportMenu'
@@ -7,6 +7,7 @@ export { useAnalytics } from './useAnalytics'
 export { useUpdater } from './useUpdater'
 export { useSetupStatus } from './useSetupStatus'
 export { useSlackChannels } from './useSlackChannels'
+export { useSyncIntervals } from './useSyncIntervals'
 
 export type {
   DigestNotification,
@@ -1,9 +1,15 @@
 import { useState, useEffect } from 'react'
+import { listen } from '@tauri-apps/api/event'
 
+/**
+ * Online when the webview has a network and every source the backend syncs
+ * could be reached; the backend reports changes with `connectivity:changed`.
+ */
 export function useConnectionStatus() {
   const [isOnline, setIsOnline] = useState(
     typeof navigator !== 'undefined' ? navigator.onLine : true
   )
+  const [backendOnline, setBackendOnline] = useState(true)
 
   useEffect(() => {
     const handleOnline = () => setIsOnline(true)
@@ -18,5 +24,27 @@ export function useConnectionStatus() {
     }
   }, [])
 
-  return isOnline
+  useEffect(() => {
+    let unlistenFn: (() => void) | undefined
+    let mounted = true
+
+    listen<{ online: boolean }>('connectivity:changed', (event) => {
+      setBackendOnline(event.payload.online)
+    }).then((fn) => {
+      if (mounted) {
+        unlistenFn = fn
+      } else {
+        fn()
+      }
+    }).catch((err) => {
+      console.warn('Failed to set up connectivity:changed listener:', err)
+    })
+
+    return () => {
+      mounted = false
+      unlistenFn?.()
+    }
+  }, [])
+
+  return isOnline && backendOnline
 }
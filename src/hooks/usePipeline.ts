@@ -2,17 +2,28 @@ import { useState, useEffect } from 'react'
 import { listen } from '@tauri-apps/api/event'
 import { useAppStore, type LocalActivity } from '../store'
 
-export type TaskStatus = 'pending' | 'running' | 'completed' | 'failed'
+export type TaskStatus = 'pending' | 'running' | 'completed' | 'failed' | 'cancelled'
 
 export type PipelineTaskType =
   | 'sync_slack'
   | 'sync_jira'
   | 'sync_confluence'
+  | 'sync_github'
+  | 'sync_gitlab'
+  | 'sync_linear'
+  | 'sync_incidents'
+  | 'sync_email'
+  | 'sync_calendar'
+  | 'sync_notes'
+  | 'sync_feeds'
+  | 'sync_webhook'
+  | 'import_transcript'
   | 'ai_summarize'
   | 'ai_categorize'
   | 'generate_daily_digest'
   | 'generate_weekly_digest'
   | 'pdf_export'
+  | 'backfill'
 
 export interface PipelineTask {
   id: string
@@ -35,22 +46,44 @@ const taskTypeDisplayNames: Record<PipelineTaskType, string> = {
   sync_slack: 'Syncing Slack',
   sync_jira: 'Syncing Jira',
   sync_confluence: 'Syncing Confluence',
+  sync_github: 'Syncing GitHub',
+  sync_gitlab: 'Syncing GitLab',
+  sync_linear: 'Syncing Linear',
+  sync_incidents: 'Syncing incidents',
+  sync_email: 'Syncing email',
+  sync_calendar: 'Syncing calendar',
+  sync_notes: 'Syncing notes',
+  sync_feeds: 'Syncing feeds',
+  sync_webhook: 'Collecting webhook events',
+  import_transcript: 'Importing transcript',
   ai_summarize: 'Summarizing content',
   ai_categorize: 'Categorizing items',
   generate_daily_digest: 'Generating daily digest',
   generate_weekly_digest: 'Generating weekly digest',
   pdf_export: 'Exporting PDF',
+  backfill: 'Backfilling history',
 }
 
 const taskTypeIcons: Record<PipelineTaskType, string> = {
   sync_slack: '🔄',
   sync_jira: '🔄',
   sync_confluence: '🔄',
+  sync_github: '🔄',
+  sync_gitlab: '🔄',
+  sync_linear: '🔄',
+  sync_incidents: '🚨',
+  sync_email: '📧',
+  sync_calendar: '📅',
+  sync_notes: '📝',
+  sync_feeds: '📡',
+  sync_webhook: '🪝',
+  import_transcript: '🎙️',
   ai_summarize: '✨',
   ai_categorize: '🏷️',
   generate_daily_digest: '📰',
   generate_weekly_digest: '📊',
   pdf_export: '📄',
+  backfill: '⏪',
 }
 
 export function getTaskDisplayName(taskType: PipelineTaskType): string {
@@ -3,7 +3,7 @@ import type { Preferences } from '../lib/api'
 import { api } from '../lib/api'
 
 const DEFAULT_PREFERENCES: Preferences = {
-  syncIntervalMinutes: 15,
+  syncSchedule: { entries: ['*/15 * * * *'], quietPeriods: [] },
   enabledSources: [],
   enabledCategories: ['sales', 'marketing', 'product', 'engineering', 'research'],
   notificationsEnabled: true,
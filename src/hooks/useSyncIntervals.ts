@@ -0,0 +1,48 @@
+import { useCallback } from 'react'
+import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query'
+import { api } from '../lib/api'
+
+interface SetIntervalArgs {
+  source: string
+  intervalMinutes: number | null
+  scope?: { resourceType: string; resourceId: string }
+}
+
+/** Per-source and per-scope background sync intervals */
+export function useSyncIntervals() {
+  const queryClient = useQueryClient()
+
+  const query = useQuery({
+    queryKey: ['syncIntervals'],
+    queryFn: () => api.listSyncIntervals(),
+  })
+
+  const mutation = useMutation({
+    mutationFn: ({ source, intervalMinutes, scope }: SetIntervalArgs) =>
+      api.setSyncInterval(source, intervalMinutes, scope),
+    onSettled: () => {
+      queryClient.invalidateQueries({ queryKey: ['syncIntervals'] })
+    },
+  })
+
+  const intervals = query.data ?? []
+
+  /** The interval set directly on a source or scope, without falling back */
+  const intervalFor = useCallback(
+    (source: string, scope?: { resourceType: string; resourceId: string }) =>
+      intervals.find(
+        (i) =>
+          i.source === source &&
+          i.resourceType === scope?.resourceType &&
+          i.resourceId === scope?.resourceId
+      )?.intervalMinutes ?? null,
+    [intervals]
+  )
+
+  return {
+    intervals,
+    intervalFor,
+    setInterval: mutation.mutate,
+    isSaving: mutation.isPending,
+  }
+}
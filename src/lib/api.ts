@@ -44,18 +44,226 @@ export interface SyncStatus {
   isSyncing: boolean
   lastSyncAt?: number
   nextSyncAt?: number
+  /** False while background sync is paused waiting for the network */
+  isOnline: boolean
   sources: SourceStatus[]
+  aiCircuit: SourceCircuit
+}
+
+/** Backoff and circuit breaker state of a source, or of the AI pass */
+export interface SourceCircuit {
+  state: 'closed' | 'open' | 'half_open'
+  consecutiveFailures: number
+  lastError?: string
+  /** When scheduled syncs try again */
+  retryAt?: number
+  /** The last sync could not reach the source */
+  unreachable?: boolean
 }
 
 export interface SourceStatus {
   name: string
+  /** 'connected', 'disconnected', 'error', 'paused' or 'offline' */
   status: string
   itemsSynced: number
   lastError?: string
+  circuit: SourceCircuit
+}
+
+/** A registered data source, as listed by `list_sources` */
+export interface SourceInfo {
+  id: string
+  displayName: string
+  description: string
+  connected: boolean
+  /** Whether scopes can be chosen with `selectSourceScopes` */
+  scopeSelection: boolean
+  /** Whether scopes can have their own sync interval */
+  scopeIntervals: boolean
+}
+
+/** A syncable unit within a source: a Slack channel, Jira project, Confluence space, ... */
+export interface SourceScope {
+  id: string
+  name: string
+  kind: string
+  selected: boolean
+}
+
+/** Background sync frequency of a source, or of one of its scopes */
+export interface SyncInterval {
+  source: string
+  /** Scope kind (`channel`, `project`, `space`, ...); absent for the source default */
+  resourceType?: string
+  resourceId?: string
+  intervalMinutes: number
+}
+
+export interface SourceHealth {
+  source: string
+  connected: boolean
+  healthy: boolean
+  message?: string
+  lastSyncAt?: number
+}
+
+export type ResourceStatus = 'pending' | 'syncing' | 'complete' | 'error'
+
+/** Sync status of one channel, project, space, folder or feed */
+export interface ResourceHealth {
+  source: string
+  resourceType: string
+  resourceId: string
+  name: string
+  status: ResourceStatus
+  /** False while the resource has an unresolved error */
+  healthy: boolean
+  errorMessage?: string
+  errorAt?: number
+  lastSyncAt?: number
+  startedAt?: number
+  updatedAt?: number
+}
+
+/** Sender addresses or domains to include or exclude when syncing email */
+export interface EmailSenderFilter {
+  allow: string[]
+  deny: string[]
+}
+
+export interface EmailImportResult {
+  messagesImported: number
+  threadsImported: number
+  filesRead: number
+  errors: string[]
+}
+
+export interface TranscriptImportResult {
+  meetingId: string
+  title: string
+  segmentsImported: number
+  speakers: string[]
+  itemsSummarized: number
+}
+
+export type BackfillSource = 'slack' | 'jira' | 'confluence' | 'github' | 'gitlab' | 'linear' | 'incident' | 'email' | 'calendar' | 'notes' | 'rss'
+
+export interface BackfillJob {
+  id: string
+  startDate: string
+  endDate: string
+  /** Next day to process; absent once every day has been handled */
+  nextDate?: string
+  timezoneOffset: number
+  sources: BackfillSource[]
+  status: 'pending' | 'running' | 'completed' | 'failed' | 'cancelled'
+  daysCompleted: number
+  /** Days whose sync or AI pass failed; retried before the job finishes */
+  failedDates: string[]
+  /** Items synced on days that completed */
+  itemsSynced: number
+  lastError?: string
+  createdAt: number
+  updatedAt: number
+}
+
+/** Items with text the AI pass would analyze, for one channel, project or space on one day */
+export interface ScopeVolume {
+  scope: string
+  date: string
+  items: number
+  tokens: number
+}
+
+export interface SourceEstimate {
+  source: string
+  items: number
+  /** Requests the sync itself would make to the source's API */
+  apiRequests: number
+  volumes: ScopeVolume[]
+}
+
+/** Projected volume, time and cost of a sync and the AI pass after it, from a dry run */
+export interface SyncEstimate {
+  sources: SourceEstimate[]
+  /** Connected sources that cannot be estimated */
+  skipped: string[]
+  errors: string[]
+  items: number
+  apiRequests: number
+  aiRequests: number
+  promptTokens: number
+  outputTokens: number
+  /** Whether any AI batch is large enough for hierarchical summarization */
+  hierarchical: boolean
+  syncSeconds: number
+  aiSeconds: number
+  costUsd: number
+}
+
+/** What a queued sync job does */
+export type SyncJobPayload =
+  | { kind: 'historical_day'; date: string; timezoneOffset: number }
+  | { kind: 'backfill'; backfillId: string; startDate: string; endDate: string }
+
+export interface SyncJob {
+  id: string
+  payload: SyncJobPayload
+  status: 'pending' | 'running' | 'completed' | 'failed' | 'cancelled'
+  attempts: number
+  maxAttempts: number
+  /** Earliest time the job may run; pushed back after each failed attempt */
+  nextRunAt: number
+  lastError?: string
+  createdAt: number
+  updatedAt: number
+}
+
+/** Sync work holding or waiting for a scheduler permit */
+export interface ScheduledJob {
+  id: number
+  label: string
+  priority: 'backfill' | 'incremental' | 'user_initiated'
+  /** Claimed sources (and 'ai' for the AI pass); absent when the job claims everything */
+  resources?: string[]
+  queuedAt: number
+  /** Absent while the job is waiting */
+  startedAt?: number
+}
+
+export interface SyncSchedule {
+  running: ScheduledJob[]
+  /** In the order the jobs will start */
+  waiting: ScheduledJob[]
+}
+
+export type AtlassianExportKind = 'jira' | 'confluence'
+
+export interface AtlassianImportResult {
+  issuesImported: number
+  pagesImported: number
+  errors: string[]
+}
+
+/** A window in which background sync does not run */
+export interface QuietPeriod {
+  /** Days the period starts on, 0 = Sunday; empty means every day */
+  days: number[]
+  /** Local HH:MM; an end before the start runs past midnight, equal times cover the whole day */
+  start: string
+  end: string
+}
+
+export interface SyncScheduleConfig {
+  /** Five-field cron expressions in local time; a sync runs whenever any matches */
+  entries: string[]
+  quietPeriods: QuietPeriod[]
+  /** Local HH:MM of the end-of-day digest run */
+  digestTime?: string
 }
 
 export interface Preferences {
-  syncIntervalMinutes: number
+  syncSchedule: SyncScheduleConfig
   enabledSources: string[]
   enabledCategories: string[]
   notificationsEnabled: boolean
@@ -103,6 +311,15 @@ export interface SlackConnectionStatus {
   selectedChannelCount: number
 }
 
+export interface SlackImportResult {
+  channelsImported: number
+  messagesImported: number
+  usersImported: number
+  /** Distinct days ('YYYY-MM-DD') that received messages */
+  days: string[]
+  errors: string[]
+}
+
 export interface SlackTokens {
   accessToken: string
   tokenType: string
@@ -128,6 +345,46 @@ export const api = {
   resyncHistoricalDay: (date: string, timezoneOffset: number) =>
     invoke<{ itemsSynced: number; channelsProcessed: number; errors: string[]; queued?: boolean }>('resync_historical_day', { date, timezoneOffset }),
 
+  startBackfill: (startDate: string, endDate: string, timezoneOffset: number, sources?: BackfillSource[]) =>
+    invoke<BackfillJob>('start_backfill', { startDate, endDate, timezoneOffset, sources }),
+
+  getBackfillJobs: () =>
+    invoke<BackfillJob[]>('get_backfill_jobs'),
+
+  /** Dry-run the next sync, or a backfill of `range`; `scopes` maps source ids to scopes to estimate instead of the current selection */
+  estimateSync: (
+    sources?: string[],
+    range?: { startDate: string; endDate: string },
+    scopes?: Record<string, string[]>,
+    timezoneOffset?: number
+  ) =>
+    invoke<SyncEstimate>('estimate_sync', {
+      sources,
+      scopes,
+      startDate: range?.startDate,
+      endDate: range?.endDate,
+      timezoneOffset,
+    }),
+
+  getSyncJobs: (limit?: number) =>
+    invoke<SyncJob[]>('get_sync_jobs', { limit }),
+
+  cancelSyncJob: (jobId: string) =>
+    invoke<SyncJob>('cancel_sync_job', { jobId }),
+
+  retrySyncJob: (jobId: string) =>
+    invoke<SyncJob>('retry_sync_job', { jobId }),
+
+  getSyncSchedule: () =>
+    invoke<SyncSchedule>('get_sync_schedule'),
+
+  /** Ask a running pipeline task to stop; resolves false if it already finished */
+  cancelTask: (taskId: string) =>
+    invoke<boolean>('cancel_task', { taskId }),
+
+  importAtlassianExport: (path: string, kind: AtlassianExportKind, baseUrl?: string) =>
+    invoke<AtlassianImportResult>('import_atlassian_export', { path, kind, baseUrl }),
+
   getSyncStatus: () =>
     invoke<SyncStatus>('get_sync_status'),
 
@@ -143,6 +400,57 @@ export const api = {
   hasApiKey: (service: string) =>
     invoke<boolean>('has_api_key', { service }),
 
+  // Source connectors
+  listSources: () =>
+    invoke<SourceInfo[]>('list_sources'),
+
+  connectSource: (source: string, config: Record<string, unknown>) =>
+    invoke<unknown>('connect_source', { source, config }),
+
+  listSourceScopes: (source: string) =>
+    invoke<SourceScope[]>('list_source_scopes', { source }),
+
+  selectSourceScopes: (source: string, scopeIds: string[]) =>
+    invoke<void>('select_source_scopes', { source, scopeIds }),
+
+  listSyncIntervals: () =>
+    invoke<SyncInterval[]>('list_sync_intervals'),
+
+  setSyncInterval: (
+    source: string,
+    intervalMinutes: number | null,
+    scope?: { resourceType: string; resourceId: string }
+  ) =>
+    invoke<void>('set_sync_interval', {
+      source,
+      resourceType: scope?.resourceType ?? null,
+      resourceId: scope?.resourceId ?? null,
+      intervalMinutes,
+    }),
+
+  getSourceHealth: () =>
+    invoke<SourceHealth[]>('get_source_health'),
+
+  getResourceHealth: (source?: string) =>
+    invoke<ResourceHealth[]>('get_resource_health', { source }),
+
+  disconnectSource: (source: string) =>
+    invoke<void>('disconnect_source', { source }),
+
+  // Email
+  getEmailSenderFilter: () =>
+    invoke<EmailSenderFilter>('get_email_sender_filter'),
+
+  saveEmailSenderFilter: (filter: EmailSenderFilter) =>
+    invoke<void>('save_email_sender_filter', { filter }),
+
+  importEmailArchive: (path: string) =>
+    invoke<EmailImportResult>('import_email_archive', { path }),
+
+  // Meeting transcripts; startedAt is epoch milliseconds, timezoneOffset picks the summary's local day
+  importMeetingTranscript: (path: string, title?: string, startedAt?: number, timezoneOffset?: number) =>
+    invoke<TranscriptImportResult>('import_meeting_transcript', { path, title, startedAt, timezoneOffset }),
+
   // Slack integration
   connectSlack: (token: string) =>
     invoke<SlackTokens>('connect_slack', { token }),
@@ -169,6 +477,9 @@ export const api = {
   getSlackConnectionStatus: () =>
     invoke<SlackConnectionStatus>('get_slack_connection_status'),
 
+  importSlackExport: (path: string, timezoneOffset: number, processAi: boolean, channels?: string[]) =>
+    invoke<SlackImportResult>('import_slack_export', { path, timezoneOffset, processAi, channels }),
+
   // Gemini authentication
   saveGeminiCredentials: (jsonContent: string, region?: string) =>
     invoke<void>('save_gemini_credentials', { jsonContent, region }),
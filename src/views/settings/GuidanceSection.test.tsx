@@ -6,7 +6,7 @@ import { GuidanceSection } from './GuidanceSection'
 const mockSave = vi.fn()
 
 const mockPreferences = {
-  syncIntervalMinutes: 15,
+  syncSchedule: { entries: ['*/15 * * * *'], quietPeriods: [] },
   enabledSources: [],
   enabledCategories: ['engineering'],
   notificationsEnabled: true,
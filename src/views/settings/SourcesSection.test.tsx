@@ -1,15 +1,19 @@
 import { describe, it, expect, vi, beforeEach } from 'vitest'
-import { render, screen, waitFor } from '@testing-library/react'
+import { render, screen, waitFor, fireEvent } from '@testing-library/react'
 import { SourcesSection } from './SourcesSection'
 
 // Mock the API
 const mockGetSlackConnectionStatus = vi.fn()
+const mockListSources = vi.fn()
 
 vi.mock('../../lib/api', () => ({
   api: {
     getSlackConnectionStatus: () => mockGetSlackConnectionStatus(),
+    listSources: () => mockListSources(),
     connectSlack: vi.fn(),
     disconnectSlack: vi.fn(),
+    connectSource: vi.fn(),
+    disconnectSource: vi.fn(),
   },
 }))
 
@@ -39,11 +43,46 @@ vi.mock('../../components', () => ({
     </div>
   ),
   SlackChannelSelector: () => null,
+  SourceScopeSelector: () => null,
+  SyncIntervalSelect: ({
+    value,
+    onChange,
+    'aria-label': ariaLabel,
+  }: {
+    value: number | null
+    onChange: (value: number | null) => void
+    'aria-label'?: string
+  }) => (
+    <select
+      aria-label={ariaLabel}
+      value={value ?? ''}
+      onChange={(e) => onChange(e.target.value === '' ? null : Number(e.target.value))}
+    >
+      <option value="">Every scheduled sync</option>
+      <option value="60">Every hour</option>
+    </select>
+  ),
+}))
+
+const mockSetSyncInterval = vi.fn()
+vi.mock('../../hooks/useSyncIntervals', () => ({
+  useSyncIntervals: () => ({
+    intervals: [{ source: 'confluence', intervalMinutes: 60 }],
+    intervalFor: (source: string) => (source === 'confluence' ? 60 : null),
+    setInterval: mockSetSyncInterval,
+    isSaving: false,
+  }),
 }))
 
 describe('SourcesSection', () => {
   beforeEach(() => {
     vi.clearAllMocks()
+    mockListSources.mockResolvedValue([
+      { id: 'slack', displayName: 'Slack', description: 'Sync Slack', connected: false, scopeSelection: false, scopeIntervals: false },
+      { id: 'jira', displayName: 'Jira', description: 'Sync Jira', connected: false, scopeSelection: false, scopeIntervals: false },
+      { id: 'confluence', displayName: 'Confluence', description: 'Sync Confluence', connected: true, scopeSelection: false, scopeIntervals: false },
+      { id: 'github', displayName: 'GitHub', description: 'Sync GitHub', connected: true, scopeSelection: true, scopeIntervals: false },
+    ])
     mockGetSlackConnectionStatus.mockResolvedValue({
       connected: false,
       teamId: null,
@@ -58,14 +97,38 @@ describe('SourcesSection', () => {
     expect(screen.getByText('Connected Sources')).toBeInTheDocument()
   })
 
-  it('renders Slack source card', () => {
+  it('renders Slack source card', async () => {
     render(<SourcesSection />)
-    expect(screen.getByTestId('source-card-slack')).toBeInTheDocument()
+    expect(await screen.findByTestId('source-card-slack')).toBeInTheDocument()
   })
 
-  it('renders Confluence source card', () => {
+  it('renders a card for every registered source', async () => {
     render(<SourcesSection />)
+    expect(await screen.findByTestId('source-card-jira')).toBeInTheDocument()
     expect(screen.getByTestId('source-card-confluence')).toBeInTheDocument()
+    expect(mockListSources).toHaveBeenCalled()
+  })
+
+  it('offers scope configuration for connected sources that support it', async () => {
+    render(<SourcesSection />)
+    const card = await screen.findByTestId('source-card-github')
+    expect(card).toHaveTextContent('Configure')
+    expect(screen.getByTestId('source-card-confluence')).not.toHaveTextContent('Configure')
+  })
+
+  it('shows sync frequency for connected sources', async () => {
+    render(<SourcesSection />)
+    expect(await screen.findByLabelText('Confluence sync frequency')).toHaveValue('60')
+    expect(screen.getByLabelText('GitHub sync frequency')).toHaveValue('')
+    expect(screen.queryByLabelText('Jira sync frequency')).not.toBeInTheDocument()
+  })
+
+  it('saves a source sync frequency', async () => {
+    render(<SourcesSection />)
+    fireEvent.change(await screen.findByLabelText('GitHub sync frequency'), {
+      target: { value: '60' },
+    })
+    expect(mockSetSyncInterval).toHaveBeenCalledWith({ source: 'github', intervalMinutes: 60 })
   })
 
   it('loads Slack connection status on mount', async () => {
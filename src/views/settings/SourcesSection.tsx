@@ -1,23 +1,188 @@
 import { useState, useEffect, useCallback } from 'react'
 import {
   Slack,
+  Github,
+  Gitlab,
+  SquareKanban,
+  Siren,
+  Mail,
+  Calendar,
+  NotebookPen,
+  Rss,
+  Webhook,
   FileText,
+  ClipboardList,
+  Database,
   Link2,
   Settings,
   RefreshCw,
+  Clock,
 } from 'lucide-react'
 import { useAppStore } from '../../store'
-import { SourceCard, SlackChannelSelector } from '../../components'
+import {
+  SourceCard,
+  SlackChannelSelector,
+  SourceScopeSelector,
+  SyncIntervalSelect,
+} from '../../components'
+import { useSyncIntervals } from '../../hooks/useSyncIntervals'
 import { Button } from '../../components/ui/Button'
 import { Input } from '../../components/ui/Input'
-import { api } from '../../lib/api'
+import { api, type SourceInfo } from '../../lib/api'
+
+const SOURCE_ICONS: Record<string, React.ComponentType<{ className?: string }>> = {
+  slack: Slack,
+  jira: ClipboardList,
+  confluence: FileText,
+  github: Github,
+  gitlab: Gitlab,
+  linear: SquareKanban,
+  incident: Siren,
+  email: Mail,
+  calendar: Calendar,
+  notes: NotebookPen,
+  rss: Rss,
+  webhook: Webhook,
+}
+
+interface ConnectField {
+  key: string
+  label: string
+  secret?: boolean
+  placeholder?: string
+  optional?: boolean
+}
+
+/** Config fields for sources connected through the generic connect dialog */
+const CONNECT_FIELDS: Record<string, ConnectField[]> = {
+  jira: [
+    { key: 'clientId', label: 'Atlassian OAuth Client ID' },
+    { key: 'clientSecret', label: 'Client Secret', secret: true },
+  ],
+  confluence: [
+    { key: 'clientId', label: 'Atlassian OAuth Client ID' },
+    { key: 'clientSecret', label: 'Client Secret', secret: true },
+  ],
+  github: [
+    { key: 'token', label: 'Personal Access Token', secret: true, placeholder: 'ghp_...' },
+    {
+      key: 'apiUrl',
+      label: 'Enterprise API URL (optional)',
+      placeholder: 'https://github.example.com/api/v3',
+      optional: true,
+    },
+  ],
+  gitlab: [
+    {
+      key: 'baseUrl',
+      label: 'GitLab URL (optional)',
+      placeholder: 'https://gitlab.example.com',
+      optional: true,
+    },
+    { key: 'token', label: 'Personal Access Token', secret: true, placeholder: 'glpat-...' },
+  ],
+  linear: [{ key: 'token', label: 'Personal API Key', secret: true, placeholder: 'lin_api_...' }],
+  incident: [
+    {
+      key: 'baseUrl',
+      label: 'API URL (optional)',
+      placeholder: 'https://api.pagerduty.com',
+      optional: true,
+    },
+    { key: 'token', label: 'API Key', secret: true },
+  ],
+  email: [
+    { key: 'host', label: 'IMAP Server', placeholder: 'imap.example.com' },
+    { key: 'port', label: 'Port (optional)', placeholder: '993', optional: true },
+    { key: 'username', label: 'Username', placeholder: 'you@example.com' },
+    { key: 'password', label: 'App Password', secret: true },
+    {
+      key: 'allowSenders',
+      label: 'Only include senders (optional)',
+      placeholder: 'customer.com, vendor.io',
+      optional: true,
+    },
+    {
+      key: 'denySenders',
+      label: 'Exclude senders (optional)',
+      placeholder: 'noreply@example.com, newsletters.com',
+      optional: true,
+    },
+  ],
+  calendar: [
+    {
+      key: 'url',
+      label: 'ICS Subscription URL',
+      secret: true,
+      placeholder: 'https://calendar.example.com/private/basic.ics',
+      optional: true,
+    },
+    {
+      key: 'path',
+      label: 'Or a local .ics file',
+      placeholder: '/Users/you/Calendars/work.ics',
+      optional: true,
+    },
+    { key: 'name', label: 'Calendar Name (optional)', placeholder: 'Work', optional: true },
+  ],
+  notes: [
+    { key: 'path', label: 'Notes Folder or Obsidian Vault', placeholder: '/Users/you/Notes' },
+    { key: 'name', label: 'Name (optional)', placeholder: 'Notes', optional: true },
+  ],
+  rss: [
+    { key: 'url', label: 'Feed URL', placeholder: 'https://status.example.com/history.rss' },
+    { key: 'name', label: 'Name (optional)', placeholder: 'Status page', optional: true },
+    {
+      key: 'intervalMinutes',
+      label: 'Check Every (minutes, optional)',
+      placeholder: '60',
+      optional: true,
+    },
+  ],
+  webhook: [
+    { key: 'name', label: 'Webhook Name', placeholder: 'Build Alerts' },
+    { key: 'secret', label: 'Shared Secret (16+ characters)', secret: true },
+    {
+      key: 'items',
+      label: 'Items Path (optional)',
+      placeholder: 'alerts',
+      optional: true,
+    },
+    {
+      key: 'title',
+      label: 'Title Field (optional)',
+      placeholder: '{labels.alertname} on {labels.service}',
+      optional: true,
+    },
+    {
+      key: 'body',
+      label: 'Body Field (optional)',
+      placeholder: 'annotations.description',
+      optional: true,
+    },
+    { key: 'port', label: 'Listener Port (optional)', placeholder: '8380', optional: true },
+  ],
+}
 
 export function SourcesSection() {
   const { slack, setSlackState, showChannelSelector, setShowChannelSelector } = useAppStore()
+  const [registered, setRegistered] = useState<SourceInfo[]>([])
   const [isConnecting, setIsConnecting] = useState(false)
   const [error, setError] = useState<string | null>(null)
   const [showSlackSetup, setShowSlackSetup] = useState(false)
   const [slackToken, setSlackToken] = useState('')
+  const [setupSource, setSetupSource] = useState<SourceInfo | null>(null)
+  const [sourceConfig, setSourceConfig] = useState<Record<string, string>>({})
+  const [scopeSource, setScopeSource] = useState<SourceInfo | null>(null)
+  const { intervalFor, setInterval: setSyncInterval, isSaving: isSavingInterval } = useSyncIntervals()
+
+  const loadSources = useCallback(async () => {
+    try {
+      setRegistered(await api.listSources())
+    } catch (e) {
+      console.error('Failed to load sources:', e)
+    }
+  }, [])
 
   const loadSlackStatus = useCallback(async () => {
     try {
@@ -35,8 +200,9 @@ export function SourcesSection() {
   }, [setSlackState])
 
   useEffect(() => {
+    loadSources()
     loadSlackStatus()
-  }, [loadSlackStatus])
+  }, [loadSources, loadSlackStatus])
 
   const handleConnectSlack = async () => {
     if (!slackToken.trim()) {
@@ -57,6 +223,7 @@ export function SourcesSection() {
       })
       setShowSlackSetup(false)
       setSlackToken('')
+      loadSources()
       // Show channel selector after successful connection
       setShowChannelSelector(true)
     } catch (e) {
@@ -77,42 +244,81 @@ export function SourcesSection() {
         selectedChannelCount: 0,
       })
       setError(null)
+      loadSources()
     } catch (e) {
       console.error('Failed to disconnect Slack:', e)
     }
   }
 
-  const sources = [
-    {
-      id: 'slack',
-      icon: Slack,
-      name: 'Slack',
-      description: slack.connected && slack.teamName
+  const handleConnectSource = async () => {
+    if (!setupSource) return
+
+    setIsConnecting(true)
+    setError(null)
+    try {
+      const config = Object.fromEntries(
+        Object.entries(sourceConfig)
+          .map(([key, value]) => [key, value.trim()])
+          .filter(([, value]) => value)
+      )
+      await api.connectSource(setupSource.id, config)
+      setSetupSource(null)
+      setSourceConfig({})
+      await loadSources()
+      if (setupSource.scopeSelection) {
+        setScopeSource(setupSource)
+      }
+    } catch (e) {
+      setError(e instanceof Error ? e.message : `Failed to connect to ${setupSource.displayName}`)
+    } finally {
+      setIsConnecting(false)
+    }
+  }
+
+  const handleDisconnectSource = async (sourceId: string) => {
+    try {
+      await api.disconnectSource(sourceId)
+      setError(null)
+      await loadSources()
+    } catch (e) {
+      console.error(`Failed to disconnect ${sourceId}:`, e)
+    }
+  }
+
+  const sources = registered.map((source) => {
+    const isSlack = source.id === 'slack'
+    const connected = isSlack ? slack.connected : source.connected
+    return {
+      id: source.id,
+      info: source,
+      icon: SOURCE_ICONS[source.id] ?? Database,
+      name: source.displayName,
+      description: isSlack && slack.connected && slack.teamName
         ? `Connected to ${slack.teamName} • ${slack.selectedChannelCount} channels`
-        : 'Sync messages and channels from your Slack workspace',
-      connected: slack.connected,
-      isConnecting: isConnecting,
-    },
-    {
-      id: 'confluence',
-      icon: FileText,
-      name: 'Confluence',
-      description: 'Sync pages and spaces from Atlassian Confluence',
-      connected: false,
-      isConnecting: false,
-      comingSoon: true,
-    },
-  ]
+        : source.description,
+      connected,
+      isConnecting: isConnecting && (isSlack ? showSlackSetup : setupSource?.id === source.id),
+      comingSoon: !isSlack && !connected && !CONNECT_FIELDS[source.id],
+    }
+  })
 
   const handleConnect = (sourceId: string) => {
     if (sourceId === 'slack') {
       setShowSlackSetup(true)
+      return
+    }
+    const source = registered.find((s) => s.id === sourceId)
+    if (source && CONNECT_FIELDS[sourceId]) {
+      setSourceConfig({})
+      setSetupSource(source)
     }
   }
 
   const handleDisconnect = (sourceId: string) => {
     if (sourceId === 'slack') {
       handleDisconnectSlack()
+    } else {
+      handleDisconnectSource(sourceId)
     }
   }
 
@@ -148,6 +354,30 @@ export function SourcesSection() {
                 Configure Channels
               </button>
             )}
+            {source.id !== 'slack' && source.connected && (source.info.scopeSelection || source.info.scopeIntervals) && (
+              <button
+                onClick={() => setScopeSource(source.info)}
+                className="flex items-center gap-2 px-3 py-1.5 text-sm font-medium text-primary-500 hover:bg-primary-50 dark:hover:bg-primary-900/20 rounded-lg transition-colors"
+              >
+                <Settings className="h-4 w-4" />
+                Configure
+              </button>
+            )}
+            {source.connected && (
+              <div className="flex items-center gap-2 text-sm text-muted-foreground">
+                <Clock className="h-4 w-4" />
+                <span>Sync frequency</span>
+                <SyncIntervalSelect
+                  aria-label={`${source.name} sync frequency`}
+                  value={intervalFor(source.id)}
+                  onChange={(intervalMinutes) =>
+                    setSyncInterval({ source: source.id, intervalMinutes })
+                  }
+                  inheritLabel="Every scheduled sync"
+                  disabled={isSavingInterval}
+                />
+              </div>
+            )}
             {source.id === 'slack' && !slack.connected && (
               <div className="flex items-start gap-3 p-3 bg-muted/50 rounded-lg">
                 <Link2 className="h-5 w-5 text-muted-foreground mt-0.5" />
@@ -255,6 +485,73 @@ export function SourcesSection() {
         </div>
       )}
 
+      {/* Generic Source Setup Modal */}
+      {setupSource && (
+        <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/50">
+          <div className="bg-background border border-border rounded-xl shadow-xl w-full max-w-lg p-6">
+            <h3 className="text-lg font-semibold text-foreground mb-2">
+              Connect {setupSource.displayName}
+            </h3>
+            <p className="text-sm text-muted-foreground mb-4">
+              {setupSource.description}. Credentials are stored securely on your device.
+            </p>
+
+            {error && (
+              <div className="mb-4 p-3 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 rounded-lg text-sm text-red-600 dark:text-red-400">
+                {error}
+              </div>
+            )}
+
+            <div className="space-y-3">
+              {(CONNECT_FIELDS[setupSource.id] ?? []).map((field) => (
+                <div key={field.key}>
+                  <label className="block text-sm font-medium text-foreground mb-1">
+                    {field.label}
+                  </label>
+                  <Input
+                    type={field.secret ? 'password' : 'text'}
+                    value={sourceConfig[field.key] ?? ''}
+                    onChange={(e) =>
+                      setSourceConfig((config) => ({ ...config, [field.key]: e.target.value }))
+                    }
+                    placeholder={field.placeholder}
+                  />
+                </div>
+              ))}
+            </div>
+
+            <div className="flex justify-end gap-3 mt-6">
+              <Button
+                variant="outline"
+                onClick={() => {
+                  setSetupSource(null)
+                  setSourceConfig({})
+                  setError(null)
+                }}
+              >
+                Cancel
+              </Button>
+              <Button
+                onClick={handleConnectSource}
+                disabled={
+                  isConnecting ||
+                  (CONNECT_FIELDS[setupSource.id] ?? []).some(
+                    (field) => !field.optional && !sourceConfig[field.key]?.trim()
+                  )
+                }
+              >
+                {isConnecting ? (
+                  <RefreshCw className="h-4 w-4 animate-spin" />
+                ) : (
+                  <Link2 className="h-4 w-4" />
+                )}
+                Connect
+              </Button>
+            </div>
+          </div>
+        </div>
+      )}
+
       {/* Channel Selector Modal */}
       <SlackChannelSelector
         isOpen={showChannelSelector}
@@ -262,6 +559,13 @@ export function SourcesSection() {
         teamId={slack.teamId ?? ''}
         onSave={loadSlackStatus}
       />
+
+      {/* Scope Selector Modal */}
+      <SourceScopeSelector
+        source={scopeSource}
+        onClose={() => setScopeSource(null)}
+        onSave={loadSources}
+      />
     </div>
   )
 }
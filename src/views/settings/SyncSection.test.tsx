@@ -6,25 +6,33 @@ import { SyncSection } from './SyncSection'
 const mockSync = vi.fn()
 const mockSave = vi.fn()
 
+const mockPreferences = {
+  syncSchedule: { entries: ['*/15 * * * *'], quietPeriods: [] },
+}
+
 vi.mock('../../hooks/usePreferences', () => ({
   usePreferences: () => ({
-    preferences: { syncIntervalMinutes: 15 },
+    preferences: mockPreferences,
     save: mockSave,
     isSaving: false,
+    saveError: null,
   }),
 }))
 
+let mockStatus: Record<string, unknown> = {}
+
 vi.mock('../../hooks/useDigest', () => ({
   useSync: () => ({
     sync: mockSync,
     isSyncing: false,
-    status: { lastSyncAt: Date.now(), nextSyncAt: Date.now() + 900000 },
+    status: mockStatus,
   }),
 }))
 
 describe('SyncSection', () => {
   beforeEach(() => {
     vi.clearAllMocks()
+    mockStatus = { lastSyncAt: Date.now(), nextSyncAt: Date.now() + 900000 }
   })
 
   it('renders the section title', () => {
@@ -53,26 +61,99 @@ describe('SyncSection', () => {
     expect(mockSync).toHaveBeenCalled()
   })
 
-  it('renders sync interval selector', () => {
+  it('shows when sources cannot be reached', () => {
+    mockStatus = {
+      ...mockStatus,
+      isOnline: false,
+      sources: [
+        { name: 'gitlab', status: 'offline', itemsSynced: 0, circuit: { state: 'closed', consecutiveFailures: 0, lastError: 'connection timed out', unreachable: true } },
+      ],
+      aiCircuit: { state: 'closed', consecutiveFailures: 0 },
+    }
+    render(<SyncSection />)
+    expect(screen.getByText('Offline · sources that cannot be reached sync once they connect')).toBeInTheDocument()
+    expect(screen.getByText(/Gitlab cannot be reached/)).toBeInTheDocument()
+  })
+
+  it('shows sources paused after repeated failures', () => {
+    mockStatus = {
+      ...mockStatus,
+      sources: [
+        { name: 'slack', status: 'paused', itemsSynced: 0, circuit: { state: 'open', consecutiveFailures: 3, lastError: 'invalid_auth' } },
+        { name: 'jira', status: 'connected', itemsSynced: 5, circuit: { state: 'closed', consecutiveFailures: 0 } },
+      ],
+      aiCircuit: { state: 'closed', consecutiveFailures: 0 },
+    }
     render(<SyncSection />)
-    expect(screen.getByText('Sync Interval')).toBeInTheDocument()
-    expect(screen.getByText('How often to sync data from sources')).toBeInTheDocument()
+    expect(screen.getByText(/Slack paused after 3 failed syncs/)).toBeInTheDocument()
+    expect(screen.getByText('invalid_auth')).toBeInTheDocument()
+    expect(screen.queryByText(/Jira paused/)).not.toBeInTheDocument()
   })
 
-  it('renders interval options', () => {
+  it('renders sync schedule selector', () => {
+    render(<SyncSection />)
+    expect(screen.getByText('Sync Schedule')).toBeInTheDocument()
+    expect(screen.getByText('When to sync data from sources')).toBeInTheDocument()
+  })
+
+  it('renders schedule presets', () => {
     render(<SyncSection />)
     const select = screen.getByRole('combobox')
-    expect(select).toBeInTheDocument()
+    expect(select).toHaveValue('15')
     expect(screen.getByText('Every 5 minutes')).toBeInTheDocument()
     expect(screen.getByText('Every 15 minutes')).toBeInTheDocument()
     expect(screen.getByText('Every 30 minutes')).toBeInTheDocument()
     expect(screen.getByText('Every hour')).toBeInTheDocument()
+    expect(screen.getByText('Custom')).toBeInTheDocument()
   })
 
-  it('calls save when changing interval', () => {
+  it('saves a preset schedule', () => {
     render(<SyncSection />)
-    const select = screen.getByRole('combobox')
-    fireEvent.change(select, { target: { value: '30' } })
-    expect(mockSave).toHaveBeenCalledWith({ syncIntervalMinutes: 30 })
+    fireEvent.change(screen.getByRole('combobox'), { target: { value: '30' } })
+    expect(mockSave).toHaveBeenCalledWith({
+      syncSchedule: { entries: ['*/30 * * * *'], quietPeriods: [] },
+    })
+  })
+
+  it('saves the working hours preset with quiet weekends', () => {
+    render(<SyncSection />)
+    fireEvent.change(screen.getByRole('combobox'), { target: { value: 'working-hours' } })
+    expect(mockSave).toHaveBeenCalledWith({
+      syncSchedule: {
+        entries: ['*/10 8-17 * * 1-5', '0 * * * *'],
+        quietPeriods: [{ days: [0, 6], start: '00:00', end: '00:00' }],
+      },
+    })
+  })
+
+  it('saves custom cron expressions', () => {
+    render(<SyncSection />)
+    fireEvent.change(screen.getByRole('combobox'), { target: { value: 'custom' } })
+    fireEvent.change(screen.getByLabelText('Cron expressions'), {
+      target: { value: '0 9 * * 1-5\n\n0 17 * * 1-5' },
+    })
+    fireEvent.click(screen.getByText('Save Schedule'))
+    expect(mockSave).toHaveBeenCalledWith({
+      syncSchedule: { entries: ['0 9 * * 1-5', '0 17 * * 1-5'], quietPeriods: [] },
+    })
+  })
+
+  it('enables quiet hours', () => {
+    render(<SyncSection />)
+    fireEvent.click(screen.getByLabelText('Enable quiet hours'))
+    expect(mockSave).toHaveBeenCalledWith({
+      syncSchedule: {
+        entries: ['*/15 * * * *'],
+        quietPeriods: [{ days: [], start: '22:00', end: '07:00' }],
+      },
+    })
+  })
+
+  it('enables the end-of-day digest', () => {
+    render(<SyncSection />)
+    fireEvent.click(screen.getByLabelText('Enable end-of-day digest'))
+    expect(mockSave).toHaveBeenCalledWith({
+      syncSchedule: { entries: ['*/15 * * * *'], quietPeriods: [], digestTime: '18:00' },
+    })
   })
 })
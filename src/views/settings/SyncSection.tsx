@@ -1,16 +1,90 @@
+import { useState } from 'react'
 import { clsx } from 'clsx'
-import { RefreshCw, Clock } from 'lucide-react'
+import { RefreshCw, Clock, Moon, Newspaper, PauseCircle } from 'lucide-react'
 import { Button } from '../../components/ui/Button'
 import { usePreferences } from '../../hooks/usePreferences'
 import { useSync } from '../../hooks/useDigest'
 import { formatRelativeTime } from '../../lib/formatRelativeTime'
+import type { QuietPeriod, SourceCircuit, SyncScheduleConfig } from '../../lib/api'
+
+const WEEKENDS: QuietPeriod = { days: [0, 6], start: '00:00', end: '00:00' }
+
+const SCHEDULE_PRESETS: { id: string; label: string; entries: string[]; quietWeekends?: boolean }[] = [
+  { id: '5', label: 'Every 5 minutes', entries: ['*/5 * * * *'] },
+  { id: '15', label: 'Every 15 minutes', entries: ['*/15 * * * *'] },
+  { id: '30', label: 'Every 30 minutes', entries: ['*/30 * * * *'] },
+  { id: '60', label: 'Every hour', entries: ['0 */1 * * *'] },
+  {
+    id: 'working-hours',
+    label: 'Working hours (every 10 min 8–18 on weekdays, hourly otherwise, never on weekends)',
+    entries: ['*/10 8-17 * * 1-5', '0 * * * *'],
+    quietWeekends: true,
+  },
+]
+
+const isWeekends = (period: QuietPeriod) =>
+  period.start === period.end && [...period.days].sort().join(',') === '0,6'
+
+function matchPreset(schedule: SyncScheduleConfig): string {
+  const quietWeekends = schedule.quietPeriods.some(isWeekends)
+  const preset = SCHEDULE_PRESETS.find(
+    p =>
+      p.entries.join('\n') === schedule.entries.join('\n') &&
+      (p.quietWeekends ?? false) === quietWeekends
+  )
+  return preset?.id ?? 'custom'
+}
+
+const capitalize = (name: string) => name.charAt(0).toUpperCase() + name.slice(1)
+
+function formatClock(timestamp: number) {
+  return new Date(timestamp).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' })
+}
 
 export function SyncSection() {
-  const { preferences, save, isSaving } = usePreferences()
+  const { preferences, save, isSaving, saveError } = usePreferences()
   const { sync, isSyncing, status } = useSync()
 
-  const handleIntervalChange = (value: number) => {
-    save({ ...preferences, syncIntervalMinutes: value })
+  const schedule = preferences.syncSchedule
+  const [customEntries, setCustomEntries] = useState<string | null>(null)
+  const selectedPreset = customEntries !== null ? 'custom' : matchPreset(schedule)
+  const overnight = schedule.quietPeriods.find(p => !isWeekends(p))
+
+  const paused: { name: string; circuit: SourceCircuit }[] = [
+    ...(status?.sources ?? []).map(s => ({ name: capitalize(s.name), circuit: s.circuit })),
+    ...(status?.aiCircuit ? [{ name: 'AI processing', circuit: status.aiCircuit }] : []),
+  ].filter(p => p.circuit && (p.circuit.unreachable || p.circuit.state !== 'closed'))
+
+  const saveSchedule = (changes: Partial<SyncScheduleConfig>) => {
+    save({ ...preferences, syncSchedule: { ...schedule, ...changes } })
+  }
+
+  const handlePresetChange = (id: string) => {
+    const preset = SCHEDULE_PRESETS.find(p => p.id === id)
+    if (!preset) {
+      setCustomEntries(schedule.entries.join('\n'))
+      return
+    }
+    setCustomEntries(null)
+    const otherPeriods = schedule.quietPeriods.filter(p => !isWeekends(p))
+    saveSchedule({
+      entries: preset.entries,
+      quietPeriods: preset.quietWeekends ? [...otherPeriods, WEEKENDS] : otherPeriods,
+    })
+  }
+
+  const handleCustomSave = () => {
+    const entries = (customEntries ?? '')
+      .split('\n')
+      .map(line => line.trim())
+      .filter(Boolean)
+    saveSchedule({ entries })
+    setCustomEntries(null)
+  }
+
+  const setOvernight = (period: QuietPeriod | undefined) => {
+    const others = schedule.quietPeriods.filter(p => p !== overnight)
+    saveSchedule({ quietPeriods: period ? [...others, period] : others })
   }
 
   return (
@@ -32,6 +106,8 @@ export function SyncSection() {
                 <p className="text-sm text-muted-foreground">
                   {isSyncing ? (
                     'Syncing now...'
+                  ) : status?.isOnline === false ? (
+                    'Offline · sources that cannot be reached sync once they connect'
                   ) : (
                     <>
                       Last: {formatRelativeTime(status?.lastSyncAt, false)}
@@ -52,29 +128,154 @@ export function SyncSection() {
               {isSyncing ? 'Syncing...' : 'Sync Now'}
             </Button>
           </div>
+          {paused.length > 0 && (
+            <ul className="mt-3 space-y-1">
+              {paused.map(({ name, circuit }) => (
+                <li key={name} className="flex items-start gap-2 text-sm text-amber-600 dark:text-amber-400">
+                  <PauseCircle className="h-4 w-4 mt-0.5 flex-shrink-0" />
+                  <span>
+                    {circuit.unreachable ? (
+                      <>{name} cannot be reached · retrying until it connects</>
+                    ) : (
+                      <>
+                        {name} paused after {circuit.consecutiveFailures} failed syncs
+                        {circuit.retryAt && <> · retrying at {formatClock(circuit.retryAt)}</>}
+                      </>
+                    )}
+                    {circuit.lastError && (
+                      <span className="block text-xs text-muted-foreground">{circuit.lastError}</span>
+                    )}
+                  </span>
+                </li>
+              ))}
+            </ul>
+          )}
         </div>
 
         <div className="p-4 bg-card border border-border rounded-lg">
           <div className="flex items-center gap-3 mb-3">
             <Clock className="h-5 w-5 text-muted-foreground" />
             <div>
-              <h4 className="font-medium text-foreground">Sync Interval</h4>
+              <h4 className="font-medium text-foreground">Sync Schedule</h4>
               <p className="text-sm text-muted-foreground">
-                How often to sync data from sources
+                When to sync data from sources
               </p>
             </div>
           </div>
           <select
-            value={preferences.syncIntervalMinutes}
-            onChange={e => handleIntervalChange(Number(e.target.value))}
+            value={selectedPreset}
+            onChange={e => handlePresetChange(e.target.value)}
             disabled={isSaving}
             className="w-full rounded-lg border border-border bg-background px-3 py-2 text-sm text-foreground focus:outline-none focus:ring-2 focus:ring-primary-500"
           >
-            <option value={5}>Every 5 minutes</option>
-            <option value={15}>Every 15 minutes</option>
-            <option value={30}>Every 30 minutes</option>
-            <option value={60}>Every hour</option>
+            {SCHEDULE_PRESETS.map(preset => (
+              <option key={preset.id} value={preset.id}>
+                {preset.label}
+              </option>
+            ))}
+            <option value="custom">Custom</option>
           </select>
+          {selectedPreset === 'custom' && (
+            <div className="mt-3 space-y-2">
+              <p className="text-xs text-muted-foreground">
+                One cron expression per line (minute hour day month weekday, local time).
+                A sync runs whenever any line matches.
+              </p>
+              <textarea
+                aria-label="Cron expressions"
+                value={customEntries ?? schedule.entries.join('\n')}
+                onChange={e => setCustomEntries(e.target.value)}
+                rows={3}
+                className="w-full rounded-lg border border-border bg-background px-3 py-2 font-mono text-sm text-foreground focus:outline-none focus:ring-2 focus:ring-primary-500"
+              />
+              <Button
+                variant="outline"
+                size="sm"
+                onClick={handleCustomSave}
+                disabled={isSaving || customEntries === null}
+              >
+                Save Schedule
+              </Button>
+            </div>
+          )}
+          {saveError && (
+            <p className="mt-2 text-sm text-red-600 dark:text-red-400">{String(saveError)}</p>
+          )}
+        </div>
+
+        <div className="p-4 bg-card border border-border rounded-lg">
+          <div className="flex items-center justify-between">
+            <div className="flex items-center gap-3">
+              <Moon className="h-5 w-5 text-muted-foreground" />
+              <div>
+                <h4 className="font-medium text-foreground">Quiet Hours</h4>
+                <p className="text-sm text-muted-foreground">
+                  Pause background sync and AI processing overnight
+                </p>
+              </div>
+            </div>
+            <input
+              type="checkbox"
+              aria-label="Enable quiet hours"
+              checked={!!overnight}
+              disabled={isSaving}
+              onChange={e =>
+                setOvernight(e.target.checked ? { days: [], start: '22:00', end: '07:00' } : undefined)
+              }
+            />
+          </div>
+          {overnight && (
+            <div className="mt-3 flex items-center gap-2 text-sm text-muted-foreground">
+              <input
+                type="time"
+                aria-label="Quiet hours start"
+                value={overnight.start}
+                disabled={isSaving}
+                onChange={e => setOvernight({ ...overnight, start: e.target.value })}
+                className="rounded-lg border border-border bg-background px-2 py-1 text-foreground"
+              />
+              <span>to</span>
+              <input
+                type="time"
+                aria-label="Quiet hours end"
+                value={overnight.end}
+                disabled={isSaving}
+                onChange={e => setOvernight({ ...overnight, end: e.target.value })}
+                className="rounded-lg border border-border bg-background px-2 py-1 text-foreground"
+              />
+            </div>
+          )}
+        </div>
+
+        <div className="p-4 bg-card border border-border rounded-lg">
+          <div className="flex items-center justify-between">
+            <div className="flex items-center gap-3">
+              <Newspaper className="h-5 w-5 text-muted-foreground" />
+              <div>
+                <h4 className="font-medium text-foreground">End-of-Day Digest</h4>
+                <p className="text-sm text-muted-foreground">
+                  Run a final sync and generate the daily digest
+                </p>
+              </div>
+            </div>
+            <input
+              type="checkbox"
+              aria-label="Enable end-of-day digest"
+              checked={!!schedule.digestTime}
+              disabled={isSaving}
+              onChange={e => saveSchedule({ digestTime: e.target.checked ? '18:00' : undefined })}
+            />
+          </div>
+          {schedule.digestTime && (
+            <input
+              type="time"
+              aria-label="Digest time"
+              value={schedule.digestTime}
+              disabled={isSaving}
+              onChange={e => saveSchedule({ digestTime: e.target.value })}
+              className="mt-3 rounded-lg border border-border bg-background px-2 py-1 text-sm text-foreground"
+            />
+          )}
         </div>
       </div>
     </div>